@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use chrono::{prelude::*, Duration};
 use plotters::prelude::*;
@@ -6,6 +6,8 @@ use seed::{prelude::*, *};
 
 pub const ENTER_KEY: u32 = 13;
 
+pub const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
 pub const COLOR_BODY_WEIGHT: usize = 1;
 pub const COLOR_AVG_BODY_WEIGHT: usize = 2;
 pub const COLOR_BODY_FAT_JP3: usize = 4;
@@ -22,6 +24,11 @@ pub const COLOR_REPS: usize = 3;
 pub const COLOR_REPS_RIR: usize = 4;
 pub const COLOR_WEIGHT: usize = 8;
 pub const COLOR_TIME: usize = 5;
+pub const COLOR_FITNESS: usize = 2;
+pub const COLOR_FATIGUE: usize = 1;
+pub const COLOR_PERFORMANCE: usize = 4;
+pub const COLOR_TARGETS_ACHIEVED: usize = 9;
+pub const COLOR_RPE_DEVIATION: usize = 11;
 
 pub struct Interval {
     pub first: NaiveDate,
@@ -42,6 +49,40 @@ pub fn init_interval(dates: &[NaiveDate], show_all: bool) -> Interval {
     Interval { first, last }
 }
 
+/// Describe a date relative to today, e.g. "today", "tomorrow", "on Fri" or "on 2024-01-31".
+pub fn format_relative_date(date: NaiveDate, today: NaiveDate) -> String {
+    match (date - today).num_days() {
+        0 => String::from("today"),
+        1 => String::from("tomorrow"),
+        2..=6 => format!(
+            "on {}",
+            WEEKDAYS[date.weekday().num_days_from_monday() as usize]
+        ),
+        _ => format!("on {date}"),
+    }
+}
+
+/// Format a duration in seconds as minutes if possible.
+pub fn format_duration(seconds: u32) -> String {
+    let (minutes, rest) = (seconds / 60, seconds % 60);
+    if minutes > 0 && rest == 0 {
+        format!("{minutes} min")
+    } else {
+        format!("{seconds} s")
+    }
+}
+
+/// Format a roughly estimated duration in hours and minutes.
+pub fn format_estimated_duration(seconds: u32) -> String {
+    let minutes = seconds.saturating_add(30) / 60;
+    match (minutes / 60, minutes % 60) {
+        (0, 0) => String::from("< 1 min"),
+        (0, minutes) => format!("{minutes} min"),
+        (hours, 0) => format!("{hours} h"),
+        (hours, minutes) => format!("{hours} h {minutes} min"),
+    }
+}
+
 pub fn view_title<Ms>(title: &Node<Ms>, margin: u8) -> Node<Ms> {
     div![
         C!["container"],
@@ -382,6 +423,16 @@ pub fn automatic_icon<Ms>() -> Node<Ms> {
 }
 
 pub fn view_calendar<Ms>(entries: Vec<(NaiveDate, usize, f64)>, interval: &Interval) -> Node<Ms> {
+    view_calendar_with_planned(entries, &BTreeSet::new(), COLOR_LOAD, interval)
+}
+
+/// Calendar with additional outlined cells for planned dates without entry.
+pub fn view_calendar_with_planned<Ms>(
+    entries: Vec<(NaiveDate, usize, f64)>,
+    planned: &BTreeSet<NaiveDate>,
+    planned_color: usize,
+    interval: &Interval,
+) -> Node<Ms> {
     let mut calendar: BTreeMap<NaiveDate, (usize, f64)> = BTreeMap::new();
 
     let mut day = interval.first.week(Weekday::Mon).first_day();
@@ -459,21 +510,19 @@ pub fn view_calendar<Ms>(entries: Vec<(NaiveDate, usize, f64)>, interval: &Inter
                                     } else {
                                         style! {}
                                     },
+                                    IF![*opacity == 0. && planned.contains(date) => {
+                                        let (r, g, b) = Palette99::pick(planned_color).rgb();
+                                        style! {
+                                            St::BoxShadow => format!("inset 0 0 0 2px rgb({r}, {g}, {b})"),
+                                            St::Color => format!("rgb({r}, {g}, {b})"),
+                                        }
+                                    }],
                                     div![date.day()]
                                 ])
                                 .collect::<Vec<_>>(),
                             td![
                                 C!["is-calendar-label"],
-                                match weekday {
-                                    0 => "Mon",
-                                    1 => "Tue",
-                                    2 => "Wed",
-                                    3 => "Thu",
-                                    4 => "Fri",
-                                    5 => "Sat",
-                                    6 => "Sun",
-                                    _ => "",
-                                }
+                                WEEKDAYS[weekday]
                             ]
                         ]
                     })
@@ -527,9 +576,22 @@ pub fn plot_line_chart(
     x_max: NaiveDate,
     y_min_opt: Option<f32>,
     y_max_opt: Option<f32>,
+) -> Result<String, Box<dyn std::error::Error>> {
+    plot_line_chart_with_dashed_lines(data, &[], x_min, x_max, y_min_opt, y_max_opt)
+}
+
+/// Line chart with additional series drawn as dashed lines without points.
+pub fn plot_line_chart_with_dashed_lines(
+    data: &[(Vec<(NaiveDate, f32)>, usize)],
+    dashed_data: &[(Vec<(NaiveDate, f32)>, usize)],
+    x_min: NaiveDate,
+    x_max: NaiveDate,
+    y_min_opt: Option<f32>,
+    y_max_opt: Option<f32>,
 ) -> Result<String, Box<dyn std::error::Error>> {
     let (y_min, y_max, y_margin) = determine_y_bounds(
         data.iter()
+            .chain(dashed_data)
             .flat_map(|(s, _)| s.iter().map(|(_, y)| *y))
             .collect::<Vec<_>>(),
         y_min_opt,
@@ -551,7 +613,11 @@ pub fn plot_line_chart(
 
         let mut chart = chart_builder.build_cartesian_2d(
             x_min..x_max,
-            f32::max(0., y_min - y_margin)..y_max + y_margin,
+            if y_min < 0. {
+                y_min - y_margin
+            } else {
+                f32::max(0., y_min - y_margin)
+            }..y_max + y_margin,
         )?;
 
         chart
@@ -581,12 +647,68 @@ pub fn plot_line_chart(
             )?;
         }
 
+        let plotting_area = chart.plotting_area();
+        let screen_area = plotting_area.use_screen_coord();
+        for (series, color_idx) in dashed_data {
+            let mut series = series.iter().collect::<Vec<_>>();
+            series.sort_by_key(|e| e.0);
+            let color = Palette99::pick(*color_idx).mix(0.9);
+            let points = series
+                .iter()
+                .map(|point| plotting_area.map_coordinate(point))
+                .collect::<Vec<_>>();
+
+            for dash in dashes(&points, 6., 4.) {
+                screen_area.draw(&PathElement::new(dash, color.stroke_width(2)))?;
+            }
+        }
+
         root.present()?;
     }
 
     Ok(result)
 }
 
+/// Split a path given in backend coordinates into the segments of a dashed line.
+#[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+fn dashes(points: &[(i32, i32)], dash_length: f32, gap_length: f32) -> Vec<Vec<(i32, i32)>> {
+    let to_backend_coord = |(x, y): (f32, f32)| (x.round() as i32, y.round() as i32);
+    let mut result = vec![];
+    let mut dash = vec![];
+    let mut drawing = true;
+    let mut remaining = dash_length;
+
+    for segment in points.windows(2) {
+        let (x0, y0) = (segment[0].0 as f32, segment[0].1 as f32);
+        let (x1, y1) = (segment[1].0 as f32, segment[1].1 as f32);
+        let length = (x1 - x0).hypot(y1 - y0);
+        let mut position = 0.;
+        if drawing && dash.is_empty() {
+            dash.push(segment[0]);
+        }
+        while length - position > remaining {
+            position += remaining;
+            let t = position / length;
+            dash.push(to_backend_coord((x0 + (x1 - x0) * t, y0 + (y1 - y0) * t)));
+            if drawing {
+                result.push(std::mem::take(&mut dash));
+            }
+            drawing = not(drawing);
+            remaining = if drawing { dash_length } else { gap_length };
+        }
+        remaining -= length - position;
+        if drawing {
+            dash.push(segment[1]);
+        }
+    }
+
+    if dash.len() > 1 {
+        result.push(dash);
+    }
+
+    result
+}
+
 pub fn plot_dual_line_chart(
     data: &[(Vec<(NaiveDate, f32)>, usize)],
     secondary_data: &[(Vec<(NaiveDate, f32)>, usize)],
@@ -859,10 +981,159 @@ pub fn valid_rpe(rpe: f32) -> bool {
     (0.0..=10.0).contains(&rpe) && (rpe % 0.5).abs() < f32::EPSILON
 }
 
+/// Names of the voices available for speech synthesis.
+pub fn speech_voices() -> Vec<String> {
+    window()
+        .speech_synthesis()
+        .map(|synthesis| {
+            synthesis
+                .get_voices()
+                .iter()
+                .filter_map(|v| v.dyn_into::<web_sys::SpeechSynthesisVoice>().ok())
+                .map(|v| v.name())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Speak the text after all previously queued texts. Nothing is spoken if the volume is zero.
+pub fn speak(text: &str, volume: u8, voice: Option<&str>) {
+    if volume == 0 {
+        return;
+    }
+    let Ok(synthesis) = window().speech_synthesis() else {
+        return;
+    };
+    let Ok(utterance) = web_sys::SpeechSynthesisUtterance::new_with_text(text) else {
+        return;
+    };
+    utterance.set_volume(f32::from(volume) / 100.);
+    if let Some(voice) = voice.and_then(|name| {
+        synthesis
+            .get_voices()
+            .iter()
+            .filter_map(|v| v.dyn_into::<web_sys::SpeechSynthesisVoice>().ok())
+            .find(|v| v.name() == name)
+    }) {
+        utterance.set_voice(Some(&voice));
+    }
+    synthesis.speak(&utterance);
+}
+
+// The Screen Wake Lock API and the Media Session API are not available in the used version of
+// web-sys and are therefore accessed dynamically. Browsers without support for these APIs are
+// silently ignored.
+
+fn get_property(target: &JsValue, name: &str) -> Option<JsValue> {
+    js_sys::Reflect::get(target, &JsValue::from_str(name))
+        .ok()
+        .filter(|v| not(v.is_undefined() || v.is_null()))
+}
+
+fn call_method(target: &JsValue, name: &str, args: &[&JsValue]) -> Option<JsValue> {
+    let method = get_property(target, name)?
+        .dyn_into::<js_sys::Function>()
+        .ok()?;
+    js_sys::Reflect::apply(
+        &method,
+        target,
+        &args.iter().copied().collect::<js_sys::Array>(),
+    )
+    .ok()
+}
+
+/// Request a screen wake lock and return the corresponding sentinel.
+pub async fn request_wake_lock() -> Option<JsValue> {
+    let wake_lock = get_property(&window().navigator(), "wakeLock")?;
+    let promise = call_method(&wake_lock, "request", &[&JsValue::from_str("screen")])?
+        .dyn_into::<js_sys::Promise>()
+        .ok()?;
+    JsFuture::from(promise).await.ok()
+}
+
+pub fn release_wake_lock(sentinel: &JsValue) {
+    call_method(sentinel, "release", &[]);
+}
+
+/// Whether the wake lock was released, e.g., by the browser when the page was hidden.
+pub fn wake_lock_released(sentinel: &JsValue) -> bool {
+    get_property(sentinel, "released").map_or(true, |released| released.is_truthy())
+}
+
+fn media_session() -> Option<JsValue> {
+    get_property(&window().navigator(), "mediaSession")
+}
+
+/// Set or remove the handler of a media session action (e.g., "play" or "nexttrack").
+pub fn set_media_session_action_handler(action: &str, handler: Option<&js_sys::Function>) {
+    if let Some(media_session) = media_session() {
+        call_method(
+            &media_session,
+            "setActionHandler",
+            &[
+                &JsValue::from_str(action),
+                handler.map_or(&JsValue::NULL, AsRef::as_ref),
+            ],
+        );
+    }
+}
+
+/// Set the title shown on the lock screen, or remove all metadata.
+pub fn set_media_session_title(title: Option<&str>) {
+    let Some(media_session) = media_session() else {
+        return;
+    };
+    let metadata = title.and_then(|title| {
+        let constructor = get_property(&window(), "MediaMetadata")?
+            .dyn_into::<js_sys::Function>()
+            .ok()?;
+        let init = js_sys::Object::new();
+        js_sys::Reflect::set(
+            &init,
+            &JsValue::from_str("title"),
+            &JsValue::from_str(title),
+        )
+        .ok()?;
+        js_sys::Reflect::construct(&constructor, &std::iter::once(init).collect()).ok()
+    });
+    let _ = js_sys::Reflect::set(
+        &media_session,
+        &JsValue::from_str("metadata"),
+        &metadata.unwrap_or(JsValue::NULL),
+    );
+}
+
+pub fn set_media_session_playback_state(state: &str) {
+    if let Some(media_session) = media_session() {
+        let _ = js_sys::Reflect::set(
+            &media_session,
+            &JsValue::from_str("playbackState"),
+            &JsValue::from_str(state),
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_dashes() {
+        assert_eq!(dashes(&[], 6., 4.), Vec::<Vec<(i32, i32)>>::new());
+        assert_eq!(
+            dashes(&[(0, 0), (5, 0)], 6., 4.),
+            vec![vec![(0, 0), (5, 0)]]
+        );
+        assert_eq!(
+            dashes(&[(0, 0), (10, 0), (10, 12)], 6., 4.),
+            vec![
+                vec![(0, 0), (6, 0)],
+                vec![(10, 0), (10, 6)],
+                vec![(10, 10), (10, 12)]
+            ]
+        );
+    }
+
     #[test]
     fn quartile_one() {
         assert_eq!(quartile(&[], Quartile::Q1), Duration::days(0));
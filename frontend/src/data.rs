@@ -16,13 +16,23 @@ const STORAGE_KEY_ONGOING_TRAINING_SESSION: &str = "ongoing training session";
 // ------ ------
 
 #[allow(clippy::needless_pass_by_value)]
-pub fn init(url: Url, _orders: &mut impl Orders<Msg>) -> Model {
+pub fn init(url: Url, orders: &mut impl Orders<Msg>) -> Model {
     let settings = gloo_storage::LocalStorage::get(STORAGE_KEY_SETTINGS).unwrap_or(Settings {
         beep_volume: 80,
         automatic_metronome: true,
+        load_model: LoadModel::SetRPE,
+        speech_volume: 0,
+        speech_voice: None,
+        keep_screen_awake: true,
     });
     let ongoing_training_session =
         gloo_storage::LocalStorage::get(STORAGE_KEY_ONGOING_TRAINING_SESSION).unwrap_or(None);
+    // The browser releases the wake lock when the page is hidden.
+    orders
+        .stream(streams::document_event(Ev::VisibilityChange, |_| {
+            Msg::UpdateWakeLock
+        }))
+        .send_msg(Msg::UpdateWakeLock);
     Model {
         base_url: url.to_hash_base_url(),
         errors: Vec::new(),
@@ -40,6 +50,11 @@ pub fn init(url: Url, _orders: &mut impl Orders<Msg>) -> Model {
         loading_exercises: false,
         routines: BTreeMap::new(),
         loading_routines: false,
+        routine_versions: BTreeMap::new(),
+        programs: BTreeMap::new(),
+        loading_programs: false,
+        schedule: None,
+        barbell_equipment: None,
         training_sessions: BTreeMap::new(),
         loading_training_sessions: false,
         last_refresh: DateTime::from_naive_utc_and_offset(
@@ -54,9 +69,15 @@ pub fn init(url: Url, _orders: &mut impl Orders<Msg>) -> Model {
             long_term_load: Vec::new(),
             avg_rpe_per_week: Vec::new(),
             total_set_volume_per_week: Vec::new(),
+            fitness: Vec::new(),
+            fatigue: Vec::new(),
+            performance: Vec::new(),
+            set_volume_per_muscle_group_per_week: BTreeMap::new(),
         },
         settings,
         ongoing_training_session,
+        wake_lock: None,
+        copied_routine_part: None,
     }
 }
 
@@ -86,6 +107,12 @@ pub struct Model {
     pub loading_exercises: bool,
     pub routines: BTreeMap<u32, Routine>,
     pub loading_routines: bool,
+    /// Versions of each routine, indexed by the routine ID.
+    pub routine_versions: BTreeMap<u32, Vec<RoutineVersion>>,
+    pub programs: BTreeMap<u32, Program>,
+    pub loading_programs: bool,
+    pub schedule: Option<Schedule>,
+    barbell_equipment: Option<BarbellEquipment>,
     pub training_sessions: BTreeMap<u32, TrainingSession>,
     pub loading_training_sessions: bool,
     pub last_refresh: DateTime<Utc>,
@@ -99,11 +126,62 @@ pub struct Model {
     // ------ Client-side data ------
     pub settings: Settings,
     pub ongoing_training_session: Option<OngoingTrainingSession>,
+    /// Sentinel of the screen wake lock held during an ongoing training session.
+    wake_lock: Option<JsValue>,
+    /// Section or activity which can be pasted into any routine.
+    pub copied_routine_part: Option<RoutinePart>,
+}
+
+impl Model {
+    /// Barbell equipment of the current user, or the default equipment if none is stored.
+    pub fn barbell_equipment(&self) -> BarbellEquipment {
+        self.barbell_equipment.clone().unwrap_or_default()
+    }
+
+    /// Highest estimated one-repetition maximum of the sets of the last training session
+    /// containing the exercise. Sets without RPE are assumed to be taken to failure.
+    pub fn estimated_one_rep_max(&self, exercise_id: u32) -> Option<f32> {
+        let mut training_sessions = self.training_sessions.values().collect::<Vec<_>>();
+        training_sessions.sort_by_key(|t| (t.date, t.id));
+        training_sessions.iter().rev().find_map(|t| {
+            t.working_elements()
+                .filter_map(|e| match e {
+                    TrainingSessionElement::Set {
+                        exercise_id: id,
+                        reps: Some(reps),
+                        weight: Some(weight),
+                        rpe,
+                        ..
+                    } if *id == exercise_id => {
+                        estimated_one_rep_max(*reps, *weight, rpe.unwrap_or(10.0))
+                    }
+                    _ => None,
+                })
+                .reduce(f32::max)
+        })
+    }
+
+    /// Weight percentage of the program week in which the training session was created.
+    pub fn program_weight_percentage(&self, training_session_id: u32) -> Option<f32> {
+        self.programs
+            .values()
+            .find_map(|p| p.weight_percentages.get(&training_session_id).copied())
+    }
+
+    /// Weight in kg to which a relative weight of the exercise refers.
+    pub fn reference_weight(&self, exercise_id: u32, basis: WeightBasis) -> Option<f32> {
+        match basis {
+            WeightBasis::TrainingMax => self
+                .exercises
+                .get(&exercise_id)
+                .and_then(|e| e.training_max),
+            WeightBasis::OneRepMax => self.estimated_one_rep_max(exercise_id),
+        }
+    }
 }
 
 #[derive(serde::Deserialize, Debug, Clone)]
 pub struct Session {
-    pub id: u32,
     pub name: String,
     pub sex: u8,
 }
@@ -173,11 +251,18 @@ pub struct TrainingStats {
     pub long_term_load: Vec<(NaiveDate, f32)>,
     pub avg_rpe_per_week: Vec<(NaiveDate, f32)>,
     pub total_set_volume_per_week: Vec<(NaiveDate, f32)>,
+    pub fitness: Vec<(NaiveDate, f32)>,
+    pub fatigue: Vec<(NaiveDate, f32)>,
+    pub performance: Vec<(NaiveDate, f32)>,
+    pub set_volume_per_muscle_group_per_week: BTreeMap<MuscleGroup, Vec<(NaiveDate, f32)>>,
 }
 
 impl TrainingStats {
     pub const LOAD_RATIO_LOW: f32 = 0.8;
     pub const LOAD_RATIO_HIGH: f32 = 1.5;
+    pub const FITNESS_TIME_CONSTANT: f32 = 42.;
+    pub const FATIGUE_TIME_CONSTANT: f32 = 7.;
+    pub const FORECAST_DAYS: i64 = 14;
 
     pub fn load_ratio(&self) -> Option<f32> {
         let long_term_load = self.long_term_load.last().map_or(0., |(_, l)| *l);
@@ -194,13 +279,230 @@ impl TrainingStats {
         self.long_term_load.clear();
         self.avg_rpe_per_week.clear();
         self.total_set_volume_per_week.clear();
+        self.fitness.clear();
+        self.fatigue.clear();
+        self.performance.clear();
+        self.set_volume_per_muscle_group_per_week.clear();
     }
 }
 
-#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
 pub struct Exercise {
     pub id: u32,
     pub name: String,
+    /// Weight in kg to which relative weights based on the training max refer.
+    #[serde(default)]
+    pub training_max: Option<f32>,
+    #[serde(flatten)]
+    pub metadata: ExerciseMetadata,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+pub struct ExerciseMetadata {
+    pub primary_muscles: BTreeSet<MuscleGroup>,
+    pub secondary_muscles: BTreeSet<MuscleGroup>,
+    pub movement_pattern: Option<MovementPattern>,
+    pub equipment: Option<Equipment>,
+    pub laterality: Option<Laterality>,
+    pub alternatives: BTreeSet<u32>,
+}
+
+#[derive(
+    serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord,
+)]
+pub enum MuscleGroup {
+    Chest,
+    Back,
+    LowerBack,
+    Shoulders,
+    Biceps,
+    Triceps,
+    Forearms,
+    Abs,
+    Glutes,
+    Quadriceps,
+    Hamstrings,
+    Adductors,
+    Calves,
+    Neck,
+}
+
+impl MuscleGroup {
+    /// Share of a set that is credited to a secondary muscle group.
+    pub const SECONDARY_SET_CREDIT: f32 = 0.5;
+
+    pub const ALL: [MuscleGroup; 14] = [
+        MuscleGroup::Chest,
+        MuscleGroup::Back,
+        MuscleGroup::LowerBack,
+        MuscleGroup::Shoulders,
+        MuscleGroup::Biceps,
+        MuscleGroup::Triceps,
+        MuscleGroup::Forearms,
+        MuscleGroup::Abs,
+        MuscleGroup::Glutes,
+        MuscleGroup::Quadriceps,
+        MuscleGroup::Hamstrings,
+        MuscleGroup::Adductors,
+        MuscleGroup::Calves,
+        MuscleGroup::Neck,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            MuscleGroup::Chest => "Chest",
+            MuscleGroup::Back => "Back",
+            MuscleGroup::LowerBack => "Lower back",
+            MuscleGroup::Shoulders => "Shoulders",
+            MuscleGroup::Biceps => "Biceps",
+            MuscleGroup::Triceps => "Triceps",
+            MuscleGroup::Forearms => "Forearms",
+            MuscleGroup::Abs => "Abs",
+            MuscleGroup::Glutes => "Glutes",
+            MuscleGroup::Quadriceps => "Quadriceps",
+            MuscleGroup::Hamstrings => "Hamstrings",
+            MuscleGroup::Adductors => "Adductors",
+            MuscleGroup::Calves => "Calves",
+            MuscleGroup::Neck => "Neck",
+        }
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MovementPattern {
+    Push,
+    Pull,
+    Squat,
+    Hinge,
+    Lunge,
+    Carry,
+    Core,
+    Isolation,
+}
+
+impl MovementPattern {
+    pub const ALL: [MovementPattern; 8] = [
+        MovementPattern::Push,
+        MovementPattern::Pull,
+        MovementPattern::Squat,
+        MovementPattern::Hinge,
+        MovementPattern::Lunge,
+        MovementPattern::Carry,
+        MovementPattern::Core,
+        MovementPattern::Isolation,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            MovementPattern::Push => "Push",
+            MovementPattern::Pull => "Pull",
+            MovementPattern::Squat => "Squat",
+            MovementPattern::Hinge => "Hinge",
+            MovementPattern::Lunge => "Lunge",
+            MovementPattern::Carry => "Carry",
+            MovementPattern::Core => "Core",
+            MovementPattern::Isolation => "Isolation",
+        }
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Equipment {
+    Barbell,
+    Dumbbell,
+    Kettlebell,
+    Machine,
+    Cable,
+    Band,
+    Bodyweight,
+    Other,
+}
+
+impl Equipment {
+    pub const ALL: [Equipment; 8] = [
+        Equipment::Barbell,
+        Equipment::Dumbbell,
+        Equipment::Kettlebell,
+        Equipment::Machine,
+        Equipment::Cable,
+        Equipment::Band,
+        Equipment::Bodyweight,
+        Equipment::Other,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Equipment::Barbell => "Barbell",
+            Equipment::Dumbbell => "Dumbbell",
+            Equipment::Kettlebell => "Kettlebell",
+            Equipment::Machine => "Machine",
+            Equipment::Cable => "Cable",
+            Equipment::Band => "Band",
+            Equipment::Bodyweight => "Bodyweight",
+            Equipment::Other => "Other",
+        }
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Laterality {
+    Bilateral,
+    Unilateral,
+}
+
+impl Laterality {
+    pub const ALL: [Laterality; 2] = [Laterality::Bilateral, Laterality::Unilateral];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Laterality::Bilateral => "Bilateral",
+            Laterality::Unilateral => "Unilateral",
+        }
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SetType {
+    #[default]
+    Working,
+    WarmUp,
+    Drop,
+    Failure,
+    Amrap,
+    BackOff,
+}
+
+impl SetType {
+    pub const ALL: [SetType; 6] = [
+        SetType::Working,
+        SetType::WarmUp,
+        SetType::Drop,
+        SetType::Failure,
+        SetType::Amrap,
+        SetType::BackOff,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            SetType::Working => "Working",
+            SetType::WarmUp => "Warm-up",
+            SetType::Drop => "Drop set",
+            SetType::Failure => "Failure",
+            SetType::Amrap => "AMRAP",
+            SetType::BackOff => "Back-off",
+        }
+    }
+
+    /// Warm-up sets are excluded from all statistics.
+    pub fn is_working(self) -> bool {
+        self != SetType::WarmUp
+    }
+
+    /// Sets which are taken to failure or performed for as many reps as possible have no
+    /// meaningful target RPE.
+    pub fn has_rpe_target(self) -> bool {
+        not(matches!(self, SetType::Failure | SetType::Amrap))
+    }
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
@@ -211,12 +513,22 @@ pub struct Routine {
     pub sections: Vec<RoutinePart>,
 }
 
+/// State of a routine after a modification.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct RoutineVersion {
+    pub id: u32,
+    pub routine_id: u32,
+    pub created: DateTime<Utc>,
+    pub sections: Vec<RoutinePart>,
+}
+
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
 #[serde(untagged)]
 pub enum RoutinePart {
     RoutineSection {
         rounds: u32,
         parts: Vec<RoutinePart>,
+        kind: SectionKind,
     },
     RoutineActivity {
         exercise_id: Option<u32>,
@@ -225,9 +537,662 @@ pub enum RoutinePart {
         weight: f32,
         rpe: f32,
         automatic: bool,
+        #[serde(flatten)]
+        options: ActivityOptions,
+    },
+}
+
+/// Time semantics of a routine section.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SectionKind {
+    /// All parts in sequence for the given number of rounds.
+    #[default]
+    Sequential,
+    /// Each round starts at the beginning of an interval (every minute on the minute).
+    Emom { interval: u32 },
+    /// As many rounds as possible within the time cap.
+    Amrap { time_cap: u32 },
+    /// Alternating timed work and rest for each exercise in each round.
+    Tabata { work: u32, rest: u32 },
+    /// All rounds as fast as possible, optionally limited by a time cap (0 for no time cap).
+    ForTime { time_cap: u32 },
+}
+
+impl SectionKind {
+    pub const DEFAULT_EMOM: SectionKind = SectionKind::Emom { interval: 60 };
+    pub const DEFAULT_AMRAP: SectionKind = SectionKind::Amrap { time_cap: 600 };
+    pub const DEFAULT_TABATA: SectionKind = SectionKind::Tabata { work: 20, rest: 10 };
+    pub const DEFAULT_FOR_TIME: SectionKind = SectionKind::ForTime { time_cap: 0 };
+
+    pub fn name(self) -> &'static str {
+        match self {
+            SectionKind::Sequential => "Sequential",
+            SectionKind::Emom { .. } => "EMOM",
+            SectionKind::Amrap { .. } => "AMRAP",
+            SectionKind::Tabata { .. } => "Tabata",
+            SectionKind::ForTime { .. } => "For Time",
+        }
+    }
+
+    pub fn description(self) -> String {
+        match self {
+            SectionKind::Sequential => String::new(),
+            SectionKind::Emom { interval } => {
+                if interval == 60 {
+                    String::from("EMOM")
+                } else {
+                    format!("EMOM every {}", common::format_duration(interval))
+                }
+            }
+            SectionKind::Amrap { time_cap } => {
+                format!("AMRAP {}", common::format_duration(time_cap))
+            }
+            SectionKind::Tabata { work, rest } => format!("Tabata {work} s / {rest} s"),
+            SectionKind::ForTime { time_cap } => {
+                if time_cap > 0 {
+                    format!("For Time (cap {})", common::format_duration(time_cap))
+                } else {
+                    String::from("For Time")
+                }
+            }
+        }
+    }
+
+    /// Number of rounds which are part of the training session. The rounds of an AMRAP section are
+    /// repeated as long as time is left.
+    pub fn planned_rounds(self, rounds: u32) -> u32 {
+        match self {
+            SectionKind::Amrap { .. } => 1,
+            _ => rounds,
+        }
+    }
+}
+
+/// Interval section of a training session, which covers a range of training session elements
+/// consisting of consecutive rounds of equal length.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct IntervalBlock {
+    pub kind: SectionKind,
+    pub start: usize,
+    pub end: usize,
+    pub rounds: u32,
+    #[serde(default)]
+    pub achieved_rounds: Option<u32>,
+    /// Needed time in seconds.
+    #[serde(default)]
+    pub time: Option<u32>,
+}
+
+impl IntervalBlock {
+    pub fn round_len(&self) -> usize {
+        (self.end - self.start) / self.rounds.max(1) as usize
+    }
+}
+
+/// Additional properties of a routine activity.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct ActivityOptions {
+    #[serde(default)]
+    pub progression: Option<Progression>,
+    /// Whether warm-up sets are added before the first set of the activity.
+    #[serde(default)]
+    pub auto_warm_up: bool,
+    #[serde(default)]
+    pub set_type: SetType,
+    /// Weight relative to a reference weight of the exercise, which replaces the weight of the
+    /// activity.
+    #[serde(default)]
+    pub relative_weight: Option<RelativeWeight>,
+}
+
+impl ActivityOptions {
+    /// Target weight in kg of the activity. A relative weight is resolved using the reference
+    /// weight of the given basis. The given weight of the activity is used if there is no relative
+    /// weight or no reference weight is known.
+    pub fn target_weight(
+        &self,
+        weight: f32,
+        reference_weight: impl FnOnce(WeightBasis) -> Option<f32>,
+    ) -> f32 {
+        self.relative_weight
+            .and_then(|r| reference_weight(r.basis).map(|w| r.resolve(w)))
+            .unwrap_or(weight)
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct RelativeWeight {
+    pub percentage: f32,
+    pub basis: WeightBasis,
+}
+
+impl RelativeWeight {
+    /// Weight in kg based on the given reference weight. The weight is rounded to 0.5 kg.
+    pub fn resolve(self, reference_weight: f32) -> f32 {
+        (reference_weight * self.percentage / 100.0 * 2.0).round() / 2.0
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WeightBasis {
+    /// Training max which is set manually for each exercise.
+    TrainingMax,
+    /// One-repetition maximum estimated from the previous sets of the exercise.
+    OneRepMax,
+}
+
+impl WeightBasis {
+    pub const ALL: [WeightBasis; 2] = [WeightBasis::TrainingMax, WeightBasis::OneRepMax];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            WeightBasis::TrainingMax => "TM",
+            WeightBasis::OneRepMax => "e1RM",
+        }
+    }
+}
+
+/// Rule for deriving the targets of an activity from the results of the previous training session.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum Progression {
+    /// Increase the weight if all target reps were achieved.
+    Linear { increment: f32 },
+    /// Increase the reps up to the maximum, then increase the weight and reset the reps.
+    Double { max_reps: u32, increment: f32 },
+    /// Increase the weight if all target reps were achieved without exceeding the target RPE.
+    RPECapped { increment: f32 },
+}
+
+impl Progression {
+    pub const DEFAULT_INCREMENT: f32 = 2.5;
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Progression::Linear { .. } => "Linear",
+            Progression::Double { .. } => "Double",
+            Progression::RPECapped { .. } => "RPE-capped",
+        }
+    }
+
+    pub fn increment(self) -> f32 {
+        match self {
+            Progression::Linear { increment }
+            | Progression::Double { increment, .. }
+            | Progression::RPECapped { increment } => increment,
+        }
+    }
+
+    /// Determine the target reps and weight based on the targets and results of the corresponding
+    /// sets of the previous training session. The reps, weight and RPE defined in the routine are
+    /// used if there was no previous training session.
+    pub fn next_targets(
+        self,
+        reps: u32,
+        weight: f32,
+        rpe: f32,
+        previous_sets: &[&TrainingSessionElement],
+    ) -> (u32, f32) {
+        let mut previous_target_reps = None;
+        let mut previous_target_weight = None;
+        let mut all_reps_achieved = not(previous_sets.is_empty());
+        let mut min_reps = u32::MAX;
+        let mut max_rpe: f32 = 0.0;
+        for set in previous_sets {
+            if let TrainingSessionElement::Set {
+                reps: achieved_reps,
+                rpe: achieved_rpe,
+                target_reps,
+                target_weight,
+                ..
+            } = set
+            {
+                previous_target_reps = previous_target_reps.or(*target_reps);
+                previous_target_weight = previous_target_weight.or(*target_weight);
+                let achieved_reps = achieved_reps.unwrap_or(0);
+                min_reps = min_reps.min(achieved_reps);
+                if achieved_reps < target_reps.unwrap_or(0) || achieved_reps == 0 {
+                    all_reps_achieved = false;
+                }
+                max_rpe = max_rpe.max(achieved_rpe.unwrap_or(0.0));
+            }
+        }
+        if previous_sets.is_empty() {
+            return (reps, weight);
+        }
+        let initial_reps = reps;
+        let reps = previous_target_reps.unwrap_or(reps);
+        let weight = previous_target_weight.unwrap_or(weight);
+        match self {
+            Progression::Linear { increment } => {
+                if all_reps_achieved {
+                    (reps, weight + increment)
+                } else {
+                    (reps, weight)
+                }
+            }
+            Progression::Double {
+                max_reps,
+                increment,
+            } => {
+                if min_reps >= max_reps {
+                    (initial_reps, weight + increment)
+                } else if all_reps_achieved {
+                    ((reps + 1).min(max_reps), weight)
+                } else {
+                    (reps, weight)
+                }
+            }
+            Progression::RPECapped { increment } => {
+                if all_reps_achieved && (rpe == 0.0 || max_rpe <= rpe) {
+                    (reps, weight + increment)
+                } else {
+                    (reps, weight)
+                }
+            }
+        }
+    }
+}
+
+/// Percentage of the one-repetition maximum that can be lifted at RPE 10 for 1, 1.5, 2, …, 12.5
+/// reps. A set of `reps` at a given RPE corresponds to `reps + RIR` reps at RPE 10, where the
+/// reps in reserve (RIR) are `10 - RPE`.
+const RPE_PERCENTAGES: [f32; 24] = [
+    100.0, 97.8, 95.5, 93.9, 92.2, 90.7, 89.2, 87.8, 86.3, 85.0, 83.7, 82.4, 81.1, 79.9, 78.6,
+    77.4, 76.2, 75.1, 73.9, 72.3, 70.7, 69.4, 68.0, 66.7,
+];
+
+/// Percentage of the one-repetition maximum which corresponds to the given reps and RPE. RPE
+/// values are rounded to half points.
+pub fn rpe_percentage(reps: u32, rpe: f32) -> Option<f32> {
+    if reps == 0 || not((1.0..=10.0).contains(&rpe)) {
+        return None;
+    }
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let rir_steps = ((10.0 - rpe) * 2.0).round() as usize;
+    RPE_PERCENTAGES
+        .get((reps as usize - 1) * 2 + rir_steps)
+        .copied()
+}
+
+/// Estimated one-repetition maximum based on a set.
+pub fn estimated_one_rep_max(reps: u32, weight: f32, rpe: f32) -> Option<f32> {
+    Some(weight * 100.0 / rpe_percentage(reps, rpe)?)
+}
+
+/// Weight for the target reps and RPE based on the reps, weight and RPE of a performed set. The
+/// weight is rounded to 0.5 kg.
+pub fn rpe_adjusted_weight(
+    reps: u32,
+    weight: f32,
+    rpe: f32,
+    target_reps: u32,
+    target_rpe: f32,
+) -> Option<f32> {
+    let one_rep_max = estimated_one_rep_max(reps, weight, rpe)?;
+    let weight = one_rep_max * rpe_percentage(target_reps, target_rpe)? / 100.0;
+    Some((weight * 2.0).round() / 2.0)
+}
+
+/// Reps for the target RPE based on the reps and RPE of a performed set with the same weight.
+pub fn rpe_adjusted_reps(reps: u32, rpe: f32, target_rpe: f32) -> Option<u32> {
+    if reps == 0 || not((1.0..=10.0).contains(&rpe)) || not((1.0..=10.0).contains(&target_rpe)) {
+        return None;
+    }
+    #[allow(clippy::cast_precision_loss)]
+    let max_reps = reps as f32 + 10.0 - rpe;
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Some((max_reps - (10.0 - target_rpe)).round().max(1.0) as u32)
+}
+
+/// Sequence of training weeks, each consisting of routines for consecutive training days.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct Program {
+    pub id: u32,
+    pub name: String,
+    pub weeks: Vec<ProgramWeek>,
+    /// Next training day of a running program.
+    pub position: Option<ProgramPosition>,
+    /// Weight percentage applied to each training session created from the program.
+    #[serde(default)]
+    pub weight_percentages: BTreeMap<u32, f32>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct ProgramWeek {
+    pub routines: Vec<u32>,
+    pub weight_percentage: f32,
+    pub rpe_offset: f32,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProgramPosition {
+    pub week: usize,
+    pub day: usize,
+}
+
+impl Program {
+    /// Routine of the next training day of a running program.
+    pub fn next_routine(&self) -> Option<(ProgramPosition, u32)> {
+        let position = self.position?;
+        let routine_id = *self.weeks.get(position.week)?.routines.get(position.day)?;
+        Some((position, routine_id))
+    }
+
+    /// Move to the next training day. The program is finished after the last day of the last week.
+    pub fn advance(&mut self) {
+        if let Some(position) = self.position {
+            self.position = self.weeks[position.week..]
+                .iter()
+                .enumerate()
+                .flat_map(|(i, w)| {
+                    let week = position.week + i;
+                    (0..w.routines.len()).map(move |day| ProgramPosition { week, day })
+                })
+                .find(|p| p.week > position.week || p.day > position.day);
+        }
+    }
+
+    /// Remove a week. The position of a running program is kept on the same training day, or moved
+    /// to the next training day if the current week is removed.
+    pub fn remove_week(&mut self, week: usize) {
+        self.weeks.remove(week);
+        if let Some(position) = &mut self.position {
+            if position.week > week {
+                position.week -= 1;
+            } else if position.week == week {
+                position.day = 0;
+            }
+        }
+        self.skip_missing_day();
+    }
+
+    /// Remove a training day. The position of a running program is kept on the same training day,
+    /// or moved to the next training day if the current day is removed.
+    pub fn remove_day(&mut self, week: usize, day: usize) {
+        self.weeks[week].routines.remove(day);
+        if let Some(position) = &mut self.position {
+            if position.week == week && position.day > day {
+                position.day -= 1;
+            }
+        }
+        self.skip_missing_day();
+    }
+
+    fn skip_missing_day(&mut self) {
+        if self.next_routine().is_none() {
+            self.advance();
+        }
+    }
+}
+
+impl Default for ProgramWeek {
+    fn default() -> Self {
+        ProgramWeek {
+            routines: vec![],
+            weight_percentage: 100.0,
+            rpe_offset: 0.0,
+        }
+    }
+}
+
+impl ProgramWeek {
+    /// Adjust the target weight and RPE of the given training session elements. Weights are rounded
+    /// to multiples of 0.5 kg.
+    pub fn apply(&self, elements: &mut [TrainingSessionElement]) {
+        for e in elements {
+            if let TrainingSessionElement::Set {
+                target_weight,
+                target_rpe,
+                ..
+            } = e
+            {
+                if let Some(weight) = target_weight {
+                    *weight = (*weight * self.weight_percentage / 100.0 * 2.0).round() / 2.0;
+                }
+                if let Some(rpe) = target_rpe {
+                    *rpe = (*rpe + self.rpe_offset).clamp(1.0, 10.0);
+                }
+            }
+        }
+    }
+}
+
+/// Restore the target weights of training session elements to which the given weight percentage
+/// was applied. The restored weights are rounded to multiples of 0.5 kg. Target RPEs are kept, as
+/// they are not considered by progressions.
+pub fn revert_weight_percentage(weight_percentage: f32, elements: &mut [TrainingSessionElement]) {
+    if weight_percentage <= 0.0 {
+        return;
+    }
+    for e in elements {
+        if let TrainingSessionElement::Set {
+            target_weight: Some(weight),
+            ..
+        } = e
+        {
+            *weight = (*weight * 100.0 / weight_percentage * 2.0).round() / 2.0;
+        }
+    }
+}
+
+/// Planned training days, either fixed routines on weekdays or a rotation of routines every N days.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct Schedule {
+    pub start: NaiveDate,
+    pub kind: ScheduleKind,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub enum ScheduleKind {
+    /// Routine for each weekday, starting with Monday.
+    Weekdays([Option<u32>; 7]),
+    Rotation {
+        interval: u32,
+        routines: Vec<u32>,
     },
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Adherence {
+    pub planned: usize,
+    pub completed: usize,
+}
+
+impl Schedule {
+    const LOOKAHEAD_DAYS: i64 = 366;
+
+    /// Routine planned for the given date.
+    pub fn planned_routine(&self, date: NaiveDate) -> Option<u32> {
+        if date < self.start {
+            return None;
+        }
+        match &self.kind {
+            ScheduleKind::Weekdays(routines) => {
+                routines[date.weekday().num_days_from_monday() as usize]
+            }
+            ScheduleKind::Rotation { interval, routines } => {
+                let days = (date - self.start).num_days();
+                let interval = i64::from((*interval).max(1));
+                if routines.is_empty() || days % interval != 0 {
+                    return None;
+                }
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                routines
+                    .get((days / interval) as usize % routines.len())
+                    .copied()
+            }
+        }
+    }
+
+    /// Next planned training day on or after the given date.
+    pub fn next_planned(&self, from: NaiveDate) -> Option<(NaiveDate, u32)> {
+        (0..=Self::LOOKAHEAD_DAYS)
+            .map(|i| from.max(self.start) + Duration::days(i))
+            .find_map(|date| self.planned_routine(date).map(|id| (date, id)))
+    }
+
+    /// Next planned training day, skipping today if its planned training session was already
+    /// completed.
+    pub fn next_pending(
+        &self,
+        training_sessions: &BTreeMap<u32, TrainingSession>,
+        today: NaiveDate,
+    ) -> Option<(NaiveDate, u32)> {
+        let completed_today = training_sessions
+            .values()
+            .any(|t| t.date == today && t.routine_id == self.planned_routine(today));
+        self.next_planned(if completed_today {
+            today + Duration::days(1)
+        } else {
+            today
+        })
+    }
+
+    /// Planned training days in the given interval that were completed by a training session of
+    /// the planned routine on the same day.
+    pub fn adherence(
+        &self,
+        training_sessions: &BTreeMap<u32, TrainingSession>,
+        first: NaiveDate,
+        last: NaiveDate,
+    ) -> Adherence {
+        let mut adherence = Adherence {
+            planned: 0,
+            completed: 0,
+        };
+        let mut date = first.max(self.start);
+        while date <= last {
+            if let Some(routine_id) = self.planned_routine(date) {
+                adherence.planned += 1;
+                if training_sessions
+                    .values()
+                    .any(|t| t.date == date && t.routine_id == Some(routine_id))
+                {
+                    adherence.completed += 1;
+                }
+            }
+            date += Duration::days(1);
+        }
+        adherence
+    }
+}
+
+impl Adherence {
+    pub fn ratio(&self) -> Option<f32> {
+        if self.planned == 0 {
+            return None;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        Some(self.completed as f32 / self.planned as f32)
+    }
+}
+
+/// Comparison of the achieved values of sets with their target values.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct TargetAdherence {
+    pub sets: u32,
+    pub reps_achieved: u32,
+    pub reps_targeted: u32,
+    pub time_achieved: u32,
+    pub time_targeted: u32,
+    pub weight_achieved: u32,
+    pub weight_targeted: u32,
+    pub rpe_deviation: f32,
+    pub rpe_targeted: u32,
+}
+
+impl TargetAdherence {
+    /// Take the set into account. Sets without any achieved values are ignored.
+    pub fn add(&mut self, element: &TrainingSessionElement) {
+        let TrainingSessionElement::Set {
+            reps,
+            time,
+            weight,
+            rpe,
+            target_reps,
+            target_time,
+            target_weight,
+            target_rpe,
+            set_type,
+            ..
+        } = element
+        else {
+            return;
+        };
+        if reps.is_none() && time.is_none() && weight.is_none() && rpe.is_none() {
+            return;
+        }
+        self.sets += 1;
+        if let Some(target_reps) = target_reps {
+            self.reps_targeted += 1;
+            self.reps_achieved += u32::from(reps.map_or(false, |r| r >= *target_reps));
+        }
+        if let Some(target_time) = target_time {
+            self.time_targeted += 1;
+            self.time_achieved += u32::from(time.map_or(false, |t| t >= *target_time));
+        }
+        if let Some(target_weight) = target_weight {
+            self.weight_targeted += 1;
+            self.weight_achieved +=
+                u32::from(weight.map_or(false, |w| w >= *target_weight - f32::EPSILON));
+        }
+        if let (Some(rpe), Some(target_rpe), true) = (rpe, target_rpe, set_type.has_rpe_target()) {
+            self.rpe_targeted += 1;
+            self.rpe_deviation += rpe - target_rpe;
+        }
+    }
+
+    pub fn merge(&mut self, other: &TargetAdherence) {
+        self.sets += other.sets;
+        self.reps_achieved += other.reps_achieved;
+        self.reps_targeted += other.reps_targeted;
+        self.time_achieved += other.time_achieved;
+        self.time_targeted += other.time_targeted;
+        self.weight_achieved += other.weight_achieved;
+        self.weight_targeted += other.weight_targeted;
+        self.rpe_deviation += other.rpe_deviation;
+        self.rpe_targeted += other.rpe_targeted;
+    }
+
+    pub fn reps(&self) -> Option<f32> {
+        ratio(self.reps_achieved, self.reps_targeted)
+    }
+
+    pub fn time(&self) -> Option<f32> {
+        ratio(self.time_achieved, self.time_targeted)
+    }
+
+    pub fn weight(&self) -> Option<f32> {
+        ratio(self.weight_achieved, self.weight_targeted)
+    }
+
+    /// Share of all targets for reps, time and weight that were achieved.
+    pub fn targets(&self) -> Option<f32> {
+        ratio(
+            self.reps_achieved + self.time_achieved + self.weight_achieved,
+            self.reps_targeted + self.time_targeted + self.weight_targeted,
+        )
+    }
+
+    /// Average difference between achieved and target RPE. A positive value means that the sets
+    /// were harder than planned.
+    pub fn avg_rpe_deviation(&self) -> Option<f32> {
+        if self.rpe_targeted == 0 {
+            return None;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        Some(self.rpe_deviation / self.rpe_targeted as f32)
+    }
+}
+
+fn ratio(count: u32, total: u32) -> Option<f32> {
+    if total == 0 {
+        return None;
+    }
+    #[allow(clippy::cast_precision_loss)]
+    Some(count as f32 / total as f32)
+}
+
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
 pub struct TrainingSession {
     pub id: u32,
@@ -235,6 +1200,10 @@ pub struct TrainingSession {
     pub date: NaiveDate,
     pub notes: Option<String>,
     pub elements: Vec<TrainingSessionElement>,
+    pub session_rpe: Option<f32>,
+    pub duration: Option<u32>,
+    pub interval_blocks: Vec<IntervalBlock>,
+    pub routine_version_id: Option<u32>,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
@@ -251,6 +1220,7 @@ pub enum TrainingSessionElement {
         target_weight: Option<f32>,
         target_rpe: Option<f32>,
         automatic: bool,
+        set_type: SetType,
     },
     Rest {
         target_time: Option<u32>,
@@ -258,10 +1228,184 @@ pub enum TrainingSessionElement {
     },
 }
 
+/// Whole-session RPE and duration (in minutes) of a training session.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SessionRPE {
+    pub rpe: Option<f32>,
+    pub duration: Option<u32>,
+}
+
 #[derive(serde::Serialize, serde::Deserialize)]
 pub struct Settings {
     pub beep_volume: u8,
     pub automatic_metronome: bool,
+    #[serde(default)]
+    pub load_model: LoadModel,
+    /// Volume of spoken announcements in guided training sessions, 0 disables them.
+    #[serde(default)]
+    pub speech_volume: u8,
+    /// Name of the voice used for spoken announcements, the default voice is used if unset.
+    #[serde(default)]
+    pub speech_voice: Option<String>,
+    /// Whether a screen wake lock is held during a training session.
+    #[serde(default = "default_keep_screen_awake")]
+    pub keep_screen_awake: bool,
+}
+
+fn default_keep_screen_awake() -> bool {
+    true
+}
+
+/// Available bars, plates and collars for loading a barbell.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct BarbellEquipment {
+    /// Weights of the bars, the first bar is used by default.
+    pub bars: Vec<f32>,
+    pub plates: Vec<PlatePair>,
+    /// Combined weight of both collars.
+    pub collars: f32,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct PlatePair {
+    pub weight: f32,
+    pub count: u32,
+}
+
+/// Plates on each side of a bar.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlateBreakdown {
+    pub bar: f32,
+    pub collars: f32,
+    /// Plates on one side in descending order.
+    pub plates: Vec<f32>,
+}
+
+impl Default for BarbellEquipment {
+    fn default() -> Self {
+        BarbellEquipment {
+            bars: vec![20.0, 15.0, 10.0],
+            plates: [25.0, 20.0, 15.0, 10.0, 5.0, 2.5, 1.25]
+                .into_iter()
+                .map(|weight| PlatePair {
+                    weight,
+                    count: if weight >= 20.0 { 4 } else { 2 },
+                })
+                .collect(),
+            collars: 0.0,
+        }
+    }
+}
+
+impl BarbellEquipment {
+    /// Granularity of plate weights in kg.
+    const UNIT: f32 = 0.05;
+
+    /// Plates needed to load the bar to the given weight. If the weight cannot be loaded exactly,
+    /// the closest achievable weight is chosen, preferring the lighter one.
+    pub fn plate_breakdown(&self, bar: f32, weight: f32) -> PlateBreakdown {
+        let to_units = |weight: f32| {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let units = (weight / Self::UNIT).round().max(0.0) as usize;
+            units
+        };
+        let mut plates = self
+            .plates
+            .iter()
+            .filter(|p| p.weight > 0.0)
+            .flat_map(|p| std::iter::repeat(p.weight).take(p.count as usize))
+            .collect::<Vec<_>>();
+        plates.sort_by(|a, b| b.total_cmp(a));
+        let max = plates.iter().map(|p| to_units(*p)).sum::<usize>();
+        // Plate and previous sum by which each sum per side has been reached first
+        let mut reached: Vec<Option<(usize, usize)>> = vec![None; max + 1];
+        let mut reachable = vec![false; max + 1];
+        reachable[0] = true;
+        for (i, plate) in plates.iter().enumerate() {
+            let units = to_units(*plate);
+            for sum in (units..=max).rev() {
+                if not(reachable[sum]) && reachable[sum - units] {
+                    reachable[sum] = true;
+                    reached[sum] = Some((i, sum - units));
+                }
+            }
+        }
+        let target = to_units((weight - bar - self.collars) / 2.0);
+        let sum = (0..=max)
+            .filter(|sum| reachable[*sum])
+            .min_by_key(|sum| (sum.abs_diff(target), *sum))
+            .unwrap_or(0);
+        // Prefer the heaviest plates, which usually leads to the least number of plates
+        let mut result = vec![];
+        let mut remaining = sum;
+        for plate in &plates {
+            let units = to_units(*plate);
+            if units <= remaining {
+                result.push(*plate);
+                remaining -= units;
+            }
+        }
+        if remaining > 0 {
+            result.clear();
+            let mut sum = sum;
+            while let Some((i, previous)) = reached[sum] {
+                result.push(plates[i]);
+                sum = previous;
+            }
+            result.sort_by(|a, b| b.total_cmp(a));
+        }
+        PlateBreakdown {
+            bar,
+            collars: self.collars,
+            plates: result,
+        }
+    }
+}
+
+impl BarbellEquipment {
+    /// Reps and percentages of the working weight of the warm-up sets following a set with the
+    /// empty bar.
+    const WARM_UP_RAMP: [(u32, f32); 3] = [(5, 0.4), (3, 0.6), (1, 0.8)];
+    const WARM_UP_BAR_REPS: u32 = 10;
+
+    /// Reps and weights of warm-up sets for the given working weight using the default bar. The
+    /// weights are rounded to achievable weights and warm-up sets which would not be heavier than
+    /// the previous set are omitted.
+    pub fn warm_up_sets(&self, working_weight: f32) -> Vec<(u32, f32)> {
+        let Some(bar) = self.bars.first().copied() else {
+            return vec![];
+        };
+        let mut result = vec![];
+        if working_weight <= bar {
+            return result;
+        }
+        result.push((Self::WARM_UP_BAR_REPS, bar));
+        for (reps, percentage) in Self::WARM_UP_RAMP {
+            let weight = self
+                .plate_breakdown(bar, working_weight * percentage)
+                .weight();
+            if result.last().map_or(false, |(_, w)| weight > *w) && weight < working_weight {
+                result.push((reps, weight));
+            }
+        }
+        result
+    }
+}
+
+impl PlateBreakdown {
+    pub fn weight(&self) -> f32 {
+        self.bar + self.collars + 2.0 * self.plates.iter().sum::<f32>()
+    }
+}
+
+/// Definition of the load of a training session, which is used for all load statistics.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LoadModel {
+    /// Sum of the loads of all sets, based on the RPE of each set.
+    #[default]
+    SetRPE,
+    /// Session RPE multiplied by the session duration in minutes (Foster).
+    SessionRPE,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Clone)]
@@ -271,6 +1415,8 @@ pub struct OngoingTrainingSession {
     pub section_idx: usize,
     pub section_start_time: DateTime<Utc>,
     pub timer_state: TimerState,
+    #[serde(default)]
+    pub block_start_time: Option<DateTime<Utc>>,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, Copy)]
@@ -336,23 +1482,299 @@ impl BodyFat {
             None
         }
     }
-
-    fn jackson_pollock(sum: f32, k0: f32, k1: f32, k2: f32, ka: f32) -> f32 {
-        let age = 30.; // assume an age of 30
-        (495. / (k0 - (k1 * sum) + (k2 * sum * sum) - (ka * age))) - 450.
-    }
+
+    fn jackson_pollock(sum: f32, k0: f32, k1: f32, k2: f32, ka: f32) -> f32 {
+        let age = 30.; // assume an age of 30
+        (495. / (k0 - (k1 * sum) + (k2 * sum * sum) - (ka * age))) - 450.
+    }
+}
+
+impl Exercise {
+    /// Exercises which can be performed instead of this exercise. Explicitly declared alternatives
+    /// (in either direction) come first, followed by exercises with the same movement pattern and
+    /// at least one common primary muscle group.
+    pub fn alternatives<'a>(&self, exercises: &'a BTreeMap<u32, Exercise>) -> Vec<&'a Exercise> {
+        let mut explicit = exercises
+            .values()
+            .filter(|e| {
+                e.id != self.id
+                    && (self.metadata.alternatives.contains(&e.id)
+                        || e.metadata.alternatives.contains(&self.id))
+            })
+            .collect::<Vec<_>>();
+        explicit.sort_by(|a, b| a.name.cmp(&b.name));
+        let mut similar = exercises
+            .values()
+            .filter(|e| {
+                e.id != self.id
+                    && not(explicit.iter().any(|a| a.id == e.id))
+                    && self.metadata.movement_pattern.is_some()
+                    && e.metadata.movement_pattern == self.metadata.movement_pattern
+                    && not(self
+                        .metadata
+                        .primary_muscles
+                        .is_disjoint(&e.metadata.primary_muscles))
+            })
+            .collect::<Vec<_>>();
+        similar.sort_by(|a, b| a.name.cmp(&b.name));
+        explicit.extend(similar);
+        explicit
+    }
+
+    /// Contribution of one set of this exercise to the set volume of each muscle group.
+    pub fn muscle_group_credits(&self) -> Vec<(MuscleGroup, f32)> {
+        self.metadata
+            .primary_muscles
+            .iter()
+            .map(|m| (*m, 1.0))
+            .chain(
+                self.metadata
+                    .secondary_muscles
+                    .iter()
+                    .map(|m| (*m, MuscleGroup::SECONDARY_SET_CREDIT)),
+            )
+            .collect()
+    }
+}
+
+impl Routine {
+    pub fn exercises(&self) -> BTreeSet<u32> {
+        self.sections
+            .iter()
+            .flat_map(RoutinePart::exercises)
+            .collect::<BTreeSet<_>>()
+    }
+
+    /// Number of activities of one of the given exercises.
+    pub fn num_activities_of(&self, exercise_ids: &BTreeSet<u32>) -> usize {
+        self.sections
+            .iter()
+            .map(|p| p.num_activities_of(exercise_ids))
+            .sum()
+    }
+
+    /// Estimated set volume per muscle group of a single training session of this routine. Only
+    /// sets with a target RPE of at least 7 are considered as hard sets.
+    pub fn set_volume_per_muscle_group(
+        &self,
+        exercises: &BTreeMap<u32, Exercise>,
+    ) -> BTreeMap<MuscleGroup, f32> {
+        let mut result = BTreeMap::new();
+        for part in &self.sections {
+            part.add_set_volume_per_muscle_group(exercises, 1.0, &mut result);
+        }
+        result
+    }
+
+    /// Estimate the duration and the number of hard sets of a single training session of this
+    /// routine and detect likely mistakes.
+    pub fn analysis(&self) -> RoutineAnalysis {
+        RoutineAnalysis::of(&self.sections)
+    }
+}
+
+/// Characteristics of a single training session of a routine.
+#[derive(Debug, Default, PartialEq)]
+pub struct RoutineAnalysis {
+    /// Estimated duration in seconds.
+    pub duration: u32,
+    /// Number of hard sets per exercise.
+    pub hard_sets: BTreeMap<u32, u32>,
+    pub warnings: Vec<RoutineWarning>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum RoutineWarning {
+    EmptySections(usize),
+    ActivitiesWithoutExercise(usize),
+    TooManySetsOfExercise { exercise_id: u32, sets: u32 },
+    TooManySets(u32),
+}
+
+impl RoutineAnalysis {
+    /// Assumed duration of a single repetition.
+    pub const SECONDS_PER_REP: u32 = 3;
+    /// Assumed duration of a set without target reps or time.
+    pub const SECONDS_PER_SET: u32 = 30;
+    pub const MAX_HARD_SETS_PER_EXERCISE: u32 = 12;
+    pub const MAX_HARD_SETS: u32 = 40;
+
+    pub fn of(parts: &[RoutinePart]) -> Self {
+        let mut result = RoutineAnalysis::default();
+        let mut empty_sections = 0;
+        let mut activities_without_exercise = 0;
+        for p in parts {
+            let duration =
+                result.add_part(p, 1, &mut empty_sections, &mut activities_without_exercise);
+            result.duration = result.duration.saturating_add(duration);
+        }
+        if empty_sections > 0 {
+            result
+                .warnings
+                .push(RoutineWarning::EmptySections(empty_sections));
+        }
+        if activities_without_exercise > 0 {
+            result
+                .warnings
+                .push(RoutineWarning::ActivitiesWithoutExercise(
+                    activities_without_exercise,
+                ));
+        }
+        for (exercise_id, sets) in &result.hard_sets {
+            if *sets > Self::MAX_HARD_SETS_PER_EXERCISE {
+                result.warnings.push(RoutineWarning::TooManySetsOfExercise {
+                    exercise_id: *exercise_id,
+                    sets: *sets,
+                });
+            }
+        }
+        let sets = result
+            .hard_sets
+            .values()
+            .fold(0, |sum: u32, sets| sum.saturating_add(*sets));
+        if sets > Self::MAX_HARD_SETS {
+            result.warnings.push(RoutineWarning::TooManySets(sets));
+        }
+        result
+    }
+
+    /// Record the hard sets of the part and return its estimated duration.
+    fn add_part(
+        &mut self,
+        part: &RoutinePart,
+        factor: u32,
+        empty_sections: &mut usize,
+        activities_without_exercise: &mut usize,
+    ) -> u32 {
+        match part {
+            RoutinePart::RoutineSection {
+                rounds,
+                parts,
+                kind,
+            } => {
+                if parts.is_empty() {
+                    *empty_sections += 1;
+                }
+                let factor = factor.saturating_mul(kind.planned_rounds(*rounds));
+                let round: u32 = parts
+                    .iter()
+                    .map(|p| self.add_part(p, factor, empty_sections, activities_without_exercise))
+                    .fold(0, u32::saturating_add);
+                let exercises = parts
+                    .iter()
+                    .filter(|p| {
+                        matches!(
+                            p,
+                            RoutinePart::RoutineActivity {
+                                exercise_id: Some(_),
+                                ..
+                            }
+                        )
+                    })
+                    .count();
+                match *kind {
+                    SectionKind::Sequential => rounds.saturating_mul(round),
+                    SectionKind::Emom { interval } => rounds.saturating_mul(interval),
+                    SectionKind::Amrap { time_cap } => time_cap,
+                    SectionKind::Tabata { work, rest } => rounds
+                        .saturating_mul(u32::try_from(exercises).unwrap_or(u32::MAX))
+                        .saturating_mul(work + rest),
+                    SectionKind::ForTime { time_cap } => {
+                        if time_cap > 0 {
+                            rounds.saturating_mul(round).min(time_cap)
+                        } else {
+                            rounds.saturating_mul(round)
+                        }
+                    }
+                }
+            }
+            RoutinePart::RoutineActivity {
+                exercise_id,
+                reps,
+                time,
+                rpe,
+                ..
+            } => {
+                let Some(exercise_id) = exercise_id else {
+                    if *time == 0 || *reps > 0 {
+                        *activities_without_exercise += 1;
+                    }
+                    return *time;
+                };
+                if is_hard_set(Some(*rpe)) {
+                    let sets = self.hard_sets.entry(*exercise_id).or_insert(0);
+                    *sets = sets.saturating_add(factor);
+                }
+                if *reps == 0 && *time == 0 {
+                    Self::SECONDS_PER_SET
+                } else {
+                    (*time).max(reps.saturating_mul(Self::SECONDS_PER_REP))
+                }
+            }
+        }
+    }
 }
 
-impl Routine {
-    pub fn exercises(&self) -> BTreeSet<u32> {
-        self.sections
-            .iter()
-            .flat_map(RoutinePart::exercises)
-            .collect::<BTreeSet<_>>()
+impl RoutineWarning {
+    pub fn description(&self, exercises: &BTreeMap<u32, Exercise>) -> String {
+        match self {
+            RoutineWarning::EmptySections(1) => String::from("Section without activities"),
+            RoutineWarning::EmptySections(n) => format!("{n} sections without activities"),
+            RoutineWarning::ActivitiesWithoutExercise(1) => {
+                String::from("Activity without exercise that is not a rest")
+            }
+            RoutineWarning::ActivitiesWithoutExercise(n) => {
+                format!("{n} activities without exercise that are not rests")
+            }
+            RoutineWarning::TooManySetsOfExercise { exercise_id, sets } => format!(
+                "{sets} hard sets of {} in a single session",
+                exercises
+                    .get(exercise_id)
+                    .map_or("unknown exercise", |e| &e.name)
+            ),
+            RoutineWarning::TooManySets(sets) => {
+                format!("{sets} hard sets in a single session")
+            }
+        }
     }
 }
 
 impl RoutinePart {
+    fn add_set_volume_per_muscle_group(
+        &self,
+        exercises: &BTreeMap<u32, Exercise>,
+        factor: f32,
+        result: &mut BTreeMap<MuscleGroup, f32>,
+    ) {
+        match self {
+            RoutinePart::RoutineSection {
+                rounds,
+                parts,
+                kind,
+            } => {
+                for p in parts {
+                    #[allow(clippy::cast_precision_loss)]
+                    p.add_set_volume_per_muscle_group(
+                        exercises,
+                        factor * kind.planned_rounds(*rounds) as f32,
+                        result,
+                    );
+                }
+            }
+            RoutinePart::RoutineActivity {
+                exercise_id, rpe, ..
+            } => {
+                if is_hard_set(Some(*rpe)) {
+                    if let Some(exercise) = exercise_id.and_then(|id| exercises.get(&id)) {
+                        for (muscle_group, credit) in exercise.muscle_group_credits() {
+                            *result.entry(muscle_group).or_insert(0.0) += factor * credit;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
     fn exercises(&self) -> BTreeSet<u32> {
         let mut result: BTreeSet<u32> = BTreeSet::new();
         match self {
@@ -369,9 +1791,31 @@ impl RoutinePart {
         }
         result
     }
+
+    fn num_activities_of(&self, exercise_ids: &BTreeSet<u32>) -> usize {
+        match self {
+            RoutinePart::RoutineSection { parts, .. } => parts
+                .iter()
+                .map(|p| p.num_activities_of(exercise_ids))
+                .sum(),
+            RoutinePart::RoutineActivity { exercise_id, .. } => {
+                usize::from(exercise_id.map_or(false, |id| exercise_ids.contains(&id)))
+            }
+        }
+    }
 }
 
 impl TrainingSession {
+    /// Number of sets of one of the given exercises.
+    pub fn num_sets_of(&self, exercise_ids: &BTreeSet<u32>) -> usize {
+        self.elements
+            .iter()
+            .filter(|e| {
+                matches!(e, TrainingSessionElement::Set { exercise_id, .. } if exercise_ids.contains(exercise_id))
+            })
+            .count()
+    }
+
     pub fn exercises(&self) -> BTreeSet<u32> {
         self.elements
             .iter()
@@ -382,10 +1826,36 @@ impl TrainingSession {
             .collect::<BTreeSet<_>>()
     }
 
+    /// All elements except warm-up sets, which are excluded from all statistics.
+    fn working_elements(&self) -> impl Iterator<Item = &TrainingSessionElement> {
+        self.elements.iter().filter(|e| match e {
+            TrainingSessionElement::Set { set_type, .. } => set_type.is_working(),
+            TrainingSessionElement::Rest { .. } => true,
+        })
+    }
+
+    pub fn target_adherence(&self) -> TargetAdherence {
+        let mut result = TargetAdherence::default();
+        for e in self.working_elements() {
+            result.add(e);
+        }
+        result
+    }
+
+    /// Target adherence of the sets of each exercise.
+    pub fn target_adherence_per_exercise(&self) -> BTreeMap<u32, TargetAdherence> {
+        let mut result: BTreeMap<u32, TargetAdherence> = BTreeMap::new();
+        for e in self.working_elements() {
+            if let TrainingSessionElement::Set { exercise_id, .. } = e {
+                result.entry(*exercise_id).or_default().add(e);
+            }
+        }
+        result
+    }
+
     pub fn avg_reps(&self) -> Option<f32> {
         let sets = &self
-            .elements
-            .iter()
+            .working_elements()
             .filter_map(|e| match e {
                 TrainingSessionElement::Set { reps, .. } => *reps,
                 _ => None,
@@ -401,8 +1871,7 @@ impl TrainingSession {
 
     pub fn avg_time(&self) -> Option<f32> {
         let sets = &self
-            .elements
-            .iter()
+            .working_elements()
             .filter_map(|e| match e {
                 TrainingSessionElement::Set { time, .. } => *time,
                 _ => None,
@@ -418,8 +1887,7 @@ impl TrainingSession {
 
     pub fn avg_weight(&self) -> Option<f32> {
         let sets = &self
-            .elements
-            .iter()
+            .working_elements()
             .filter_map(|e| match e {
                 TrainingSessionElement::Set { weight, .. } => *weight,
                 _ => None,
@@ -435,8 +1903,7 @@ impl TrainingSession {
 
     pub fn avg_rpe(&self) -> Option<f32> {
         let sets = &self
-            .elements
-            .iter()
+            .working_elements()
             .filter_map(|e| match e {
                 TrainingSessionElement::Set { rpe, .. } => *rpe,
                 _ => None,
@@ -452,34 +1919,70 @@ impl TrainingSession {
 
     pub fn load(&self) -> u32 {
         let sets = &self
-            .elements
-            .iter()
+            .working_elements()
             .filter_map(|e| match e {
                 TrainingSessionElement::Set {
                     reps, time, rpe, ..
-                } => Some(if let Some(rpe) = *rpe {
-                    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
-                    if rpe > 5.0 {
-                        (2.0_f32).powf(rpe - 5.0).round() as u32
-                    } else {
-                        1
-                    }
-                } else {
-                    u32::from(reps.is_some() || time.is_some())
-                }),
+                } => Some(set_load(*rpe, reps.is_some() || time.is_some())),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        sets.iter().sum::<u32>()
+    }
+
+    /// Session load as defined by Foster (session RPE × duration in minutes).
+    pub fn session_load(&self) -> Option<u32> {
+        #[allow(
+            clippy::cast_possible_truncation,
+            clippy::cast_precision_loss,
+            clippy::cast_sign_loss
+        )]
+        Some((self.session_rpe? * self.duration? as f32).round() as u32)
+    }
+
+    pub fn load_by(&self, load_model: LoadModel) -> u32 {
+        match load_model {
+            LoadModel::SetRPE => self.load(),
+            LoadModel::SessionRPE => self.session_load().unwrap_or(0),
+        }
+    }
+
+    /// Expected load of a planned training session, based on the target values.
+    pub fn target_load(&self) -> u32 {
+        let sets = &self
+            .working_elements()
+            .filter_map(|e| match e {
+                TrainingSessionElement::Set {
+                    target_reps,
+                    target_time,
+                    target_rpe,
+                    ..
+                } => Some(set_load(
+                    *target_rpe,
+                    target_reps.is_some() || target_time.is_some(),
+                )),
                 _ => None,
             })
             .collect::<Vec<_>>();
         sets.iter().sum::<u32>()
     }
 
+    /// Whether any set of the training session has been performed.
+    pub fn performed(&self) -> bool {
+        self.elements.iter().any(|e| {
+            matches!(
+                e,
+                TrainingSessionElement::Set { reps, time, .. } if reps.is_some() || time.is_some()
+            )
+        })
+    }
+
     pub fn set_volume(&self) -> u32 {
         let sets = &self
-            .elements
-            .iter()
+            .working_elements()
             .filter_map(|e| match e {
                 TrainingSessionElement::Set { rpe, .. } => {
-                    if rpe.unwrap_or(0.0) >= 7.0 {
+                    if is_hard_set(*rpe) {
                         Some(1)
                     } else {
                         None
@@ -491,10 +1994,31 @@ impl TrainingSession {
         sets.iter().sum::<u32>()
     }
 
+    pub fn set_volume_per_muscle_group(
+        &self,
+        exercises: &BTreeMap<u32, Exercise>,
+    ) -> BTreeMap<MuscleGroup, f32> {
+        let mut result = BTreeMap::new();
+        for e in self.working_elements() {
+            if let TrainingSessionElement::Set {
+                exercise_id, rpe, ..
+            } = e
+            {
+                if is_hard_set(*rpe) {
+                    if let Some(exercise) = exercises.get(exercise_id) {
+                        for (muscle_group, credit) in exercise.muscle_group_credits() {
+                            *result.entry(muscle_group).or_insert(0.0) += credit;
+                        }
+                    }
+                }
+            }
+        }
+        result
+    }
+
     pub fn volume_load(&self) -> u32 {
         let sets = &self
-            .elements
-            .iter()
+            .working_elements()
             .filter_map(|e| match e {
                 TrainingSessionElement::Set { reps, weight, .. } => {
                     if let Some(reps) = reps {
@@ -520,8 +2044,7 @@ impl TrainingSession {
 
     pub fn tut(&self) -> u32 {
         let sets = &self
-            .elements
-            .iter()
+            .working_elements()
             .map(|e| match e {
                 TrainingSessionElement::Set { reps, time, .. } => {
                     reps.unwrap_or(1) * time.unwrap_or(0)
@@ -533,6 +2056,25 @@ impl TrainingSession {
     }
 }
 
+/// Whether a set with the given RPE counts as hard set. Sets without an RPE are not considered as
+/// hard sets, both in training sessions and in routines.
+fn is_hard_set(rpe: Option<f32>) -> bool {
+    rpe.unwrap_or(0.0) >= 7.0
+}
+
+fn set_load(rpe: Option<f32>, performed: bool) -> u32 {
+    if let Some(rpe) = rpe {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        if rpe > 5.0 {
+            (2.0_f32).powf(rpe - 5.0).round() as u32
+        } else {
+            1
+        }
+    } else {
+        u32::from(performed)
+    }
+}
+
 impl OngoingTrainingSession {
     pub fn new(training_session_id: u32) -> OngoingTrainingSession {
         OngoingTrainingSession {
@@ -541,6 +2083,7 @@ impl OngoingTrainingSession {
             section_idx: 0,
             section_start_time: Utc::now(),
             timer_state: TimerState::Unset,
+            block_start_time: None,
         }
     }
 }
@@ -638,19 +2181,102 @@ pub fn calculate_cycle_stats(cycles: &[&Cycle]) -> CycleStats {
     }
 }
 
-fn calculate_training_stats(training_sessions: &[&TrainingSession]) -> TrainingStats {
-    let short_term_load = calculate_weighted_sum_of_load(training_sessions, 7);
+fn calculate_training_stats(
+    training_sessions: &[&TrainingSession],
+    exercises: &BTreeMap<u32, Exercise>,
+    load_model: LoadModel,
+) -> TrainingStats {
+    let short_term_load = calculate_weighted_sum_of_load(training_sessions, load_model, 7);
     let long_term_load = calculate_average_weighted_sum_of_load(&short_term_load, 28);
+    let daily_load =
+        calculate_daily_load(training_sessions, load_model, TrainingStats::FORECAST_DAYS);
+    let fitness = calculate_exponential_moving_average_of_load(
+        &daily_load,
+        TrainingStats::FITNESS_TIME_CONSTANT,
+    );
+    let fatigue = calculate_exponential_moving_average_of_load(
+        &daily_load,
+        TrainingStats::FATIGUE_TIME_CONSTANT,
+    );
+    let performance = calculate_performance(&fitness, &fatigue);
     TrainingStats {
         short_term_load,
         long_term_load,
         total_set_volume_per_week: calculate_total_set_volume_per_week(training_sessions),
         avg_rpe_per_week: calculate_avg_rpe_per_week(training_sessions),
+        fitness,
+        fatigue,
+        performance,
+        set_volume_per_muscle_group_per_week: calculate_set_volume_per_muscle_group_per_week(
+            training_sessions,
+            exercises,
+        ),
+    }
+}
+
+/// Load per day from the first training session until the end of the forecast period. Training
+/// sessions in the future are considered as planned and contribute their target load, all other
+/// days of the forecast period are assumed to be rest days.
+fn calculate_daily_load(
+    training_sessions: &[&TrainingSession],
+    load_model: LoadModel,
+    forecast_days: i64,
+) -> Vec<(NaiveDate, f32)> {
+    let mut result: BTreeMap<NaiveDate, f32> = BTreeMap::new();
+
+    let today = Local::now().date_naive();
+    let mut day = training_sessions
+        .iter()
+        .map(|t| t.date)
+        .min()
+        .unwrap_or(today);
+    while day <= today + Duration::days(forecast_days) {
+        result.insert(day, 0.0);
+        day += Duration::days(1);
+    }
+
+    for t in training_sessions {
+        let load = if t.date > today && load_model == LoadModel::SetRPE {
+            t.target_load()
+        } else {
+            t.load_by(load_model)
+        };
+        #[allow(clippy::cast_precision_loss)]
+        result.entry(t.date).and_modify(|e| *e += load as f32);
     }
+
+    result.into_iter().collect()
+}
+
+/// Exponentially weighted moving average of the daily load, used for fitness and fatigue in the
+/// fitness-fatigue model by Banister.
+fn calculate_exponential_moving_average_of_load(
+    daily_load: &[(NaiveDate, f32)],
+    time_constant: f32,
+) -> Vec<(NaiveDate, f32)> {
+    let mut average = 0.0;
+    daily_load
+        .iter()
+        .map(|(date, load)| {
+            average += (load - average) / time_constant;
+            (*date, average)
+        })
+        .collect()
+}
+
+/// Performance (also called form) as the difference between fitness and fatigue.
+fn calculate_performance(
+    fitness: &[(NaiveDate, f32)],
+    fatigue: &[(NaiveDate, f32)],
+) -> Vec<(NaiveDate, f32)> {
+    zip(fitness, fatigue)
+        .map(|((date, fitness), (_, fatigue))| (*date, fitness - fatigue))
+        .collect()
 }
 
 fn calculate_weighted_sum_of_load(
     training_sessions: &[&TrainingSession],
+    load_model: LoadModel,
     window_size: usize,
 ) -> Vec<(NaiveDate, f32)> {
     let mut result: BTreeMap<NaiveDate, f32> = BTreeMap::new();
@@ -666,8 +2292,8 @@ fn calculate_weighted_sum_of_load(
         #[allow(clippy::cast_precision_loss)]
         result
             .entry(t.date)
-            .and_modify(|e| *e += t.load() as f32)
-            .or_insert(t.load() as f32);
+            .and_modify(|e| *e += t.load_by(load_model) as f32)
+            .or_insert(t.load_by(load_model) as f32);
     }
 
     #[allow(clippy::cast_precision_loss)]
@@ -729,6 +2355,44 @@ fn calculate_total_set_volume_per_week(
     result.into_iter().collect()
 }
 
+fn calculate_set_volume_per_muscle_group_per_week(
+    training_sessions: &[&TrainingSession],
+    exercises: &BTreeMap<u32, Exercise>,
+) -> BTreeMap<MuscleGroup, Vec<(NaiveDate, f32)>> {
+    let mut set_volume: BTreeMap<MuscleGroup, BTreeMap<NaiveDate, f32>> = BTreeMap::new();
+
+    for t in training_sessions {
+        for (muscle_group, volume) in t.set_volume_per_muscle_group(exercises) {
+            *set_volume
+                .entry(muscle_group)
+                .or_default()
+                .entry(t.date.week(Weekday::Mon).last_day())
+                .or_insert(0.0) += volume;
+        }
+    }
+
+    let today = Local::now().date_naive();
+    let first = training_sessions
+        .iter()
+        .map(|t| t.date)
+        .min()
+        .unwrap_or(today);
+
+    set_volume
+        .into_iter()
+        .map(|(muscle_group, mut weeks)| {
+            let mut day = first;
+            while day <= today.week(Weekday::Mon).last_day() {
+                weeks
+                    .entry(day.week(Weekday::Mon).last_day())
+                    .or_insert(0.0);
+                day += Duration::days(7);
+            }
+            (muscle_group, weeks.into_iter().collect())
+        })
+        .collect()
+}
+
 fn calculate_avg_rpe_per_week(training_sessions: &[&TrainingSession]) -> Vec<(NaiveDate, f32)> {
     let mut result: BTreeMap<NaiveDate, Vec<f32>> = BTreeMap::new();
 
@@ -824,36 +2488,79 @@ pub enum Msg {
 
     ReadExercises,
     ExercisesRead(Result<Vec<Exercise>, String>),
-    CreateExercise(String),
+    CreateExercise(String, ExerciseMetadata),
     ExerciseCreated(Result<Exercise, String>),
     ReplaceExercise(Exercise),
     ExerciseReplaced(Result<Exercise, String>),
     DeleteExercise(u32),
     ExerciseDeleted(Result<u32, String>),
+    MergeExercises(u32, BTreeSet<u32>),
+    ExercisesMerged(Result<Exercise, String>),
 
     ReadRoutines,
     RoutinesRead(Result<Vec<Routine>, String>),
-    CreateRoutine(String),
+    CreateRoutine(String, String, Vec<RoutinePart>),
     RoutineCreated(Result<Routine, String>),
     ModifyRoutine(u32, Option<String>, Option<Vec<RoutinePart>>),
     RoutineModified(Result<Routine, String>),
     DeleteRoutine(u32),
     RoutineDeleted(Result<u32, String>),
+    ReadRoutineVersions,
+    RoutineVersionsRead(Result<Vec<RoutineVersion>, String>),
+    CopyRoutinePart(RoutinePart),
+
+    ReadPrograms,
+    ProgramsRead(Result<Vec<Program>, String>),
+    CreateProgram(String),
+    ProgramCreated(Result<Program, String>),
+    ReplaceProgram(Program),
+    ProgramReplaced(Result<Program, String>),
+    DeleteProgram(u32),
+    ProgramDeleted(Result<u32, String>),
+    AdvanceProgram(u32, u32),
+
+    ReadSchedule,
+    ScheduleRead(Result<Option<Schedule>, String>),
+    SetSchedule(Option<Schedule>),
+    ScheduleSet(Result<Option<Schedule>, String>),
 
     ReadTrainingSessions,
     TrainingSessionsRead(Result<Vec<TrainingSession>, String>),
-    CreateTrainingSession(u32, NaiveDate, String, Vec<TrainingSessionElement>),
+    CreateTrainingSession(
+        u32,
+        NaiveDate,
+        String,
+        Vec<TrainingSessionElement>,
+        Vec<IntervalBlock>,
+    ),
     TrainingSessionCreated(Result<TrainingSession, String>),
-    ModifyTrainingSession(u32, Option<String>, Option<Vec<TrainingSessionElement>>),
+    ModifyTrainingSession(
+        u32,
+        Option<String>,
+        Option<SessionRPE>,
+        Option<Vec<TrainingSessionElement>>,
+        Option<Vec<IntervalBlock>>,
+    ),
     TrainingSessionModified(Result<TrainingSession, String>),
     DeleteTrainingSession(u32),
     TrainingSessionDeleted(Result<u32, String>),
 
     SetBeepVolume(u8),
+    SetSpeechVolume(u8),
+    SetSpeechVoice(Option<String>),
     SetAutomaticMetronome(bool),
+    SetKeepScreenAwake(bool),
+    UpdateWakeLock,
+    WakeLockAcquired(JsValue),
+    SetLoadModel(LoadModel),
+    ReadBarbellEquipment,
+    BarbellEquipmentRead(Result<Option<BarbellEquipment>, String>),
+    SetBarbellEquipment(BarbellEquipment),
+    BarbellEquipmentSet(Result<BarbellEquipment, String>),
 
     StartTrainingSession(u32),
     UpdateTrainingSession(usize, TimerState),
+    UpdateTrainingSessionBlock(Option<DateTime<Utc>>),
     EndTrainingSession,
 }
 
@@ -889,12 +2596,20 @@ pub enum Event {
     ExerciseReplacedErr,
     ExerciseDeletedOk,
     ExerciseDeletedErr,
+    ExercisesMergedOk,
+    ExercisesMergedErr,
     RoutineCreatedOk,
     RoutineCreatedErr,
     RoutineModifiedOk,
     RoutineModifiedErr,
     RoutineDeletedOk,
     RoutineDeletedErr,
+    ProgramCreatedOk,
+    ProgramCreatedErr,
+    ProgramReplacedOk,
+    ProgramReplacedErr,
+    ProgramDeletedOk,
+    ProgramDeletedErr,
     TrainingSessionCreatedOk,
     TrainingSessionCreatedErr,
     TrainingSessionModifiedOk,
@@ -923,6 +2638,10 @@ pub fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
                 .send_msg(Msg::ReadPeriod)
                 .send_msg(Msg::ReadExercises)
                 .send_msg(Msg::ReadRoutines)
+                .send_msg(Msg::ReadRoutineVersions)
+                .send_msg(Msg::ReadPrograms)
+                .send_msg(Msg::ReadSchedule)
+                .send_msg(Msg::ReadBarbellEquipment)
                 .send_msg(Msg::ReadTrainingSessions);
             model.last_refresh = Utc::now();
         }
@@ -932,6 +2651,10 @@ pub fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
             model.period.clear();
             model.exercises.clear();
             model.routines.clear();
+            model.routine_versions.clear();
+            model.programs.clear();
+            model.schedule = None;
+            model.barbell_equipment = None;
             model.training_sessions.clear();
             model.body_weight_stats.clear();
             model.cycles.clear();
@@ -1391,6 +3114,7 @@ pub fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
             let exercises = exercises.into_iter().map(|e| (e.id, e)).collect();
             if model.exercises != exercises {
                 model.exercises = exercises;
+                update_training_stats(model);
                 orders.notify(Event::DataChanged);
             }
             model.loading_exercises = false;
@@ -1401,12 +3125,14 @@ pub fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
                 .push("Failed to read exercises: ".to_owned() + &message);
             model.loading_exercises = false;
         }
-        Msg::CreateExercise(exercise_name) => {
+        Msg::CreateExercise(exercise_name, metadata) => {
+            let mut content = json!(metadata);
+            content["name"] = json!(exercise_name);
             orders.perform_cmd(async move {
                 fetch(
                     Request::new("api/exercises")
                         .method(Method::Post)
-                        .json(&json!({ "name": exercise_name }))
+                        .json(&content)
                         .expect("serialization failed"),
                     Msg::ExerciseCreated,
                 )
@@ -1457,6 +3183,9 @@ pub fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
         }
         Msg::ExerciseDeleted(Ok(id)) => {
             model.exercises.remove(&id);
+            for exercise in model.exercises.values_mut() {
+                exercise.metadata.alternatives.remove(&id);
+            }
             orders.notify(Event::ExerciseDeletedOk);
         }
         Msg::ExerciseDeleted(Err(message)) => {
@@ -1465,6 +3194,32 @@ pub fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
                 .errors
                 .push("Failed to delete exercise: ".to_owned() + &message);
         }
+        Msg::MergeExercises(survivor, exercise_ids) => {
+            orders.perform_cmd(async move {
+                fetch(
+                    Request::new(format!("api/exercises/{survivor}/merge"))
+                        .method(Method::Post)
+                        .json(&json!({ "exercise_ids": exercise_ids }))
+                        .expect("serialization failed"),
+                    Msg::ExercisesMerged,
+                )
+                .await
+            });
+        }
+        Msg::ExercisesMerged(Ok(_)) => {
+            orders
+                .notify(Event::ExercisesMergedOk)
+                .send_msg(Msg::ReadExercises)
+                .send_msg(Msg::ReadRoutines)
+                .send_msg(Msg::ReadRoutineVersions)
+                .send_msg(Msg::ReadTrainingSessions);
+        }
+        Msg::ExercisesMerged(Err(message)) => {
+            orders.notify(Event::ExercisesMergedErr);
+            model
+                .errors
+                .push("Failed to merge exercises: ".to_owned() + &message);
+        }
 
         Msg::ReadRoutines => {
             model.loading_routines = true;
@@ -1486,15 +3241,15 @@ pub fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
                 .push("Failed to read routines: ".to_owned() + &message);
             model.loading_routines = false;
         }
-        Msg::CreateRoutine(routine_name) => {
+        Msg::CreateRoutine(routine_name, notes, sections) => {
             orders.perform_cmd(async move {
                 fetch(
                     Request::new("api/routines")
                         .method(Method::Post)
                         .json(&json!({
                             "name": routine_name,
-                            "notes": "",
-                            "sections": []
+                            "notes": notes,
+                            "sections": sections
                         }))
                         .expect("serialization failed"),
                     Msg::RoutineCreated,
@@ -1502,64 +3257,247 @@ pub fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
                 .await
             });
         }
-        Msg::RoutineCreated(Ok(routine)) => {
-            model.routines.insert(routine.id, routine);
-            orders.notify(Event::RoutineCreatedOk);
+        Msg::RoutineCreated(Ok(routine)) => {
+            model.routines.insert(routine.id, routine);
+            orders
+                .notify(Event::RoutineCreatedOk)
+                .send_msg(Msg::ReadRoutineVersions);
+        }
+        Msg::RoutineCreated(Err(message)) => {
+            orders.notify(Event::RoutineCreatedErr);
+            model
+                .errors
+                .push("Failed to create routine: ".to_owned() + &message);
+        }
+        Msg::ModifyRoutine(id, name, sections) => {
+            let mut content = Map::new();
+            if let Some(name) = name {
+                content.insert("name".into(), json!(name));
+            }
+            if let Some(sections) = sections {
+                content.insert("sections".into(), json!(sections));
+            }
+            orders.perform_cmd(async move {
+                fetch(
+                    Request::new(format!("api/routines/{id}"))
+                        .method(Method::Patch)
+                        .json(&content)
+                        .expect("serialization failed"),
+                    Msg::RoutineModified,
+                )
+                .await
+            });
+        }
+        Msg::RoutineModified(Ok(routine)) => {
+            model.routines.insert(routine.id, routine);
+            orders
+                .notify(Event::RoutineModifiedOk)
+                .send_msg(Msg::ReadRoutineVersions);
+        }
+        Msg::RoutineModified(Err(message)) => {
+            orders.notify(Event::RoutineModifiedErr);
+            model
+                .errors
+                .push("Failed to modify routine: ".to_owned() + &message);
+        }
+        Msg::DeleteRoutine(id) => {
+            orders.perform_cmd(async move {
+                fetch_no_content(
+                    Request::new(format!("api/routines/{id}")).method(Method::Delete),
+                    Msg::RoutineDeleted,
+                    id,
+                )
+                .await
+            });
+        }
+        Msg::RoutineDeleted(Ok(id)) => {
+            model.routines.remove(&id);
+            model.routine_versions.remove(&id);
+            orders.notify(Event::RoutineDeletedOk);
+        }
+        Msg::RoutineDeleted(Err(message)) => {
+            orders.notify(Event::RoutineDeletedErr);
+            model
+                .errors
+                .push("Failed to delete routine: ".to_owned() + &message);
+        }
+        Msg::ReadRoutineVersions => {
+            orders.skip().perform_cmd(async {
+                fetch("api/routine_versions", Msg::RoutineVersionsRead).await
+            });
+        }
+        Msg::RoutineVersionsRead(Ok(versions)) => {
+            let mut routine_versions = BTreeMap::<u32, Vec<RoutineVersion>>::new();
+            for version in versions {
+                routine_versions
+                    .entry(version.routine_id)
+                    .or_default()
+                    .push(version);
+            }
+            if model.routine_versions != routine_versions {
+                model.routine_versions = routine_versions;
+                orders.notify(Event::DataChanged);
+            }
+        }
+        Msg::RoutineVersionsRead(Err(message)) => {
+            model
+                .errors
+                .push("Failed to read routine versions: ".to_owned() + &message);
+        }
+        Msg::CopyRoutinePart(part) => {
+            model.copied_routine_part = Some(part);
+        }
+
+        Msg::ReadPrograms => {
+            model.loading_programs = true;
+            orders
+                .skip()
+                .perform_cmd(async { fetch("api/programs", Msg::ProgramsRead).await });
+        }
+        Msg::ProgramsRead(Ok(programs)) => {
+            let programs = programs.into_iter().map(|p| (p.id, p)).collect();
+            if model.programs != programs {
+                model.programs = programs;
+                orders.notify(Event::DataChanged);
+            }
+            model.loading_programs = false;
+        }
+        Msg::ProgramsRead(Err(message)) => {
+            model
+                .errors
+                .push("Failed to read programs: ".to_owned() + &message);
+            model.loading_programs = false;
+        }
+        Msg::CreateProgram(name) => {
+            orders.perform_cmd(async move {
+                fetch(
+                    Request::new("api/programs")
+                        .method(Method::Post)
+                        .json(&json!({
+                            "name": name,
+                            "weeks": [ProgramWeek::default()],
+                            "position": null,
+                            "weight_percentages": {}
+                        }))
+                        .expect("serialization failed"),
+                    Msg::ProgramCreated,
+                )
+                .await
+            });
+        }
+        Msg::ProgramCreated(Ok(program)) => {
+            model.programs.insert(program.id, program);
+            orders.notify(Event::ProgramCreatedOk);
         }
-        Msg::RoutineCreated(Err(message)) => {
-            orders.notify(Event::RoutineCreatedErr);
+        Msg::ProgramCreated(Err(message)) => {
+            orders.notify(Event::ProgramCreatedErr);
             model
                 .errors
-                .push("Failed to create routine: ".to_owned() + &message);
+                .push("Failed to create program: ".to_owned() + &message);
         }
-        Msg::ModifyRoutine(id, name, sections) => {
-            let mut content = Map::new();
-            if let Some(name) = name {
-                content.insert("name".into(), json!(name));
-            }
-            if let Some(sections) = sections {
-                content.insert("sections".into(), json!(sections));
-            }
+        Msg::ReplaceProgram(program) => {
             orders.perform_cmd(async move {
                 fetch(
-                    Request::new(format!("api/routines/{id}"))
-                        .method(Method::Patch)
-                        .json(&content)
+                    Request::new(format!("api/programs/{}", program.id))
+                        .method(Method::Put)
+                        .json(&program)
                         .expect("serialization failed"),
-                    Msg::RoutineModified,
+                    Msg::ProgramReplaced,
                 )
                 .await
             });
         }
-        Msg::RoutineModified(Ok(routine)) => {
-            model.routines.insert(routine.id, routine);
-            orders.notify(Event::RoutineModifiedOk);
+        Msg::ProgramReplaced(Ok(program)) => {
+            model.programs.insert(program.id, program);
+            orders.notify(Event::ProgramReplacedOk);
         }
-        Msg::RoutineModified(Err(message)) => {
-            orders.notify(Event::RoutineModifiedErr);
+        Msg::ProgramReplaced(Err(message)) => {
+            orders.notify(Event::ProgramReplacedErr);
             model
                 .errors
-                .push("Failed to modify routine: ".to_owned() + &message);
+                .push("Failed to replace program: ".to_owned() + &message);
         }
-        Msg::DeleteRoutine(id) => {
+        Msg::DeleteProgram(id) => {
             orders.perform_cmd(async move {
                 fetch_no_content(
-                    Request::new(format!("api/routines/{id}")).method(Method::Delete),
-                    Msg::RoutineDeleted,
+                    Request::new(format!("api/programs/{id}")).method(Method::Delete),
+                    Msg::ProgramDeleted,
                     id,
                 )
                 .await
             });
         }
-        Msg::RoutineDeleted(Ok(id)) => {
-            model.routines.remove(&id);
-            orders.notify(Event::RoutineDeletedOk);
+        Msg::ProgramDeleted(Ok(id)) => {
+            model.programs.remove(&id);
+            orders.notify(Event::ProgramDeletedOk);
         }
-        Msg::RoutineDeleted(Err(message)) => {
-            orders.notify(Event::RoutineDeletedErr);
+        Msg::ProgramDeleted(Err(message)) => {
+            orders.notify(Event::ProgramDeletedErr);
             model
                 .errors
-                .push("Failed to delete routine: ".to_owned() + &message);
+                .push("Failed to delete program: ".to_owned() + &message);
+        }
+        Msg::AdvanceProgram(id, training_session_id) => {
+            if let Some(program) = model.programs.get(&id) {
+                let mut program = program.clone();
+                if let Some((position, _)) = program.next_routine() {
+                    program.weight_percentages.insert(
+                        training_session_id,
+                        program.weeks[position.week].weight_percentage,
+                    );
+                }
+                program.advance();
+                orders.send_msg(Msg::ReplaceProgram(program));
+            }
+        }
+
+        Msg::ReadSchedule => {
+            orders
+                .skip()
+                .perform_cmd(async { fetch("api/schedule", Msg::ScheduleRead).await });
+        }
+        Msg::ScheduleRead(Ok(schedule)) => {
+            if model.schedule != schedule {
+                model.schedule = schedule;
+                orders.notify(Event::DataChanged);
+            }
+        }
+        Msg::ScheduleRead(Err(message)) => {
+            model
+                .errors
+                .push("Failed to read schedule: ".to_owned() + &message);
+        }
+        Msg::SetSchedule(schedule) => {
+            if schedule.is_none() && model.schedule.is_none() {
+                return;
+            }
+            orders.perform_cmd(async move {
+                if let Some(schedule) = schedule {
+                    fetch(
+                        Request::new("api/schedule")
+                            .method(Method::Put)
+                            .json(&schedule)
+                            .expect("serialization failed"),
+                        |result: Result<Schedule, String>| Msg::ScheduleSet(result.map(Some)),
+                    )
+                    .await
+                } else {
+                    fetch_no_content(
+                        Request::new("api/schedule").method(Method::Delete),
+                        Msg::ScheduleSet,
+                        None,
+                    )
+                    .await
+                }
+            });
+        }
+        Msg::ScheduleSet(Ok(schedule)) => {
+            model.schedule = schedule;
+        }
+        Msg::ScheduleSet(Err(message)) => {
+            model
+                .errors
+                .push("Failed to set schedule: ".to_owned() + &message);
         }
 
         Msg::ReadTrainingSessions => {
@@ -1572,8 +3510,7 @@ pub fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
             let training_sessions = training_sessions.into_iter().map(|t| (t.id, t)).collect();
             if model.training_sessions != training_sessions {
                 model.training_sessions = training_sessions;
-                model.training_stats =
-                    calculate_training_stats(&model.training_sessions.values().collect::<Vec<_>>());
+                update_training_stats(model);
                 orders.notify(Event::DataChanged);
             }
             model.loading_training_sessions = false;
@@ -1584,7 +3521,7 @@ pub fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
                 .push("Failed to read training sessions: ".to_owned() + &message);
             model.loading_training_sessions = false;
         }
-        Msg::CreateTrainingSession(routine_id, date, notes, elements) => {
+        Msg::CreateTrainingSession(routine_id, date, notes, elements, interval_blocks) => {
             orders.perform_cmd(async move {
                 fetch(
                     Request::new("api/workouts")
@@ -1593,7 +3530,8 @@ pub fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
                             "routine_id": routine_id,
                             "date": date,
                             "notes": notes,
-                            "elements": elements
+                            "elements": elements,
+                            "interval_blocks": interval_blocks
                         }))
                         .expect("serialization failed"),
                     Msg::TrainingSessionCreated,
@@ -1605,9 +3543,10 @@ pub fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
             model
                 .training_sessions
                 .insert(training_session.id, training_session);
-            model.training_stats =
-                calculate_training_stats(&model.training_sessions.values().collect::<Vec<_>>());
-            orders.notify(Event::TrainingSessionCreatedOk);
+            update_training_stats(model);
+            orders
+                .notify(Event::TrainingSessionCreatedOk)
+                .send_msg(Msg::ReadRoutineVersions);
         }
         Msg::TrainingSessionCreated(Err(message)) => {
             orders.notify(Event::TrainingSessionCreatedErr);
@@ -1615,14 +3554,21 @@ pub fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
                 .errors
                 .push("Failed to create training session: ".to_owned() + &message);
         }
-        Msg::ModifyTrainingSession(id, notes, elements) => {
+        Msg::ModifyTrainingSession(id, notes, session_rpe, elements, interval_blocks) => {
             let mut content = Map::new();
             if let Some(notes) = notes {
                 content.insert("notes".into(), json!(notes));
             }
+            if let Some(session_rpe) = session_rpe {
+                content.insert("session_rpe".into(), json!(session_rpe.rpe));
+                content.insert("duration".into(), json!(session_rpe.duration));
+            }
             if let Some(elements) = elements {
                 content.insert("elements".into(), json!(elements));
             }
+            if let Some(interval_blocks) = interval_blocks {
+                content.insert("interval_blocks".into(), json!(interval_blocks));
+            }
             orders.perform_cmd(async move {
                 fetch(
                     Request::new(format!("api/workouts/{id}"))
@@ -1638,8 +3584,7 @@ pub fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
             model
                 .training_sessions
                 .insert(training_session.id, training_session);
-            model.training_stats =
-                calculate_training_stats(&model.training_sessions.values().collect::<Vec<_>>());
+            update_training_stats(model);
             orders.notify(Event::TrainingSessionModifiedOk);
         }
         Msg::TrainingSessionModified(Err(message)) => {
@@ -1660,8 +3605,10 @@ pub fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
         }
         Msg::TrainingSessionDeleted(Ok(id)) => {
             model.training_sessions.remove(&id);
-            model.training_stats =
-                calculate_training_stats(&model.training_sessions.values().collect::<Vec<_>>());
+            for program in model.programs.values_mut() {
+                program.weight_percentages.remove(&id);
+            }
+            update_training_stats(model);
             orders.notify(Event::TrainingSessionDeletedOk);
         }
         Msg::TrainingSessionDeleted(Err(message)) => {
@@ -1676,10 +3623,75 @@ pub fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
             local_storage_set(STORAGE_KEY_SETTINGS, &model.settings, &mut model.errors);
             orders.notify(Event::BeepVolumeChanged);
         }
+        Msg::SetSpeechVolume(value) => {
+            model.settings.speech_volume = value;
+            local_storage_set(STORAGE_KEY_SETTINGS, &model.settings, &mut model.errors);
+        }
+        Msg::SetSpeechVoice(value) => {
+            model.settings.speech_voice = value;
+            local_storage_set(STORAGE_KEY_SETTINGS, &model.settings, &mut model.errors);
+        }
+        Msg::SetKeepScreenAwake(value) => {
+            model.settings.keep_screen_awake = value;
+            local_storage_set(STORAGE_KEY_SETTINGS, &model.settings, &mut model.errors);
+            update_wake_lock(model, orders);
+        }
+        Msg::UpdateWakeLock => {
+            update_wake_lock(model, orders);
+        }
+        Msg::WakeLockAcquired(sentinel) => {
+            if model.wake_lock.is_none() && wake_lock_required(model) {
+                model.wake_lock = Some(sentinel);
+            } else {
+                common::release_wake_lock(&sentinel);
+            }
+        }
         Msg::SetAutomaticMetronome(value) => {
             model.settings.automatic_metronome = value;
             local_storage_set(STORAGE_KEY_SETTINGS, &model.settings, &mut model.errors);
         }
+        Msg::SetLoadModel(value) => {
+            model.settings.load_model = value;
+            local_storage_set(STORAGE_KEY_SETTINGS, &model.settings, &mut model.errors);
+            update_training_stats(model);
+            orders.notify(Event::DataChanged);
+        }
+        Msg::ReadBarbellEquipment => {
+            orders.skip().perform_cmd(async {
+                fetch("api/barbell_equipment", Msg::BarbellEquipmentRead).await
+            });
+        }
+        Msg::BarbellEquipmentRead(Ok(equipment)) => {
+            if model.barbell_equipment != equipment {
+                model.barbell_equipment = equipment;
+                orders.notify(Event::DataChanged);
+            }
+        }
+        Msg::BarbellEquipmentRead(Err(message)) => {
+            model
+                .errors
+                .push("Failed to read barbell equipment: ".to_owned() + &message);
+        }
+        Msg::SetBarbellEquipment(equipment) => {
+            orders.perform_cmd(async move {
+                fetch(
+                    Request::new("api/barbell_equipment")
+                        .method(Method::Put)
+                        .json(&equipment)
+                        .expect("serialization failed"),
+                    Msg::BarbellEquipmentSet,
+                )
+                .await
+            });
+        }
+        Msg::BarbellEquipmentSet(Ok(equipment)) => {
+            model.barbell_equipment = Some(equipment);
+        }
+        Msg::BarbellEquipmentSet(Err(message)) => {
+            model
+                .errors
+                .push("Failed to set barbell equipment: ".to_owned() + &message);
+        }
 
         Msg::StartTrainingSession(training_session_id) => {
             model.ongoing_training_session = Some(OngoingTrainingSession::new(training_session_id));
@@ -1688,6 +3700,7 @@ pub fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
                 &model.ongoing_training_session,
                 &mut model.errors,
             );
+            update_wake_lock(model, orders);
         }
         Msg::UpdateTrainingSession(section_idx, timer_state) => {
             if let Some(ongoing_training_session) = &mut model.ongoing_training_session {
@@ -1701,6 +3714,16 @@ pub fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
                 &mut model.errors,
             );
         }
+        Msg::UpdateTrainingSessionBlock(block_start_time) => {
+            if let Some(ongoing_training_session) = &mut model.ongoing_training_session {
+                ongoing_training_session.block_start_time = block_start_time;
+            }
+            local_storage_set(
+                STORAGE_KEY_ONGOING_TRAINING_SESSION,
+                &model.ongoing_training_session,
+                &mut model.errors,
+            );
+        }
         Msg::EndTrainingSession => {
             model.ongoing_training_session = None;
             local_storage_set(
@@ -1708,6 +3731,7 @@ pub fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
                 &model.ongoing_training_session,
                 &mut model.errors,
             );
+            update_wake_lock(model, orders);
         }
     }
 }
@@ -1745,6 +3769,39 @@ async fn fetch_no_content<'a, Ms, T>(
     }
 }
 
+fn wake_lock_required(model: &Model) -> bool {
+    model.ongoing_training_session.is_some()
+        && model.settings.keep_screen_awake
+        && not(seed::document().hidden())
+}
+
+fn update_wake_lock(model: &mut Model, orders: &mut impl Orders<Msg>) {
+    if model
+        .wake_lock
+        .as_ref()
+        .map_or(false, common::wake_lock_released)
+    {
+        model.wake_lock = None;
+    }
+    if wake_lock_required(model) {
+        if model.wake_lock.is_none() {
+            orders.perform_cmd(async {
+                common::request_wake_lock().await.map(Msg::WakeLockAcquired)
+            });
+        }
+    } else if let Some(sentinel) = model.wake_lock.take() {
+        common::release_wake_lock(&sentinel);
+    }
+}
+
+fn update_training_stats(model: &mut Model) {
+    model.training_stats = calculate_training_stats(
+        &model.training_sessions.values().collect::<Vec<_>>(),
+        &model.exercises,
+        model.settings.load_model,
+    );
+}
+
 fn local_storage_set<T: serde::Serialize>(key: &str, value: &T, errors: &mut Vec<String>) {
     if let Err(message) = gloo_storage::LocalStorage::set(key, value) {
         errors.push(format!("Failed to store {key} in local storage: {message}"));
@@ -1808,4 +3865,854 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_calculate_fitness_fatigue_performance() {
+        let daily_load = [
+            (from_num_days(1), 42.0),
+            (from_num_days(2), 0.0),
+            (from_num_days(3), 0.0),
+        ];
+        let fitness = calculate_exponential_moving_average_of_load(
+            &daily_load,
+            TrainingStats::FITNESS_TIME_CONSTANT,
+        );
+        let fatigue = calculate_exponential_moving_average_of_load(
+            &daily_load,
+            TrainingStats::FATIGUE_TIME_CONSTANT,
+        );
+        let performance = calculate_performance(&fitness, &fatigue);
+        assert_eq!(
+            performance.iter().map(|(d, _)| *d).collect::<Vec<_>>(),
+            vec![from_num_days(1), from_num_days(2), from_num_days(3)]
+        );
+        assert!((fitness[0].1 - 1.0).abs() < f32::EPSILON);
+        assert!((fatigue[0].1 - 6.0).abs() < f32::EPSILON);
+        assert!((performance[0].1 + 5.0).abs() < f32::EPSILON);
+        assert!(fitness[1].1 < fitness[0].1);
+        assert!(fatigue[2].1 < fatigue[1].1);
+        assert!(performance[2].1 > performance[1].1);
+        assert!(calculate_exponential_moving_average_of_load(&[], 7.).is_empty());
+    }
+
+    #[test]
+    fn test_training_session_load_by() {
+        let mut training_session = TrainingSession {
+            id: 1,
+            routine_id: None,
+            date: from_num_days(1),
+            notes: None,
+            elements: vec![TrainingSessionElement::Set {
+                exercise_id: 1,
+                reps: Some(5),
+                time: None,
+                weight: None,
+                rpe: Some(8.0),
+                target_reps: None,
+                target_time: None,
+                target_weight: None,
+                target_rpe: None,
+                automatic: false,
+                set_type: SetType::Working,
+            }],
+            session_rpe: None,
+            duration: None,
+            interval_blocks: vec![],
+            routine_version_id: None,
+        };
+        assert_eq!(training_session.load_by(LoadModel::SetRPE), 8);
+        assert_eq!(training_session.load_by(LoadModel::SessionRPE), 0);
+        training_session.session_rpe = Some(7.5);
+        training_session.duration = Some(60);
+        assert_eq!(training_session.session_load(), Some(450));
+        assert_eq!(training_session.load_by(LoadModel::SessionRPE), 450);
+    }
+
+    #[test]
+    fn test_set_volume_per_muscle_group() {
+        let exercises = BTreeMap::from([(
+            1,
+            Exercise {
+                id: 1,
+                name: String::from("Bench Press"),
+                training_max: None,
+                metadata: ExerciseMetadata {
+                    primary_muscles: BTreeSet::from([MuscleGroup::Chest]),
+                    secondary_muscles: BTreeSet::from([MuscleGroup::Triceps]),
+                    ..ExerciseMetadata::default()
+                },
+            },
+        )]);
+        let set = |rpe| TrainingSessionElement::Set {
+            exercise_id: 1,
+            reps: Some(5),
+            time: None,
+            weight: None,
+            rpe,
+            target_reps: None,
+            target_time: None,
+            target_weight: None,
+            target_rpe: None,
+            automatic: false,
+            set_type: SetType::Working,
+        };
+        let training_session = TrainingSession {
+            id: 1,
+            routine_id: None,
+            date: from_num_days(1),
+            notes: None,
+            elements: vec![set(Some(8.0)), set(Some(7.0)), set(Some(6.0)), set(None)],
+            session_rpe: None,
+            duration: None,
+            interval_blocks: vec![],
+            routine_version_id: None,
+        };
+        assert_eq!(
+            training_session.set_volume_per_muscle_group(&exercises),
+            BTreeMap::from([(MuscleGroup::Chest, 2.0), (MuscleGroup::Triceps, 1.0)])
+        );
+
+        let activity = |exercise_id, rpe| RoutinePart::RoutineActivity {
+            exercise_id,
+            reps: 5,
+            time: 0,
+            weight: 0.0,
+            rpe,
+            automatic: false,
+            options: ActivityOptions::default(),
+        };
+        let routine = Routine {
+            id: 1,
+            name: String::from("A"),
+            notes: None,
+            sections: vec![
+                RoutinePart::RoutineSection {
+                    rounds: 3,
+                    parts: vec![activity(Some(1), 8.0), activity(None, 0.0)],
+                    kind: SectionKind::Sequential,
+                },
+                activity(Some(1), 0.0),
+                activity(Some(1), 6.0),
+            ],
+        };
+        assert_eq!(
+            routine.set_volume_per_muscle_group(&exercises),
+            BTreeMap::from([(MuscleGroup::Chest, 3.0), (MuscleGroup::Triceps, 1.5)])
+        );
+    }
+
+    #[test]
+    fn test_num_sets_and_activities_of() {
+        let exercise_ids = BTreeSet::from([2, 3]);
+        let set = |exercise_id| TrainingSessionElement::Set {
+            exercise_id,
+            reps: Some(5),
+            time: None,
+            weight: None,
+            rpe: None,
+            target_reps: None,
+            target_time: None,
+            target_weight: None,
+            target_rpe: None,
+            automatic: false,
+            set_type: SetType::Working,
+        };
+        let training_session = TrainingSession {
+            id: 1,
+            routine_id: None,
+            date: from_num_days(1),
+            notes: None,
+            elements: vec![set(1), set(2), set(3), set(4)],
+            session_rpe: None,
+            duration: None,
+            interval_blocks: vec![],
+            routine_version_id: None,
+        };
+        assert_eq!(training_session.num_sets_of(&exercise_ids), 2);
+
+        let activity = |exercise_id| RoutinePart::RoutineActivity {
+            exercise_id,
+            reps: 5,
+            time: 0,
+            weight: 0.0,
+            rpe: 0.0,
+            automatic: false,
+            options: ActivityOptions::default(),
+        };
+        let routine = Routine {
+            id: 1,
+            name: String::from("A"),
+            notes: None,
+            sections: vec![
+                RoutinePart::RoutineSection {
+                    rounds: 3,
+                    parts: vec![activity(Some(2)), activity(None)],
+                    kind: SectionKind::Sequential,
+                },
+                activity(Some(3)),
+                activity(Some(4)),
+            ],
+        };
+        assert_eq!(routine.num_activities_of(&exercise_ids), 2);
+    }
+
+    #[test]
+    fn test_exercise_alternatives() {
+        let exercise = |id,
+                        name: &str,
+                        movement_pattern,
+                        primary_muscles: &[MuscleGroup],
+                        alternatives: &[u32]| {
+            (
+                id,
+                Exercise {
+                    id,
+                    name: String::from(name),
+                    training_max: None,
+                    metadata: ExerciseMetadata {
+                        primary_muscles: primary_muscles.iter().copied().collect(),
+                        movement_pattern,
+                        alternatives: alternatives.iter().copied().collect(),
+                        ..ExerciseMetadata::default()
+                    },
+                },
+            )
+        };
+        let exercises = BTreeMap::from([
+            exercise(
+                1,
+                "Squat",
+                Some(MovementPattern::Squat),
+                &[MuscleGroup::Quadriceps],
+                &[3],
+            ),
+            exercise(
+                2,
+                "Leg Press",
+                Some(MovementPattern::Squat),
+                &[MuscleGroup::Quadriceps],
+                &[],
+            ),
+            exercise(
+                3,
+                "Split Squat",
+                Some(MovementPattern::Lunge),
+                &[MuscleGroup::Quadriceps],
+                &[],
+            ),
+            exercise(
+                4,
+                "Hack Squat",
+                Some(MovementPattern::Squat),
+                &[MuscleGroup::Quadriceps],
+                &[],
+            ),
+            exercise(
+                5,
+                "Deadlift",
+                Some(MovementPattern::Hinge),
+                &[MuscleGroup::Hamstrings],
+                &[1],
+            ),
+            exercise(
+                6,
+                "Goblet Squat",
+                Some(MovementPattern::Squat),
+                &[MuscleGroup::Glutes],
+                &[],
+            ),
+        ]);
+        let names = |id| {
+            exercises[&id]
+                .alternatives(&exercises)
+                .iter()
+                .map(|e| e.name.as_str())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            names(1),
+            vec!["Deadlift", "Split Squat", "Hack Squat", "Leg Press"]
+        );
+        assert_eq!(names(3), vec!["Squat"]);
+        assert_eq!(names(6), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn test_progression_next_targets() {
+        let set = |reps, rpe| TrainingSessionElement::Set {
+            exercise_id: 1,
+            reps,
+            time: None,
+            weight: Some(100.0),
+            rpe,
+            target_reps: Some(5),
+            target_time: None,
+            target_weight: Some(100.0),
+            target_rpe: None,
+            automatic: false,
+            set_type: SetType::Working,
+        };
+        let achieved = [set(Some(5), Some(8.0)), set(Some(6), Some(9.0))];
+        let missed = [set(Some(5), Some(8.0)), set(Some(4), Some(10.0))];
+        let achieved = achieved.iter().collect::<Vec<_>>();
+        let missed = missed.iter().collect::<Vec<_>>();
+
+        let linear = Progression::Linear { increment: 2.5 };
+        assert_eq!(linear.next_targets(3, 80.0, 0.0, &[]), (3, 80.0));
+        assert_eq!(linear.next_targets(3, 80.0, 0.0, &achieved), (5, 102.5));
+        assert_eq!(linear.next_targets(3, 80.0, 0.0, &missed), (5, 100.0));
+
+        let double = Progression::Double {
+            max_reps: 6,
+            increment: 5.0,
+        };
+        assert_eq!(double.next_targets(3, 80.0, 0.0, &achieved), (6, 100.0));
+        assert_eq!(double.next_targets(3, 80.0, 0.0, &missed), (5, 100.0));
+        let top = [set(Some(6), None), set(Some(7), None)];
+        assert_eq!(
+            double.next_targets(3, 80.0, 0.0, &top.iter().collect::<Vec<_>>()),
+            (3, 105.0)
+        );
+
+        let rpe_capped = Progression::RPECapped { increment: 2.5 };
+        assert_eq!(rpe_capped.next_targets(3, 80.0, 9.0, &achieved), (5, 102.5));
+        assert_eq!(rpe_capped.next_targets(3, 80.0, 8.0, &achieved), (5, 100.0));
+        assert_eq!(rpe_capped.next_targets(3, 80.0, 0.0, &achieved), (5, 102.5));
+    }
+
+    #[test]
+    fn test_program() {
+        let week = |routines: Vec<u32>| ProgramWeek {
+            routines,
+            weight_percentage: 90.0,
+            rpe_offset: 1.0,
+        };
+        let mut program = Program {
+            id: 1,
+            name: String::from("A"),
+            weeks: vec![week(vec![1, 2]), week(vec![]), week(vec![3])],
+            position: None,
+            weight_percentages: BTreeMap::new(),
+        };
+        assert_eq!(program.next_routine(), None);
+
+        program.position = Some(ProgramPosition { week: 0, day: 0 });
+        assert_eq!(
+            program.next_routine(),
+            Some((ProgramPosition { week: 0, day: 0 }, 1))
+        );
+        program.advance();
+        assert_eq!(
+            program.next_routine(),
+            Some((ProgramPosition { week: 0, day: 1 }, 2))
+        );
+        program.advance();
+        assert_eq!(
+            program.next_routine(),
+            Some((ProgramPosition { week: 2, day: 0 }, 3))
+        );
+        program.advance();
+        assert_eq!(program.position, None);
+
+        program.position = Some(ProgramPosition { week: 2, day: 0 });
+        program.remove_week(1);
+        assert_eq!(program.position, Some(ProgramPosition { week: 1, day: 0 }));
+        program.weeks.insert(1, week(vec![4, 5, 6]));
+        program.position = Some(ProgramPosition { week: 1, day: 2 });
+        program.remove_day(1, 0);
+        assert_eq!(program.position, Some(ProgramPosition { week: 1, day: 1 }));
+        program.remove_day(1, 1);
+        assert_eq!(program.position, Some(ProgramPosition { week: 2, day: 0 }));
+        program.position = Some(ProgramPosition { week: 1, day: 0 });
+        program.remove_week(1);
+        assert_eq!(program.position, Some(ProgramPosition { week: 1, day: 0 }));
+        program.remove_week(1);
+        assert_eq!(program.position, None);
+        program.weeks = vec![week(vec![1, 2]), week(vec![]), week(vec![3])];
+
+        let mut elements = vec![
+            TrainingSessionElement::Set {
+                exercise_id: 1,
+                reps: None,
+                time: None,
+                weight: None,
+                rpe: None,
+                target_reps: Some(5),
+                target_time: None,
+                target_weight: Some(102.5),
+                target_rpe: Some(9.5),
+                automatic: false,
+                set_type: SetType::Working,
+            },
+            TrainingSessionElement::Rest {
+                target_time: Some(60),
+                automatic: true,
+            },
+        ];
+        program.weeks[0].apply(&mut elements);
+        assert_eq!(
+            elements[0],
+            TrainingSessionElement::Set {
+                exercise_id: 1,
+                reps: None,
+                time: None,
+                weight: None,
+                rpe: None,
+                target_reps: Some(5),
+                target_time: None,
+                target_weight: Some(92.5),
+                target_rpe: Some(10.0),
+                automatic: false,
+                set_type: SetType::Working,
+            }
+        );
+    }
+
+    #[test]
+    fn test_program_week_progression() {
+        let deload = ProgramWeek {
+            routines: vec![1],
+            weight_percentage: 60.0,
+            rpe_offset: -2.0,
+        };
+        let normal = ProgramWeek {
+            routines: vec![1],
+            ..ProgramWeek::default()
+        };
+        let set = |target_weight, target_rpe| TrainingSessionElement::Set {
+            exercise_id: 1,
+            reps: Some(5),
+            time: None,
+            weight: None,
+            rpe: Some(6.0),
+            target_reps: Some(5),
+            target_time: None,
+            target_weight,
+            target_rpe,
+            automatic: false,
+            set_type: SetType::Working,
+        };
+        let linear = Progression::Linear { increment: 2.5 };
+
+        let mut deload_sets = vec![set(Some(102.5), Some(8.0))];
+        deload.apply(&mut deload_sets);
+        assert_eq!(deload_sets, vec![set(Some(61.5), Some(6.0))]);
+
+        revert_weight_percentage(deload.weight_percentage, &mut deload_sets);
+        assert_eq!(deload_sets, vec![set(Some(102.5), Some(6.0))]);
+
+        let (reps, weight) =
+            linear.next_targets(5, 80.0, 8.0, &deload_sets.iter().collect::<Vec<_>>());
+        assert_eq!((reps, weight), (5, 105.0));
+
+        let mut normal_sets = vec![set(Some(weight), Some(8.0))];
+        normal.apply(&mut normal_sets);
+        assert_eq!(normal_sets, vec![set(Some(105.0), Some(8.0))]);
+    }
+
+    #[test]
+    fn test_schedule() {
+        let date = |day| NaiveDate::from_ymd_opt(2024, 1, day).unwrap();
+        let weekdays = Schedule {
+            start: date(3),
+            kind: ScheduleKind::Weekdays([Some(1), None, Some(2), None, None, None, None]),
+        };
+        assert_eq!(weekdays.planned_routine(date(1)), None);
+        assert_eq!(weekdays.planned_routine(date(3)), Some(2));
+        assert_eq!(weekdays.planned_routine(date(8)), Some(1));
+        assert_eq!(weekdays.next_planned(date(1)), Some((date(3), 2)));
+        assert_eq!(weekdays.next_planned(date(4)), Some((date(8), 1)));
+
+        let rotation = Schedule {
+            start: date(1),
+            kind: ScheduleKind::Rotation {
+                interval: 2,
+                routines: vec![1, 2, 3],
+            },
+        };
+        assert_eq!(rotation.planned_routine(date(1)), Some(1));
+        assert_eq!(rotation.planned_routine(date(2)), None);
+        assert_eq!(rotation.planned_routine(date(3)), Some(2));
+        assert_eq!(rotation.planned_routine(date(7)), Some(1));
+        assert_eq!(rotation.next_planned(date(4)), Some((date(5), 3)));
+
+        let training_session = |id, day, routine_id| {
+            (
+                id,
+                TrainingSession {
+                    id,
+                    routine_id: Some(routine_id),
+                    date: date(day),
+                    notes: None,
+                    elements: vec![],
+                    session_rpe: None,
+                    duration: None,
+                    interval_blocks: vec![],
+                    routine_version_id: None,
+                },
+            )
+        };
+        let training_sessions = BTreeMap::from([
+            training_session(1, 1, 1),
+            training_session(2, 4, 2),
+            training_session(3, 5, 3),
+        ]);
+        assert_eq!(
+            rotation.adherence(&training_sessions, date(1), date(6)),
+            Adherence {
+                planned: 3,
+                completed: 2
+            }
+        );
+        assert_eq!(
+            weekdays.adherence(&training_sessions, date(1), date(2)),
+            Adherence {
+                planned: 0,
+                completed: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_section_kinds() {
+        let section = |kind, parts| RoutinePart::RoutineSection {
+            rounds: 3,
+            parts,
+            kind,
+        };
+        let kinds = [
+            SectionKind::Emom { interval: 60 },
+            SectionKind::Sequential,
+            SectionKind::Amrap { time_cap: 600 },
+        ];
+        let parts = vec![
+            section(kinds[0], vec![section(kinds[1], vec![])]),
+            section(kinds[2], vec![]),
+        ];
+        let json = serde_json::to_value(&parts).unwrap();
+        assert_eq!(json[0]["kind"], json!({ "Emom": { "interval": 60 } }));
+        assert_eq!(json[0]["parts"][0]["kind"], "Sequential");
+        assert_eq!(
+            serde_json::from_value::<Vec<RoutinePart>>(json).unwrap(),
+            parts
+        );
+
+        assert_eq!(kinds[0].description(), "EMOM");
+        assert_eq!(kinds[2].description(), "AMRAP 10 min");
+        assert_eq!(
+            SectionKind::Tabata { work: 20, rest: 10 }.description(),
+            "Tabata 20 s / 10 s"
+        );
+        assert_eq!(kinds[0].planned_rounds(3), 3);
+        assert_eq!(kinds[2].planned_rounds(3), 1);
+
+        let block = IntervalBlock {
+            kind: kinds[0],
+            start: 2,
+            end: 8,
+            rounds: 3,
+            achieved_rounds: None,
+            time: None,
+        };
+        assert_eq!(block.round_len(), 2);
+    }
+
+    #[test]
+    fn test_target_adherence() {
+        let set = |exercise_id, reps, target_reps, weight, target_weight, rpe, target_rpe| {
+            TrainingSessionElement::Set {
+                exercise_id,
+                reps,
+                time: None,
+                weight,
+                rpe,
+                target_reps,
+                target_time: None,
+                target_weight,
+                target_rpe,
+                automatic: false,
+                set_type: SetType::Working,
+            }
+        };
+        let training_session = TrainingSession {
+            id: 1,
+            routine_id: None,
+            date: from_num_days(1),
+            notes: None,
+            elements: vec![
+                set(
+                    1,
+                    Some(5),
+                    Some(5),
+                    Some(100.0),
+                    Some(100.0),
+                    Some(9.0),
+                    Some(8.0),
+                ),
+                TrainingSessionElement::Rest {
+                    target_time: Some(60),
+                    automatic: true,
+                },
+                set(
+                    1,
+                    Some(4),
+                    Some(5),
+                    Some(100.0),
+                    Some(100.0),
+                    Some(9.5),
+                    Some(8.0),
+                ),
+                set(
+                    2,
+                    Some(12),
+                    Some(10),
+                    Some(20.0),
+                    Some(25.0),
+                    Some(7.0),
+                    Some(8.0),
+                ),
+                set(2, None, Some(10), None, Some(25.0), None, Some(8.0)),
+                set(2, Some(10), None, None, None, None, None),
+            ],
+            session_rpe: None,
+            duration: None,
+            interval_blocks: vec![],
+            routine_version_id: None,
+        };
+        let per_exercise = training_session.target_adherence_per_exercise();
+        assert_eq!(per_exercise[&1].sets, 2);
+        assert_eq!(per_exercise[&1].reps(), Some(0.5));
+        assert_eq!(per_exercise[&1].weight(), Some(1.0));
+        assert_eq!(per_exercise[&1].time(), None);
+        assert_eq!(per_exercise[&1].avg_rpe_deviation(), Some(1.25));
+        assert_eq!(per_exercise[&2].sets, 2);
+        assert_eq!(per_exercise[&2].reps(), Some(1.0));
+        assert_eq!(per_exercise[&2].weight(), Some(0.0));
+        assert_eq!(per_exercise[&2].avg_rpe_deviation(), Some(-1.0));
+
+        let adherence = training_session.target_adherence();
+        assert_eq!(adherence.sets, 4);
+        assert_eq!(adherence.targets(), Some(4.0 / 6.0));
+        let mut merged = per_exercise[&1];
+        merged.merge(&per_exercise[&2]);
+        assert_eq!(merged, adherence);
+        assert_eq!(TargetAdherence::default().targets(), None);
+    }
+
+    #[test]
+    fn test_plate_breakdown() {
+        let equipment = BarbellEquipment::default();
+        let breakdown = equipment.plate_breakdown(20.0, 102.5);
+        assert_eq!(breakdown.plates, vec![25.0, 15.0, 1.25]);
+        assert!((breakdown.weight() - 102.5).abs() < f32::EPSILON);
+
+        assert!(equipment.plate_breakdown(20.0, 20.0).plates.is_empty());
+        assert!(equipment.plate_breakdown(20.0, 10.0).plates.is_empty());
+        assert!(equipment.plate_breakdown(20.0, 21.0).plates.is_empty());
+        assert_eq!(equipment.plate_breakdown(20.0, 22.0).plates, vec![1.25]);
+        assert!(
+            (equipment.plate_breakdown(20.0, 1000.0).weight()
+                - (20.0
+                    + 2.0 * (4.0 * 25.0 + 4.0 * 20.0 + 2.0 * (15.0 + 10.0 + 5.0 + 2.5 + 1.25))))
+                .abs()
+                < f32::EPSILON
+        );
+
+        let equipment = BarbellEquipment {
+            bars: vec![20.0],
+            plates: vec![
+                PlatePair {
+                    weight: 20.0,
+                    count: 1,
+                },
+                PlatePair {
+                    weight: 15.0,
+                    count: 2,
+                },
+            ],
+            collars: 5.0,
+        };
+        let breakdown = equipment.plate_breakdown(20.0, 90.0);
+        assert_eq!(breakdown.plates, vec![15.0, 15.0]);
+        assert!((breakdown.weight() - 85.0).abs() < f32::EPSILON);
+        assert_eq!(
+            equipment.plate_breakdown(20.0, 97.0).plates,
+            vec![20.0, 15.0]
+        );
+    }
+
+    #[test]
+    fn test_warm_up_sets() {
+        let equipment = BarbellEquipment::default();
+        assert_eq!(
+            equipment.warm_up_sets(100.0),
+            vec![(10, 20.0), (5, 40.0), (3, 60.0), (1, 80.0)]
+        );
+        assert_eq!(
+            equipment.warm_up_sets(62.5),
+            vec![(10, 20.0), (5, 25.0), (3, 37.5), (1, 50.0)]
+        );
+        assert_eq!(equipment.warm_up_sets(30.0), vec![(10, 20.0), (1, 25.0)]);
+        assert!(equipment.warm_up_sets(20.0).is_empty());
+    }
+
+    #[test]
+    fn test_rpe_percentage() {
+        assert_eq!(rpe_percentage(1, 10.0), Some(100.0));
+        assert_eq!(rpe_percentage(1, 9.5), Some(97.8));
+        assert_eq!(rpe_percentage(3, 8.0), Some(86.3));
+        assert_eq!(rpe_percentage(12, 9.5), Some(66.7));
+        assert_eq!(rpe_percentage(12, 9.0), None);
+        assert_eq!(rpe_percentage(0, 8.0), None);
+        assert_eq!(rpe_percentage(5, 0.0), None);
+    }
+
+    #[test]
+    fn test_rpe_adjustment() {
+        assert_eq!(estimated_one_rep_max(1, 100.0, 10.0), Some(100.0));
+        assert_eq!(rpe_adjusted_weight(5, 100.0, 9.0, 5, 8.0), Some(97.0));
+        assert_eq!(rpe_adjusted_weight(5, 100.0, 7.0, 5, 8.0), Some(103.0));
+        assert_eq!(rpe_adjusted_weight(5, 100.0, 8.0, 3, 8.0), Some(106.5));
+        assert_eq!(rpe_adjusted_weight(5, 100.0, 8.0, 5, 11.0), None);
+        assert_eq!(rpe_adjusted_reps(5, 9.0, 8.0), Some(4));
+        assert_eq!(rpe_adjusted_reps(5, 6.5, 8.0), Some(7));
+        assert_eq!(rpe_adjusted_reps(1, 10.0, 7.0), Some(1));
+        assert_eq!(rpe_adjusted_reps(5, 0.0, 8.0), None);
+    }
+
+    #[test]
+    fn test_relative_weight() {
+        let relative_weight = RelativeWeight {
+            percentage: 85.0,
+            basis: WeightBasis::TrainingMax,
+        };
+        assert!((relative_weight.resolve(100.0) - 85.0).abs() < f32::EPSILON);
+        assert!((relative_weight.resolve(142.5) - 121.0).abs() < f32::EPSILON);
+
+        let options = ActivityOptions {
+            relative_weight: Some(relative_weight),
+            ..ActivityOptions::default()
+        };
+        assert!((options.target_weight(80.0, |_| Some(100.0)) - 85.0).abs() < f32::EPSILON);
+        assert!((options.target_weight(80.0, |_| None) - 80.0).abs() < f32::EPSILON);
+        assert!(
+            (ActivityOptions::default().target_weight(80.0, |_| Some(100.0)) - 80.0).abs()
+                < f32::EPSILON
+        );
+    }
+
+    #[test]
+    fn test_set_types() {
+        let set = |set_type| TrainingSessionElement::Set {
+            exercise_id: 1,
+            reps: Some(5),
+            time: None,
+            weight: Some(100.0),
+            rpe: Some(9.0),
+            target_reps: Some(5),
+            target_time: None,
+            target_weight: None,
+            target_rpe: Some(8.0),
+            automatic: false,
+            set_type,
+        };
+        let training_session = TrainingSession {
+            id: 1,
+            routine_id: None,
+            date: from_num_days(1),
+            notes: None,
+            elements: vec![
+                set(SetType::WarmUp),
+                set(SetType::Working),
+                set(SetType::Failure),
+            ],
+            session_rpe: None,
+            duration: None,
+            interval_blocks: vec![],
+            routine_version_id: None,
+        };
+        assert_eq!(training_session.set_volume(), 2);
+        assert_eq!(training_session.volume_load(), 1000);
+        let adherence = training_session.target_adherence();
+        assert_eq!(adherence.sets, 2);
+        assert_eq!(adherence.reps_targeted, 2);
+        assert_eq!(adherence.rpe_targeted, 1);
+
+        let elements = serde_json::to_value(&training_session.elements).unwrap();
+        assert_eq!(elements[0]["set_type"], "WarmUp");
+        assert_eq!(
+            serde_json::from_value::<Vec<TrainingSessionElement>>(elements).unwrap(),
+            training_session.elements
+        );
+    }
+
+    #[test]
+    fn test_routine_analysis() {
+        let section = |rounds, kind, parts| RoutinePart::RoutineSection {
+            rounds,
+            parts,
+            kind,
+        };
+        let activity = |exercise_id, reps, time, rpe| RoutinePart::RoutineActivity {
+            exercise_id,
+            reps,
+            time,
+            weight: 0.0,
+            rpe,
+            automatic: false,
+            options: ActivityOptions::default(),
+        };
+        let analysis = RoutineAnalysis::of(&[
+            section(
+                3,
+                SectionKind::Sequential,
+                vec![activity(Some(1), 5, 0, 8.0), activity(None, 0, 120, 0.0)],
+            ),
+            section(
+                10,
+                SectionKind::Emom { interval: 60 },
+                vec![activity(Some(2), 10, 0, 6.0)],
+            ),
+            section(
+                8,
+                SectionKind::Tabata { work: 20, rest: 10 },
+                vec![activity(Some(1), 0, 0, 8.0), activity(None, 0, 10, 0.0)],
+            ),
+            section(1, SectionKind::Sequential, vec![]),
+            activity(None, 5, 0, 0.0),
+        ]);
+        assert_eq!(
+            analysis,
+            RoutineAnalysis {
+                duration: 405 + 600 + 240,
+                hard_sets: BTreeMap::from([(1, 11)]),
+                warnings: vec![
+                    RoutineWarning::EmptySections(1),
+                    RoutineWarning::ActivitiesWithoutExercise(1)
+                ],
+            }
+        );
+
+        let analysis = RoutineAnalysis::of(&[
+            section(
+                15,
+                SectionKind::Sequential,
+                vec![activity(Some(1), 0, 0, 8.0)],
+            ),
+            section(
+                30,
+                SectionKind::Amrap { time_cap: 600 },
+                vec![activity(Some(2), 10, 0, 8.0)],
+            ),
+        ]);
+        assert_eq!(analysis.duration, 15 * 30 + 600);
+        assert_eq!(analysis.hard_sets, BTreeMap::from([(1, 15), (2, 1)]));
+        assert_eq!(
+            analysis.warnings,
+            vec![RoutineWarning::TooManySetsOfExercise {
+                exercise_id: 1,
+                sets: 15
+            }]
+        );
+    }
 }
@@ -11,7 +11,9 @@ use seed::{prelude::*, *};
 
 mod common;
 mod data;
+mod notation;
 mod page;
+mod templates;
 
 // ------ ------
 //     Init
@@ -52,6 +54,7 @@ const EXERCISES: &str = "exercises";
 const EXERCISE: &str = "exercise";
 const ROUTINES: &str = "routines";
 const ROUTINE: &str = "routine";
+const PROGRAMS: &str = "programs";
 const TRAINING: &str = "training";
 const TRAINING_SESSION: &str = "training_session";
 
@@ -87,6 +90,9 @@ impl<'a> Urls<'a> {
     pub fn routine(self) -> Url {
         self.base_url().set_hash_path([ROUTINE])
     }
+    pub fn programs(self) -> Url {
+        self.base_url().set_hash_path([PROGRAMS])
+    }
     pub fn training(self) -> Url {
         self.base_url().set_hash_path([TRAINING])
     }
@@ -123,8 +129,9 @@ enum Page {
     Exercise(page::exercise::Model),
     Routines(page::routines::Model),
     Routine(page::routine::Model),
+    Programs(page::programs::Model),
     Training(page::training::Model),
-    TrainingSession(page::training_session::Model),
+    TrainingSession(Box<page::training_session::Model>),
     NotFound,
 }
 
@@ -195,18 +202,25 @@ impl Page {
                     data_model,
                     navbar,
                 )),
-                Some(TRAINING) => Self::Training(page::training::init(
+                Some(PROGRAMS) => Self::Programs(page::programs::init(
                     url,
-                    &mut orders.proxy(Msg::Training),
-                    data_model,
+                    &mut orders.proxy(Msg::Programs),
                     navbar,
                 )),
-                Some(TRAINING_SESSION) => Self::TrainingSession(page::training_session::init(
+                Some(TRAINING) => Self::Training(page::training::init(
                     url,
-                    &mut orders.proxy(Msg::TrainingSession),
+                    &mut orders.proxy(Msg::Training),
                     data_model,
                     navbar,
                 )),
+                Some(TRAINING_SESSION) => {
+                    Self::TrainingSession(Box::new(page::training_session::init(
+                        url,
+                        &mut orders.proxy(Msg::TrainingSession),
+                        data_model,
+                        navbar,
+                    )))
+                }
                 Some(_) => Self::NotFound,
             }
         } else {
@@ -243,7 +257,14 @@ enum Msg {
     ShowSettingsDialog,
     CloseSettingsDialog,
     BeepVolumeChanged(String),
+    SpeechVolumeChanged(String),
+    SpeechVoiceChanged(String),
+    ToggleKeepScreenAwake,
     ToggleAutomaticMetronome,
+    LoadModelChanged(data::LoadModel),
+    BarsChanged(String),
+    PlatesChanged(String),
+    CollarsChanged(String),
     EnableNotifications,
     GoUp,
     LogOut,
@@ -259,6 +280,7 @@ enum Msg {
     Exercise(page::exercise::Msg),
     Routines(page::routines::Msg),
     Routine(page::routine::Msg),
+    Programs(page::programs::Msg),
     Training(page::training::Msg),
     TrainingSession(page::training_session::Msg),
 
@@ -312,12 +334,75 @@ fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
                 orders.send_msg(Msg::Data(data::Msg::SetBeepVolume(value)));
             }
         }
+        Msg::SpeechVolumeChanged(input) => {
+            if let Ok(value) = input.parse::<u8>() {
+                orders.send_msg(Msg::Data(data::Msg::SetSpeechVolume(value)));
+            }
+        }
+        Msg::SpeechVoiceChanged(input) => {
+            common::speak(
+                "Voice changed",
+                model.data.settings.speech_volume,
+                Some(&input)
+                    .filter(|v| not(v.is_empty()))
+                    .map(String::as_str),
+            );
+            orders.send_msg(Msg::Data(data::Msg::SetSpeechVoice(
+                Some(input).filter(|v| not(v.is_empty())),
+            )));
+        }
+        Msg::ToggleKeepScreenAwake => {
+            orders.send_msg(Msg::Data(data::Msg::SetKeepScreenAwake(not(model
+                .data
+                .settings
+                .keep_screen_awake))));
+        }
         Msg::ToggleAutomaticMetronome => {
             orders.send_msg(Msg::Data(data::Msg::SetAutomaticMetronome(not(model
                 .data
                 .settings
                 .automatic_metronome))));
         }
+        Msg::LoadModelChanged(load_model) => {
+            orders.send_msg(Msg::Data(data::Msg::SetLoadModel(load_model)));
+        }
+        Msg::BarsChanged(input) => {
+            let bars = input
+                .split(',')
+                .map(|b| b.trim().parse::<f32>().ok().filter(|b| *b >= 0.0))
+                .collect::<Option<Vec<_>>>();
+            let mut equipment = model.data.barbell_equipment();
+            match bars {
+                Some(bars) if not(bars.is_empty()) => equipment.bars = bars,
+                _ => {}
+            }
+            orders.send_msg(Msg::Data(data::Msg::SetBarbellEquipment(equipment)));
+        }
+        Msg::PlatesChanged(input) => {
+            let plates = input
+                .split(',')
+                .filter(|p| not(p.trim().is_empty()))
+                .map(|p| {
+                    let (weight, count) = p.split_once(['x', '×', '*']).unwrap_or((p, "1"));
+                    Some(data::PlatePair {
+                        weight: weight.trim().parse::<f32>().ok().filter(|w| *w > 0.0)?,
+                        count: count.trim().parse::<u32>().ok()?,
+                    })
+                })
+                .collect::<Option<Vec<_>>>();
+            let mut equipment = model.data.barbell_equipment();
+            if let Some(plates) = plates {
+                equipment.plates = plates;
+            }
+            orders.send_msg(Msg::Data(data::Msg::SetBarbellEquipment(equipment)));
+        }
+        Msg::CollarsChanged(input) => {
+            let mut equipment = model.data.barbell_equipment();
+            if let Some(collars) = input.trim().parse::<f32>().ok().filter(|c| *c >= 0.0) {
+                equipment.collars = collars;
+            }
+            orders.send_msg(Msg::Data(data::Msg::SetBarbellEquipment(equipment)));
+        }
         Msg::EnableNotifications => {
             orders.skip().perform_cmd(async {
                 if let Ok(promise) = web_sys::Notification::request_permission() {
@@ -352,7 +437,12 @@ fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
             Some(Page::Routine(_)) => {
                 orders.request_url(crate::Urls::new(&model.data.base_url).routines());
             }
-            Some(Page::TrainingSession(_) | Page::Exercises(_) | Page::Routines(_)) => {
+            Some(
+                Page::TrainingSession(_)
+                | Page::Exercises(_)
+                | Page::Routines(_)
+                | Page::Programs(_),
+            ) => {
                 orders.request_url(crate::Urls::new(&model.data.base_url).training());
             }
         },
@@ -436,6 +526,16 @@ fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
                 );
             }
         }
+        Msg::Programs(msg) => {
+            if let Some(Page::Programs(page_model)) = &mut model.page {
+                page::programs::update(
+                    msg,
+                    page_model,
+                    &model.data,
+                    &mut orders.proxy(Msg::Programs),
+                );
+            }
+        }
         Msg::Routine(msg) => {
             if let Some(Page::Routine(page_model)) = &mut model.page {
                 page::routine::update(
@@ -640,6 +740,8 @@ fn view_page(page: &Option<Page>, data_model: &data::Model) -> Node<Msg> {
                 page::routines::view(model, data_model).map_msg(Msg::Routines),
             Some(Page::Routine(model)) =>
                 page::routine::view(model, data_model).map_msg(Msg::Routine),
+            Some(Page::Programs(model)) =>
+                page::programs::view(model, data_model).map_msg(Msg::Programs),
             Some(Page::Training(model)) =>
                 page::training::view(model, data_model).map_msg(Msg::Training),
             Some(Page::TrainingSession(model)) =>
@@ -671,6 +773,7 @@ fn view_settings_dialog(data_model: &data::Model) -> Node<Msg> {
                     input_ev(Ev::Input, Msg::BeepVolumeChanged),
                 ]
             ],
+            view_speech_settings(&data_model.settings),
             p![
                 C!["mb-5"],
                 h1![C!["subtitle"], "Metronome"],
@@ -689,6 +792,47 @@ fn view_settings_dialog(data_model: &data::Model) -> Node<Msg> {
                     },
                 ],
             ],
+            p![
+                C!["mb-5"],
+                h1![C!["subtitle"], "Screen during training sessions"],
+                button![
+                    C!["button"],
+                    if data_model.settings.keep_screen_awake {
+                        C!["is-primary"]
+                    } else {
+                        C!["is-danger"]
+                    },
+                    ev(Ev::Click, |_| Msg::ToggleKeepScreenAwake),
+                    if data_model.settings.keep_screen_awake {
+                        "Keep awake"
+                    } else {
+                        "Allow sleep"
+                    },
+                ],
+            ],
+            p![
+                C!["mb-5"],
+                h1![C!["subtitle"], "Load"],
+                div![
+                    C!["buttons"],
+                    C!["has-addons"],
+                    [
+                        (data::LoadModel::SetRPE, "Set RPE"),
+                        (data::LoadModel::SessionRPE, "Session RPE × duration"),
+                    ]
+                    .into_iter()
+                    .map(|(load_model, label)| {
+                        button![
+                            C!["button"],
+                            IF![data_model.settings.load_model == load_model => C!["is-primary"]],
+                            ev(Ev::Click, move |_| Msg::LoadModelChanged(load_model)),
+                            label,
+                        ]
+                    })
+                    .collect::<Vec<_>>(),
+                ],
+            ],
+            IF![data_model.session.is_some() => view_barbell_equipment_settings(&data_model.barbell_equipment())],
             {
                 let permission = web_sys::Notification::permission();
                 p![
@@ -714,6 +858,107 @@ fn view_settings_dialog(data_model: &data::Model) -> Node<Msg> {
     )
 }
 
+fn view_speech_settings(settings: &data::Settings) -> Node<Msg> {
+    p![
+        C!["mb-5"],
+        h1![C!["subtitle"], "Spoken announcements"],
+        input![
+            C!["slider"],
+            C!["is-fullwidth"],
+            C!["is-info"],
+            attrs! {
+                At::Type => "range",
+                At::Value => settings.speech_volume,
+                At::Min => 0,
+                At::Max => 100,
+                At::Step => 10,
+                At::Title => "Volume (disabled if zero)",
+            },
+            input_ev(Ev::Input, Msg::SpeechVolumeChanged),
+        ],
+        div![
+            C!["select"],
+            C!["is-fullwidth"],
+            input_ev(Ev::Change, Msg::SpeechVoiceChanged),
+            select![
+                attrs! {At::Disabled => (settings.speech_volume == 0).as_at_value()},
+                option![
+                    "Default voice",
+                    attrs! {
+                        At::Value => "",
+                        At::Selected => settings.speech_voice.is_none().as_at_value(),
+                    }
+                ],
+                common::speech_voices()
+                    .into_iter()
+                    .map(|voice| {
+                        option![
+                            attrs! {
+                                At::Value => voice,
+                                At::Selected => (settings.speech_voice.as_ref() == Some(&voice)).as_at_value(),
+                            },
+                            &voice,
+                        ]
+                    })
+                    .collect::<Vec<_>>()
+            ]
+        ]
+    ]
+}
+
+fn view_barbell_equipment_settings(equipment: &data::BarbellEquipment) -> Node<Msg> {
+    let field = |label: &str, value: String, help: &str, msg: fn(String) -> Msg| {
+        div![
+            C!["field"],
+            label![C!["label"], label],
+            div![
+                C!["control"],
+                input![
+                    C!["input"],
+                    attrs! {
+                        At::Type => "text",
+                        At::Value => value,
+                    },
+                    input_ev(Ev::Change, msg),
+                ]
+            ],
+            p![C!["help"], help],
+        ]
+    };
+    p![
+        C!["mb-5"],
+        h1![C!["subtitle"], "Barbell"],
+        field(
+            "Bars (kg)",
+            equipment
+                .bars
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", "),
+            "The first bar is used by default",
+            Msg::BarsChanged
+        ),
+        field(
+            "Plate pairs (kg × number of pairs)",
+            equipment
+                .plates
+                .iter()
+                .map(|p| format!("{}×{}", p.weight, p.count))
+                .collect::<Vec<_>>()
+                .join(", "),
+            "For example: 25×4, 20×2, 10×2, 5×2",
+            Msg::PlatesChanged
+        ),
+        field(
+            "Collars (kg)",
+            equipment.collars.to_string(),
+            "Combined weight of both collars",
+            Msg::CollarsChanged
+        ),
+    ]
+}
+
 // ------ ------
 //     Start
 // ------ ------
@@ -0,0 +1,741 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use seed::prelude::*;
+
+use crate::common;
+use crate::data;
+
+// Compact text notation for routines
+//
+// Parts are separated by new lines or semicolons. A section consists of an optional kind, the
+// number of rounds and its parts in brackets. An activity consists of the exercise name (or
+// "rest") followed by its properties:
+//
+//     Squat 5@100kg RPE8 linear(2.5)
+//     3x[Push-up 10; rest 60s auto]
+//     EMOM 90s 10x[Burpee 10; "Clean & Jerk" 2@60kg]
+//
+// Names which could be mistaken for properties are quoted, exercises which cannot be referenced
+// unambiguously by their name are referenced by their ID (e.g. #42).
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Error {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+pub fn print(parts: &[data::RoutinePart], exercises: &BTreeMap<u32, data::Exercise>) -> String {
+    parts
+        .iter()
+        .map(|p| print_part(p, exercises))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn parse(
+    text: &str,
+    exercises: &BTreeMap<u32, data::Exercise>,
+) -> Result<Vec<data::RoutinePart>, Error> {
+    Parser {
+        text,
+        pos: 0,
+        exercises,
+    }
+    .parse_parts(false)
+}
+
+fn print_part(part: &data::RoutinePart, exercises: &BTreeMap<u32, data::Exercise>) -> String {
+    match part {
+        data::RoutinePart::RoutineSection {
+            rounds,
+            parts,
+            kind,
+        } => {
+            let kind = match kind {
+                data::SectionKind::Sequential => String::new(),
+                data::SectionKind::Emom { interval } => format!("EMOM {interval}s "),
+                data::SectionKind::Amrap { time_cap } => format!("AMRAP {time_cap}s "),
+                data::SectionKind::Tabata { work, rest } => format!("Tabata {work}s/{rest}s "),
+                data::SectionKind::ForTime { time_cap } => format!("ForTime {time_cap}s "),
+            };
+            let parts = parts
+                .iter()
+                .map(|p| print_part(p, exercises))
+                .collect::<Vec<_>>()
+                .join("; ");
+            format!("{kind}{rounds}x[{parts}]")
+        }
+        data::RoutinePart::RoutineActivity {
+            exercise_id,
+            reps,
+            time,
+            weight,
+            rpe,
+            automatic,
+            options,
+        } => {
+            let mut tokens = vec![match exercise_id {
+                Some(exercise_id) => print_exercise(*exercise_id, exercises),
+                None => String::from("rest"),
+            }];
+            let weight = if let Some(relative_weight) = options.relative_weight {
+                Some(format!(
+                    "{}%{}",
+                    relative_weight.percentage,
+                    relative_weight.basis.name()
+                ))
+            } else if *weight != 0.0 {
+                Some(format!("{weight}kg"))
+            } else {
+                None
+            };
+            if let Some(weight) = weight {
+                if *reps > 0 {
+                    tokens.push(format!("{reps}@{weight}"));
+                } else {
+                    tokens.push(format!("@{weight}"));
+                }
+            } else if *reps > 0 {
+                tokens.push(reps.to_string());
+            }
+            if *time > 0 {
+                tokens.push(format!("{time}s"));
+            }
+            if *rpe != 0.0 {
+                tokens.push(format!("RPE{rpe}"));
+            }
+            if *automatic {
+                tokens.push(String::from("auto"));
+            }
+            if options.auto_warm_up {
+                tokens.push(String::from("rampup"));
+            }
+            if options.set_type != data::SetType::Working {
+                tokens.push(format!("type({})", set_type_keyword(options.set_type)));
+            }
+            match options.progression {
+                Some(data::Progression::Linear { increment }) => {
+                    tokens.push(format!("linear({increment})"));
+                }
+                Some(data::Progression::Double {
+                    max_reps,
+                    increment,
+                }) => tokens.push(format!("double({max_reps},{increment})")),
+                Some(data::Progression::RPECapped { increment }) => {
+                    tokens.push(format!("rpecap({increment})"));
+                }
+                None => {}
+            }
+            tokens.join(" ")
+        }
+    }
+}
+
+fn print_exercise(exercise_id: u32, exercises: &BTreeMap<u32, data::Exercise>) -> String {
+    let Some(exercise) = exercises.get(&exercise_id) else {
+        return format!("#{exercise_id}");
+    };
+    let name = &exercise.name;
+    let unique = exercises
+        .values()
+        .filter(|e| e.name.to_lowercase() == name.to_lowercase())
+        .count()
+        == 1;
+    if name.is_empty() || name.contains('"') || not(unique) {
+        format!("#{exercise_id}")
+    } else if name.split(' ').all(|t| {
+        not(t.is_empty())
+            && not(t.contains(|c: char| c.is_whitespace() || "[];\"".contains(c)))
+            && parse_property(t).is_none()
+    }) && not(name.eq_ignore_ascii_case("rest"))
+        && not(name.starts_with('#'))
+    {
+        name.clone()
+    } else {
+        format!("\"{name}\"")
+    }
+}
+
+struct Token<'a> {
+    text: &'a str,
+    pos: usize,
+    quoted: bool,
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+    exercises: &'a BTreeMap<u32, data::Exercise>,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<char> {
+        self.text[self.pos..].chars().next()
+    }
+
+    fn error(&self, pos: usize, message: impl Into<String>) -> Error {
+        let before = &self.text[..pos];
+        Error {
+            line: before.matches('\n').count() + 1,
+            column: before.rsplit('\n').next().unwrap_or("").chars().count() + 1,
+            message: message.into(),
+        }
+    }
+
+    fn parse_parts(&mut self, nested: bool) -> Result<Vec<data::RoutinePart>, Error> {
+        let mut parts = vec![];
+        loop {
+            while let Some(c) = self.peek() {
+                if c == ';' || c.is_whitespace() {
+                    self.pos += c.len_utf8();
+                } else {
+                    break;
+                }
+            }
+            match self.peek() {
+                None if nested => return Err(self.error(self.pos, "Missing \"]\"")),
+                None => return Ok(parts),
+                Some(']') if nested => {
+                    self.pos += 1;
+                    return Ok(parts);
+                }
+                Some(']') => return Err(self.error(self.pos, "Unexpected \"]\"")),
+                Some(_) => parts.push(self.parse_part()?),
+            }
+        }
+    }
+
+    fn parse_part(&mut self) -> Result<data::RoutinePart, Error> {
+        let tokens = self.read_tokens()?;
+        if self.peek() == Some('[') {
+            let (rounds, kind) = self.parse_section_head(&tokens)?;
+            self.pos += 1;
+            Ok(data::RoutinePart::RoutineSection {
+                rounds,
+                parts: self.parse_parts(true)?,
+                kind,
+            })
+        } else {
+            self.parse_activity(&tokens)
+        }
+    }
+
+    fn read_tokens(&mut self) -> Result<Vec<Token<'a>>, Error> {
+        let mut tokens = vec![];
+        loop {
+            while let Some(c) = self.peek() {
+                if c != '\n' && c.is_whitespace() {
+                    self.pos += c.len_utf8();
+                } else {
+                    break;
+                }
+            }
+            let start = self.pos;
+            match self.peek() {
+                None | Some(';' | '[' | ']' | '\n') => return Ok(tokens),
+                Some('"') => {
+                    let rest = &self.text[start + 1..];
+                    let Some(len) = rest.find('"') else {
+                        return Err(self.error(start, "Missing closing quote"));
+                    };
+                    tokens.push(Token {
+                        text: &rest[..len],
+                        pos: start,
+                        quoted: true,
+                    });
+                    self.pos = start + len + 2;
+                }
+                Some(_) => {
+                    let rest = &self.text[start..];
+                    let len = rest
+                        .find(|c: char| c.is_whitespace() || ";[]".contains(c))
+                        .unwrap_or(rest.len());
+                    tokens.push(Token {
+                        text: &rest[..len],
+                        pos: start,
+                        quoted: false,
+                    });
+                    self.pos = start + len;
+                }
+            }
+        }
+    }
+
+    fn parse_section_head(&self, tokens: &[Token]) -> Result<(u32, data::SectionKind), Error> {
+        let Some((last, head)) = tokens.split_last() else {
+            return Err(self.error(self.pos, "Missing number of rounds (e.g. \"3x\")"));
+        };
+        let rounds = last
+            .text
+            .strip_suffix(['x', 'X'])
+            .and_then(|r| r.parse::<u32>().ok())
+            .ok_or_else(|| {
+                self.error(
+                    last.pos,
+                    format!("Invalid number of rounds \"{}\"", last.text),
+                )
+            })?;
+        let Some((name, args)) = head.split_first() else {
+            return Ok((rounds, data::SectionKind::Sequential));
+        };
+        if let Some(arg) = args.get(1) {
+            return Err(self.error(arg.pos, format!("Unexpected \"{}\"", arg.text)));
+        }
+        let duration = |default: u32| match args.first() {
+            Some(arg) => parse_duration(arg.text)
+                .ok_or_else(|| self.error(arg.pos, format!("Invalid duration \"{}\"", arg.text))),
+            None => Ok(default),
+        };
+        let kind = match name.text.to_lowercase().as_str() {
+            "emom" => data::SectionKind::Emom {
+                interval: duration(60)?,
+            },
+            "amrap" => data::SectionKind::Amrap {
+                time_cap: duration(600)?,
+            },
+            "fortime" => data::SectionKind::ForTime {
+                time_cap: duration(0)?,
+            },
+            "tabata" => match args.first() {
+                Some(arg) => arg
+                    .text
+                    .split_once('/')
+                    .and_then(|(work, rest)| Some((parse_duration(work)?, parse_duration(rest)?)))
+                    .map(|(work, rest)| data::SectionKind::Tabata { work, rest })
+                    .ok_or_else(|| {
+                        self.error(arg.pos, format!("Invalid durations \"{}\"", arg.text))
+                    })?,
+                None => data::SectionKind::DEFAULT_TABATA,
+            },
+            _ => {
+                return Err(self.error(name.pos, format!("Unknown section kind \"{}\"", name.text)))
+            }
+        };
+        Ok((rounds, kind))
+    }
+
+    fn parse_activity(&self, tokens: &[Token]) -> Result<data::RoutinePart, Error> {
+        let Some(first) = tokens.first() else {
+            return Err(self.error(self.pos, "Missing exercise"));
+        };
+        let (exercise_id, properties) = if first.quoted {
+            (
+                Some(self.find_exercise(first.text, first.pos)?),
+                &tokens[1..],
+            )
+        } else if let Some(id) = first.text.strip_prefix('#') {
+            let id = id.parse::<u32>().map_err(|_| {
+                self.error(first.pos, format!("Invalid exercise ID \"{}\"", first.text))
+            })?;
+            (Some(id), &tokens[1..])
+        } else {
+            let name_len = tokens
+                .iter()
+                .position(|t| t.quoted || parse_property(t.text).is_some())
+                .unwrap_or(tokens.len());
+            if name_len == 0 {
+                return Err(self.error(first.pos, "Missing exercise"));
+            }
+            let name = tokens[..name_len]
+                .iter()
+                .map(|t| t.text)
+                .collect::<Vec<_>>()
+                .join(" ");
+            if name.eq_ignore_ascii_case("rest") {
+                (None, &tokens[name_len..])
+            } else {
+                (
+                    Some(self.find_exercise(&name, first.pos)?),
+                    &tokens[name_len..],
+                )
+            }
+        };
+
+        let mut reps = 0;
+        let mut time = 0;
+        let mut weight = 0.0;
+        let mut rpe = 0.0;
+        let mut automatic = false;
+        let mut progression = None;
+        let mut progression_pos = 0;
+        let mut auto_warm_up = false;
+        let mut set_type = data::SetType::Working;
+        let mut relative_weight = None;
+        for token in properties {
+            match parse_property(token.text).filter(|_| not(token.quoted)) {
+                Some(Property::Reps(r)) => reps = r,
+                Some(Property::Weight(r, w)) => {
+                    reps = r.unwrap_or(reps);
+                    weight = w;
+                    relative_weight = None;
+                }
+                Some(Property::RelativeWeight(r, w)) => {
+                    reps = r.unwrap_or(reps);
+                    weight = 0.0;
+                    relative_weight = Some(w);
+                }
+                Some(Property::Time(t)) => time = t,
+                Some(Property::Rpe(r)) => rpe = r,
+                Some(Property::Automatic) => automatic = true,
+                Some(Property::AutoWarmUp) => auto_warm_up = true,
+                Some(Property::SetType(t)) => set_type = t,
+                Some(Property::Progression(p)) => {
+                    progression = Some(p);
+                    progression_pos = token.pos;
+                }
+                None => return Err(self.error(token.pos, format!("Unexpected \"{}\"", token.text))),
+            }
+        }
+        if progression.is_some() && relative_weight.is_some() {
+            return Err(self.error(
+                progression_pos,
+                "Progression cannot be combined with relative weight",
+            ));
+        }
+
+        Ok(data::RoutinePart::RoutineActivity {
+            exercise_id,
+            reps,
+            time,
+            weight,
+            rpe,
+            automatic,
+            options: data::ActivityOptions {
+                progression,
+                auto_warm_up,
+                set_type,
+                relative_weight,
+            },
+        })
+    }
+
+    fn find_exercise(&self, name: &str, pos: usize) -> Result<u32, Error> {
+        let matches = self
+            .exercises
+            .values()
+            .filter(|e| e.name.to_lowercase() == name.to_lowercase())
+            .collect::<Vec<_>>();
+        match matches[..] {
+            [exercise] => Ok(exercise.id),
+            [] => Err(self.error(pos, format!("Unknown exercise \"{name}\""))),
+            _ => Err(self.error(
+                pos,
+                format!(
+                    "Ambiguous exercise \"{name}\" (use its ID, e.g. #{})",
+                    matches[0].id
+                ),
+            )),
+        }
+    }
+}
+
+enum Property {
+    Reps(u32),
+    Weight(Option<u32>, f32),
+    RelativeWeight(Option<u32>, data::RelativeWeight),
+    Time(u32),
+    Rpe(f32),
+    Automatic,
+    AutoWarmUp,
+    SetType(data::SetType),
+    Progression(data::Progression),
+}
+
+fn set_type_keyword(set_type: data::SetType) -> &'static str {
+    match set_type {
+        data::SetType::Working => "working",
+        data::SetType::WarmUp => "warmup",
+        data::SetType::Drop => "drop",
+        data::SetType::Failure => "failure",
+        data::SetType::Amrap => "amrap",
+        data::SetType::BackOff => "backoff",
+    }
+}
+
+fn parse_property(token: &str) -> Option<Property> {
+    let token = token.to_lowercase();
+    if token == "auto" {
+        return Some(Property::Automatic);
+    }
+    if token == "rampup" {
+        return Some(Property::AutoWarmUp);
+    }
+    if let Some(args) = token.strip_suffix(')') {
+        let (name, args) = args.split_once('(')?;
+        if name == "type" {
+            return data::SetType::ALL
+                .into_iter()
+                .find(|t| set_type_keyword(*t) == args)
+                .map(Property::SetType);
+        }
+        let progression = match (name, args.split_once(',')) {
+            ("linear", None) => data::Progression::Linear {
+                increment: parse_float(args)?,
+            },
+            ("double", Some((max_reps, increment))) => data::Progression::Double {
+                max_reps: parse_reps(max_reps)?,
+                increment: parse_float(increment)?,
+            },
+            ("rpecap", None) => data::Progression::RPECapped {
+                increment: parse_float(args)?,
+            },
+            _ => return None,
+        };
+        return Some(Property::Progression(progression));
+    }
+    if let Some(rpe) = token.strip_prefix("rpe") {
+        return parse_float(rpe)
+            .filter(|r| common::valid_rpe(*r))
+            .map(Property::Rpe);
+    }
+    if let Some((reps, weight)) = token.split_once('@') {
+        let reps = if reps.is_empty() {
+            None
+        } else {
+            Some(parse_reps(reps)?)
+        };
+        if let Some((percentage, basis)) = weight.split_once('%') {
+            let percentage = parse_float(percentage).filter(|p| common::valid_weight(*p))?;
+            let basis = data::WeightBasis::ALL
+                .into_iter()
+                .find(|b| b.name().to_lowercase() == basis)?;
+            return Some(Property::RelativeWeight(
+                reps,
+                data::RelativeWeight { percentage, basis },
+            ));
+        }
+        let weight = parse_float(weight.strip_suffix("kg").unwrap_or(weight))
+            .filter(|w| common::valid_weight(*w))?;
+        return Some(Property::Weight(reps, weight));
+    }
+    if let Some(weight) = token.strip_suffix("kg") {
+        return parse_float(weight)
+            .filter(|w| common::valid_weight(*w))
+            .map(|w| Property::Weight(None, w));
+    }
+    if token.ends_with('s') || token.ends_with("min") {
+        return parse_duration(&token)
+            .filter(|t| common::valid_time(*t))
+            .map(Property::Time);
+    }
+    parse_reps(&token).map(Property::Reps)
+}
+
+fn parse_reps(text: &str) -> Option<u32> {
+    text.parse::<u32>().ok().filter(|r| common::valid_reps(*r))
+}
+
+fn parse_float(text: &str) -> Option<f32> {
+    text.parse::<f32>().ok().filter(|v| v.is_finite())
+}
+
+fn parse_duration(text: &str) -> Option<u32> {
+    let text = text.to_lowercase();
+    if let Some(minutes) = text.strip_suffix("min") {
+        minutes.parse::<u32>().ok()?.checked_mul(60)
+    } else {
+        text.strip_suffix('s').unwrap_or(&text).parse::<u32>().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exercises() -> BTreeMap<u32, data::Exercise> {
+        [
+            "Squat",
+            "Bench Press",
+            "Clean & Jerk",
+            "21s",
+            "Rest",
+            "Row",
+            "row",
+        ]
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            let id = u32::try_from(i).unwrap() + 1;
+            (
+                id,
+                data::Exercise {
+                    id,
+                    name: (*name).to_string(),
+                    training_max: None,
+                    metadata: data::ExerciseMetadata::default(),
+                },
+            )
+        })
+        .collect()
+    }
+
+    fn activity(exercise_id: Option<u32>, reps: u32, time: u32, weight: f32) -> data::RoutinePart {
+        data::RoutinePart::RoutineActivity {
+            exercise_id,
+            reps,
+            time,
+            weight,
+            rpe: 0.0,
+            automatic: false,
+            options: data::ActivityOptions::default(),
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            parse("3x[Squat 5@100kg RPE8; rest 120s]", &exercises()),
+            Ok(vec![data::RoutinePart::RoutineSection {
+                rounds: 3,
+                parts: vec![
+                    data::RoutinePart::RoutineActivity {
+                        exercise_id: Some(1),
+                        reps: 5,
+                        time: 0,
+                        weight: 100.0,
+                        rpe: 8.0,
+                        automatic: false,
+                        options: data::ActivityOptions::default(),
+                    },
+                    activity(None, 0, 120, 0.0)
+                ],
+                kind: data::SectionKind::Sequential,
+            }])
+        );
+        assert_eq!(
+            parse(
+                "bench press 8\n\"21s\" 2min\nAMRAP 10min 1x[\n  #9 @20kg\n]",
+                &exercises()
+            ),
+            Ok(vec![
+                activity(Some(2), 8, 0, 0.0),
+                activity(Some(4), 0, 120, 0.0),
+                data::RoutinePart::RoutineSection {
+                    rounds: 1,
+                    parts: vec![activity(Some(9), 0, 0, 20.0)],
+                    kind: data::SectionKind::Amrap { time_cap: 600 },
+                }
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |text| parse(text, &exercises()).unwrap_err().to_string();
+        assert_eq!(
+            error("Squat 5\nDeadlift 5"),
+            "2:1: Unknown exercise \"Deadlift\""
+        );
+        assert_eq!(error("Squat 5 RPE11"), "1:9: Unexpected \"RPE11\"");
+        assert_eq!(
+            error("2x[Squat 5; Row 5]"),
+            "1:13: Ambiguous exercise \"Row\" (use its ID, e.g. #6)"
+        );
+        assert_eq!(error("3x[Squat 5"), "1:11: Missing \"]\"");
+        assert_eq!(error("Squat 5]"), "1:8: Unexpected \"]\"");
+        assert_eq!(error("  x[Squat 5]"), "1:3: Invalid number of rounds \"x\"");
+        assert_eq!(error("EMOM 1h 3x[Squat 5]"), "1:6: Invalid duration \"1h\"");
+        assert_eq!(error("\"Squat 5"), "1:1: Missing closing quote");
+        assert_eq!(
+            error("Squat 5@80%TM linear(2.5)"),
+            "1:15: Progression cannot be combined with relative weight"
+        );
+    }
+
+    #[test]
+    fn test_print_parse_round_trip() {
+        let parts = vec![
+            data::RoutinePart::RoutineActivity {
+                exercise_id: Some(1),
+                reps: 5,
+                time: 3,
+                weight: 102.5,
+                rpe: 8.5,
+                automatic: false,
+                options: data::ActivityOptions {
+                    progression: Some(data::Progression::Double {
+                        max_reps: 8,
+                        increment: 2.5,
+                    }),
+                    auto_warm_up: true,
+                    set_type: data::SetType::Amrap,
+                    relative_weight: None,
+                },
+            },
+            data::RoutinePart::RoutineSection {
+                rounds: 8,
+                parts: vec![
+                    activity(Some(3), 0, 0, 40.0),
+                    activity(Some(4), 10, 0, 0.0),
+                    activity(Some(5), 0, 0, 0.0),
+                    activity(Some(6), 0, 0, 0.0),
+                    data::RoutinePart::RoutineSection {
+                        rounds: 2,
+                        parts: vec![],
+                        kind: data::SectionKind::Emom { interval: 90 },
+                    },
+                ],
+                kind: data::SectionKind::Tabata { work: 20, rest: 10 },
+            },
+            data::RoutinePart::RoutineActivity {
+                exercise_id: None,
+                reps: 0,
+                time: 60,
+                weight: 0.0,
+                rpe: 0.0,
+                automatic: true,
+                options: data::ActivityOptions::default(),
+            },
+            data::RoutinePart::RoutineActivity {
+                exercise_id: Some(2),
+                reps: 5,
+                time: 0,
+                weight: 0.0,
+                rpe: 0.0,
+                automatic: false,
+                options: data::ActivityOptions {
+                    relative_weight: Some(data::RelativeWeight {
+                        percentage: 82.5,
+                        basis: data::WeightBasis::TrainingMax,
+                    }),
+                    ..data::ActivityOptions::default()
+                },
+            },
+            data::RoutinePart::RoutineActivity {
+                exercise_id: Some(1),
+                reps: 0,
+                time: 0,
+                weight: 0.0,
+                rpe: 0.0,
+                automatic: false,
+                options: data::ActivityOptions {
+                    relative_weight: Some(data::RelativeWeight {
+                        percentage: 70.0,
+                        basis: data::WeightBasis::OneRepMax,
+                    }),
+                    ..data::ActivityOptions::default()
+                },
+            },
+            activity(Some(42), 1, 0, 0.0),
+        ];
+        let text = print(&parts, &exercises());
+        assert_eq!(
+            text,
+            "Squat 5@102.5kg 3s RPE8.5 rampup type(amrap) double(8,2.5)\n\
+             Tabata 20s/10s 8x[Clean & Jerk @40kg; \"21s\" 10; \"Rest\"; #6; EMOM 90s 2x[]]\n\
+             rest 60s auto\n\
+             Bench Press 5@82.5%TM\n\
+             Squat @70%e1RM\n\
+             #42 1"
+        );
+        assert_eq!(parse(&text, &exercises()), Ok(parts));
+    }
+}
@@ -7,6 +7,7 @@ pub mod home;
 pub mod login;
 pub mod menstrual_cycle;
 pub mod not_found;
+pub mod programs;
 pub mod routine;
 pub mod routines;
 pub mod training;
@@ -71,6 +71,7 @@ pub enum Msg {
     DataEvent(data::Event),
 
     ChangeInterval(NaiveDate, NaiveDate),
+    TrainingMaxChanged(String),
 }
 
 pub fn update(
@@ -122,6 +123,26 @@ pub fn update(
             model.interval.first = first;
             model.interval.last = last;
         }
+        Msg::TrainingMaxChanged(input) => {
+            let input = input.trim();
+            let training_max = if input.is_empty() {
+                Some(None)
+            } else {
+                input
+                    .parse::<f32>()
+                    .ok()
+                    .filter(|w| common::valid_weight(*w))
+                    .map(Some)
+            };
+            if let (Some(training_max), Some(exercise)) =
+                (training_max, data_model.exercises.get(&model.exercise_id))
+            {
+                orders.notify(data::Msg::ReplaceExercise(data::Exercise {
+                    training_max,
+                    ..exercise.clone()
+                }));
+            }
+        }
     }
 }
 
@@ -146,6 +167,7 @@ pub fn view(model: &Model, data_model: &data::Model) -> Node<Msg> {
         div![
             common::view_title(&span![&exercise.name], 5),
             common::view_interval_buttons(&model.interval, &exercise_interval, Msg::ChangeInterval),
+            view_one_rep_max(model.exercise_id, data_model),
             view_charts(&training_sessions, &model.interval),
             view_calendar(&training_sessions, &model.interval),
             training::view_table(
@@ -161,6 +183,53 @@ pub fn view(model: &Model, data_model: &data::Model) -> Node<Msg> {
     }
 }
 
+fn view_one_rep_max(exercise_id: u32, data_model: &data::Model) -> Node<Msg> {
+    div![
+        C!["is-flex"],
+        C!["is-flex-wrap-wrap"],
+        C!["is-justify-content-center"],
+        C!["is-align-items-center"],
+        C!["mx-2"],
+        C!["mb-4"],
+        div![
+            C!["field"],
+            C!["has-addons"],
+            C!["mb-0"],
+            C!["mr-5"],
+            div![
+                C!["control"],
+                a![C!["button"], C!["is-static"], "Training max"]
+            ],
+            div![
+                C!["control"],
+                input![
+                    C!["input"],
+                    C!["has-text-right"],
+                    attrs! {
+                        At::from("inputmode") => "numeric",
+                        At::Size => 5,
+                        At::Value => data_model
+                            .exercises
+                            .get(&exercise_id)
+                            .and_then(|e| e.training_max)
+                            .map(|w| w.to_string())
+                            .unwrap_or_default(),
+                    },
+                    input_ev(Ev::Change, Msg::TrainingMaxChanged),
+                ]
+            ],
+            div![C!["control"], a![C!["button"], C!["is-static"], "kg"]],
+        ],
+        span![
+            attrs! {At::Title => "Estimated one-repetition maximum based on the last training session"},
+            "e1RM: ",
+            data_model
+                .estimated_one_rep_max(exercise_id)
+                .map_or_else(|| String::from("-"), |w| format!("{w:.1} kg"))
+        ],
+    ]
+}
+
 pub fn view_charts<Ms>(
     training_sessions: &[&data::TrainingSession],
     interval: &common::Interval,
@@ -414,6 +483,10 @@ fn exercise_training_sessions(
                 })
                 .cloned()
                 .collect::<Vec<_>>(),
+            session_rpe: t.session_rpe,
+            duration: t.duration,
+            interval_blocks: vec![],
+            routine_version_id: t.routine_version_id,
         })
         .collect::<Vec<_>>()
 }
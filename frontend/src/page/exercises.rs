@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use seed::{prelude::*, *};
 
@@ -20,6 +20,7 @@ pub fn init(mut url: Url, orders: &mut impl Orders<Msg>, navbar: &mut crate::Nav
 
     Model {
         search_term: String::new(),
+        filter: Filter::default(),
         dialog: Dialog::Hidden,
         loading: false,
     }
@@ -31,20 +32,46 @@ pub fn init(mut url: Url, orders: &mut impl Orders<Msg>, navbar: &mut crate::Nav
 
 pub struct Model {
     search_term: String,
+    filter: Filter,
     dialog: Dialog,
     loading: bool,
 }
 
+#[derive(Default)]
+struct Filter {
+    muscle_group: Option<data::MuscleGroup>,
+    movement_pattern: Option<data::MovementPattern>,
+    equipment: Option<data::Equipment>,
+    laterality: Option<data::Laterality>,
+}
+
+impl Filter {
+    fn matches(&self, metadata: &data::ExerciseMetadata) -> bool {
+        if let Some(muscle_group) = self.muscle_group {
+            if not(metadata.primary_muscles.contains(&muscle_group)
+                || metadata.secondary_muscles.contains(&muscle_group))
+            {
+                return false;
+            }
+        }
+        (self.movement_pattern.is_none() || metadata.movement_pattern == self.movement_pattern)
+            && (self.equipment.is_none() || metadata.equipment == self.equipment)
+            && (self.laterality.is_none() || metadata.laterality == self.laterality)
+    }
+}
+
 enum Dialog {
     Hidden,
     AddExercise(Form),
     EditExercise(Form),
     DeleteExercise(u32),
+    MergeExercises(u32, BTreeSet<u32>),
 }
 
 struct Form {
     id: u32,
     name: (String, Option<String>),
+    metadata: data::ExerciseMetadata,
 }
 
 // ------ ------
@@ -55,13 +82,27 @@ pub enum Msg {
     ShowAddExerciseDialog,
     ShowEditExerciseDialog(u32),
     ShowDeleteExerciseDialog(u32),
+    ShowMergeExercisesDialog(u32),
     CloseExerciseDialog,
 
     SearchTermChanged(String),
+    FilterMuscleGroupChanged(String),
+    FilterMovementPatternChanged(String),
+    FilterEquipmentChanged(String),
+    FilterLateralityChanged(String),
+
     NameChanged(String),
+    MuscleGroupToggled(data::MuscleGroup),
+    MovementPatternChanged(String),
+    EquipmentChanged(String),
+    LateralityChanged(String),
+    AlternativeAdded(String),
+    AlternativeRemoved(u32),
+    MergedExerciseToggled(u32),
 
     SaveExercise,
     DeleteExercise(u32),
+    MergeExercises,
     DataEvent(data::Event),
 }
 
@@ -76,6 +117,7 @@ pub fn update(
             model.dialog = Dialog::AddExercise(Form {
                 id: 0,
                 name: (String::new(), None),
+                metadata: data::ExerciseMetadata::default(),
             });
         }
         Msg::ShowEditExerciseDialog(id) => {
@@ -84,11 +126,15 @@ pub fn update(
             model.dialog = Dialog::EditExercise(Form {
                 id,
                 name: (name.clone(), Some(name)),
+                metadata: data_model.exercises[&id].metadata.clone(),
             });
         }
         Msg::ShowDeleteExerciseDialog(id) => {
             model.dialog = Dialog::DeleteExercise(id);
         }
+        Msg::ShowMergeExercisesDialog(id) => {
+            model.dialog = Dialog::MergeExercises(id, BTreeSet::new());
+        }
         Msg::CloseExerciseDialog => {
             model.dialog = Dialog::Hidden;
             Url::go_and_replace(&crate::Urls::new(&data_model.base_url).exercises());
@@ -97,6 +143,18 @@ pub fn update(
         Msg::SearchTermChanged(search_term) => {
             model.search_term = search_term;
         }
+        Msg::FilterMuscleGroupChanged(value) => {
+            model.filter.muscle_group = parse_option(&value, &data::MuscleGroup::ALL);
+        }
+        Msg::FilterMovementPatternChanged(value) => {
+            model.filter.movement_pattern = parse_option(&value, &data::MovementPattern::ALL);
+        }
+        Msg::FilterEquipmentChanged(value) => {
+            model.filter.equipment = parse_option(&value, &data::Equipment::ALL);
+        }
+        Msg::FilterLateralityChanged(value) => {
+            model.filter.laterality = parse_option(&value, &data::Laterality::ALL);
+        }
         Msg::NameChanged(name) => match model.dialog {
             Dialog::AddExercise(ref mut form) | Dialog::EditExercise(ref mut form) => {
                 if data_model.exercises.values().all(|e| e.name != name) {
@@ -105,7 +163,75 @@ pub fn update(
                     form.name = (name, None);
                 }
             }
-            Dialog::Hidden | Dialog::DeleteExercise(_) => {
+            Dialog::Hidden | Dialog::DeleteExercise(_) | Dialog::MergeExercises(..) => {
+                panic!();
+            }
+        },
+        Msg::MuscleGroupToggled(muscle_group) => match model.dialog {
+            Dialog::AddExercise(ref mut form) | Dialog::EditExercise(ref mut form) => {
+                let metadata = &mut form.metadata;
+                if metadata.primary_muscles.remove(&muscle_group) {
+                    metadata.secondary_muscles.insert(muscle_group);
+                } else if not(metadata.secondary_muscles.remove(&muscle_group)) {
+                    metadata.primary_muscles.insert(muscle_group);
+                }
+            }
+            Dialog::Hidden | Dialog::DeleteExercise(_) | Dialog::MergeExercises(..) => {
+                panic!();
+            }
+        },
+        Msg::MovementPatternChanged(value) => match model.dialog {
+            Dialog::AddExercise(ref mut form) | Dialog::EditExercise(ref mut form) => {
+                form.metadata.movement_pattern = parse_option(&value, &data::MovementPattern::ALL);
+            }
+            Dialog::Hidden | Dialog::DeleteExercise(_) | Dialog::MergeExercises(..) => {
+                panic!();
+            }
+        },
+        Msg::EquipmentChanged(value) => match model.dialog {
+            Dialog::AddExercise(ref mut form) | Dialog::EditExercise(ref mut form) => {
+                form.metadata.equipment = parse_option(&value, &data::Equipment::ALL);
+            }
+            Dialog::Hidden | Dialog::DeleteExercise(_) | Dialog::MergeExercises(..) => {
+                panic!();
+            }
+        },
+        Msg::LateralityChanged(value) => match model.dialog {
+            Dialog::AddExercise(ref mut form) | Dialog::EditExercise(ref mut form) => {
+                form.metadata.laterality = parse_option(&value, &data::Laterality::ALL);
+            }
+            Dialog::Hidden | Dialog::DeleteExercise(_) | Dialog::MergeExercises(..) => {
+                panic!();
+            }
+        },
+        Msg::AlternativeAdded(value) => match model.dialog {
+            Dialog::AddExercise(ref mut form) | Dialog::EditExercise(ref mut form) => {
+                if let Ok(id) = value.parse::<u32>() {
+                    form.metadata.alternatives.insert(id);
+                }
+            }
+            Dialog::Hidden | Dialog::DeleteExercise(_) | Dialog::MergeExercises(..) => {
+                panic!();
+            }
+        },
+        Msg::AlternativeRemoved(id) => match model.dialog {
+            Dialog::AddExercise(ref mut form) | Dialog::EditExercise(ref mut form) => {
+                form.metadata.alternatives.remove(&id);
+            }
+            Dialog::Hidden | Dialog::DeleteExercise(_) | Dialog::MergeExercises(..) => {
+                panic!();
+            }
+        },
+        Msg::MergedExerciseToggled(id) => match model.dialog {
+            Dialog::MergeExercises(_, ref mut exercise_ids) => {
+                if not(exercise_ids.remove(&id)) {
+                    exercise_ids.insert(id);
+                }
+            }
+            Dialog::Hidden
+            | Dialog::AddExercise(_)
+            | Dialog::EditExercise(_)
+            | Dialog::DeleteExercise(_) => {
                 panic!();
             }
         },
@@ -114,15 +240,23 @@ pub fn update(
             model.loading = true;
             match model.dialog {
                 Dialog::AddExercise(ref mut form) => {
-                    orders.notify(data::Msg::CreateExercise(form.name.1.clone().unwrap()));
+                    orders.notify(data::Msg::CreateExercise(
+                        form.name.1.clone().unwrap(),
+                        form.metadata.clone(),
+                    ));
                 }
                 Dialog::EditExercise(ref mut form) => {
                     orders.notify(data::Msg::ReplaceExercise(data::Exercise {
                         id: form.id,
                         name: form.name.1.clone().unwrap(),
+                        training_max: data_model
+                            .exercises
+                            .get(&form.id)
+                            .and_then(|e| e.training_max),
+                        metadata: form.metadata.clone(),
                     }));
                 }
-                Dialog::Hidden | Dialog::DeleteExercise(_) => {
+                Dialog::Hidden | Dialog::DeleteExercise(_) | Dialog::MergeExercises(..) => {
                     panic!();
                 }
             };
@@ -131,12 +265,19 @@ pub fn update(
             model.loading = true;
             orders.notify(data::Msg::DeleteExercise(id));
         }
+        Msg::MergeExercises => {
+            if let Dialog::MergeExercises(survivor, ref exercise_ids) = model.dialog {
+                model.loading = true;
+                orders.notify(data::Msg::MergeExercises(survivor, exercise_ids.clone()));
+            }
+        }
         Msg::DataEvent(event) => {
             model.loading = false;
             match event {
                 data::Event::ExerciseCreatedOk
                 | data::Event::ExerciseReplacedOk
-                | data::Event::ExerciseDeletedOk => {
+                | data::Event::ExerciseDeletedOk
+                | data::Event::ExercisesMergedOk => {
                     orders.skip().send_msg(Msg::CloseExerciseDialog);
                 }
                 _ => {}
@@ -154,22 +295,62 @@ pub fn view(model: &Model, data_model: &data::Model) -> Node<Msg> {
         common::view_page_loading()
     } else {
         div![
-            view_exercise_dialog(&model.dialog, &data_model.exercises, model.loading),
+            view_exercise_dialog(&model.dialog, data_model, model.loading),
             div![
                 C!["px-4"],
-                common::view_search_box(&model.search_term, Msg::SearchTermChanged)
+                C!["field"],
+                C!["is-grouped"],
+                C!["is-grouped-multiline"],
+                common::view_search_box(&model.search_term, Msg::SearchTermChanged),
+                div![
+                    C!["control"],
+                    view_select(
+                        "Muscle",
+                        &data::MuscleGroup::ALL,
+                        data::MuscleGroup::name,
+                        model.filter.muscle_group,
+                        Msg::FilterMuscleGroupChanged
+                    )
+                ],
+                div![
+                    C!["control"],
+                    view_select(
+                        "Pattern",
+                        &data::MovementPattern::ALL,
+                        data::MovementPattern::name,
+                        model.filter.movement_pattern,
+                        Msg::FilterMovementPatternChanged
+                    )
+                ],
+                div![
+                    C!["control"],
+                    view_select(
+                        "Equipment",
+                        &data::Equipment::ALL,
+                        data::Equipment::name,
+                        model.filter.equipment,
+                        Msg::FilterEquipmentChanged
+                    )
+                ],
+                div![
+                    C!["control"],
+                    view_select(
+                        "Laterality",
+                        &data::Laterality::ALL,
+                        data::Laterality::name,
+                        model.filter.laterality,
+                        Msg::FilterLateralityChanged
+                    )
+                ],
             ],
-            view_table(&model.search_term, data_model),
+            view_table(&model.search_term, &model.filter, data_model),
             common::view_fab("plus", |_| Msg::ShowAddExerciseDialog),
         ]
     }
 }
 
-fn view_exercise_dialog(
-    dialog: &Dialog,
-    exercises: &BTreeMap<u32, data::Exercise>,
-    loading: bool,
-) -> Node<Msg> {
+fn view_exercise_dialog(dialog: &Dialog, data_model: &data::Model, loading: bool) -> Node<Msg> {
+    let exercises = &data_model.exercises;
     let title;
     let form;
     match dialog {
@@ -191,6 +372,9 @@ fn view_exercise_dialog(
                 loading,
             );
         }
+        Dialog::MergeExercises(survivor, exercise_ids) => {
+            return view_merge_exercises_dialog(*survivor, exercise_ids, data_model, loading);
+        }
         Dialog::Hidden => {
             return empty![];
         }
@@ -223,6 +407,71 @@ fn view_exercise_dialog(
                     ],
                 ]
             ],
+            div![
+                C!["field"],
+                label![C!["label"], "Muscles"],
+                p![
+                    C!["help"],
+                    C!["mb-2"],
+                    "Select once for primary and twice for secondary muscles."
+                ],
+                div![
+                    C!["buttons"],
+                    data::MuscleGroup::ALL
+                        .iter()
+                        .map(|m| {
+                            let muscle_group = *m;
+                            button![
+                                C!["button"],
+                                C!["is-small"],
+                                IF![form.metadata.primary_muscles.contains(m) => C!["is-link"]],
+                                IF![form.metadata.secondary_muscles.contains(m) => C!["is-link is-light"]],
+                                ev(Ev::Click, move |_| Msg::MuscleGroupToggled(muscle_group)),
+                                m.name(),
+                            ]
+                        })
+                        .collect::<Vec<_>>()
+                ],
+            ],
+            div![
+                C!["field"],
+                C!["is-grouped"],
+                C!["is-grouped-multiline"],
+                div![
+                    C!["control"],
+                    label![C!["label"], "Movement pattern"],
+                    view_select(
+                        "-",
+                        &data::MovementPattern::ALL,
+                        data::MovementPattern::name,
+                        form.metadata.movement_pattern,
+                        Msg::MovementPatternChanged
+                    ),
+                ],
+                div![
+                    C!["control"],
+                    label![C!["label"], "Equipment"],
+                    view_select(
+                        "-",
+                        &data::Equipment::ALL,
+                        data::Equipment::name,
+                        form.metadata.equipment,
+                        Msg::EquipmentChanged
+                    ),
+                ],
+                div![
+                    C!["control"],
+                    label![C!["label"], "Laterality"],
+                    view_select(
+                        "-",
+                        &data::Laterality::ALL,
+                        data::Laterality::name,
+                        form.metadata.laterality,
+                        Msg::LateralityChanged
+                    ),
+                ],
+            ],
+            view_alternatives(form, exercises),
             div![
                 C!["field"],
                 C!["is-grouped"],
@@ -256,11 +505,202 @@ fn view_exercise_dialog(
     )
 }
 
-fn view_table(search_term: &str, data_model: &data::Model) -> Node<Msg> {
+fn view_alternatives(form: &Form, exercises: &BTreeMap<u32, data::Exercise>) -> Node<Msg> {
+    let mut candidates = exercises
+        .values()
+        .filter(|e| e.id != form.id && not(form.metadata.alternatives.contains(&e.id)))
+        .collect::<Vec<_>>();
+    candidates.sort_by(|a, b| a.name.cmp(&b.name));
+    div![
+        C!["field"],
+        label![C!["label"], "Alternatives"],
+        div![
+            C!["tags"],
+            form.metadata
+                .alternatives
+                .iter()
+                .filter_map(|id| exercises.get(id))
+                .map(|e| {
+                    let id = e.id;
+                    span![
+                        C!["tag"],
+                        C!["is-link"],
+                        C!["is-light"],
+                        &e.name,
+                        button![
+                            C!["delete"],
+                            C!["is-small"],
+                            ev(Ev::Click, move |_| Msg::AlternativeRemoved(id)),
+                        ]
+                    ]
+                })
+                .collect::<Vec<_>>()
+        ],
+        div![
+            C!["select"],
+            input_ev(Ev::Change, Msg::AlternativeAdded),
+            select![
+                option![
+                    "Add alternative",
+                    attrs! {
+                        At::Value => "",
+                        At::Selected => true.as_at_value(),
+                    }
+                ],
+                candidates
+                    .iter()
+                    .map(|e| option![&e.name, attrs! {At::Value => e.id}])
+                    .collect::<Vec<_>>()
+            ]
+        ]
+    ]
+}
+
+fn view_merge_exercises_dialog(
+    survivor: u32,
+    exercise_ids: &BTreeSet<u32>,
+    data_model: &data::Model,
+    loading: bool,
+) -> Node<Msg> {
     let mut exercises = data_model
         .exercises
         .values()
-        .filter(|e| e.name.to_lowercase().contains(&search_term.to_lowercase()))
+        .filter(|e| e.id != survivor)
+        .collect::<Vec<_>>();
+    exercises.sort_by(|a, b| a.name.cmp(&b.name));
+    let affected_sets = data_model
+        .training_sessions
+        .values()
+        .map(|t| t.num_sets_of(exercise_ids))
+        .collect::<Vec<_>>();
+    let affected_activities = data_model
+        .routines
+        .values()
+        .map(|r| r.num_activities_of(exercise_ids))
+        .collect::<Vec<_>>();
+    let merge_disabled = loading || exercise_ids.is_empty();
+    common::view_dialog(
+        "warning",
+        &format!("Merge into {}", data_model.exercises[&survivor].name),
+        nodes![
+            p![
+                C!["mb-3"],
+                "Select the exercises to merge. All their sets and routine activities will be assigned to this exercise and the merged exercises will be deleted."
+            ],
+            div![
+                C!["field"],
+                C!["is-grouped"],
+                C!["is-grouped-multiline"],
+                exercises
+                    .iter()
+                    .map(|e| {
+                        let id = e.id;
+                        div![
+                            C!["control"],
+                            label![
+                                C!["checkbox"],
+                                input![
+                                    attrs! {
+                                        At::Type => "checkbox",
+                                        At::Checked => exercise_ids.contains(&id).as_at_value(),
+                                    },
+                                    ev(Ev::Change, move |_| Msg::MergedExerciseToggled(id)),
+                                ],
+                                format!(" {}", e.name),
+                            ]
+                        ]
+                    })
+                    .collect::<Vec<_>>()
+            ],
+            IF![not(exercise_ids.is_empty()) => p![
+                C!["notification"],
+                C!["is-warning"],
+                C!["is-light"],
+                format!(
+                    "{} sets in {} training sessions and {} activities in {} routines will be changed. {} exercises will be deleted.",
+                    affected_sets.iter().sum::<usize>(),
+                    affected_sets.iter().filter(|n| **n > 0).count(),
+                    affected_activities.iter().sum::<usize>(),
+                    affected_activities.iter().filter(|n| **n > 0).count(),
+                    exercise_ids.len(),
+                )
+            ]],
+            div![
+                C!["field"],
+                C!["is-grouped"],
+                C!["is-grouped-centered"],
+                C!["mt-5"],
+                div![
+                    C!["control"],
+                    button![
+                        C!["button"],
+                        C!["is-light"],
+                        ev(Ev::Click, |_| Msg::CloseExerciseDialog),
+                        "Cancel",
+                    ]
+                ],
+                div![
+                    C!["control"],
+                    button![
+                        C!["button"],
+                        C!["is-warning"],
+                        C![IF![loading => "is-loading"]],
+                        attrs! {
+                            At::Disabled => merge_disabled.as_at_value(),
+                        },
+                        ev(Ev::Click, |_| Msg::MergeExercises),
+                        "Merge",
+                    ]
+                ],
+            ],
+        ],
+        &ev(Ev::Click, |_| Msg::CloseExerciseDialog),
+    )
+}
+
+fn view_select<T: Copy + PartialEq>(
+    placeholder: &str,
+    options: &[T],
+    name: fn(T) -> &'static str,
+    selected: Option<T>,
+    message: fn(String) -> Msg,
+) -> Node<Msg> {
+    div![
+        C!["select"],
+        input_ev(Ev::Change, message),
+        select![
+            option![
+                placeholder,
+                attrs! {
+                    At::Value => "",
+                    At::Selected => selected.is_none().as_at_value(),
+                }
+            ],
+            options
+                .iter()
+                .enumerate()
+                .map(|(i, o)| {
+                    option![
+                        name(*o),
+                        attrs! {
+                            At::Value => i,
+                            At::Selected => (selected == Some(*o)).as_at_value(),
+                        }
+                    ]
+                })
+                .collect::<Vec<_>>()
+        ]
+    ]
+}
+
+fn view_table(search_term: &str, filter: &Filter, data_model: &data::Model) -> Node<Msg> {
+    let mut exercises = data_model
+        .exercises
+        .values()
+        .filter(|e| {
+            e.name.to_lowercase().contains(&search_term.to_lowercase())
+                && filter.matches(&e.metadata)
+        })
         .collect::<Vec<_>>();
     exercises.sort_by(|a, b| a.name.cmp(&b.name));
 
@@ -296,6 +736,12 @@ fn view_table(search_term: &str, data_model: &data::Model) -> Node<Msg> {
                                 ev(Ev::Click, move |_| Msg::ShowEditExerciseDialog(id)),
                                 i![C!["fas fa-edit"]]
                             ],
+                            a![
+                                C!["icon"],
+                                C!["mx-1"],
+                                ev(Ev::Click, move |_| Msg::ShowMergeExercisesDialog(id)),
+                                i![C!["fas fa-object-group"]]
+                            ],
                             a![
                                 C!["icon"],
                                 C!["ml-1"],
@@ -309,3 +755,10 @@ fn view_table(search_term: &str, data_model: &data::Model) -> Node<Msg> {
         ]
     ]
 }
+
+fn parse_option<T: Copy>(value: &str, options: &[T]) -> Option<T> {
+    value
+        .parse::<usize>()
+        .ok()
+        .and_then(|i| options.get(i).copied())
+}
@@ -67,12 +67,29 @@ pub fn view(_model: &Model, data_model: &data::Model) -> Node<Msg> {
         } else {
             String::new()
         };
-    let training_content =
+    let mut training_content =
         if let Some((_, training_session)) = &data_model.training_sessions.last_key_value() {
             last("session", today - training_session.date)
         } else {
             String::new()
         };
+    if let Some(schedule) = data_model.schedule.as_ref() {
+        if let Some((date, routine_id)) =
+            schedule.next_pending(&data_model.training_sessions, today)
+        {
+            if let Some(routine) = data_model.routines.get(&routine_id) {
+                let next = format!(
+                    "Next: <strong>{}</strong> {}.",
+                    escape_html(&routine.name),
+                    common::format_relative_date(date, today)
+                );
+                if not(training_content.is_empty()) {
+                    training_content.push_str("<br>");
+                }
+                training_content.push_str(&next);
+            }
+        }
+    }
 
     if data_model.body_weight.is_empty() && data_model.loading_body_weight {
         body_weight_subtitle = common::view_loading::<Msg>().to_string();
@@ -194,6 +211,12 @@ fn view_tile(title: &str, subtitle: &str, content: &str, target: Url) -> Node<Ms
     ]
 }
 
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
 fn last(text: &str, duration: chrono::Duration) -> String {
     if duration.num_days() == 0 {
         return format!("Last {text} <strong>today</strong>.");
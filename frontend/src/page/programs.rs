@@ -0,0 +1,519 @@
+use std::collections::BTreeMap;
+
+use seed::{prelude::*, *};
+
+use crate::common;
+use crate::data;
+
+// ------ ------
+//     Init
+// ------ ------
+
+pub fn init(mut url: Url, orders: &mut impl Orders<Msg>, navbar: &mut crate::Navbar) -> Model {
+    if url.next_hash_path_part() == Some("add") {
+        orders.send_msg(Msg::ShowAddProgramDialog);
+    }
+
+    orders.subscribe(Msg::DataEvent);
+
+    navbar.title = String::from("Programs");
+
+    Model {
+        dialog: Dialog::Hidden,
+    }
+}
+
+// ------ ------
+//     Model
+// ------ ------
+
+pub struct Model {
+    dialog: Dialog,
+}
+
+enum Dialog {
+    Hidden,
+    AddProgram(Form),
+    EditProgram(Form),
+    DeleteProgram(u32),
+}
+
+struct Form {
+    id: u32,
+    name: (String, Option<String>),
+}
+
+// ------ ------
+//    Update
+// ------ ------
+
+pub enum Msg {
+    ShowAddProgramDialog,
+    ShowEditProgramDialog(u32),
+    ShowDeleteProgramDialog(u32),
+    CloseProgramDialog,
+
+    NameChanged(String),
+
+    AddWeek(u32),
+    RemoveWeek(u32, usize),
+    AddDay(u32, usize),
+    RoutineChanged(u32, usize, usize, String),
+    WeightPercentageChanged(u32, usize, String),
+    RPEOffsetChanged(u32, usize, String),
+
+    StartProgram(u32),
+    StopProgram(u32),
+
+    SaveProgram,
+    DeleteProgram(u32),
+    DataEvent(data::Event),
+}
+
+pub fn update(
+    msg: Msg,
+    model: &mut Model,
+    data_model: &data::Model,
+    orders: &mut impl Orders<Msg>,
+) {
+    match msg {
+        Msg::ShowAddProgramDialog => {
+            model.dialog = Dialog::AddProgram(Form {
+                id: 0,
+                name: (String::new(), None),
+            });
+        }
+        Msg::ShowEditProgramDialog(id) => {
+            let name = data_model.programs[&id].name.clone();
+            model.dialog = Dialog::EditProgram(Form {
+                id,
+                name: (name.clone(), Some(name)),
+            });
+        }
+        Msg::ShowDeleteProgramDialog(id) => {
+            model.dialog = Dialog::DeleteProgram(id);
+        }
+        Msg::CloseProgramDialog => {
+            model.dialog = Dialog::Hidden;
+            Url::go_and_replace(&crate::Urls::new(&data_model.base_url).programs());
+        }
+
+        Msg::NameChanged(name) => match model.dialog {
+            Dialog::AddProgram(ref mut form) | Dialog::EditProgram(ref mut form) => {
+                if not(name.trim().is_empty())
+                    && data_model.programs.values().all(|p| p.name != name)
+                {
+                    form.name = (name.clone(), Some(name));
+                } else {
+                    form.name = (name, None);
+                }
+            }
+            Dialog::Hidden | Dialog::DeleteProgram(_) => {
+                panic!();
+            }
+        },
+
+        Msg::AddWeek(id) => {
+            modify_program(id, data_model, orders, |program| {
+                let week = program.weeks.last().cloned().unwrap_or_default();
+                program.weeks.push(week);
+            });
+        }
+        Msg::RemoveWeek(id, week) => {
+            modify_program(id, data_model, orders, |program| {
+                program.remove_week(week);
+            });
+        }
+        Msg::AddDay(id, week) => {
+            if let Some(routine_id) = data_model.routines.keys().max().copied() {
+                modify_program(id, data_model, orders, |program| {
+                    program.weeks[week].routines.push(routine_id);
+                });
+            }
+        }
+        Msg::RoutineChanged(id, week, day, input) => {
+            modify_program(id, data_model, orders, |program| {
+                if let Ok(routine_id) = input.parse::<u32>() {
+                    program.weeks[week].routines[day] = routine_id;
+                } else {
+                    program.remove_day(week, day);
+                }
+            });
+        }
+        Msg::WeightPercentageChanged(id, week, input) => {
+            if let Ok(weight_percentage) = input.parse::<f32>() {
+                if weight_percentage > 0.0 && weight_percentage <= 200.0 {
+                    modify_program(id, data_model, orders, |program| {
+                        program.weeks[week].weight_percentage = weight_percentage;
+                    });
+                }
+            }
+        }
+        Msg::RPEOffsetChanged(id, week, input) => {
+            let input = if input.is_empty() {
+                String::from("0")
+            } else {
+                input
+            };
+            if let Ok(rpe_offset) = input.parse::<f32>() {
+                if (-5.0..=5.0).contains(&rpe_offset) {
+                    modify_program(id, data_model, orders, |program| {
+                        program.weeks[week].rpe_offset = rpe_offset;
+                    });
+                }
+            }
+        }
+
+        Msg::StartProgram(id) => {
+            modify_program(id, data_model, orders, |program| {
+                program.position = Some(data::ProgramPosition { week: 0, day: 0 });
+                if program.next_routine().is_none() {
+                    program.advance();
+                }
+            });
+        }
+        Msg::StopProgram(id) => {
+            modify_program(id, data_model, orders, |program| {
+                program.position = None;
+            });
+        }
+
+        Msg::SaveProgram => match model.dialog {
+            Dialog::AddProgram(ref mut form) => {
+                orders.notify(data::Msg::CreateProgram(form.name.1.clone().unwrap()));
+            }
+            Dialog::EditProgram(ref mut form) => {
+                let name = form.name.1.clone().unwrap();
+                modify_program(form.id, data_model, orders, |program| {
+                    program.name = name;
+                });
+                orders.send_msg(Msg::CloseProgramDialog);
+            }
+            Dialog::Hidden | Dialog::DeleteProgram(_) => {
+                panic!();
+            }
+        },
+        Msg::DeleteProgram(id) => {
+            orders.notify(data::Msg::DeleteProgram(id));
+        }
+        Msg::DataEvent(event) => match event {
+            data::Event::ProgramCreatedOk | data::Event::ProgramDeletedOk => {
+                orders.skip().send_msg(Msg::CloseProgramDialog);
+            }
+            _ => {}
+        },
+    }
+}
+
+fn modify_program(
+    id: u32,
+    data_model: &data::Model,
+    orders: &mut impl Orders<Msg>,
+    modify: impl FnOnce(&mut data::Program),
+) {
+    if let Some(program) = data_model.programs.get(&id) {
+        let mut program = program.clone();
+        modify(&mut program);
+        orders.notify(data::Msg::ReplaceProgram(program));
+    }
+}
+
+// ------ ------
+//     View
+// ------ ------
+
+pub fn view(model: &Model, data_model: &data::Model) -> Node<Msg> {
+    if data_model.programs.is_empty() && data_model.loading_programs {
+        common::view_page_loading()
+    } else {
+        div![
+            view_program_dialog(&model.dialog),
+            data_model
+                .programs
+                .values()
+                .rev()
+                .map(|p| view_program(p, &data_model.routines))
+                .collect::<Vec<_>>(),
+            common::view_fab("plus", |_| Msg::ShowAddProgramDialog),
+        ]
+    }
+}
+
+fn view_program_dialog(dialog: &Dialog) -> Node<Msg> {
+    let title;
+    let form;
+    match dialog {
+        Dialog::AddProgram(ref f) => {
+            title = "Add program";
+            form = f;
+        }
+        Dialog::EditProgram(ref f) => {
+            title = "Edit program";
+            form = f;
+        }
+        Dialog::DeleteProgram(id) => {
+            let id = *id;
+            return common::view_delete_confirmation_dialog(
+                "program",
+                &ev(Ev::Click, move |_| Msg::DeleteProgram(id)),
+                &ev(Ev::Click, |_| Msg::CloseProgramDialog),
+                false,
+            );
+        }
+        Dialog::Hidden => {
+            return empty![];
+        }
+    }
+    let save_disabled = form.name.1.is_none();
+    common::view_dialog(
+        "primary",
+        title,
+        nodes![
+            div![
+                C!["field"],
+                label![C!["label"], "Name"],
+                div![
+                    C!["control"],
+                    input_ev(Ev::Input, Msg::NameChanged),
+                    keyboard_ev(Ev::KeyDown, move |keyboard_event| {
+                        IF!(
+                            not(save_disabled) && keyboard_event.key_code() == common::ENTER_KEY => {
+                                Msg::SaveProgram
+                            }
+                        )
+                    }),
+                    input![
+                        C!["input"],
+                        C![IF![form.name.1.is_none() => "is-danger"]],
+                        attrs! {
+                            At::Type => "text",
+                            At::Value => form.name.0,
+                        }
+                    ],
+                ]
+            ],
+            div![
+                C!["field"],
+                C!["is-grouped"],
+                C!["is-grouped-centered"],
+                C!["mt-5"],
+                div![
+                    C!["control"],
+                    button![
+                        C!["button"],
+                        C!["is-light"],
+                        ev(Ev::Click, |_| Msg::CloseProgramDialog),
+                        "Cancel",
+                    ]
+                ],
+                div![
+                    C!["control"],
+                    button![
+                        C!["button"],
+                        C!["is-primary"],
+                        attrs! {
+                            At::Disabled => save_disabled.as_at_value(),
+                        },
+                        ev(Ev::Click, |_| Msg::SaveProgram),
+                        "Save",
+                    ]
+                ],
+            ],
+        ],
+        &ev(Ev::Click, |_| Msg::CloseProgramDialog),
+    )
+}
+
+fn view_program(program: &data::Program, routines: &BTreeMap<u32, data::Routine>) -> Node<Msg> {
+    let id = program.id;
+    div![
+        C!["box"],
+        C!["mx-2"],
+        div![
+            C!["is-flex"],
+            C!["is-justify-content-space-between"],
+            C!["mb-3"],
+            h1![C!["title"], C!["is-5"], C!["mb-0"], &program.name],
+            p![
+                C!["is-flex is-flex-wrap-nowrap"],
+                a![
+                    C!["icon"],
+                    C!["mr-1"],
+                    ev(Ev::Click, move |_| Msg::ShowEditProgramDialog(id)),
+                    i![C!["fas fa-edit"]]
+                ],
+                a![
+                    C!["icon"],
+                    C!["ml-1"],
+                    ev(Ev::Click, move |_| Msg::ShowDeleteProgramDialog(id)),
+                    i![C!["fas fa-times"]]
+                ]
+            ]
+        ],
+        div![
+            C!["mb-3"],
+            if let Some(position) = program.position {
+                nodes![
+                    span![
+                        C!["tag"],
+                        C!["is-success"],
+                        C!["mr-2"],
+                        format!("Next: week {}, day {}", position.week + 1, position.day + 1)
+                    ],
+                    button![
+                        C!["button"],
+                        C!["is-small"],
+                        ev(Ev::Click, move |_| Msg::StopProgram(id)),
+                        span![C!["icon"], i![C!["fas fa-stop"]]],
+                        span!["Stop"]
+                    ]
+                ]
+            } else {
+                nodes![button![
+                    C!["button"],
+                    C!["is-small"],
+                    C!["is-link"],
+                    attrs! {
+                        At::Disabled => program.weeks.iter().all(|w| w.routines.is_empty()).as_at_value(),
+                    },
+                    ev(Ev::Click, move |_| Msg::StartProgram(id)),
+                    span![C!["icon"], i![C!["fas fa-play"]]],
+                    span!["Start"]
+                ]]
+            }
+        ],
+        div![
+            C!["table-container"],
+            table![
+                C!["table"],
+                C!["is-fullwidth"],
+                thead![tr![
+                    th!["Week"],
+                    th!["Routines"],
+                    th!["Weight"],
+                    th!["RPE"],
+                    th![],
+                ]],
+                tbody![program
+                    .weeks
+                    .iter()
+                    .enumerate()
+                    .map(|(week_idx, week)| view_week(program, week_idx, week, routines))
+                    .collect::<Vec<_>>()],
+            ]
+        ],
+        button![
+            C!["button"],
+            C!["is-small"],
+            ev(Ev::Click, move |_| Msg::AddWeek(id)),
+            span![C!["icon"], i![C!["fas fa-plus"]]],
+            span!["Week"]
+        ]
+    ]
+}
+
+fn view_week(
+    program: &data::Program,
+    week_idx: usize,
+    week: &data::ProgramWeek,
+    routines: &BTreeMap<u32, data::Routine>,
+) -> Node<Msg> {
+    let id = program.id;
+    tr![
+        IF![program.position.map_or(false, |p| p.week == week_idx) => C!["is-selected"]],
+        td![week_idx + 1],
+        td![div![
+            C!["field"],
+            C!["is-grouped"],
+            C!["is-grouped-multiline"],
+            week.routines
+                .iter()
+                .enumerate()
+                .map(|(day_idx, routine_id)| {
+                    div![
+                        C!["control"],
+                        div![
+                            C!["select"],
+                            C!["is-small"],
+                            input_ev(Ev::Change, move |v| Msg::RoutineChanged(
+                                id, week_idx, day_idx, v
+                            )),
+                            select![
+                                option![
+                                    "–",
+                                    attrs! {
+                                        At::Value => "",
+                                    }
+                                ],
+                                routines
+                                    .values()
+                                    .rev()
+                                    .map(|r| {
+                                        option![
+                                            &r.name,
+                                            attrs! {
+                                                At::Value => r.id,
+                                                At::Selected => (r.id == *routine_id).as_at_value(),
+                                            }
+                                        ]
+                                    })
+                                    .collect::<Vec<_>>()
+                            ]
+                        ]
+                    ]
+                })
+                .collect::<Vec<_>>(),
+            div![
+                C!["control"],
+                button![
+                    C!["button"],
+                    C!["is-small"],
+                    attrs! {
+                        At::Disabled => routines.is_empty().as_at_value(),
+                    },
+                    ev(Ev::Click, move |_| Msg::AddDay(id, week_idx)),
+                    span![C!["icon"], i![C!["fas fa-plus"]]],
+                ]
+            ]
+        ]],
+        td![div![
+            C!["control"],
+            C!["has-icons-right"],
+            input_ev(Ev::Change, move |v| Msg::WeightPercentageChanged(
+                id, week_idx, v
+            )),
+            input![
+                C!["input"],
+                C!["is-small"],
+                C!["has-text-right"],
+                attrs! {
+                    At::from("inputmode") => "numeric",
+                    At::Size => 3,
+                    At::Value => week.weight_percentage,
+                }
+            ],
+            span![C!["icon"], C!["is-small"], C!["is-right"], "%"],
+        ]],
+        td![div![
+            C!["control"],
+            C!["has-icons-left"],
+            input_ev(Ev::Change, move |v| Msg::RPEOffsetChanged(id, week_idx, v)),
+            span![C!["icon"], C!["is-small"], C!["is-left"], "±"],
+            input![
+                C!["input"],
+                C!["is-small"],
+                C!["has-text-right"],
+                attrs! {
+                    At::from("inputmode") => "numeric",
+                    At::Size => 2,
+                    At::Value => week.rpe_offset,
+                }
+            ],
+        ]],
+        td![a![
+            C!["icon"],
+            ev(Ev::Click, move |_| Msg::RemoveWeek(id, week_idx)),
+            i![C!["fas fa-times"]]
+        ]],
+    ]
+}
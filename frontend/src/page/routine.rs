@@ -1,11 +1,12 @@
 use std::collections::BTreeMap;
 use std::collections::BTreeSet;
 
-use chrono::prelude::*;
+use chrono::{prelude::*, Duration};
 use seed::{prelude::*, *};
 
 use crate::common;
 use crate::data;
+use crate::notation;
 use crate::page::training;
 
 // ------ ------
@@ -26,6 +27,24 @@ pub fn init(
     let editing = url.next_hash_path_part() == Some("edit");
 
     orders.subscribe(Msg::DataEvent);
+    let keydown_stream = orders.stream_with_handle(streams::window_event(Ev::KeyDown, |event| {
+        let keyboard_event: web_sys::KeyboardEvent = event.unchecked_into();
+        let in_textarea = keyboard_event
+            .target()
+            .and_then(|t| t.dyn_into::<web_sys::HtmlTextAreaElement>().ok())
+            .is_some();
+        if not(keyboard_event.ctrl_key() || keyboard_event.meta_key()) || in_textarea {
+            return None;
+        }
+        let msg = match keyboard_event.key().to_lowercase().as_str() {
+            "z" if keyboard_event.shift_key() => Msg::Redo,
+            "z" => Msg::Undo,
+            "y" => Msg::Redo,
+            _ => return None,
+        };
+        keyboard_event.prevent_default();
+        Some(msg)
+    }));
 
     navbar.title = String::from("Routine");
 
@@ -36,6 +55,11 @@ pub fn init(
         previous_exercises: BTreeSet::new(),
         dialog: Dialog::Hidden,
         editing,
+        text_editor: TextEditor::default(),
+        drag: None,
+        history: History::default(),
+        compared_versions: None,
+        _keydown_stream: keydown_stream,
         loading: false,
     };
 
@@ -55,31 +79,90 @@ pub struct Model {
     previous_exercises: BTreeSet<u32>,
     dialog: Dialog,
     editing: bool,
+    text_editor: TextEditor,
+    drag: Option<Drag>,
+    history: History,
+    /// Indices of the routine versions whose differences are shown.
+    compared_versions: Option<(usize, usize)>,
+    _keydown_stream: StreamHandle,
     loading: bool,
 }
 
 impl Model {
     pub fn has_unsaved_changes(&self) -> bool {
-        self.sections.iter().any(Form::changed)
+        self.text_editor.changed || self.sections.iter().any(Form::changed)
     }
 
     pub fn mark_as_unchanged(&mut self) {
+        self.text_editor = TextEditor::default();
         for s in &mut self.sections {
             s.mark_as_unchanged();
         }
     }
 }
 
+/// Editor for the routine in text notation.
+#[derive(Default)]
+struct TextEditor {
+    visible: bool,
+    input: String,
+    error: Option<notation::Error>,
+    changed: bool,
+}
+
+/// Part which is currently dragged and the section ID and index at which it would be dropped.
+struct Drag {
+    id: Vec<usize>,
+    section: bool,
+    target: Option<(Vec<usize>, usize)>,
+}
+
+impl Drag {
+    fn valid_target(&self, parent: &[usize], index: usize) -> bool {
+        not(parent.ends_with(&self.id))
+            && (self.section || not(parent.is_empty()))
+            && not(parent == &self.id[1..] && (index == self.id[0] || index == self.id[0] + 1))
+    }
+}
+
+/// Previous and undone states of the routine editor.
+#[derive(Default)]
+struct History {
+    undo: Vec<Vec<Form>>,
+    redo: Vec<Vec<Form>>,
+    last_edit: Option<Edit>,
+}
+
+impl History {
+    /// Store the state before an edit, unless the edit continues the previous value change.
+    fn record(&mut self, edit: Edit, sections: &[Form]) {
+        if not(matches!(edit, Edit::Value(..)) && self.last_edit.as_ref() == Some(&edit)) {
+            self.undo.push(sections.to_vec());
+            self.redo.clear();
+        }
+        self.last_edit = Some(edit);
+    }
+}
+
+#[derive(PartialEq)]
+enum Edit {
+    Structure,
+    /// Change of a value, consecutive changes of the same value are combined
+    Value(&'static str, Vec<usize>),
+}
+
 enum Dialog {
     Hidden,
     SelectExercise(Vec<usize>, String),
     DeleteTrainingSession(u32),
 }
 
+#[derive(Clone)]
 #[cfg_attr(test, derive(Debug, PartialEq))]
 enum Form {
     Section {
         rounds: InputField<u32>,
+        kind: SectionKindForm,
         parts: Vec<Form>,
     },
     Activity {
@@ -87,29 +170,50 @@ enum Form {
         reps: InputField<u32>,
         time: InputField<u32>,
         weight: InputField<f32>,
+        /// Reference weight if the weight is a percentage.
+        weight_basis: Option<data::WeightBasis>,
         rpe: InputField<f32>,
         automatic: bool,
+        auto_warm_up: bool,
+        set_type: data::SetType,
+        progression: ProgressionForm,
     },
 }
 
 impl Form {
     fn changed(&self) -> bool {
         match self {
-            Form::Section { rounds, parts } => rounds.changed || parts.iter().any(Form::changed),
+            Form::Section {
+                rounds,
+                kind,
+                parts,
+            } => rounds.changed || kind.changed() || parts.iter().any(Form::changed),
             Form::Activity {
                 reps,
                 time,
                 weight,
                 rpe,
+                progression,
                 ..
-            } => reps.changed || time.changed || weight.changed || rpe.changed,
+            } => {
+                reps.changed
+                    || time.changed
+                    || weight.changed
+                    || rpe.changed
+                    || progression.changed()
+            }
         }
     }
 
     fn mark_as_unchanged(&mut self) {
         match self {
-            Form::Section { rounds, parts } => {
+            Form::Section {
+                rounds,
+                kind,
+                parts,
+            } => {
                 rounds.changed = false;
+                kind.mark_as_unchanged();
                 for p in parts {
                     p.mark_as_unchanged();
                 }
@@ -119,26 +223,245 @@ impl Form {
                 time,
                 weight,
                 rpe,
+                progression,
                 ..
             } => {
                 reps.changed = false;
                 time.changed = false;
                 weight.changed = false;
                 rpe.changed = false;
+                progression.mark_as_unchanged();
             }
         }
     }
 
     fn valid(&self) -> bool {
         match self {
-            Form::Section { rounds, parts } => rounds.valid && parts.iter().all(Form::valid),
+            Form::Section {
+                rounds,
+                kind,
+                parts,
+            } => rounds.valid && kind.valid() && parts.iter().all(Form::valid),
             Form::Activity {
                 reps,
                 time,
                 weight,
+                weight_basis,
                 rpe,
+                progression,
                 ..
-            } => reps.valid && time.valid && weight.valid && rpe.valid,
+            } => {
+                reps.valid
+                    && time.valid
+                    && weight.valid
+                    && rpe.valid
+                    && progression.valid()
+                    && (weight_basis.is_none() || progression.kind.is_none())
+            }
+        }
+    }
+}
+
+/// Selectable section kinds with default durations.
+const SECTION_KINDS: [data::SectionKind; 5] = [
+    data::SectionKind::Sequential,
+    data::SectionKind::DEFAULT_EMOM,
+    data::SectionKind::DEFAULT_AMRAP,
+    data::SectionKind::DEFAULT_TABATA,
+    data::SectionKind::DEFAULT_FOR_TIME,
+];
+
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+struct SectionKindForm {
+    kind: data::SectionKind,
+    kind_changed: bool,
+    /// Interval of EMOM, time cap of AMRAP and For Time, work time of Tabata
+    duration: InputField<u32>,
+    /// Rest time of Tabata
+    rest: InputField<u32>,
+}
+
+impl SectionKindForm {
+    fn changed(&self) -> bool {
+        self.kind_changed || self.duration.changed || self.rest.changed
+    }
+
+    fn mark_as_unchanged(&mut self) {
+        self.kind_changed = false;
+        self.duration.changed = false;
+        self.rest.changed = false;
+    }
+
+    fn valid(&self) -> bool {
+        match self.kind {
+            data::SectionKind::Sequential => true,
+            data::SectionKind::Emom { .. } | data::SectionKind::Amrap { .. } => {
+                self.duration.parsed.map_or(false, |d| d > 0)
+            }
+            data::SectionKind::Tabata { .. } => {
+                self.duration.parsed.map_or(false, |d| d > 0) && self.rest.parsed.is_some()
+            }
+            data::SectionKind::ForTime { .. } => self.duration.parsed.is_some(),
+        }
+    }
+
+    fn to_section_kind(&self) -> data::SectionKind {
+        let duration = self.duration.parsed.unwrap_or(0);
+        match self.kind {
+            data::SectionKind::Sequential => data::SectionKind::Sequential,
+            data::SectionKind::Emom { .. } => data::SectionKind::Emom { interval: duration },
+            data::SectionKind::Amrap { .. } => data::SectionKind::Amrap { time_cap: duration },
+            data::SectionKind::Tabata { .. } => data::SectionKind::Tabata {
+                work: duration,
+                rest: self.rest.parsed.unwrap_or(0),
+            },
+            data::SectionKind::ForTime { .. } => data::SectionKind::ForTime { time_cap: duration },
+        }
+    }
+}
+
+impl Default for SectionKindForm {
+    fn default() -> Self {
+        SectionKindForm::from(data::SectionKind::Sequential)
+    }
+}
+
+impl From<data::SectionKind> for SectionKindForm {
+    fn from(kind: data::SectionKind) -> Self {
+        let (duration, rest) = match kind {
+            data::SectionKind::Sequential => (0, 0),
+            data::SectionKind::Emom { interval } => (interval, 0),
+            data::SectionKind::Amrap { time_cap } | data::SectionKind::ForTime { time_cap } => {
+                (time_cap, 0)
+            }
+            data::SectionKind::Tabata { work, rest } => (work, rest),
+        };
+        SectionKindForm {
+            kind,
+            kind_changed: false,
+            duration: InputField {
+                input: duration.to_string(),
+                valid: true,
+                parsed: Some(duration),
+                changed: false,
+            },
+            rest: InputField {
+                input: rest.to_string(),
+                valid: true,
+                parsed: Some(rest),
+                changed: false,
+            },
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(test, derive(Debug))]
+enum ProgressionKind {
+    Linear,
+    Double,
+    RPECapped,
+}
+
+impl ProgressionKind {
+    const ALL: [ProgressionKind; 3] = [
+        ProgressionKind::Linear,
+        ProgressionKind::Double,
+        ProgressionKind::RPECapped,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            ProgressionKind::Linear => "Linear",
+            ProgressionKind::Double => "Double",
+            ProgressionKind::RPECapped => "RPE-capped",
+        }
+    }
+}
+
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+struct ProgressionForm {
+    kind: Option<ProgressionKind>,
+    kind_changed: bool,
+    increment: InputField<f32>,
+    max_reps: InputField<u32>,
+}
+
+impl ProgressionForm {
+    fn changed(&self) -> bool {
+        self.kind_changed || self.increment.changed || self.max_reps.changed
+    }
+
+    fn mark_as_unchanged(&mut self) {
+        self.kind_changed = false;
+        self.increment.changed = false;
+        self.max_reps.changed = false;
+    }
+
+    fn valid(&self) -> bool {
+        match self.kind {
+            None => true,
+            Some(ProgressionKind::Linear | ProgressionKind::RPECapped) => self.increment.valid,
+            Some(ProgressionKind::Double) => {
+                self.increment.valid && self.max_reps.valid && self.max_reps.parsed.is_some()
+            }
+        }
+    }
+
+    fn to_progression(&self) -> Option<data::Progression> {
+        let increment = self
+            .increment
+            .parsed
+            .unwrap_or(data::Progression::DEFAULT_INCREMENT);
+        match self.kind? {
+            ProgressionKind::Linear => Some(data::Progression::Linear { increment }),
+            ProgressionKind::Double => Some(data::Progression::Double {
+                max_reps: self.max_reps.parsed?,
+                increment,
+            }),
+            ProgressionKind::RPECapped => Some(data::Progression::RPECapped { increment }),
+        }
+    }
+}
+
+impl Default for ProgressionForm {
+    fn default() -> Self {
+        ProgressionForm::from(None)
+    }
+}
+
+impl From<Option<data::Progression>> for ProgressionForm {
+    fn from(progression: Option<data::Progression>) -> Self {
+        let increment = progression.map_or(
+            data::Progression::DEFAULT_INCREMENT,
+            data::Progression::increment,
+        );
+        let max_reps = match progression {
+            Some(data::Progression::Double { max_reps, .. }) => Some(max_reps),
+            _ => None,
+        };
+        ProgressionForm {
+            kind: match progression {
+                None => None,
+                Some(data::Progression::Linear { .. }) => Some(ProgressionKind::Linear),
+                Some(data::Progression::Double { .. }) => Some(ProgressionKind::Double),
+                Some(data::Progression::RPECapped { .. }) => Some(ProgressionKind::RPECapped),
+            },
+            kind_changed: false,
+            increment: InputField {
+                input: increment.to_string(),
+                valid: true,
+                parsed: Some(increment),
+                changed: false,
+            },
+            max_reps: InputField {
+                input: max_reps.map(|r| r.to_string()).unwrap_or_default(),
+                valid: true,
+                parsed: max_reps,
+                changed: false,
+            },
         }
     }
 }
@@ -166,7 +489,11 @@ impl<T> Default for InputField<T> {
 impl From<&data::RoutinePart> for Form {
     fn from(part: &data::RoutinePart) -> Self {
         match part {
-            data::RoutinePart::RoutineSection { rounds, parts, .. } => Form::Section {
+            data::RoutinePart::RoutineSection {
+                rounds,
+                parts,
+                kind,
+            } => Form::Section {
                 rounds: InputField {
                     input: if *rounds == 1 {
                         String::new()
@@ -177,6 +504,7 @@ impl From<&data::RoutinePart> for Form {
                     parsed: Some(*rounds),
                     changed: false,
                 },
+                kind: SectionKindForm::from(*kind),
                 parts: parts.iter().map(Into::into).collect(),
             },
             data::RoutinePart::RoutineActivity {
@@ -186,51 +514,58 @@ impl From<&data::RoutinePart> for Form {
                 weight,
                 rpe,
                 automatic,
-                ..
-            } => Form::Activity {
-                exercise_id: *exercise_id,
-                reps: InputField {
-                    input: if *reps == 0 {
-                        String::new()
-                    } else {
-                        reps.to_string()
+                options,
+            } => {
+                let weight = options.relative_weight.map_or(*weight, |w| w.percentage);
+                Form::Activity {
+                    exercise_id: *exercise_id,
+                    reps: InputField {
+                        input: if *reps == 0 {
+                            String::new()
+                        } else {
+                            reps.to_string()
+                        },
+                        valid: true,
+                        parsed: Some(*reps),
+                        changed: false,
                     },
-                    valid: true,
-                    parsed: Some(*reps),
-                    changed: false,
-                },
-                time: InputField {
-                    input: if *time == 0 {
-                        String::new()
-                    } else {
-                        time.to_string()
+                    time: InputField {
+                        input: if *time == 0 {
+                            String::new()
+                        } else {
+                            time.to_string()
+                        },
+                        valid: true,
+                        parsed: Some(*time),
+                        changed: false,
                     },
-                    valid: true,
-                    parsed: Some(*time),
-                    changed: false,
-                },
-                weight: InputField {
-                    input: if *weight == 0.0 {
-                        String::new()
-                    } else {
-                        weight.to_string()
+                    weight: InputField {
+                        input: if weight == 0.0 {
+                            String::new()
+                        } else {
+                            weight.to_string()
+                        },
+                        valid: true,
+                        parsed: Some(weight),
+                        changed: false,
                     },
-                    valid: true,
-                    parsed: Some(*weight),
-                    changed: false,
-                },
-                rpe: InputField {
-                    input: if *rpe == 0.0 {
-                        String::new()
-                    } else {
-                        rpe.to_string()
+                    weight_basis: options.relative_weight.map(|w| w.basis),
+                    rpe: InputField {
+                        input: if *rpe == 0.0 {
+                            String::new()
+                        } else {
+                            rpe.to_string()
+                        },
+                        valid: true,
+                        parsed: Some(*rpe),
+                        changed: false,
                     },
-                    valid: true,
-                    parsed: Some(*rpe),
-                    changed: false,
-                },
-                automatic: *automatic,
-            },
+                    automatic: *automatic,
+                    auto_warm_up: options.auto_warm_up,
+                    set_type: options.set_type,
+                    progression: ProgressionForm::from(options.progression),
+                }
+            }
         }
     }
 }
@@ -239,25 +574,58 @@ fn to_routine_parts(parts: &[Form]) -> Vec<data::RoutinePart> {
     parts
         .iter()
         .map(|p| match p {
-            Form::Section { rounds, parts } => data::RoutinePart::RoutineSection {
+            Form::Section {
+                rounds,
+                kind,
+                parts,
+            } => data::RoutinePart::RoutineSection {
                 rounds: rounds.parsed.unwrap(),
                 parts: to_routine_parts(parts),
+                kind: kind.to_section_kind(),
             },
             Form::Activity {
                 exercise_id,
                 reps,
                 time,
                 weight,
+                weight_basis,
                 rpe,
                 automatic,
-            } => data::RoutinePart::RoutineActivity {
-                exercise_id: *exercise_id,
-                reps: reps.parsed.unwrap_or(0),
-                time: time.parsed.unwrap_or(0),
-                weight: weight.parsed.unwrap_or(0.0),
-                rpe: rpe.parsed.unwrap_or(0.0),
-                automatic: *automatic,
-            },
+                auto_warm_up,
+                set_type,
+                progression,
+            } => {
+                let relative_weight = weight_basis
+                    .filter(|_| exercise_id.is_some())
+                    .zip(weight.parsed.filter(|w| *w > 0.0))
+                    .map(|(basis, percentage)| data::RelativeWeight { percentage, basis });
+                data::RoutinePart::RoutineActivity {
+                    exercise_id: *exercise_id,
+                    reps: reps.parsed.unwrap_or(0),
+                    time: time.parsed.unwrap_or(0),
+                    weight: if relative_weight.is_some() {
+                        0.0
+                    } else {
+                        weight.parsed.unwrap_or(0.0)
+                    },
+                    rpe: rpe.parsed.unwrap_or(0.0),
+                    automatic: *automatic,
+                    options: data::ActivityOptions {
+                        progression: if exercise_id.is_some() {
+                            progression.to_progression()
+                        } else {
+                            None
+                        },
+                        auto_warm_up: *auto_warm_up && exercise_id.is_some(),
+                        set_type: if exercise_id.is_some() {
+                            *set_type
+                        } else {
+                            data::SetType::Working
+                        },
+                        relative_weight,
+                    },
+                }
+            }
         })
         .collect()
 }
@@ -269,6 +637,12 @@ fn to_routine_parts(parts: &[Form]) -> Vec<data::RoutinePart> {
 pub enum Msg {
     EditRoutine,
     SaveRoutine,
+    Undo,
+    Redo,
+
+    ShowTextEditor,
+    HideTextEditor,
+    TextChanged(String),
 
     ShowSelectExerciseDialog(Vec<usize>),
     ShowDeleteTrainingSessionDialog(u32),
@@ -277,15 +651,31 @@ pub enum Msg {
     AddSection(Vec<usize>),
     AddActivity(Vec<usize>, Option<u32>),
     RemovePart(Vec<usize>),
+    CopyPart(Vec<usize>),
+    PastePart(Vec<usize>),
+    DragStart(Vec<usize>),
+    DragOver(Vec<usize>, usize),
+    TouchMove(i32, i32),
+    Drop,
+    DragEnd,
     MovePartDown(Vec<usize>),
     MovePartUp(Vec<usize>),
     RoundsChanged(Vec<usize>, String),
+    SectionKindChanged(Vec<usize>, String),
+    SectionDurationChanged(Vec<usize>, String),
+    SectionRestChanged(Vec<usize>, String),
     ExerciseChanged(Vec<usize>, u32),
     RepsChanged(Vec<usize>, String),
     TimeChanged(Vec<usize>, String),
     WeightChanged(Vec<usize>, String),
     RPEChanged(Vec<usize>, String),
     AutomaticChanged(Vec<usize>),
+    AutoWarmUpChanged(Vec<usize>),
+    ProgressionChanged(Vec<usize>, String),
+    SetTypeChanged(Vec<usize>, String),
+    WeightBasisChanged(Vec<usize>, String),
+    ProgressionIncrementChanged(Vec<usize>, String),
+    ProgressionMaxRepsChanged(Vec<usize>, String),
 
     SearchTermChanged(String),
 
@@ -294,6 +684,9 @@ pub enum Msg {
     DataEvent(data::Event),
 
     ChangeInterval(NaiveDate, NaiveDate),
+
+    CompareVersions(usize, usize),
+    CloseVersionComparison,
 }
 
 pub fn update(
@@ -302,6 +695,10 @@ pub fn update(
     data_model: &data::Model,
     orders: &mut impl Orders<Msg>,
 ) {
+    if let Some(edit) = edit_of(&msg, model) {
+        model.history.record(edit, &model.sections);
+    }
+
     match msg {
         Msg::EditRoutine => {
             model.editing = true;
@@ -321,6 +718,44 @@ pub fn update(
             ));
         }
 
+        Msg::Undo => {
+            if let Some(sections) = model.history.undo.pop() {
+                let sections = std::mem::replace(&mut model.sections, sections);
+                model.history.redo.push(sections);
+                restore_history_state(model, data_model);
+            }
+        }
+        Msg::Redo => {
+            if let Some(sections) = model.history.redo.pop() {
+                let sections = std::mem::replace(&mut model.sections, sections);
+                model.history.undo.push(sections);
+                restore_history_state(model, data_model);
+            }
+        }
+
+        Msg::ShowTextEditor => {
+            model.text_editor.visible = true;
+            model.text_editor.input =
+                notation::print(&to_routine_parts(&model.sections), &data_model.exercises);
+            model.text_editor.error = None;
+        }
+        Msg::HideTextEditor => {
+            model.text_editor.visible = false;
+        }
+        Msg::TextChanged(input) => {
+            match notation::parse(&input, &data_model.exercises) {
+                Ok(parts) => {
+                    model.sections = parts.iter().map(Into::into).collect();
+                    model.text_editor.error = None;
+                }
+                Err(error) => {
+                    model.text_editor.error = Some(error);
+                }
+            }
+            model.text_editor.input = input;
+            model.text_editor.changed = true;
+        }
+
         Msg::ShowSelectExerciseDialog(part_id) => {
             model.dialog = Dialog::SelectExercise(part_id, String::new());
         }
@@ -345,6 +780,7 @@ pub fn update(
                     parsed: Some(1),
                     changed: false,
                 },
+                kind: SectionKindForm::default(),
                 parts: vec![],
             };
             if id.is_empty() {
@@ -383,6 +819,7 @@ pub fn update(
                     parsed: Some(0.0),
                     changed: false,
                 },
+                weight_basis: None,
                 rpe: InputField {
                     input: String::new(),
                     valid: true,
@@ -390,6 +827,9 @@ pub fn update(
                     changed: false,
                 },
                 automatic: exercise_id.is_none(),
+                progression: ProgressionForm::default(),
+                auto_warm_up: false,
+                set_type: data::SetType::Working,
             };
             if let Some(Form::Section { parts, .. }) = get_part(&mut model.sections, &id) {
                 parts.push(new_activity);
@@ -404,6 +844,82 @@ pub fn update(
                 parts.remove(id[0]);
             }
         }
+        Msg::CopyPart(id) => {
+            if let Some(part) = get_part(&mut model.sections, &id) {
+                if part.valid() {
+                    orders.notify(data::Msg::CopyRoutinePart(
+                        to_routine_parts(std::slice::from_ref(part)).remove(0),
+                    ));
+                }
+            }
+        }
+        Msg::PastePart(id) => {
+            if let Some(part) = &data_model.copied_routine_part {
+                if id.is_empty() {
+                    model.sections.push(part.into());
+                } else if let Some(Form::Section { parts, .. }) = get_part(&mut model.sections, &id)
+                {
+                    parts.push(part.into());
+                }
+            }
+        }
+        Msg::DragStart(id) => {
+            let section = matches!(
+                get_part(&mut model.sections, &id),
+                Some(Form::Section { .. })
+            );
+            model.drag = Some(Drag {
+                id,
+                section,
+                target: None,
+            });
+        }
+        Msg::DragOver(parent, index) => {
+            if let Some(drag) = &mut model.drag {
+                if drag.target.as_ref() == Some(&(parent.clone(), index)) {
+                    orders.skip();
+                } else {
+                    drag.target = Some((parent, index));
+                }
+            }
+        }
+        Msg::TouchMove(x, y) => {
+            #[allow(clippy::cast_precision_loss)]
+            let target = document()
+                .element_from_point(x as f32, y as f32)
+                .and_then(|element| element.closest("[data-drop-index]").ok().flatten())
+                .and_then(|element| {
+                    let parent = element
+                        .get_attribute("data-drop-parent")?
+                        .split(',')
+                        .filter(|i| not(i.is_empty()))
+                        .map(str::parse::<usize>)
+                        .collect::<Result<Vec<_>, _>>()
+                        .ok()?;
+                    let index = element.get_attribute("data-drop-index")?.parse().ok()?;
+                    Some((parent, index))
+                });
+            if let Some(drag) = &mut model.drag {
+                if drag.target == target {
+                    orders.skip();
+                } else {
+                    drag.target = target;
+                }
+            }
+        }
+        Msg::Drop => {
+            if let Some(Drag {
+                id,
+                target: Some((parent, index)),
+                ..
+            }) = model.drag.take()
+            {
+                move_part(&mut model.sections, &id, parent, index);
+            }
+        }
+        Msg::DragEnd => {
+            model.drag = None;
+        }
         Msg::MovePartDown(id) => {
             if id.len() == 1 {
                 if id[0] == model.sections.len() - 1 {
@@ -473,6 +989,28 @@ pub fn update(
                 }
             }
         }
+        Msg::SectionKindChanged(id, input) => {
+            if let Some(Form::Section { kind, .. }) = get_part(&mut model.sections, &id) {
+                if let Some(k) = input
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|i| SECTION_KINDS.get(i))
+                {
+                    *kind = SectionKindForm::from(*k);
+                    kind.kind_changed = true;
+                }
+            }
+        }
+        Msg::SectionDurationChanged(id, input) => {
+            if let Some(Form::Section { kind, .. }) = get_part(&mut model.sections, &id) {
+                kind.duration = parse_seconds(input);
+            }
+        }
+        Msg::SectionRestChanged(id, input) => {
+            if let Some(Form::Section { kind, .. }) = get_part(&mut model.sections, &id) {
+                kind.rest = parse_seconds(input);
+            }
+        }
         Msg::ExerciseChanged(id, input) => {
             if let Some(Form::Activity { exercise_id, .. }) = get_part(&mut model.sections, &id) {
                 *exercise_id = Some(input);
@@ -611,6 +1149,86 @@ pub fn update(
                 *automatic = not(*automatic);
             }
         }
+        Msg::AutoWarmUpChanged(id) => {
+            if let Some(Form::Activity { auto_warm_up, .. }) = get_part(&mut model.sections, &id) {
+                *auto_warm_up = not(*auto_warm_up);
+            }
+        }
+        Msg::WeightBasisChanged(id, input) => {
+            if let Some(Form::Activity { weight_basis, .. }) = get_part(&mut model.sections, &id) {
+                *weight_basis = input
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|i| data::WeightBasis::ALL.get(i).copied());
+            }
+        }
+        Msg::SetTypeChanged(id, input) => {
+            if let Some(Form::Activity { set_type, .. }) = get_part(&mut model.sections, &id) {
+                if let Some(t) = input
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|i| data::SetType::ALL.get(i))
+                {
+                    *set_type = *t;
+                }
+            }
+        }
+        Msg::ProgressionChanged(id, input) => {
+            if let Some(Form::Activity { progression, .. }) = get_part(&mut model.sections, &id) {
+                progression.kind = input
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|i| ProgressionKind::ALL.get(i).copied());
+                progression.kind_changed = true;
+            }
+        }
+        Msg::ProgressionIncrementChanged(id, input) => {
+            if let Some(Form::Activity { progression, .. }) = get_part(&mut model.sections, &id) {
+                match input.parse::<f32>() {
+                    Ok(parsed_increment) => {
+                        let valid =
+                            parsed_increment > 0.0 && common::valid_weight(parsed_increment);
+                        progression.increment = InputField {
+                            input,
+                            valid,
+                            parsed: if valid { Some(parsed_increment) } else { None },
+                            changed: true,
+                        }
+                    }
+                    Err(_) => {
+                        progression.increment = InputField {
+                            input,
+                            valid: false,
+                            parsed: None,
+                            changed: true,
+                        }
+                    }
+                }
+            }
+        }
+        Msg::ProgressionMaxRepsChanged(id, input) => {
+            if let Some(Form::Activity { progression, .. }) = get_part(&mut model.sections, &id) {
+                match input.parse::<u32>() {
+                    Ok(parsed_reps) => {
+                        let valid = parsed_reps > 0 && common::valid_reps(parsed_reps);
+                        progression.max_reps = InputField {
+                            input,
+                            valid,
+                            parsed: if valid { Some(parsed_reps) } else { None },
+                            changed: true,
+                        }
+                    }
+                    Err(_) => {
+                        progression.max_reps = InputField {
+                            input,
+                            valid: false,
+                            parsed: None,
+                            changed: true,
+                        }
+                    }
+                }
+            }
+        }
 
         Msg::SearchTermChanged(search_term) => {
             if let Dialog::SelectExercise(_, dialog_search_term) = &mut model.dialog {
@@ -621,7 +1239,10 @@ pub fn update(
         Msg::CreateExercise => {
             model.loading = true;
             if let Dialog::SelectExercise(_, search_term) = &model.dialog {
-                orders.notify(data::Msg::CreateExercise(search_term.trim().to_string()));
+                orders.notify(data::Msg::CreateExercise(
+                    search_term.trim().to_string(),
+                    data::ExerciseMetadata::default(),
+                ));
             };
         }
         Msg::DeleteTrainingSession(id) => {
@@ -639,6 +1260,7 @@ pub fn update(
                 | data::Event::RoutineDeletedOk => {
                     model.editing = false;
                     model.mark_as_unchanged();
+                    model.history = History::default();
                     Url::go_and_push(
                         &crate::Urls::new(&data_model.base_url)
                             .routine()
@@ -656,10 +1278,63 @@ pub fn update(
             model.interval.first = first;
             model.interval.last = last;
         }
+
+        Msg::CompareVersions(old, new) => {
+            model.compared_versions = Some((old, new));
+        }
+        Msg::CloseVersionComparison => {
+            model.compared_versions = None;
+        }
+    }
+}
+
+/// Determine whether the message changes the routine and hence must be recorded in the history.
+fn edit_of(msg: &Msg, model: &Model) -> Option<Edit> {
+    match msg {
+        Msg::AddSection(_)
+        | Msg::AddActivity(..)
+        | Msg::RemovePart(_)
+        | Msg::PastePart(_)
+        | Msg::MovePartDown(_)
+        | Msg::MovePartUp(_)
+        | Msg::ExerciseChanged(..)
+        | Msg::AutomaticChanged(_)
+        | Msg::AutoWarmUpChanged(_)
+        | Msg::SectionKindChanged(..)
+        | Msg::ProgressionChanged(..)
+        | Msg::SetTypeChanged(..)
+        | Msg::WeightBasisChanged(..) => Some(Edit::Structure),
+        Msg::Drop => model
+            .drag
+            .as_ref()
+            .and_then(|d| d.target.as_ref())
+            .map(|_| Edit::Structure),
+        Msg::RoundsChanged(id, _) => Some(Edit::Value("rounds", id.clone())),
+        Msg::SectionDurationChanged(id, _) => Some(Edit::Value("duration", id.clone())),
+        Msg::SectionRestChanged(id, _) => Some(Edit::Value("rest", id.clone())),
+        Msg::RepsChanged(id, _) => Some(Edit::Value("reps", id.clone())),
+        Msg::TimeChanged(id, _) => Some(Edit::Value("time", id.clone())),
+        Msg::WeightChanged(id, _) => Some(Edit::Value("weight", id.clone())),
+        Msg::RPEChanged(id, _) => Some(Edit::Value("rpe", id.clone())),
+        Msg::ProgressionIncrementChanged(id, _) => Some(Edit::Value("increment", id.clone())),
+        Msg::ProgressionMaxRepsChanged(id, _) => Some(Edit::Value("max reps", id.clone())),
+        Msg::TextChanged(_) => Some(Edit::Value("text", vec![])),
+        _ => None,
+    }
+}
+
+fn restore_history_state(model: &mut Model, data_model: &data::Model) {
+    model.history.last_edit = None;
+    model.drag = None;
+    if model.text_editor.visible && model.sections.iter().all(Form::valid) {
+        model.text_editor.input =
+            notation::print(&to_routine_parts(&model.sections), &data_model.exercises);
+        model.text_editor.error = None;
     }
 }
 
 fn update_model(model: &mut Model, data_model: &data::Model) {
+    model.history = History::default();
     model.interval = common::init_interval(
         &data_model
             .training_sessions
@@ -698,26 +1373,46 @@ pub fn view(model: &Model, data_model: &data::Model) -> Node<Msg> {
     if data_model.routines.is_empty() && data_model.loading_routines {
         common::view_page_loading()
     } else if let Some(routine) = &data_model.routines.get(&model.routine_id) {
-        let saving_disabled = not(model.sections.iter().all(Form::valid));
+        let valid = model.sections.iter().all(Form::valid);
+        let saving_disabled = not(valid) || model.text_editor.error.is_some();
         div![
             common::view_title(&span![&routine.name], 0),
             view_dialog(&model.dialog, &data_model.exercises, model.loading),
-            view_routine(data_model, &model.sections, model.editing),
+            IF![model.editing => view_editor_buttons(model, valid)],
+            if model.editing && model.text_editor.visible {
+                view_text_editor(&model.text_editor)
+            } else {
+                view_routine(
+                    data_model,
+                    &model.sections,
+                    model.editing,
+                    model.drag.as_ref(),
+                )
+            },
             if model.editing {
-                nodes![button![
-                    C!["button"],
-                    C!["is-fab"],
-                    C!["is-medium"],
-                    C!["is-link"],
-                    C![IF![model.loading => "is-loading"]],
-                    attrs![
-                        At::Disabled => saving_disabled.as_at_value(),
-                    ],
-                    ev(Ev::Click, |_| Msg::SaveRoutine),
-                    span![C!["icon"], i![C!["fas fa-save"]]]
-                ]]
+                nodes![
+                    IF![valid => view_analysis(
+                        &data::RoutineAnalysis::of(&to_routine_parts(&model.sections)),
+                        data_model
+                    )],
+                    button![
+                        C!["button"],
+                        C!["is-fab"],
+                        C!["is-medium"],
+                        C!["is-link"],
+                        C![IF![model.loading => "is-loading"]],
+                        attrs![
+                            At::Disabled => saving_disabled.as_at_value(),
+                        ],
+                        ev(Ev::Click, |_| Msg::SaveRoutine),
+                        span![C!["icon"], i![C!["fas fa-save"]]]
+                    ]
+                ]
             } else {
                 nodes![
+                    view_analysis(&routine.analysis(), data_model),
+                    view_set_volume(routine, data_model),
+                    view_versions(model, data_model),
                     view_previous_exercises(model, data_model),
                     view_training_sessions(model, data_model),
                     common::view_fab("edit", |_| Msg::EditRoutine),
@@ -817,29 +1512,156 @@ fn view_dialog(
     }
 }
 
-fn view_routine(data_model: &data::Model, routine_sections: &[Form], editing: bool) -> Node<Msg> {
+fn view_editor_buttons(model: &Model, valid: bool) -> Node<Msg> {
+    let text_editor = &model.text_editor;
     div![
-        C!["container"],
+        C!["is-flex"],
+        C!["is-justify-content-center"],
         C!["m-2"],
-        &routine_sections
-            .iter()
-            .enumerate()
-            .map(|(i, s)| { view_routine_part(data_model, s, vec![i], editing) })
-            .collect::<Vec<_>>(),
-        IF![editing => view_add_section_button(vec![])]
+        div![
+            C!["buttons"],
+            C!["has-addons"],
+            C!["mb-0"],
+            C!["mr-4"],
+            button![
+                C!["button"],
+                attrs! {
+                    At::Title => "Undo (Ctrl+Z)",
+                    At::Disabled => model.history.undo.is_empty().as_at_value(),
+                },
+                ev(Ev::Click, |_| Msg::Undo),
+                span![C!["icon"], i![C!["fas fa-rotate-left"]]],
+            ],
+            button![
+                C!["button"],
+                attrs! {
+                    At::Title => "Redo (Ctrl+Y)",
+                    At::Disabled => model.history.redo.is_empty().as_at_value(),
+                },
+                ev(Ev::Click, |_| Msg::Redo),
+                span![C!["icon"], i![C!["fas fa-rotate-right"]]],
+            ],
+        ],
+        div![
+            C!["buttons"],
+            C!["has-addons"],
+            C!["mb-0"],
+            button![
+                C!["button"],
+                C![IF![not(text_editor.visible) => "is-link"]],
+                C![IF![not(text_editor.visible) => "is-selected"]],
+                attrs! {
+                    At::Disabled => text_editor.error.is_some().as_at_value(),
+                },
+                ev(Ev::Click, |_| Msg::HideTextEditor),
+                span![C!["icon"], i![C!["fas fa-list"]]],
+                span!["Form"]
+            ],
+            button![
+                C!["button"],
+                C![IF![text_editor.visible => "is-link"]],
+                C![IF![text_editor.visible => "is-selected"]],
+                attrs! {
+                    At::Disabled => not(valid).as_at_value(),
+                },
+                ev(Ev::Click, |_| Msg::ShowTextEditor),
+                span![C!["icon"], i![C!["fas fa-align-left"]]],
+                span!["Text"]
+            ],
+        ]
     ]
 }
 
-fn view_routine_part(
-    data_model: &data::Model,
-    part: &Form,
-    id: Vec<usize>,
+fn view_text_editor(text_editor: &TextEditor) -> Node<Msg> {
+    div![
+        C!["container"],
+        C!["m-2"],
+        div![
+            C!["field"],
+            div![
+                C!["control"],
+                textarea![
+                    C!["textarea"],
+                    C!["is-family-monospace"],
+                    C![IF![text_editor.error.is_some() => "is-danger"]],
+                    C![IF![text_editor.changed => "is-info"]],
+                    attrs! {
+                        At::Rows => text_editor.input.lines().count().max(5) + 2,
+                        At::Placeholder => "3x[Squat 5@100kg RPE8; rest 120s]",
+                        At::Value => text_editor.input,
+                    },
+                    input_ev(Ev::Input, Msg::TextChanged),
+                ]
+            ],
+            if let Some(error) = &text_editor.error {
+                p![C!["help"], C!["is-danger"], error.to_string()]
+            } else {
+                p![
+                    C!["help"],
+                    "One part per line or separated by \";\". ",
+                    "Sections: ",
+                    code!["[EMOM 60s|AMRAP 10min|Tabata 20s/10s|ForTime] 3x[…]"],
+                    ", activities: ",
+                    code!["Exercise 5@100kg 30s RPE8 auto"],
+                    ", ",
+                    code!["Exercise 3@85%TM"],
+                    " or ",
+                    code!["rest 120s"]
+                ]
+            }
+        ]
+    ]
+}
+
+fn view_routine(
+    data_model: &data::Model,
+    routine_sections: &[Form],
     editing: bool,
+    drag: Option<&Drag>,
 ) -> Node<Msg> {
+    div![
+        C!["container"],
+        C!["m-2"],
+        &routine_sections
+            .iter()
+            .enumerate()
+            .map(|(i, s)| {
+                nodes![
+                    view_drop_zone(drag, &[], i),
+                    view_routine_part(data_model, s, vec![i], editing, drag)
+                ]
+            })
+            .collect::<Vec<_>>(),
+        view_drop_zone(drag, &[], routine_sections.len()),
+        IF![editing => view_add_section_button(vec![])],
+        IF![
+            editing
+                && matches!(
+                    data_model.copied_routine_part,
+                    Some(data::RoutinePart::RoutineSection { .. })
+                ) =>
+            view_paste_button(vec![])
+        ]
+    ]
+}
+
+fn view_routine_part(
+    data_model: &data::Model,
+    part: &Form,
+    id: Vec<usize>,
+    editing: bool,
+    drag: Option<&Drag>,
+) -> Node<Msg> {
+    let dragged = drag.map_or(false, |d| d.id == id);
     match part {
-        Form::Section { rounds, parts } => {
+        Form::Section {
+            rounds,
+            kind,
+            parts,
+        } => {
             div![
                 C!["message"],
+                IF![dragged => C!["is-semitransparent"]],
                 IF![editing || id.first() != Some(&0) => C!["mt-3"]],
                 C!["mb-0"],
                 C!["is-grey"],
@@ -852,64 +1674,83 @@ fn view_routine_part(
                             C!["is-flex"],
                             C!["is-justify-content-space-between"],
                             div![
-                                C!["field"],
-                                C!["mb-0"],
-                                div![
-                                    C!["control"],
-                                    C!["has-icons-left"],
-                                    input_ev(Ev::Input, {
-                                        let id = id.clone();
-                                        move |v| Msg::RoundsChanged(id, v)
-                                    }),
-                                    span![
-                                        C!["icon"],
-                                        C!["is-small"],
-                                        C!["is-left"],
-                                        i![C!["fas fa-repeat"]]
-                                    ],
-                                    input![
-                                        C!["input"],
-                                        C!["has-text-right"],
-                                        C![IF![not(rounds.valid) => "is-danger"]],
-                                        C![IF![rounds.changed => "is-info"]],
-                                        attrs! {
-                                            At::Type => "number",
-                                            At::Min => 1,
-                                            At::Max => 999,
-                                            At::Step => 1,
-                                            At::Size => 2,
-                                            At::Value => rounds.input,
-                                            At::Placeholder => 1,
-                                        }
+                                C!["is-flex"],
+                                C!["is-flex-wrap-wrap"],
+                                IF![not(matches!(kind.kind, data::SectionKind::Amrap { .. })) => div![
+                                    C!["field"],
+                                    C!["mb-0"],
+                                    C!["mr-2"],
+                                    div![
+                                        C!["control"],
+                                        C!["has-icons-left"],
+                                        input_ev(Ev::Input, {
+                                            let id = id.clone();
+                                            move |v| Msg::RoundsChanged(id, v)
+                                        }),
+                                        span![
+                                            C!["icon"],
+                                            C!["is-small"],
+                                            C!["is-left"],
+                                            i![C!["fas fa-repeat"]]
+                                        ],
+                                        input![
+                                            C!["input"],
+                                            C!["has-text-right"],
+                                            C![IF![not(rounds.valid) => "is-danger"]],
+                                            C![IF![rounds.changed => "is-info"]],
+                                            attrs! {
+                                                At::Type => "number",
+                                                At::Min => 1,
+                                                At::Max => 999,
+                                                At::Step => 1,
+                                                At::Size => 2,
+                                                At::Value => rounds.input,
+                                                At::Placeholder => 1,
+                                            }
+                                        ]
                                     ]
-                                ]
+                                ]],
+                                view_section_kind_fields(kind, &id)
                             ],
                             view_position_buttons(id.clone())
                         ]
-                    } else if let Some(rounds) = rounds.parsed {
-                        if rounds > 1 {
-                            span![
-                                C!["icon-text"],
-                                C!["mb-3"],
-                                span![C!["icon"], i![C!["fas fa-repeat"]],],
-                                span![rounds]
-                            ]
-                        } else {
-                            empty![]
-                        }
                     } else {
-                        empty![]
+                        let section_kind = kind.to_section_kind();
+                        div![
+                            IF![rounds.parsed.map_or(false, |r| r > 1) && not(matches!(section_kind, data::SectionKind::Amrap { .. })) =>
+                                span![
+                                    C!["icon-text"],
+                                    C!["mb-3"],
+                                    C!["mr-3"],
+                                    span![C!["icon"], i![C!["fas fa-repeat"]],],
+                                    span![rounds.parsed]
+                                ]
+                            ],
+                            IF![section_kind != data::SectionKind::Sequential =>
+                                span![
+                                    C!["icon-text"],
+                                    C!["mb-3"],
+                                    span![C!["icon"], i![C!["fas fa-stopwatch"]],],
+                                    span![section_kind.description()]
+                                ]
+                            ]
+                        ]
                     },
                     parts
                         .iter()
                         .enumerate()
-                        .map(|(i, p)| view_routine_part(
-                            data_model,
-                            p,
-                            [&[i], &id[..]].concat(),
-                            editing
-                        ))
+                        .map(|(i, p)| nodes![
+                            view_drop_zone(drag, &id, i),
+                            view_routine_part(
+                                data_model,
+                                p,
+                                [&[i], &id[..]].concat(),
+                                editing,
+                                drag
+                            )
+                        ])
                         .collect::<Vec<_>>(),
+                    view_drop_zone(drag, &id, parts.len()),
                     IF![editing => view_add_part_buttons(data_model,id)]
                 ],
             ]
@@ -919,11 +1760,16 @@ fn view_routine_part(
             reps,
             time,
             weight,
+            weight_basis,
             rpe,
             automatic,
+            auto_warm_up,
+            set_type,
+            progression,
         } => {
             div![
                 C!["message"],
+                IF![dragged => C!["is-semitransparent"]],
                 IF![editing || id.first() != Some(&0) => C!["mt-3"]],
                 C!["mb-0"],
                 if exercise_id.is_some() {
@@ -1069,12 +1915,12 @@ fn view_routine_part(
                                 exercise_id.is_some() =>
                                 div![
                                     C!["field"],
+                                    C!["has-addons"],
                                     C!["mb-0"],
                                     C!["mr-2"],
                                     div![
                                         C!["control"],
                                         C!["has-icons-left"],
-                                        C!["has-icons-right"],
                                         input_ev(Ev::Input, {
                                             let id = id.clone();
                                             move |v| Msg::WeightChanged(id, v)
@@ -1096,8 +1942,8 @@ fn view_routine_part(
                                                 At::Value => weight.input,
                                             }
                                         ],
-                                        span![C!["icon"], C!["is-small"], C!["is-right"], "kg"],
-                                    ]
+                                    ],
+                                    view_weight_basis_select(*weight_basis, &id),
                                 ]
                             ],
                             IF![
@@ -1129,6 +1975,35 @@ fn view_routine_part(
                                     ]
                                 ]
                             ],
+                            IF![
+                                exercise_id.is_some() =>
+                                view_set_type_field(*set_type, &id)
+                            ],
+                            IF![
+                                exercise_id.is_some() =>
+                                view_progression_fields(progression, weight_basis.is_some(), &id)
+                            ],
+                            IF![
+                                exercise_id.is_some() =>
+                                button![
+                                    C!["button"],
+                                    C!["mr-2"],
+                                    attrs! {At::Title => "Warm-up sets"},
+                                    ev(Ev::Click, {
+                                        let id = id.clone();
+                                        move |_| Msg::AutoWarmUpChanged(id)
+                                    }),
+                                    span![
+                                        C!["icon"],
+                                        if *auto_warm_up {
+                                            C!["has-text-dark"]
+                                        } else {
+                                            C!["has-text-grey-lighter"]
+                                        },
+                                        i![C!["fas fa-fire"]]
+                                    ]
+                                ]
+                            ],
                             button![
                                 C!["button"],
                                 ev(Ev::Click, {
@@ -1174,7 +2049,13 @@ fn view_routine_part(
                                         C!["icon-text"],
                                         C!["mr-4"],
                                         span![C!["mr-2"], i![C!["fas fa-weight-hanging"]]],
-                                        span![&weight.input, " kg"]
+                                        span![
+                                            &weight.input,
+                                            weight_basis.map_or_else(
+                                                || String::from(" kg"),
+                                                |b| format!(" % {}", b.name())
+                                            )
+                                        ]
                                     ]
                                 }
                             ],
@@ -1194,12 +2075,664 @@ fn view_routine_part(
                                     common::automatic_icon(),
                                 ]
                             ],
+                            IF![
+                                *auto_warm_up => span![
+                                    C!["icon"],
+                                    C!["mr-4"],
+                                    attrs! {At::Title => "Warm-up sets"},
+                                    i![C!["fas fa-fire"]],
+                                ]
+                            ],
+                            IF![
+                                *set_type != data::SetType::Working => span![
+                                    C!["icon-text"],
+                                    C!["mr-4"],
+                                    span![C!["mr-2"], i![C!["fas fa-layer-group"]]],
+                                    span![set_type.name()]
+                                ]
+                            ],
+                            if let Some(progression) = progression.to_progression() {
+                                span![
+                                    C!["icon-text"],
+                                    C!["mr-4"],
+                                    span![C!["mr-2"], i![C!["fas fa-arrow-trend-up"]]],
+                                    span![format_progression(progression)]
+                                ]
+                            } else {
+                                empty![]
+                            },
+                        ]
+                    }
+                ]
+            ]
+        }
+    }
+}
+
+fn view_section_kind_fields(kind: &SectionKindForm, id: &[usize]) -> Vec<Node<Msg>> {
+    let duration_icon = match kind.kind {
+        data::SectionKind::Sequential => return nodes![view_section_kind_select(kind, id)],
+        data::SectionKind::Emom { .. } => "fa-rotate",
+        data::SectionKind::Amrap { .. } | data::SectionKind::ForTime { .. } => "fa-stopwatch",
+        data::SectionKind::Tabata { .. } => "fa-person-running",
+    };
+    nodes![
+        view_section_kind_select(kind, id),
+        div![
+            C!["field"],
+            C!["mb-0"],
+            C!["mr-2"],
+            div![
+                C!["control"],
+                C!["has-icons-left"],
+                C!["has-icons-right"],
+                input_ev(Ev::Input, {
+                    let id = id.to_vec();
+                    move |v| Msg::SectionDurationChanged(id, v)
+                }),
+                span![
+                    C!["icon"],
+                    C!["is-small"],
+                    C!["is-left"],
+                    i![C!["fas", duration_icon]]
+                ],
+                input![
+                    C!["input"],
+                    C!["has-text-right"],
+                    C![IF![not(kind.duration.valid) || not(kind.valid()) => "is-danger"]],
+                    C![IF![kind.duration.changed => "is-info"]],
+                    attrs! {
+                        At::Type => "number",
+                        At::Min => 0,
+                        At::Max => 3600,
+                        At::Step => 1,
+                        At::Size => 3,
+                        At::Value => kind.duration.input,
+                    }
+                ],
+                span![C!["icon"], C!["is-small"], C!["is-right"], "s"],
+            ]
+        ],
+        IF![
+            matches!(kind.kind, data::SectionKind::Tabata { .. }) =>
+            div![
+                C!["field"],
+                C!["mb-0"],
+                C!["mr-2"],
+                div![
+                    C!["control"],
+                    C!["has-icons-left"],
+                    C!["has-icons-right"],
+                    input_ev(Ev::Input, {
+                        let id = id.to_vec();
+                        move |v| Msg::SectionRestChanged(id, v)
+                    }),
+                    span![C!["icon"], C!["is-small"], C!["is-left"], i![C!["fas fa-bed"]]],
+                    input![
+                        C!["input"],
+                        C!["has-text-right"],
+                        C![IF![not(kind.rest.valid) => "is-danger"]],
+                        C![IF![kind.rest.changed => "is-info"]],
+                        attrs! {
+                            At::Type => "number",
+                            At::Min => 0,
+                            At::Max => 3600,
+                            At::Step => 1,
+                            At::Size => 3,
+                            At::Value => kind.rest.input,
+                        }
+                    ],
+                    span![C!["icon"], C!["is-small"], C!["is-right"], "s"],
+                ]
+            ]
+        ],
+    ]
+}
+
+fn view_section_kind_select(kind: &SectionKindForm, id: &[usize]) -> Node<Msg> {
+    div![
+        C!["field"],
+        C!["mb-0"],
+        C!["mr-2"],
+        div![
+            C!["control"],
+            div![
+                C!["select"],
+                C![IF![kind.kind_changed => "is-info"]],
+                input_ev(Ev::Change, {
+                    let id = id.to_vec();
+                    move |v| Msg::SectionKindChanged(id, v)
+                }),
+                select![SECTION_KINDS
+                    .iter()
+                    .enumerate()
+                    .map(|(i, k)| {
+                        option![
+                            k.name(),
+                            attrs! {
+                                At::Value => i,
+                                At::Selected => (k.name() == kind.kind.name()).as_at_value(),
+                            }
                         ]
+                    })
+                    .collect::<Vec<_>>()]
+            ]
+        ]
+    ]
+}
+
+fn view_weight_basis_select(weight_basis: Option<data::WeightBasis>, id: &[usize]) -> Node<Msg> {
+    div![
+        C!["control"],
+        div![
+            C!["select"],
+            input_ev(Ev::Change, {
+                let id = id.to_vec();
+                move |v| Msg::WeightBasisChanged(id, v)
+            }),
+            select![
+                attrs! {At::Title => "Unit of weight"},
+                option![
+                    "kg",
+                    attrs! {
+                        At::Value => "",
+                        At::Selected => weight_basis.is_none().as_at_value(),
                     }
+                ],
+                data::WeightBasis::ALL
+                    .iter()
+                    .enumerate()
+                    .map(|(i, b)| {
+                        option![
+                            format!("% {}", b.name()),
+                            attrs! {
+                                At::Value => i,
+                                At::Selected => (weight_basis == Some(*b)).as_at_value(),
+                            }
+                        ]
+                    })
+                    .collect::<Vec<_>>()
+            ]
+        ]
+    ]
+}
+
+fn view_set_type_field(set_type: data::SetType, id: &[usize]) -> Node<Msg> {
+    div![
+        C!["field"],
+        C!["mb-0"],
+        C!["mr-2"],
+        div![
+            C!["control"],
+            C!["has-icons-left"],
+            div![
+                C!["select"],
+                input_ev(Ev::Change, {
+                    let id = id.to_vec();
+                    move |v| Msg::SetTypeChanged(id, v)
+                }),
+                select![data::SetType::ALL
+                    .iter()
+                    .enumerate()
+                    .map(|(i, t)| {
+                        option![
+                            t.name(),
+                            attrs! {
+                                At::Value => i,
+                                At::Selected => (*t == set_type).as_at_value(),
+                            }
+                        ]
+                    })
+                    .collect::<Vec<_>>()]
+            ],
+            span![
+                C!["icon"],
+                C!["is-small"],
+                C!["is-left"],
+                i![C!["fas fa-layer-group"]]
+            ],
+        ]
+    ]
+}
+
+/// A progression cannot be combined with a relative weight, as the progression would change the
+/// weight of the activity, which is replaced by the relative weight.
+fn view_progression_fields(
+    progression: &ProgressionForm,
+    relative_weight: bool,
+    id: &[usize],
+) -> Vec<Node<Msg>> {
+    nodes![
+        div![
+            C!["field"],
+            C!["mb-0"],
+            C!["mr-2"],
+            div![
+                C!["control"],
+                C!["has-icons-left"],
+                div![
+                    C!["select"],
+                    C![IF![relative_weight && progression.kind.is_some() => "is-danger"]],
+                    C![IF![progression.kind_changed => "is-info"]],
+                    input_ev(Ev::Change, {
+                        let id = id.to_vec();
+                        move |v| Msg::ProgressionChanged(id, v)
+                    }),
+                    select![
+                        option![
+                            "No progression",
+                            attrs! {
+                                At::Value => "",
+                                At::Selected => progression.kind.is_none().as_at_value(),
+                            }
+                        ],
+                        ProgressionKind::ALL
+                            .iter()
+                            .enumerate()
+                            .map(|(i, k)| {
+                                option![
+                                    k.name(),
+                                    attrs! {
+                                        At::Value => i,
+                                        At::Selected => (progression.kind == Some(*k)).as_at_value(),
+                                    }
+                                ]
+                            })
+                            .collect::<Vec<_>>()
+                    ]
+                ],
+                span![
+                    C!["icon"],
+                    C!["is-small"],
+                    C!["is-left"],
+                    i![C!["fas fa-arrow-trend-up"]]
+                ],
+            ]
+        ],
+        IF![
+            progression.kind == Some(ProgressionKind::Double) =>
+            div![
+                C!["field"],
+                C!["mb-0"],
+                C!["mr-2"],
+                div![
+                    C!["control"],
+                    C!["has-icons-left"],
+                    C!["has-icons-right"],
+                    input_ev(Ev::Input, {
+                        let id = id.to_vec();
+                        move |v| Msg::ProgressionMaxRepsChanged(id, v)
+                    }),
+                    span![C!["icon"], C!["is-small"], C!["is-left"], "≤"],
+                    input![
+                        C!["input"],
+                        C!["has-text-right"],
+                        C![IF![not(progression.max_reps.valid) || progression.max_reps.parsed.is_none() => "is-danger"]],
+                        C![IF![progression.max_reps.changed => "is-info"]],
+                        attrs! {
+                            At::Type => "number",
+                            At::Min => 1,
+                            At::Max => 999,
+                            At::Step => 1,
+                            At::Size => 2,
+                            At::Value => progression.max_reps.input,
+                        }
+                    ],
+                    span![C!["icon"], C!["is-small"], C!["is-right"], "✕"],
+                ]
+            ]
+        ],
+        IF![
+            progression.kind.is_some() =>
+            div![
+                C!["field"],
+                C!["mb-0"],
+                C!["mr-2"],
+                div![
+                    C!["control"],
+                    C!["has-icons-left"],
+                    C!["has-icons-right"],
+                    input_ev(Ev::Input, {
+                        let id = id.to_vec();
+                        move |v| Msg::ProgressionIncrementChanged(id, v)
+                    }),
+                    span![C!["icon"], C!["is-small"], C!["is-left"], "+"],
+                    input![
+                        C!["input"],
+                        C!["has-text-right"],
+                        C![IF![not(progression.increment.valid) => "is-danger"]],
+                        C![IF![progression.increment.changed => "is-info"]],
+                        attrs! {
+                            At::from("inputmode") => "numeric",
+                            At::Size => 3,
+                            At::Value => progression.increment.input,
+                        }
+                    ],
+                    span![C!["icon"], C!["is-small"], C!["is-right"], "kg"],
+                ]
+            ]
+        ],
+    ]
+}
+
+fn format_progression(progression: data::Progression) -> String {
+    match progression {
+        data::Progression::Linear { increment } | data::Progression::RPECapped { increment } => {
+            format!("{} +{increment} kg", progression.name())
+        }
+        data::Progression::Double {
+            max_reps,
+            increment,
+        } => format!("{} ≤{max_reps} ✕ +{increment} kg", progression.name()),
+    }
+}
+
+fn view_analysis(analysis: &data::RoutineAnalysis, data_model: &data::Model) -> Node<Msg> {
+    if analysis.duration == 0 && analysis.hard_sets.is_empty() && analysis.warnings.is_empty() {
+        return empty![];
+    }
+    let mut hard_sets = analysis
+        .hard_sets
+        .iter()
+        .map(|(exercise_id, sets)| {
+            (
+                data_model
+                    .exercises
+                    .get(exercise_id)
+                    .map_or("", |e| e.name.as_str()),
+                *sets,
+            )
+        })
+        .collect::<Vec<_>>();
+    hard_sets.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    div![
+        C!["container"],
+        C!["has-text-centered"],
+        C!["mt-6"],
+        h1![C!["title"], C!["is-5"], "Analysis"],
+        p![
+            C!["mb-3"],
+            "Estimated duration: ",
+            strong![common::format_estimated_duration(analysis.duration)]
+        ],
+        analysis.warnings.iter().map(|w| {
+            div![
+                C!["notification"],
+                C!["is-warning"],
+                C!["is-light"],
+                C!["py-2"],
+                C!["mx-2"],
+                C!["mb-2"],
+                span![
+                    C!["icon-text"],
+                    span![C!["icon"], i![C!["fas fa-triangle-exclamation"]]],
+                    span![w.description(&data_model.exercises)]
                 ]
             ]
+        }),
+        IF![not(hard_sets.is_empty()) => div![
+            C!["table-container"],
+            C!["mt-3"],
+            table![
+                C!["table"],
+                C!["is-fullwidth"],
+                C!["is-hoverable"],
+                C!["has-text-centered"],
+                thead![tr![th!["Exercise"], th!["Hard sets"]]],
+                tbody![hard_sets.iter().map(|(name, sets)| {
+                    tr![td![name], td![sets]]
+                })],
+            ]
+        ]],
+    ]
+}
+
+fn view_set_volume(routine: &data::Routine, data_model: &data::Model) -> Node<Msg> {
+    let set_volume = routine.set_volume_per_muscle_group(&data_model.exercises);
+    if set_volume.is_empty() {
+        return empty![];
+    }
+    let today = Local::now().date_naive();
+    let recent_sessions = data_model
+        .training_sessions
+        .values()
+        .filter(|t| {
+            t.routine_id == Some(routine.id)
+                && t.date > today - Duration::days(28)
+                && t.date <= today
+        })
+        .count();
+    #[allow(clippy::cast_precision_loss)]
+    let sessions_per_week = if recent_sessions > 0 {
+        recent_sessions as f32 / 4.0
+    } else {
+        1.0
+    };
+    let mut set_volume = set_volume.into_iter().collect::<Vec<_>>();
+    set_volume.sort_by(|a, b| b.1.total_cmp(&a.1));
+    div![
+        C!["container"],
+        C!["has-text-centered"],
+        C!["mt-6"],
+        h1![C!["title"], C!["is-5"], "Set volume"],
+        p![
+            C!["is-size-7"],
+            C!["mb-3"],
+            format!(
+                "Estimated weekly hard sets (target RPE ≥ 7) per muscle group based on {} session{} per week",
+                format_set_volume(sessions_per_week),
+                if (sessions_per_week - 1.0).abs() < f32::EPSILON {
+                    ""
+                } else {
+                    "s"
+                }
+            )
+        ],
+        div![
+            C!["table-container"],
+            table![
+                C!["table"],
+                C!["is-fullwidth"],
+                C!["is-hoverable"],
+                C!["has-text-centered"],
+                thead![tr![
+                    th!["Muscle group"],
+                    th!["Per session"],
+                    th!["Per week"],
+                ]],
+                tbody![set_volume.iter().map(|(muscle_group, volume)| {
+                    tr![
+                        td![muscle_group.name()],
+                        td![format_set_volume(*volume)],
+                        td![format_set_volume(volume * sessions_per_week)],
+                    ]
+                })],
+            ]
+        ]
+    ]
+}
+
+fn format_set_volume(value: f32) -> String {
+    let formatted = format!("{value:.1}");
+    formatted
+        .strip_suffix(".0")
+        .map_or(formatted.clone(), String::from)
+}
+
+fn view_versions(model: &Model, data_model: &data::Model) -> Node<Msg> {
+    let Some(versions) = data_model.routine_versions.get(&model.routine_id) else {
+        return empty![];
+    };
+    if versions.is_empty() {
+        return empty![];
+    }
+    div![
+        C!["container"],
+        C!["has-text-centered"],
+        C!["mt-6"],
+        h1![C!["title"], C!["is-5"], "Versions"],
+        div![
+            C!["table-container"],
+            table![
+                C!["table"],
+                C!["is-fullwidth"],
+                C!["is-hoverable"],
+                C!["has-text-centered"],
+                thead![tr![
+                    th!["Version"],
+                    th!["Created"],
+                    th!["Training sessions"],
+                    th![]
+                ]],
+                tbody![versions.iter().enumerate().rev().map(|(i, version)| {
+                    let sessions = data_model
+                        .training_sessions
+                        .values()
+                        .filter(|t| t.routine_version_id == Some(version.id))
+                        .count();
+                    tr![
+                        td![i + 1],
+                        td![version
+                            .created
+                            .with_timezone(&Local)
+                            .format("%Y-%m-%d %H:%M")
+                            .to_string()],
+                        td![sessions],
+                        td![IF![i > 0 => a![
+                            C!["icon"],
+                            attrs! {At::Title => "Show changes"},
+                            ev(Ev::Click, move |_| Msg::CompareVersions(i - 1, i)),
+                            i![C!["fas fa-code-compare"]]
+                        ]]],
+                    ]
+                })],
+            ]
+        ],
+        if let Some((old, new)) = model.compared_versions {
+            if let (Some(old_version), Some(new_version)) = (versions.get(old), versions.get(new)) {
+                view_version_comparison(
+                    versions.len(),
+                    (old, &old_version.sections),
+                    (new, &new_version.sections),
+                    data_model,
+                )
+            } else {
+                empty![]
+            }
+        } else {
+            empty![]
+        }
+    ]
+}
+
+fn view_version_comparison(
+    num_versions: usize,
+    old: (usize, &[data::RoutinePart]),
+    new: (usize, &[data::RoutinePart]),
+    data_model: &data::Model,
+) -> Node<Msg> {
+    let old_text = notation::print(old.1, &data_model.exercises);
+    let new_text = notation::print(new.1, &data_model.exercises);
+    let old_lines = old_text.lines().collect::<Vec<_>>();
+    let new_lines = new_text.lines().collect::<Vec<_>>();
+    let version_select = |selected: usize, other: usize, is_old: bool| {
+        div![
+            C!["select"],
+            C!["is-small"],
+            select![
+                (0..num_versions).map(|i| {
+                    option![
+                        attrs! {
+                            At::Value => i,
+                            At::Selected => (i == selected).as_at_value(),
+                        },
+                        format!("Version {}", i + 1)
+                    ]
+                }),
+                input_ev(Ev::Change, move |value| {
+                    value.parse::<usize>().ok().map(|i| {
+                        if is_old {
+                            Msg::CompareVersions(i, other)
+                        } else {
+                            Msg::CompareVersions(other, i)
+                        }
+                    })
+                }),
+            ]
+        ]
+    };
+    div![
+        C!["box"],
+        C!["mx-2"],
+        C!["has-text-left"],
+        div![
+            C!["is-flex"],
+            C!["is-justify-content-space-between"],
+            C!["is-align-items-center"],
+            C!["mb-3"],
+            div![
+                C!["is-flex"],
+                C!["is-align-items-center"],
+                version_select(old.0, new.0, true),
+                span![C!["icon"], C!["mx-2"], i![C!["fas fa-arrow-right"]]],
+                version_select(new.0, old.0, false),
+            ],
+            button![C!["delete"], ev(Ev::Click, |_| Msg::CloseVersionComparison)],
+        ],
+        if old_text == new_text {
+            p![C!["has-text-centered"], "No changes"]
+        } else {
+            pre![
+                C!["p-2"],
+                diff_lines(&old_lines, &new_lines)
+                    .into_iter()
+                    .map(|(change, line)| match change {
+                        Change::Unchanged => div![format!("  {line}")],
+                        Change::Removed =>
+                            div![C!["has-background-danger-light"], format!("- {line}")],
+                        Change::Added =>
+                            div![C!["has-background-success-light"], format!("+ {line}")],
+                    })
+            ]
+        }
+    ]
+}
+
+#[derive(Debug, PartialEq)]
+enum Change {
+    Unchanged,
+    Removed,
+    Added,
+}
+
+/// Line-based difference based on the longest common subsequence.
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(Change, &'a str)> {
+    let mut lcs = vec![vec![0; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
         }
     }
+    let mut result = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            result.push((Change::Unchanged, old[i]));
+            i += 1;
+            j += 1;
+        } else if j == new.len() || (i < old.len() && lcs[i + 1][j] >= lcs[i][j + 1]) {
+            result.push((Change::Removed, old[i]));
+            i += 1;
+        } else {
+            result.push((Change::Added, new[j]));
+            j += 1;
+        }
+    }
+    result
 }
 
 fn view_previous_exercises(model: &Model, data_model: &data::Model) -> Node<Msg> {
@@ -1252,7 +2785,16 @@ fn view_training_sessions(model: &Model, data_model: &data::Model) -> Node<Msg>
         h1![C!["title"], C!["is-5"], "Training sessions"],
         common::view_interval_buttons(&model.interval, &routine_interval, Msg::ChangeInterval),
         view_charts(&training_sessions, &model.interval),
-        training::view_calendar(&training_sessions, &model.interval),
+        view_target_adherence(&training_sessions, data_model),
+        training::view_calendar(
+            &training_sessions,
+            &training::planned_dates(
+                data_model.schedule.as_ref(),
+                Some(model.routine_id),
+                &model.interval
+            ),
+            &model.interval
+        ),
         training::view_table(
             &training_sessions,
             &data_model.routines,
@@ -1268,7 +2810,12 @@ pub fn view_charts<Ms>(
     let mut load: BTreeMap<NaiveDate, f32> = BTreeMap::new();
     let mut set_volume: BTreeMap<NaiveDate, f32> = BTreeMap::new();
     let mut intensity: BTreeMap<NaiveDate, Vec<f32>> = BTreeMap::new();
+    let mut adherence: BTreeMap<NaiveDate, data::TargetAdherence> = BTreeMap::new();
     for training_session in training_sessions {
+        adherence
+            .entry(training_session.date)
+            .or_default()
+            .merge(&training_session.target_adherence());
         #[allow(clippy::cast_precision_loss)]
         load.entry(training_session.date)
             .and_modify(|e| *e += training_session.load() as f32)
@@ -1335,12 +2882,138 @@ pub fn view_charts<Ms>(
                 None,
             )
         ),
+        IF![adherence.values().any(|a| a.targets().is_some() || a.avg_rpe_deviation().is_some()) =>
+            common::view_chart(
+                &[
+                    ("Targets achieved (%)", common::COLOR_TARGETS_ACHIEVED),
+                    ("RPE deviation", common::COLOR_RPE_DEVIATION)
+                ],
+                common::plot_dual_line_chart(
+                    &[(
+                        adherence
+                            .iter()
+                            .filter_map(|(date, a)| a.targets().map(|t| (*date, t * 100.)))
+                            .collect::<Vec<_>>(),
+                        common::COLOR_TARGETS_ACHIEVED,
+                    )],
+                    &[(
+                        adherence
+                            .iter()
+                            .filter_map(|(date, a)| a.avg_rpe_deviation().map(|d| (*date, d)))
+                            .collect::<Vec<_>>(),
+                        common::COLOR_RPE_DEVIATION,
+                    )],
+                    interval.first,
+                    interval.last,
+                )
+            )
+        ],
+    ]
+}
+
+fn view_target_adherence(
+    training_sessions: &[&data::TrainingSession],
+    data_model: &data::Model,
+) -> Node<Msg> {
+    let mut adherence: BTreeMap<u32, data::TargetAdherence> = BTreeMap::new();
+    for training_session in training_sessions {
+        for (exercise_id, a) in training_session.target_adherence_per_exercise() {
+            adherence.entry(exercise_id).or_default().merge(&a);
+        }
+    }
+    adherence.retain(|_, a| a.targets().is_some() || a.avg_rpe_deviation().is_some());
+    if adherence.is_empty() {
+        return empty![];
+    }
+    let format_share =
+        |share: Option<f32>| share.map_or(String::from("-"), |s| format!("{:.0} %", s * 100.));
+    div![
+        C!["table-container"],
+        C!["mt-4"],
+        table![
+            C!["table"],
+            C!["is-fullwidth"],
+            C!["is-hoverable"],
+            C!["has-text-centered"],
+            thead![tr![
+                th!["Exercise"],
+                th!["Sets"],
+                th!["Reps"],
+                th!["Time"],
+                th!["Weight"],
+                th!["RPE deviation"],
+                th![],
+            ]],
+            tbody![adherence.iter().map(|(exercise_id, a)| {
+                let rpe_deviation = a.avg_rpe_deviation();
+                let targets = a.targets();
+                let (assessment, color) = if rpe_deviation.map_or(false, |d| d >= 1.0)
+                    || targets.map_or(false, |t| t < 0.7)
+                {
+                    ("Too hard", "is-danger")
+                } else if rpe_deviation.map_or(false, |d| d <= -1.5)
+                    && targets.map_or(true, |t| t >= 0.95)
+                {
+                    ("Too easy", "is-info")
+                } else {
+                    ("On target", "is-success")
+                };
+                tr![
+                    td![data_model
+                        .exercises
+                        .get(exercise_id)
+                        .map_or("", |e| e.name.as_str())],
+                    td![a.sets],
+                    td![format_share(a.reps())],
+                    td![format_share(a.time())],
+                    td![format_share(a.weight())],
+                    td![rpe_deviation.map_or(String::from("-"), |d| format!("{d:+.1}"))],
+                    td![span![C!["tag"], C![color], C!["is-light"], assessment]],
+                ]
+            })],
+        ]
     ]
 }
 
 fn view_position_buttons(id: Vec<usize>) -> Node<Msg> {
     div![
         style! {St::WhiteSpace => "nowrap" },
+        span![
+            C!["button"],
+            C!["is-small"],
+            C!["ml-2"],
+            style! {St::Cursor => "grab", "touch-action" => "none"},
+            attrs! {At::Draggable => "true", At::Title => "Drag to move"},
+            ev(Ev::DragStart, {
+                let id = id.clone();
+                move |event| {
+                    if let Some(data_transfer) = event
+                        .dyn_ref::<web_sys::DragEvent>()
+                        .and_then(web_sys::DragEvent::data_transfer)
+                    {
+                        data_transfer.set_effect_allowed("move");
+                        // Firefox only starts dragging if some data is set
+                        let _ = data_transfer.set_data("text/plain", "");
+                    }
+                    Msg::DragStart(id)
+                }
+            }),
+            ev(Ev::DragEnd, |_| Msg::DragEnd),
+            ev(Ev::TouchStart, {
+                let id = id.clone();
+                move |_| Msg::DragStart(id)
+            }),
+            ev(Ev::TouchMove, |event| {
+                event.prevent_default();
+                event
+                    .dyn_ref::<web_sys::TouchEvent>()
+                    .and_then(|e| e.touches().get(0))
+                    .map(|touch| Msg::TouchMove(touch.client_x(), touch.client_y()))
+            }),
+            ev(Ev::TouchEnd, |_| Msg::Drop),
+            ev(Ev::TouchCancel, |_| Msg::DragEnd),
+            span![C!["icon"], i![C!["fas fa-grip-vertical"]]]
+        ],
         button![
             C!["button"],
             C!["is-small"],
@@ -1361,6 +3034,17 @@ fn view_position_buttons(id: Vec<usize>) -> Node<Msg> {
             }),
             span![C!["icon"], i![C!["fas fa-arrow-up"]],]
         ],
+        button![
+            C!["button"],
+            C!["is-small"],
+            C!["ml-2"],
+            attrs! {At::Title => "Copy"},
+            ev(Ev::Click, {
+                let id = id.clone();
+                move |_| Msg::CopyPart(id)
+            }),
+            span![C!["icon"], i![C!["fas fa-copy"]],]
+        ],
         button![
             C!["button"],
             C!["is-small"],
@@ -1375,6 +3059,46 @@ fn view_position_buttons(id: Vec<usize>) -> Node<Msg> {
     ]
 }
 
+fn view_drop_zone(drag: Option<&Drag>, parent: &[usize], index: usize) -> Node<Msg> {
+    let Some(drag) = drag else {
+        return empty![];
+    };
+    if not(drag.valid_target(parent, index)) {
+        return empty![];
+    }
+    let active = drag
+        .target
+        .as_ref()
+        .map_or(false, |(p, i)| p == parent && *i == index);
+    div![
+        C!["mt-2"],
+        C![if active {
+            "has-background-link"
+        } else {
+            "has-background-link-light"
+        }],
+        style! {
+            St::Height => "1.5rem",
+            St::BorderRadius => "4px",
+        },
+        attrs! {
+            At::from("data-drop-parent") => parent.iter().map(ToString::to_string).collect::<Vec<_>>().join(","),
+            At::from("data-drop-index") => index,
+        },
+        ev(Ev::DragOver, {
+            let parent = parent.to_vec();
+            move |event| {
+                event.prevent_default();
+                Msg::DragOver(parent, index)
+            }
+        }),
+        ev(Ev::Drop, |event| {
+            event.prevent_default();
+            Msg::Drop
+        }),
+    ]
+}
+
 fn view_add_part_buttons(data_model: &data::Model, id: Vec<usize>) -> Node<Msg> {
     let mut exercises = data_model.exercises.values().collect::<Vec<_>>();
     exercises.sort_by(|a, b| a.name.cmp(&b.name));
@@ -1409,7 +3133,19 @@ fn view_add_part_buttons(data_model: &data::Model, id: Vec<usize>) -> Node<Msg>
                 i![C!["ml-1"], C!["fas fa-plus-circle"]],
             ]
         ],
-        view_add_section_button(id),
+        view_add_section_button(id.clone()),
+        IF![data_model.copied_routine_part.is_some() => view_paste_button(id)],
+    ]
+}
+
+fn view_paste_button(id: Vec<usize>) -> Node<Msg> {
+    button![
+        C!["button"],
+        C!["mt-2"],
+        C!["mr-2"],
+        attrs! {At::Title => "Paste"},
+        ev(Ev::Click, move |_| Msg::PastePart(id)),
+        span![C!["icon"], i![C!["fas fa-paste"]]]
     ]
 }
 
@@ -1431,6 +3167,65 @@ fn view_add_section_button(id: Vec<usize>) -> Node<Msg> {
     ]
 }
 
+fn parse_seconds(input: String) -> InputField<u32> {
+    match input.parse::<u32>() {
+        Ok(parsed) => {
+            let valid = parsed <= 3600;
+            InputField {
+                input,
+                valid,
+                parsed: if valid { Some(parsed) } else { None },
+                changed: true,
+            }
+        }
+        Err(_) => InputField {
+            input,
+            valid: false,
+            parsed: None,
+            changed: true,
+        },
+    }
+}
+
+/// Move the part with the given ID to the given index of the section with the given ID.
+fn move_part(sections: &mut Vec<Form>, id: &[usize], mut parent: Vec<usize>, mut index: usize) {
+    let Some((&position, source_parent)) = id.split_first() else {
+        return;
+    };
+    if parent.ends_with(id) || get_parts(sections, &parent).is_none() {
+        return;
+    }
+    if parent.len() > source_parent.len() && parent.ends_with(source_parent) {
+        // Removing the part shifts the following sibling sections
+        let level = parent.len() - source_parent.len() - 1;
+        if parent[level] > position {
+            parent[level] -= 1;
+        }
+    } else if parent == source_parent && index > position {
+        index -= 1;
+    }
+    let Some(parts) = get_parts(sections, source_parent) else {
+        return;
+    };
+    if position >= parts.len() {
+        return;
+    }
+    let part = parts.remove(position);
+    if let Some(parts) = get_parts(sections, &parent) {
+        parts.insert(index.min(parts.len()), part);
+    }
+}
+
+fn get_parts<'a>(sections: &'a mut Vec<Form>, id: &[usize]) -> Option<&'a mut Vec<Form>> {
+    if id.is_empty() {
+        Some(sections)
+    } else if let Some(Form::Section { parts, .. }) = get_part(sections, id) {
+        Some(parts)
+    } else {
+        None
+    }
+}
+
 fn get_part<'a>(sections: &'a mut Vec<Form>, id: &[usize]) -> Option<&'a mut Form> {
     if let Some(i) = id.last() {
         if i < &sections.len() {
@@ -1438,7 +3233,7 @@ fn get_part<'a>(sections: &'a mut Vec<Form>, id: &[usize]) -> Option<&'a mut For
             if id.len() == 1 {
                 return Some(p);
             }
-            if let Form::Section { rounds: _, parts } = p {
+            if let Form::Section { parts, .. } = p {
                 return get_part(parts, &id[..id.len() - 1]);
             }
         }
@@ -1455,6 +3250,7 @@ mod tests {
         let mut sections = vec![
             Form::Section {
                 rounds: form_value(1),
+                kind: SectionKindForm::default(),
                 parts: vec![Form::Activity {
                     exercise_id: None,
                     reps: form_value(1),
@@ -1462,10 +3258,15 @@ mod tests {
                     weight: form_value(4.0),
                     rpe: form_value(5.0),
                     automatic: false,
+                    progression: ProgressionForm::default(),
+                    auto_warm_up: false,
+                    set_type: data::SetType::Working,
+                    weight_basis: None,
                 }],
             },
             Form::Section {
                 rounds: form_value(2),
+                kind: SectionKindForm::default(),
                 parts: vec![Form::Activity {
                     exercise_id: None,
                     reps: form_value(2),
@@ -1473,6 +3274,10 @@ mod tests {
                     weight: form_value(5.0),
                     rpe: form_value(6.0),
                     automatic: false,
+                    progression: ProgressionForm::default(),
+                    auto_warm_up: false,
+                    set_type: data::SetType::Working,
+                    weight_basis: None,
                 }],
             },
         ];
@@ -1480,6 +3285,7 @@ mod tests {
             *get_part(&mut sections, &[0]).unwrap(),
             Form::Section {
                 rounds: form_value(1),
+                kind: SectionKindForm::default(),
                 parts: vec![Form::Activity {
                     exercise_id: None,
                     reps: form_value(1),
@@ -1487,6 +3293,10 @@ mod tests {
                     weight: form_value(4.0),
                     rpe: form_value(5.0),
                     automatic: false,
+                    progression: ProgressionForm::default(),
+                    auto_warm_up: false,
+                    set_type: data::SetType::Working,
+                    weight_basis: None,
                 }],
             }
         );
@@ -1494,6 +3304,7 @@ mod tests {
             *get_part(&mut sections, &[1]).unwrap(),
             Form::Section {
                 rounds: form_value(2),
+                kind: SectionKindForm::default(),
                 parts: vec![Form::Activity {
                     exercise_id: None,
                     reps: form_value(2),
@@ -1501,6 +3312,10 @@ mod tests {
                     weight: form_value(5.0),
                     rpe: form_value(6.0),
                     automatic: false,
+                    progression: ProgressionForm::default(),
+                    auto_warm_up: false,
+                    set_type: data::SetType::Working,
+                    weight_basis: None,
                 }],
             }
         );
@@ -1514,6 +3329,10 @@ mod tests {
                 weight: form_value(4.0),
                 rpe: form_value(5.0),
                 automatic: false,
+                progression: ProgressionForm::default(),
+                auto_warm_up: false,
+                set_type: data::SetType::Working,
+                weight_basis: None,
             },
         );
         assert!(get_part(&mut sections, &[1, 0]).is_none());
@@ -1526,6 +3345,10 @@ mod tests {
                 weight: form_value(5.0),
                 rpe: form_value(6.0),
                 automatic: false,
+                progression: ProgressionForm::default(),
+                auto_warm_up: false,
+                set_type: data::SetType::Working,
+                weight_basis: None,
             },
         );
         assert!(get_part(&mut sections, &[1, 1]).is_none());
@@ -1535,6 +3358,7 @@ mod tests {
     fn get_part_in_nested_sections() {
         let mut sections = vec![Form::Section {
             rounds: form_value(1),
+            kind: SectionKindForm::default(),
             parts: vec![
                 Form::Activity {
                     exercise_id: None,
@@ -1543,9 +3367,14 @@ mod tests {
                     weight: form_value(4.0),
                     rpe: form_value(5.0),
                     automatic: false,
+                    progression: ProgressionForm::default(),
+                    auto_warm_up: false,
+                    set_type: data::SetType::Working,
+                    weight_basis: None,
                 },
                 Form::Section {
                     rounds: form_value(2),
+                    kind: SectionKindForm::default(),
                     parts: vec![Form::Activity {
                         exercise_id: None,
                         reps: form_value(2),
@@ -1553,6 +3382,10 @@ mod tests {
                         weight: form_value(5.0),
                         rpe: form_value(6.0),
                         automatic: false,
+                        progression: ProgressionForm::default(),
+                        auto_warm_up: false,
+                        set_type: data::SetType::Working,
+                        weight_basis: None,
                     }],
                 },
             ],
@@ -1561,6 +3394,7 @@ mod tests {
             *get_part(&mut sections, &[0]).unwrap(),
             Form::Section {
                 rounds: form_value(1),
+                kind: SectionKindForm::default(),
                 parts: vec![
                     Form::Activity {
                         exercise_id: None,
@@ -1569,9 +3403,14 @@ mod tests {
                         weight: form_value(4.0),
                         rpe: form_value(5.0),
                         automatic: false,
+                        progression: ProgressionForm::default(),
+                        auto_warm_up: false,
+                        set_type: data::SetType::Working,
+                        weight_basis: None,
                     },
                     Form::Section {
                         rounds: form_value(2),
+                        kind: SectionKindForm::default(),
                         parts: vec![Form::Activity {
                             exercise_id: None,
                             reps: form_value(2),
@@ -1579,6 +3418,10 @@ mod tests {
                             weight: form_value(5.0),
                             rpe: form_value(6.0),
                             automatic: false,
+                            progression: ProgressionForm::default(),
+                            auto_warm_up: false,
+                            set_type: data::SetType::Working,
+                            weight_basis: None,
                         }],
                     },
                 ],
@@ -1594,12 +3437,17 @@ mod tests {
                 weight: form_value(4.0),
                 rpe: form_value(5.0),
                 automatic: false,
+                progression: ProgressionForm::default(),
+                auto_warm_up: false,
+                set_type: data::SetType::Working,
+                weight_basis: None,
             },
         );
         assert_eq!(
             *get_part(&mut sections, &[1, 0]).unwrap(),
             Form::Section {
                 rounds: form_value(2),
+                kind: SectionKindForm::default(),
                 parts: vec![Form::Activity {
                     exercise_id: None,
                     reps: form_value(2),
@@ -1607,6 +3455,10 @@ mod tests {
                     weight: form_value(5.0),
                     rpe: form_value(6.0),
                     automatic: false,
+                    progression: ProgressionForm::default(),
+                    auto_warm_up: false,
+                    set_type: data::SetType::Working,
+                    weight_basis: None,
                 }],
             },
         );
@@ -1621,12 +3473,179 @@ mod tests {
                 weight: form_value(5.0),
                 rpe: form_value(6.0),
                 automatic: false,
+                progression: ProgressionForm::default(),
+                auto_warm_up: false,
+                set_type: data::SetType::Working,
+                weight_basis: None,
             },
         );
         assert!(get_part(&mut sections, &[1, 1, 0]).is_none());
         assert!(get_part(&mut sections, &[0, 0, 1, 0]).is_none());
     }
 
+    #[test]
+    fn diff_lines_of_versions() {
+        assert_eq!(diff_lines(&[], &[]), vec![]);
+        assert_eq!(
+            diff_lines(&["a", "b", "c", "d"], &["a", "c", "x", "d", "e"]),
+            vec![
+                (Change::Unchanged, "a"),
+                (Change::Removed, "b"),
+                (Change::Unchanged, "c"),
+                (Change::Added, "x"),
+                (Change::Unchanged, "d"),
+                (Change::Added, "e"),
+            ]
+        );
+        assert_eq!(
+            diff_lines(&["a"], &["b"]),
+            vec![(Change::Removed, "a"), (Change::Added, "b")]
+        );
+    }
+
+    #[test]
+    fn history_record() {
+        let section = |rounds| Form::Section {
+            rounds: form_value(rounds),
+            kind: SectionKindForm::default(),
+            parts: vec![],
+        };
+        let mut history = History::default();
+        history.record(Edit::Structure, &[]);
+        history.record(Edit::Value("rounds", vec![0]), &[section(1)]);
+        history.record(Edit::Value("rounds", vec![0]), &[section(2)]);
+        history.record(Edit::Value("rounds", vec![1]), &[section(3)]);
+        history.record(Edit::Structure, &[section(4)]);
+        history.record(Edit::Structure, &[section(5)]);
+        assert_eq!(
+            history.undo,
+            vec![
+                vec![],
+                vec![section(1)],
+                vec![section(3)],
+                vec![section(4)],
+                vec![section(5)]
+            ]
+        );
+
+        history.redo.push(vec![section(6)]);
+        history.record(Edit::Value("rounds", vec![0]), &[section(7)]);
+        assert!(history.redo.is_empty());
+        assert_eq!(history.undo.len(), 6);
+    }
+
+    #[test]
+    fn copy_part() {
+        let mut sections = vec![Form::Section {
+            rounds: form_value(2),
+            kind: SectionKindForm::from(data::SectionKind::Emom { interval: 90 }),
+            parts: vec![Form::Activity {
+                exercise_id: Some(3),
+                reps: form_value(5),
+                time: form_value(2),
+                weight: form_value(40.0),
+                rpe: form_value(8.0),
+                automatic: false,
+                progression: ProgressionForm::default(),
+                auto_warm_up: false,
+                set_type: data::SetType::Working,
+                weight_basis: None,
+            }],
+        }];
+        let part = get_part(&mut sections, &[0, 0]).unwrap();
+        let copied = to_routine_parts(std::slice::from_ref(part)).remove(0);
+        assert_eq!(Form::from(&copied), *part);
+        let section = get_part(&mut sections, &[0]).unwrap();
+        let copied = to_routine_parts(std::slice::from_ref(section)).remove(0);
+        assert_eq!(Form::from(&copied), *section);
+    }
+
+    #[test]
+    fn move_part_in_sections() {
+        let activity = |exercise_id| Form::Activity {
+            exercise_id: Some(exercise_id),
+            reps: form_value(1),
+            time: form_value(0),
+            weight: form_value(0.0),
+            rpe: form_value(0.0),
+            automatic: false,
+            progression: ProgressionForm::default(),
+            auto_warm_up: false,
+            set_type: data::SetType::Working,
+            weight_basis: None,
+        };
+        let section = |parts| Form::Section {
+            rounds: form_value(1),
+            kind: SectionKindForm::default(),
+            parts,
+        };
+        let sections = || {
+            vec![
+                section(vec![activity(0), activity(1), section(vec![activity(2)])]),
+                section(vec![activity(3)]),
+            ]
+        };
+
+        let mut s = sections();
+        move_part(&mut s, &[0, 0], vec![0], 2);
+        assert_eq!(
+            s,
+            vec![
+                section(vec![activity(1), activity(0), section(vec![activity(2)])]),
+                section(vec![activity(3)]),
+            ]
+        );
+
+        let mut s = sections();
+        move_part(&mut s, &[0, 1], vec![2, 0], 0);
+        assert_eq!(
+            s,
+            vec![
+                section(vec![
+                    activity(0),
+                    activity(1),
+                    section(vec![activity(3), activity(2)])
+                ]),
+                section(vec![]),
+            ]
+        );
+
+        let mut s = sections();
+        move_part(&mut s, &[1, 0], vec![2, 0], 1);
+        assert_eq!(
+            s,
+            vec![
+                section(vec![activity(0), section(vec![activity(2), activity(1)])]),
+                section(vec![activity(3)]),
+            ]
+        );
+
+        let mut s = sections();
+        move_part(&mut s, &[0], vec![1], 1);
+        assert_eq!(
+            s,
+            vec![section(vec![
+                activity(3),
+                section(vec![activity(0), activity(1), section(vec![activity(2)])])
+            ])]
+        );
+
+        let mut s = sections();
+        move_part(&mut s, &[0, 2, 0], vec![], 2);
+        assert_eq!(
+            s,
+            vec![
+                section(vec![activity(0), activity(1), section(vec![])]),
+                section(vec![activity(3)]),
+                activity(2),
+            ]
+        );
+
+        let mut s = sections();
+        move_part(&mut s, &[0], vec![2, 0], 0);
+        assert_eq!(s, sections());
+    }
+
     fn form_value<T: std::fmt::Display>(number: T) -> InputField<T> {
         InputField {
             input: number.to_string(),
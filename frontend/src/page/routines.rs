@@ -4,6 +4,7 @@ use seed::{prelude::*, *};
 
 use crate::common;
 use crate::data;
+use crate::templates;
 
 // ------ ------
 //     Init
@@ -21,6 +22,8 @@ pub fn init(mut url: Url, orders: &mut impl Orders<Msg>, navbar: &mut crate::Nav
     Model {
         search_term: String::new(),
         dialog: Dialog::Hidden,
+        templates: templates::all(),
+        pending_template: None,
         loading: false,
     }
 }
@@ -32,6 +35,9 @@ pub fn init(mut url: Url, orders: &mut impl Orders<Msg>, navbar: &mut crate::Nav
 pub struct Model {
     search_term: String,
     dialog: Dialog,
+    templates: Vec<templates::Template>,
+    /// Template whose routine will be created as soon as all exercises exist
+    pending_template: Option<usize>,
     loading: bool,
 }
 
@@ -39,7 +45,9 @@ enum Dialog {
     Hidden,
     AddRoutine(Form),
     EditRoutine(Form),
+    DuplicateRoutine(Form),
     DeleteRoutine(u32),
+    Templates,
 }
 
 struct Form {
@@ -54,7 +62,9 @@ struct Form {
 pub enum Msg {
     ShowAddRoutineDialog,
     ShowEditRoutineDialog(u32),
+    ShowDuplicateRoutineDialog(u32),
     ShowDeleteRoutineDialog(u32),
+    ShowTemplatesDialog,
     CloseRoutineDialog,
 
     SearchTermChanged(String),
@@ -62,6 +72,7 @@ pub enum Msg {
 
     SaveRoutine,
     DeleteRoutine(u32),
+    UseTemplate(usize),
     DataEvent(data::Event),
 }
 
@@ -86,9 +97,29 @@ pub fn update(
                 name: (name.clone(), Some(name)),
             });
         }
+        Msg::ShowDuplicateRoutineDialog(id) => {
+            let name = &data_model.routines[&id].name;
+            let name = (1..=data_model.routines.len() + 1)
+                .map(|i| {
+                    if i == 1 {
+                        format!("{name} (copy)")
+                    } else {
+                        format!("{name} (copy {i})")
+                    }
+                })
+                .find(|n| data_model.routines.values().all(|r| r.name != *n))
+                .unwrap();
+            model.dialog = Dialog::DuplicateRoutine(Form {
+                id,
+                name: (name.clone(), Some(name)),
+            });
+        }
         Msg::ShowDeleteRoutineDialog(id) => {
             model.dialog = Dialog::DeleteRoutine(id);
         }
+        Msg::ShowTemplatesDialog => {
+            model.dialog = Dialog::Templates;
+        }
         Msg::CloseRoutineDialog => {
             model.dialog = Dialog::Hidden;
             Url::go_and_replace(&crate::Urls::new(&data_model.base_url).routines());
@@ -98,14 +129,16 @@ pub fn update(
             model.search_term = search_term;
         }
         Msg::NameChanged(name) => match model.dialog {
-            Dialog::AddRoutine(ref mut form) | Dialog::EditRoutine(ref mut form) => {
+            Dialog::AddRoutine(ref mut form)
+            | Dialog::EditRoutine(ref mut form)
+            | Dialog::DuplicateRoutine(ref mut form) => {
                 if data_model.routines.values().all(|e| e.name != name) {
                     form.name = (name.clone(), Some(name));
                 } else {
                     form.name = (name, None);
                 }
             }
-            Dialog::Hidden | Dialog::DeleteRoutine(_) => {
+            Dialog::Hidden | Dialog::DeleteRoutine(_) | Dialog::Templates => {
                 panic!();
             }
         },
@@ -114,12 +147,24 @@ pub fn update(
             model.loading = true;
             match model.dialog {
                 Dialog::AddRoutine(ref mut form) => {
-                    orders.notify(data::Msg::CreateRoutine(form.name.1.clone().unwrap()));
+                    orders.notify(data::Msg::CreateRoutine(
+                        form.name.1.clone().unwrap(),
+                        String::new(),
+                        vec![],
+                    ));
+                }
+                Dialog::DuplicateRoutine(ref mut form) => {
+                    let routine = &data_model.routines[&form.id];
+                    orders.notify(data::Msg::CreateRoutine(
+                        form.name.1.clone().unwrap(),
+                        routine.notes.clone().unwrap_or_default(),
+                        routine.sections.clone(),
+                    ));
                 }
                 Dialog::EditRoutine(ref mut form) => {
                     orders.notify(data::Msg::ModifyRoutine(form.id, form.name.1.clone(), None));
                 }
-                Dialog::Hidden | Dialog::DeleteRoutine(_) => {
+                Dialog::Hidden | Dialog::DeleteRoutine(_) | Dialog::Templates => {
                     panic!();
                 }
             };
@@ -128,7 +173,40 @@ pub fn update(
             model.loading = true;
             orders.notify(data::Msg::DeleteRoutine(id));
         }
+        Msg::UseTemplate(index) => {
+            model.loading = true;
+            model.pending_template = Some(index);
+            let missing_exercises = model.templates[index]
+                .exercises
+                .iter()
+                .filter(|name| find_exercise(data_model, name).is_none())
+                .map(ToString::to_string)
+                .collect::<Vec<_>>();
+            if missing_exercises.is_empty() {
+                create_routine_from_template(model, data_model, orders);
+            }
+            for name in missing_exercises {
+                orders.notify(data::Msg::CreateExercise(
+                    name,
+                    data::ExerciseMetadata::default(),
+                ));
+            }
+        }
         Msg::DataEvent(event) => {
+            if model.pending_template.is_some() {
+                match event {
+                    data::Event::ExerciseCreatedOk => {
+                        create_routine_from_template(model, data_model, orders);
+                        return;
+                    }
+                    data::Event::ExerciseCreatedErr
+                    | data::Event::RoutineCreatedOk
+                    | data::Event::RoutineCreatedErr => {
+                        model.pending_template = None;
+                    }
+                    _ => {}
+                }
+            }
             model.loading = false;
             match event {
                 data::Event::RoutineCreatedOk
@@ -142,6 +220,49 @@ pub fn update(
     }
 }
 
+fn find_exercise(data_model: &data::Model, name: &str) -> Option<u32> {
+    data_model
+        .exercises
+        .values()
+        .find(|e| e.name.to_lowercase() == name.to_lowercase())
+        .map(|e| e.id)
+}
+
+/// Create the routine of the pending template if all its exercises exist.
+fn create_routine_from_template(
+    model: &mut Model,
+    data_model: &data::Model,
+    orders: &mut impl Orders<Msg>,
+) {
+    let Some(index) = model.pending_template else {
+        return;
+    };
+    let template = &model.templates[index];
+    let Some(exercise_ids) = template
+        .exercises
+        .iter()
+        .map(|name| find_exercise(data_model, name))
+        .collect::<Option<Vec<_>>>()
+    else {
+        return;
+    };
+    let name = (1..=data_model.routines.len() + 1)
+        .map(|i| {
+            if i == 1 {
+                template.name.to_string()
+            } else {
+                format!("{} ({i})", template.name)
+            }
+        })
+        .find(|n| data_model.routines.values().all(|r| r.name != *n))
+        .unwrap();
+    orders.notify(data::Msg::CreateRoutine(
+        name,
+        template.notes.to_string(),
+        template.instantiate(&exercise_ids),
+    ));
+}
+
 // ------ ------
 //     View
 // ------ ------
@@ -151,10 +272,36 @@ pub fn view(model: &Model, data_model: &data::Model) -> Node<Msg> {
         common::view_page_loading()
     } else {
         div![
-            view_routine_dialog(&model.dialog, &data_model.routines, model.loading),
+            view_routine_dialog(model, &data_model.routines, model.loading),
             div![
                 C!["px-4"],
-                common::view_search_box(&model.search_term, Msg::SearchTermChanged)
+                C!["field"],
+                C!["is-grouped"],
+                common::view_search_box(&model.search_term, Msg::SearchTermChanged),
+                div![
+                    C!["control"],
+                    button![
+                        C!["button"],
+                        attrs! {At::Title => "Templates"},
+                        ev(Ev::Click, |_| Msg::ShowTemplatesDialog),
+                        span![C!["icon"], i![C!["fas fa-book"]]]
+                    ]
+                ]
+            ],
+            IF![
+                data_model.routines.is_empty() =>
+                div![
+                    C!["has-text-centered"],
+                    C!["m-5"],
+                    p![C!["mb-3"], "No routines yet."],
+                    button![
+                        C!["button"],
+                        C!["is-link"],
+                        ev(Ev::Click, |_| Msg::ShowTemplatesDialog),
+                        span![C!["icon"], i![C!["fas fa-book"]]],
+                        span!["Start from a template"]
+                    ]
+                ]
             ],
             view_table(&model.search_term, data_model),
             common::view_fab("plus", |_| Msg::ShowAddRoutineDialog),
@@ -163,13 +310,13 @@ pub fn view(model: &Model, data_model: &data::Model) -> Node<Msg> {
 }
 
 fn view_routine_dialog(
-    dialog: &Dialog,
+    model: &Model,
     routines: &BTreeMap<u32, data::Routine>,
     loading: bool,
 ) -> Node<Msg> {
     let title;
     let form;
-    match dialog {
+    match &model.dialog {
         Dialog::AddRoutine(ref f) => {
             title = "Add routine";
             form = f;
@@ -178,6 +325,10 @@ fn view_routine_dialog(
             title = "Edit routine";
             form = f;
         }
+        Dialog::DuplicateRoutine(ref f) => {
+            title = "Duplicate routine";
+            form = f;
+        }
         Dialog::DeleteRoutine(id) => {
             let routine = &routines[id];
             let id = routine.id;
@@ -188,6 +339,9 @@ fn view_routine_dialog(
                 loading,
             );
         }
+        Dialog::Templates => {
+            return view_templates_dialog(&model.templates, loading);
+        }
         Dialog::Hidden => {
             return empty![];
         }
@@ -253,6 +407,68 @@ fn view_routine_dialog(
     )
 }
 
+fn view_templates_dialog(templates: &[templates::Template], loading: bool) -> Node<Msg> {
+    let mut programs: Vec<(&str, Vec<(usize, &templates::Template)>)> = vec![];
+    for (index, template) in templates.iter().enumerate() {
+        match programs.last_mut() {
+            Some((program, entries)) if *program == template.program => {
+                entries.push((index, template));
+            }
+            _ => programs.push((template.program, vec![(index, template)])),
+        }
+    }
+    common::view_dialog(
+        "primary",
+        "Templates",
+        nodes![
+            p![
+                C!["mb-4"],
+                "Missing exercises are created, existing exercises with the same name are used."
+            ],
+            div![programs.iter().map(|(program, entries)| {
+                div![
+                    C!["mb-4"],
+                    h2![C!["title"], C!["is-6"], C!["mb-2"], program],
+                    div![
+                        C!["buttons"],
+                        entries.iter().map(|(index, template)| {
+                            let index = *index;
+                            button![
+                                C!["button"],
+                                C!["is-link"],
+                                C!["is-outlined"],
+                                C![IF![loading => "is-loading"]],
+                                attrs! {
+                                    At::Title => template.exercises.join(", "),
+                                    At::Disabled => loading.as_at_value(),
+                                },
+                                ev(Ev::Click, move |_| Msg::UseTemplate(index)),
+                                template.name
+                            ]
+                        })
+                    ]
+                ]
+            })],
+            div![
+                C!["field"],
+                C!["is-grouped"],
+                C!["is-grouped-centered"],
+                C!["mt-5"],
+                div![
+                    C!["control"],
+                    button![
+                        C!["button"],
+                        C!["is-light"],
+                        ev(Ev::Click, |_| Msg::CloseRoutineDialog),
+                        "Cancel",
+                    ]
+                ],
+            ],
+        ],
+        &ev(Ev::Click, |_| Msg::CloseRoutineDialog),
+    )
+}
+
 fn view_table(search_term: &str, data_model: &data::Model) -> Node<Msg> {
     div![
         C!["table-container"],
@@ -268,6 +484,7 @@ fn view_table(search_term: &str, data_model: &data::Model) -> Node<Msg> {
                 .filter(|e| e.name.to_lowercase().contains(&search_term.to_lowercase()))
                 .map(|e| {
                     let id = e.id;
+                    let analysis = e.analysis();
                     tr![td![
                         C!["is-flex"],
                         C!["is-justify-content-space-between"],
@@ -283,12 +500,41 @@ fn view_table(search_term: &str, data_model: &data::Model) -> Node<Msg> {
                         ],
                         p![
                             C!["is-flex is-flex-wrap-nowrap"],
+                            IF![not(analysis.warnings.is_empty()) => span![
+                                C!["icon"],
+                                C!["has-text-warning"],
+                                C!["mx-1"],
+                                attrs! {
+                                    At::Title => analysis
+                                        .warnings
+                                        .iter()
+                                        .map(|w| w.description(&data_model.exercises))
+                                        .collect::<Vec<_>>()
+                                        .join("\n")
+                                },
+                                i![C!["fas fa-triangle-exclamation"]]
+                            ]],
+                            IF![analysis.duration > 0 => span![
+                                C!["is-size-7"],
+                                C!["has-text-grey"],
+                                C!["is-align-self-center"],
+                                C!["mx-2"],
+                                attrs! {At::Title => "Estimated duration"},
+                                common::format_estimated_duration(analysis.duration)
+                            ]],
                             a![
                                 C!["icon"],
                                 C!["mr-1"],
                                 ev(Ev::Click, move |_| Msg::ShowEditRoutineDialog(id)),
                                 i![C!["fas fa-edit"]]
                             ],
+                            a![
+                                C!["icon"],
+                                C!["mx-1"],
+                                attrs! {At::Title => "Duplicate"},
+                                ev(Ev::Click, move |_| Msg::ShowDuplicateRoutineDialog(id)),
+                                i![C!["fas fa-copy"]]
+                            ],
                             a![
                                 C!["icon"],
                                 C!["ml-1"],
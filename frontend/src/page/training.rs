@@ -1,6 +1,6 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
-use chrono::prelude::*;
+use chrono::{prelude::*, Duration};
 use seed::{prelude::*, *};
 
 use crate::common;
@@ -52,11 +52,97 @@ enum Dialog {
     Hidden,
     AddTrainingSession(Form),
     DeleteTrainingSession(u32),
+    EditSchedule(ScheduleForm),
 }
 
 struct Form {
     date: (String, Option<NaiveDate>),
     routine_id: (String, Option<u32>),
+    program: Option<(u32, data::ProgramPosition, u32)>,
+}
+
+struct ScheduleForm {
+    kind: ScheduleKind,
+    start: (String, Option<NaiveDate>),
+    weekdays: [Option<u32>; 7],
+    interval: (String, Option<u32>),
+    routines: Vec<u32>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ScheduleKind {
+    None,
+    Weekdays,
+    Rotation,
+}
+
+impl ScheduleKind {
+    const ALL: [ScheduleKind; 3] = [
+        ScheduleKind::None,
+        ScheduleKind::Weekdays,
+        ScheduleKind::Rotation,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            ScheduleKind::None => "None",
+            ScheduleKind::Weekdays => "Weekdays",
+            ScheduleKind::Rotation => "Every N days",
+        }
+    }
+}
+
+impl ScheduleForm {
+    fn new(schedule: Option<&data::Schedule>) -> Self {
+        let today = Local::now().date_naive();
+        let mut form = ScheduleForm {
+            kind: ScheduleKind::None,
+            start: (today.to_string(), Some(today)),
+            weekdays: [None; 7],
+            interval: (String::from("2"), Some(2)),
+            routines: vec![],
+        };
+        if let Some(schedule) = schedule {
+            form.start = (schedule.start.to_string(), Some(schedule.start));
+            match &schedule.kind {
+                data::ScheduleKind::Weekdays(weekdays) => {
+                    form.kind = ScheduleKind::Weekdays;
+                    form.weekdays = *weekdays;
+                }
+                data::ScheduleKind::Rotation { interval, routines } => {
+                    form.kind = ScheduleKind::Rotation;
+                    form.interval = (interval.to_string(), Some(*interval));
+                    form.routines.clone_from(routines);
+                }
+            }
+        }
+        form
+    }
+
+    fn valid(&self) -> bool {
+        match self.kind {
+            ScheduleKind::None => true,
+            ScheduleKind::Weekdays => self.start.1.is_some(),
+            ScheduleKind::Rotation => {
+                self.start.1.is_some() && self.interval.1.is_some() && not(self.routines.is_empty())
+            }
+        }
+    }
+
+    fn to_schedule(&self) -> Option<data::Schedule> {
+        let kind = match self.kind {
+            ScheduleKind::None => return None,
+            ScheduleKind::Weekdays => data::ScheduleKind::Weekdays(self.weekdays),
+            ScheduleKind::Rotation => data::ScheduleKind::Rotation {
+                interval: self.interval.1?,
+                routines: self.routines.clone(),
+            },
+        };
+        Some(data::Schedule {
+            start: self.start.1?,
+            kind,
+        })
+    }
 }
 
 // ------ ------
@@ -71,6 +157,15 @@ pub enum Msg {
     DateChanged(String),
     RoutineChanged(String),
 
+    ShowScheduleDialog,
+    ScheduleKindChanged(String),
+    ScheduleStartChanged(String),
+    ScheduleWeekdayChanged(usize, String),
+    ScheduleIntervalChanged(String),
+    ScheduleRoutineChanged(usize, String),
+    AddScheduleRoutine,
+    SaveSchedule,
+
     SaveTrainingSession,
     DeleteTrainingSession(u32),
     DataEvent(data::Event),
@@ -87,9 +182,26 @@ pub fn update(
     match msg {
         Msg::ShowAddTrainingSessionDialog => {
             let local = Local::now().date_naive();
+            let program = data_model.programs.values().find_map(|p| {
+                p.next_routine()
+                    .filter(|(_, routine_id)| data_model.routines.contains_key(routine_id))
+                    .map(|(position, routine_id)| (p.id, position, routine_id))
+            });
+            let planned_routine_id = data_model
+                .schedule
+                .as_ref()
+                .and_then(|s| s.planned_routine(local))
+                .filter(|routine_id| data_model.routines.contains_key(routine_id));
             model.dialog = Dialog::AddTrainingSession(Form {
                 date: (local.to_string(), Some(local)),
-                routine_id: (String::new(), data_model.routines.keys().max().copied()),
+                routine_id: (
+                    String::new(),
+                    program
+                        .map(|(_, _, routine_id)| routine_id)
+                        .or(planned_routine_id)
+                        .or_else(|| data_model.routines.keys().max().copied()),
+                ),
+                program,
             });
         }
         Msg::ShowDeleteTrainingSessionDialog(id) => {
@@ -109,7 +221,7 @@ pub fn update(
                     Err(_) => form.date = (date, None),
                 }
             }
-            Dialog::Hidden | Dialog::DeleteTrainingSession(_) => {
+            Dialog::Hidden | Dialog::DeleteTrainingSession(_) | Dialog::EditSchedule(_) => {
                 panic!();
             }
         },
@@ -127,11 +239,69 @@ pub fn update(
                 }
                 Err(_) => form.routine_id = (routine_id, None),
             },
-            Dialog::Hidden | Dialog::DeleteTrainingSession(_) => {
+            Dialog::Hidden | Dialog::DeleteTrainingSession(_) | Dialog::EditSchedule(_) => {
                 panic!();
             }
         },
 
+        Msg::ShowScheduleDialog => {
+            model.dialog = Dialog::EditSchedule(ScheduleForm::new(data_model.schedule.as_ref()));
+        }
+        Msg::ScheduleKindChanged(kind) => {
+            if let Dialog::EditSchedule(ref mut form) = model.dialog {
+                if let Some(kind) = ScheduleKind::ALL.iter().find(|k| k.name() == kind) {
+                    form.kind = *kind;
+                }
+            }
+        }
+        Msg::ScheduleStartChanged(date) => {
+            if let Dialog::EditSchedule(ref mut form) = model.dialog {
+                match NaiveDate::parse_from_str(&date, "%Y-%m-%d") {
+                    Ok(parsed_date) => {
+                        form.start = (date, Some(parsed_date));
+                    }
+                    Err(_) => form.start = (date, None),
+                }
+            }
+        }
+        Msg::ScheduleWeekdayChanged(weekday, routine_id) => {
+            if let Dialog::EditSchedule(ref mut form) = model.dialog {
+                form.weekdays[weekday] = routine_id.parse::<u32>().ok();
+            }
+        }
+        Msg::ScheduleIntervalChanged(interval) => {
+            if let Dialog::EditSchedule(ref mut form) = model.dialog {
+                match interval.parse::<u32>() {
+                    Ok(parsed_interval) if (1..=28).contains(&parsed_interval) => {
+                        form.interval = (interval, Some(parsed_interval));
+                    }
+                    _ => form.interval = (interval, None),
+                }
+            }
+        }
+        Msg::ScheduleRoutineChanged(idx, routine_id) => {
+            if let Dialog::EditSchedule(ref mut form) = model.dialog {
+                if let Ok(routine_id) = routine_id.parse::<u32>() {
+                    form.routines[idx] = routine_id;
+                } else {
+                    form.routines.remove(idx);
+                }
+            }
+        }
+        Msg::AddScheduleRoutine => {
+            if let Dialog::EditSchedule(ref mut form) = model.dialog {
+                if let Some(routine_id) = data_model.routines.keys().max() {
+                    form.routines.push(*routine_id);
+                }
+            }
+        }
+        Msg::SaveSchedule => {
+            if let Dialog::EditSchedule(ref form) = model.dialog {
+                orders.notify(data::Msg::SetSchedule(form.to_schedule()));
+            }
+            orders.send_msg(Msg::CloseTrainingSessionDialog);
+        }
+
         Msg::SaveTrainingSession => {
             model.loading = true;
             match model.dialog {
@@ -140,19 +310,39 @@ pub fn update(
                         .routines
                         .get(&form.routine_id.1.unwrap())
                         .unwrap();
-                    let sets = routine
-                        .sections
-                        .iter()
-                        .flat_map(to_training_session_elements)
-                        .collect::<Vec<data::TrainingSessionElement>>();
+                    let date = form.date.1.unwrap();
+                    let previous_training_session = data_model
+                        .training_sessions
+                        .values()
+                        .filter(|t| {
+                            t.routine_id == Some(routine.id) && t.date <= date && t.performed()
+                        })
+                        .max_by_key(|t| (t.date, t.id));
+                    let (mut sets, mut interval_blocks) = to_training_session_elements(
+                        routine,
+                        previous_training_session,
+                        data_model,
+                    );
+                    if let Some((program_id, position, routine_id)) = form.program {
+                        if routine_id == routine.id {
+                            data_model.programs[&program_id].weeks[position.week].apply(&mut sets);
+                        }
+                    }
+                    add_warm_up_sets(
+                        routine,
+                        &data_model.barbell_equipment(),
+                        &mut sets,
+                        &mut interval_blocks,
+                    );
                     orders.notify(data::Msg::CreateTrainingSession(
                         form.routine_id.1.unwrap(),
                         form.date.1.unwrap(),
                         String::new(),
                         sets,
+                        interval_blocks,
                     ));
                 }
-                Dialog::Hidden | Dialog::DeleteTrainingSession(_) => {
+                Dialog::Hidden | Dialog::DeleteTrainingSession(_) | Dialog::EditSchedule(_) => {
                     panic!();
                 }
             };
@@ -175,6 +365,20 @@ pub fn update(
                     );
                 }
                 data::Event::TrainingSessionCreatedOk => {
+                    if let Dialog::AddTrainingSession(ref form) = model.dialog {
+                        if let Some((program_id, _, routine_id)) = form.program {
+                            if form.routine_id.1 == Some(routine_id) {
+                                if let Some((training_session_id, _)) =
+                                    data_model.training_sessions.last_key_value()
+                                {
+                                    orders.notify(data::Msg::AdvanceProgram(
+                                        program_id,
+                                        *training_session_id,
+                                    ));
+                                }
+                            }
+                        }
+                    }
                     if let Some((training_session_id, _)) =
                         data_model.training_sessions.last_key_value()
                     {
@@ -242,6 +446,52 @@ pub fn view(model: &Model, data_model: &data::Model) -> Node<Msg> {
             })
             .copied()
             .collect::<Vec<_>>();
+        let today = Local::now().date_naive();
+        let forecast_last = if model.interval.last >= today {
+            today + Duration::days(data::TrainingStats::FORECAST_DAYS)
+        } else {
+            model.interval.last
+        };
+        let fitness = data_model
+            .training_stats
+            .fitness
+            .iter()
+            .filter(|(date, _)| *date >= model.interval.first && *date <= forecast_last)
+            .copied()
+            .collect::<Vec<_>>();
+        let fatigue = data_model
+            .training_stats
+            .fatigue
+            .iter()
+            .filter(|(date, _)| *date >= model.interval.first && *date <= forecast_last)
+            .copied()
+            .collect::<Vec<_>>();
+        let performance = data_model
+            .training_stats
+            .performance
+            .iter()
+            .filter(|(date, _)| *date >= model.interval.first && *date <= forecast_last)
+            .copied()
+            .collect::<Vec<_>>();
+        let set_volume_per_muscle_group_per_week = data_model
+            .training_stats
+            .set_volume_per_muscle_group_per_week
+            .iter()
+            .map(|(muscle_group, set_volume)| {
+                (
+                    *muscle_group,
+                    set_volume
+                        .iter()
+                        .filter(|(date, _)| {
+                            *date >= model.interval.first
+                                && *date <= model.interval.last.week(Weekday::Mon).last_day()
+                        })
+                        .copied()
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .filter(|(_, set_volume)| set_volume.iter().any(|(_, v)| *v > 0.0))
+            .collect::<Vec<_>>();
         let training_sessions = data_model
             .training_sessions
             .values()
@@ -258,8 +508,10 @@ pub fn view(model: &Model, data_model: &data::Model) -> Node<Msg> {
                     .routines
                     .values()
                     .collect::<Vec<&data::Routine>>(),
+                &data_model.programs,
                 &model.dialog,
-                model.loading
+                model.loading,
+                data_model
             ),
             div![
                 C!["container"],
@@ -285,6 +537,21 @@ pub fn view(model: &Model, data_model: &data::Model) -> Node<Msg> {
                             "Routines",
                         ]
                     ],
+                    div![
+                        C!["column"],
+                        a![
+                            C!["box"],
+                            C!["title"],
+                            C!["is-size-5"],
+                            C!["has-text-link"],
+                            C!["mx-2"],
+                            C!["p-3"],
+                            attrs! {
+                                At::Href => crate::Urls::new(&data_model.base_url).programs(),
+                            },
+                            "Programs",
+                        ]
+                    ],
                     div![
                         C!["column"],
                         a![
@@ -314,7 +581,23 @@ pub fn view(model: &Model, data_model: &data::Model) -> Node<Msg> {
                 avg_rpe_per_week,
                 &model.interval
             ),
-            view_calendar(&training_sessions, &model.interval),
+            view_performance_chart(
+                &fitness,
+                &fatigue,
+                &performance,
+                model.interval.first,
+                forecast_last,
+            ),
+            view_muscle_group_set_volume_chart(
+                &set_volume_per_muscle_group_per_week,
+                &model.interval
+            ),
+            view_calendar(
+                &training_sessions,
+                &planned_dates(data_model.schedule.as_ref(), None, &model.interval),
+                &model.interval
+            ),
+            view_schedule(data_model, &model.interval),
             view_table(
                 &training_sessions,
                 &data_model.routines,
@@ -326,8 +609,31 @@ pub fn view(model: &Model, data_model: &data::Model) -> Node<Msg> {
     }
 }
 
+/// Planned dates of the schedule in the calendar weeks of the interval, optionally limited to a
+/// specific routine.
+pub fn planned_dates(
+    schedule: Option<&data::Schedule>,
+    routine_id: Option<u32>,
+    interval: &common::Interval,
+) -> BTreeSet<NaiveDate> {
+    let mut dates = BTreeSet::new();
+    if let Some(schedule) = schedule {
+        let mut date = interval.first;
+        while date <= interval.last.week(Weekday::Mon).last_day() {
+            if let Some(planned_routine_id) = schedule.planned_routine(date) {
+                if routine_id.is_none() || routine_id == Some(planned_routine_id) {
+                    dates.insert(date);
+                }
+            }
+            date += Duration::days(1);
+        }
+    }
+    dates
+}
+
 pub fn view_calendar<Ms>(
     training_sessions: &[&data::TrainingSession],
+    planned: &BTreeSet<NaiveDate>,
     interval: &common::Interval,
 ) -> Node<Ms> {
     let mut load: BTreeMap<NaiveDate, u32> = BTreeMap::new();
@@ -349,7 +655,7 @@ pub fn view_calendar<Ms>(
         .copied()
         .unwrap_or(0);
 
-    common::view_calendar(
+    common::view_calendar_with_planned(
         load.iter()
             .map(|(date, load)| {
                 (
@@ -363,14 +669,277 @@ pub fn view_calendar<Ms>(
                 )
             })
             .collect(),
+        planned,
+        common::COLOR_LOAD,
         interval,
     )
 }
 
+fn view_schedule(data_model: &data::Model, interval: &common::Interval) -> Node<Msg> {
+    let Some(schedule) = data_model.schedule.as_ref() else {
+        return div![
+            C!["has-text-centered"],
+            C!["mb-5"],
+            button![
+                C!["button"],
+                C!["is-small"],
+                ev(Ev::Click, |_| Msg::ShowScheduleDialog),
+                span![C!["icon"], i![C!["fas fa-calendar-plus"]]],
+                span!["Schedule"]
+            ]
+        ];
+    };
+    let today = Local::now().date_naive();
+    let routine_name = |id: &u32| {
+        data_model
+            .routines
+            .get(id)
+            .map_or(String::from("–"), |r| r.name.clone())
+    };
+    let plan = match &schedule.kind {
+        data::ScheduleKind::Weekdays(weekdays) => weekdays
+            .iter()
+            .zip(common::WEEKDAYS)
+            .filter_map(|(routine_id, weekday)| {
+                routine_id.map(|id| format!("{weekday}: {}", routine_name(&id)))
+            })
+            .collect::<Vec<_>>()
+            .join(", "),
+        data::ScheduleKind::Rotation { interval, routines } => format!(
+            "Every {interval} days: {}",
+            routines
+                .iter()
+                .map(routine_name)
+                .collect::<Vec<_>>()
+                .join(" → ")
+        ),
+    };
+    let adherence = schedule.adherence(
+        &data_model.training_sessions,
+        interval.first,
+        interval.last.min(today - Duration::days(1)),
+    );
+    div![
+        C!["box"],
+        C!["mx-2"],
+        C!["mb-5"],
+        div![
+            C!["is-flex"],
+            C!["is-justify-content-space-between"],
+            p![span![C!["icon"], i![C!["fas fa-calendar-week"]]], plan],
+            a![
+                C!["icon"],
+                ev(Ev::Click, |_| Msg::ShowScheduleDialog),
+                i![C!["fas fa-edit"]]
+            ]
+        ],
+        if let Some((date, routine_id)) =
+            schedule.next_pending(&data_model.training_sessions, today)
+        {
+            p![
+                "Next: ",
+                strong![routine_name(&routine_id)],
+                " ",
+                common::format_relative_date(date, today)
+            ]
+        } else {
+            empty![]
+        },
+        if let Some(ratio) = adherence.ratio() {
+            p![format!(
+                "{} of {} planned sessions completed ({:.0} %)",
+                adherence.completed,
+                adherence.planned,
+                ratio * 100.0
+            )]
+        } else {
+            empty![]
+        }
+    ]
+}
+
+fn view_schedule_dialog(form: &ScheduleForm, routines: &[&data::Routine]) -> Node<Msg> {
+    let routine_select = |routine_id: Option<u32>| {
+        div![
+            C!["select"],
+            select![
+                option![
+                    "–",
+                    attrs! {
+                        At::Value => "",
+                    }
+                ],
+                routines
+                    .iter()
+                    .rev()
+                    .map(|r| {
+                        option![
+                            &r.name,
+                            attrs! {
+                                At::Value => r.id,
+                                At::Selected => (routine_id == Some(r.id)).as_at_value(),
+                            }
+                        ]
+                    })
+                    .collect::<Vec<_>>()
+            ]
+        ]
+    };
+    let save_disabled = not(form.valid());
+    common::view_dialog(
+        "primary",
+        "Schedule",
+        nodes![
+            div![
+                C!["field"],
+                div![
+                    C!["control"],
+                    div![
+                        C!["select"],
+                        input_ev(Ev::Change, Msg::ScheduleKindChanged),
+                        select![ScheduleKind::ALL
+                            .iter()
+                            .map(|k| {
+                                option![
+                                    k.name(),
+                                    attrs! {
+                                        At::Selected => (*k == form.kind).as_at_value(),
+                                    }
+                                ]
+                            })
+                            .collect::<Vec<_>>()]
+                    ]
+                ]
+            ],
+            IF![form.kind != ScheduleKind::None => div![
+                C!["field"],
+                label![C!["label"], "Start"],
+                div![
+                    C!["control"],
+                    input_ev(Ev::Input, Msg::ScheduleStartChanged),
+                    input![
+                        C!["input"],
+                        C![IF![form.start.1.is_none() => "is-danger"]],
+                        attrs! {
+                            At::Type => "date",
+                            At::Value => form.start.0,
+                        }
+                    ],
+                ]
+            ]],
+            match form.kind {
+                ScheduleKind::None => nodes![],
+                ScheduleKind::Weekdays => form
+                    .weekdays
+                    .iter()
+                    .zip(common::WEEKDAYS)
+                    .enumerate()
+                    .map(|(i, (routine_id, weekday))| {
+                        div![
+                            C!["field"],
+                            C!["is-horizontal"],
+                            div![
+                                C!["field-label"],
+                                C!["is-normal"],
+                                label![C!["label"], weekday]
+                            ],
+                            div![
+                                C!["field-body"],
+                                div![
+                                    C!["control"],
+                                    input_ev(Ev::Change, move |v| Msg::ScheduleWeekdayChanged(
+                                        i, v
+                                    )),
+                                    routine_select(*routine_id)
+                                ]
+                            ]
+                        ]
+                    })
+                    .collect::<Vec<_>>(),
+                ScheduleKind::Rotation => nodes![
+                    div![
+                        C!["field"],
+                        label![C!["label"], "Interval (days)"],
+                        div![
+                            C!["control"],
+                            input_ev(Ev::Input, Msg::ScheduleIntervalChanged),
+                            input![
+                                C!["input"],
+                                C![IF![form.interval.1.is_none() => "is-danger"]],
+                                attrs! {
+                                    At::from("inputmode") => "numeric",
+                                    At::Value => form.interval.0,
+                                }
+                            ],
+                        ]
+                    ],
+                    div![
+                        C!["field"],
+                        label![C!["label"], "Routines"],
+                        form.routines
+                            .iter()
+                            .enumerate()
+                            .map(|(i, routine_id)| {
+                                div![
+                                    C!["control"],
+                                    C!["mb-2"],
+                                    input_ev(Ev::Change, move |v| Msg::ScheduleRoutineChanged(
+                                        i, v
+                                    )),
+                                    routine_select(Some(*routine_id))
+                                ]
+                            })
+                            .collect::<Vec<_>>(),
+                        button![
+                            C!["button"],
+                            C!["is-small"],
+                            attrs! {
+                                At::Disabled => routines.is_empty().as_at_value(),
+                            },
+                            ev(Ev::Click, |_| Msg::AddScheduleRoutine),
+                            span![C!["icon"], i![C!["fas fa-plus"]]],
+                        ]
+                    ]
+                ],
+            },
+            div![
+                C!["field"],
+                C!["is-grouped"],
+                C!["is-grouped-centered"],
+                C!["mt-5"],
+                div![
+                    C!["control"],
+                    button![
+                        C!["button"],
+                        C!["is-light"],
+                        ev(Ev::Click, |_| Msg::CloseTrainingSessionDialog),
+                        "Cancel",
+                    ]
+                ],
+                div![
+                    C!["control"],
+                    button![
+                        C!["button"],
+                        C!["is-primary"],
+                        attrs! {
+                            At::Disabled => save_disabled.as_at_value(),
+                        },
+                        ev(Ev::Click, |_| Msg::SaveSchedule),
+                        "Save",
+                    ]
+                ],
+            ],
+        ],
+        &ev(Ev::Click, |_| Msg::CloseTrainingSessionDialog),
+    )
+}
+
 fn view_training_sessions_dialog(
     routines: &[&data::Routine],
+    programs: &BTreeMap<u32, data::Program>,
     dialog: &Dialog,
     loading: bool,
+    data_model: &data::Model,
 ) -> Node<Msg> {
     let title;
     let form;
@@ -391,11 +960,26 @@ fn view_training_sessions_dialog(
                 loading,
             );
         }
+        Dialog::EditSchedule(ref f) => {
+            return view_schedule_dialog(f, routines);
+        }
         Dialog::Hidden => {
             return empty![];
         }
     }
     let save_disabled = loading || form.date.1.is_none() || form.routine_id.1.is_none();
+    let mut exercises_without_reference_weight = BTreeSet::new();
+    if let Some(routine) = form
+        .routine_id
+        .1
+        .and_then(|id| data_model.routines.get(&id))
+    {
+        collect_exercises_without_reference_weight(
+            &routine.sections,
+            data_model,
+            &mut exercises_without_reference_weight,
+        );
+    }
     common::view_dialog(
         "primary",
         title,
@@ -433,12 +1017,42 @@ fn view_training_sessions_dialog(
                                     &r.name,
                                     attrs![
                                         At::Value => r.id,
+                                        At::Selected => (form.routine_id.1 == Some(r.id)).as_at_value(),
                                     ]
                                 ]
                             })
                             .collect::<Vec<_>>()],
                     ],
                 ],
+                if let Some((program_id, position, routine_id)) = form.program {
+                    p![
+                        C!["help"],
+                        C![IF![form.routine_id.1 != Some(routine_id) => "has-text-grey"]],
+                        span![C!["icon"], i![C!["fas fa-calendar-week"]]],
+                        format!(
+                            "{}: week {}, day {}",
+                            programs[&program_id].name,
+                            position.week + 1,
+                            position.day + 1
+                        )
+                    ]
+                } else {
+                    empty![]
+                },
+                IF![not(exercises_without_reference_weight.is_empty()) => p![
+                    C!["help"],
+                    C!["has-text-warning-dark"],
+                    span![C!["icon"], i![C!["fas fa-triangle-exclamation"]]],
+                    format!(
+                        "No reference weight for {}, the weight of the routine is used",
+                        exercises_without_reference_weight
+                            .iter()
+                            .filter_map(|id| data_model.exercises.get(id))
+                            .map(|e| e.name.as_str())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )
+                ]]
             ],
             div![
                 C!["field"],
@@ -532,6 +1146,98 @@ pub fn view_charts<Ms>(
     ]
 }
 
+/// Display fitness, fatigue and performance. Values after the current day are a forecast based on
+/// the planned training sessions, assuming rest on all other days, and are shown as dashed lines.
+pub fn view_performance_chart<Ms>(
+    fitness: &[(NaiveDate, f32)],
+    fatigue: &[(NaiveDate, f32)],
+    performance: &[(NaiveDate, f32)],
+    first: NaiveDate,
+    last: NaiveDate,
+) -> Node<Ms> {
+    let today = Local::now().date_naive();
+    let past = |series: &[(NaiveDate, f32)]| {
+        series
+            .iter()
+            .filter(|(date, _)| *date <= today)
+            .copied()
+            .collect::<Vec<_>>()
+    };
+    let forecast = |series: &[(NaiveDate, f32)]| {
+        series
+            .iter()
+            .filter(|(date, _)| *date >= today)
+            .copied()
+            .collect::<Vec<_>>()
+    };
+    common::view_chart(
+        &[
+            ("Fitness", common::COLOR_FITNESS),
+            ("Fatigue", common::COLOR_FATIGUE),
+            ("Performance", common::COLOR_PERFORMANCE),
+        ],
+        common::plot_line_chart_with_dashed_lines(
+            &[
+                (past(fitness), common::COLOR_FITNESS),
+                (past(fatigue), common::COLOR_FATIGUE),
+                (past(performance), common::COLOR_PERFORMANCE),
+            ],
+            &[
+                (forecast(fitness), common::COLOR_FITNESS),
+                (forecast(fatigue), common::COLOR_FATIGUE),
+                (forecast(performance), common::COLOR_PERFORMANCE),
+            ],
+            first,
+            last,
+            None,
+            None,
+        ),
+    )
+}
+
+/// Display the weekly number of hard sets (RPE >= 7) per muscle group. Sets of exercises targeting a
+/// muscle group only secondarily are counted fractionally.
+pub fn view_muscle_group_set_volume_chart<Ms>(
+    set_volume_per_muscle_group_per_week: &[(data::MuscleGroup, Vec<(NaiveDate, f32)>)],
+    interval: &common::Interval,
+) -> Node<Ms> {
+    if set_volume_per_muscle_group_per_week.is_empty() {
+        return empty![];
+    }
+    div![
+        common::view_chart(
+            &set_volume_per_muscle_group_per_week
+                .iter()
+                .map(|(muscle_group, _)| (muscle_group.name(), muscle_group_color(*muscle_group)))
+                .collect::<Vec<_>>(),
+            common::plot_line_chart(
+                &set_volume_per_muscle_group_per_week
+                    .iter()
+                    .map(|(muscle_group, set_volume)| {
+                        (set_volume.clone(), muscle_group_color(*muscle_group))
+                    })
+                    .collect::<Vec<_>>(),
+                interval.first.week(Weekday::Mon).first_day(),
+                interval.last.week(Weekday::Mon).last_day(),
+                Some(0.),
+                None,
+            ),
+        ),
+        p![
+            C!["is-size-7"],
+            C!["has-text-centered"],
+            "Weekly hard sets (RPE ≥ 7) per muscle group"
+        ]
+    ]
+}
+
+fn muscle_group_color(muscle_group: data::MuscleGroup) -> usize {
+    data::MuscleGroup::ALL
+        .iter()
+        .position(|m| *m == muscle_group)
+        .unwrap_or(0)
+}
+
 pub fn view_table<Ms: 'static>(
     training_sessions: &[&data::TrainingSession],
     routines: &BTreeMap<u32, data::Routine>,
@@ -617,46 +1323,378 @@ pub fn view_table<Ms: 'static>(
     ]
 }
 
-fn to_training_session_elements(part: &data::RoutinePart) -> Vec<data::TrainingSessionElement> {
-    let mut result = vec![];
-    match part {
-        data::RoutinePart::RoutineSection { rounds, parts, .. } => {
-            for _ in 0..*rounds {
-                for p in parts {
-                    for s in to_training_session_elements(p) {
-                        result.push(s);
-                    }
-                }
-            }
+/// Create the elements of a new training session based on the routine. The targets of activities
+/// with a progression rule are derived from the corresponding sets of the previous training session.
+/// The weight percentage of the program week in which the previous training session was created is
+/// reverted beforehand.
+/// Interval sections are returned as interval blocks covering the corresponding elements.
+/// Relative weights are resolved to absolute weights and take precedence over the weight derived
+/// from the progression rule. Activities whose reference weight is unknown keep their weight.
+fn to_training_session_elements(
+    routine: &data::Routine,
+    previous_training_session: Option<&data::TrainingSession>,
+    data_model: &data::Model,
+) -> (Vec<data::TrainingSessionElement>, Vec<data::IntervalBlock>) {
+    let resolve_weight = |exercise_id: u32, weight: f32, options: &data::ActivityOptions| {
+        options.target_weight(weight, |basis| {
+            data_model.reference_weight(exercise_id, basis)
+        })
+    };
+    let mut steps = vec![];
+    let mut interval_blocks = vec![];
+    let mut activity_idx = 0;
+    for part in &routine.sections {
+        collect_steps(part, &mut activity_idx, &mut steps, &mut interval_blocks);
+    }
+
+    let mut previous_elements = vec![];
+    if let Some(previous_training_session) = previous_training_session {
+        previous_elements.clone_from(&previous_training_session.elements);
+        if let Some(weight_percentage) =
+            data_model.program_weight_percentage(previous_training_session.id)
+        {
+            data::revert_weight_percentage(weight_percentage, &mut previous_elements);
         }
-        data::RoutinePart::RoutineActivity {
+    }
+    let mut previous_sets: HashMap<u32, Vec<&data::TrainingSessionElement>> = HashMap::new();
+    for e in &previous_elements {
+        if let data::TrainingSessionElement::Set {
             exercise_id,
-            reps,
-            time,
-            weight,
-            rpe,
-            automatic,
-        } => {
-            result.push(if let Some(exercise_id) = exercise_id {
+            set_type,
+            ..
+        } = e
+        {
+            if set_type.is_working() {
+                previous_sets.entry(*exercise_id).or_default().push(e);
+            }
+        }
+    }
+    let mut set_positions: HashMap<u32, usize> = HashMap::new();
+    let mut previous_sets_of_activity: HashMap<usize, Vec<&data::TrainingSessionElement>> =
+        HashMap::new();
+    for step in &steps {
+        if let Step::Activity(
+            idx,
+            data::RoutinePart::RoutineActivity {
+                exercise_id: Some(exercise_id),
+                ..
+            },
+        )
+        | Step::TimedActivity(
+            idx,
+            data::RoutinePart::RoutineActivity {
+                exercise_id: Some(exercise_id),
+                ..
+            },
+            _,
+        ) = step
+        {
+            let position = set_positions
+                .entry(*exercise_id)
+                .and_modify(|position| *position += 1)
+                .or_insert(0);
+            if let Some(set) = previous_sets
+                .get(exercise_id)
+                .and_then(|sets| sets.get(*position))
+            {
+                previous_sets_of_activity.entry(*idx).or_default().push(set);
+            }
+        }
+    }
+
+    let elements = steps
+        .iter()
+        .filter_map(|step| match step {
+            Step::Rest(time) => Some(data::TrainingSessionElement::Rest {
+                target_time: if *time > 0 { Some(*time) } else { None },
+                automatic: true,
+            }),
+            Step::Activity(_, data::RoutinePart::RoutineSection { .. })
+            | Step::TimedActivity(_, data::RoutinePart::RoutineSection { .. }, _) => None,
+            Step::Activity(
+                idx,
+                data::RoutinePart::RoutineActivity {
+                    exercise_id,
+                    reps,
+                    time,
+                    weight,
+                    rpe,
+                    automatic,
+                    options,
+                },
+            ) => Some(if let Some(exercise_id) = exercise_id {
+                let (reps, weight) = if let Some(progression) = options.progression {
+                    progression.next_targets(
+                        *reps,
+                        *weight,
+                        *rpe,
+                        previous_sets_of_activity
+                            .get(idx)
+                            .map(Vec::as_slice)
+                            .unwrap_or_default(),
+                    )
+                } else {
+                    (*reps, *weight)
+                };
+                let weight = resolve_weight(*exercise_id, weight, options);
                 data::TrainingSessionElement::Set {
                     exercise_id: *exercise_id,
                     reps: None,
                     time: None,
                     weight: None,
                     rpe: None,
-                    target_reps: if *reps > 0 { Some(*reps) } else { None },
+                    target_reps: if reps > 0 { Some(reps) } else { None },
                     target_time: if *time > 0 { Some(*time) } else { None },
-                    target_weight: if *weight > 0.0 { Some(*weight) } else { None },
+                    target_weight: if weight > 0.0 { Some(weight) } else { None },
                     target_rpe: if *rpe > 0.0 { Some(*rpe) } else { None },
                     automatic: *automatic,
+                    set_type: options.set_type,
                 }
             } else {
                 data::TrainingSessionElement::Rest {
                     target_time: if *time > 0 { Some(*time) } else { None },
                     automatic: *automatic,
                 }
-            });
+            }),
+            Step::TimedActivity(
+                _,
+                data::RoutinePart::RoutineActivity {
+                    exercise_id,
+                    weight,
+                    rpe,
+                    options,
+                    ..
+                },
+                work,
+            ) => exercise_id.map(|exercise_id| {
+                let weight = resolve_weight(exercise_id, *weight, options);
+                data::TrainingSessionElement::Set {
+                    exercise_id,
+                    reps: None,
+                    time: None,
+                    weight: None,
+                    rpe: None,
+                    target_reps: None,
+                    target_time: Some(*work),
+                    target_weight: if weight > 0.0 { Some(weight) } else { None },
+                    target_rpe: if *rpe > 0.0 { Some(*rpe) } else { None },
+                    automatic: true,
+                    set_type: options.set_type,
+                }
+            }),
+        })
+        .collect();
+
+    (elements, interval_blocks)
+}
+
+/// Collect the exercises with a relative weight whose reference weight is unknown.
+fn collect_exercises_without_reference_weight(
+    parts: &[data::RoutinePart],
+    data_model: &data::Model,
+    result: &mut BTreeSet<u32>,
+) {
+    for part in parts {
+        match part {
+            data::RoutinePart::RoutineSection { parts, .. } => {
+                collect_exercises_without_reference_weight(parts, data_model, result);
+            }
+            data::RoutinePart::RoutineActivity {
+                exercise_id: Some(exercise_id),
+                options:
+                    data::ActivityOptions {
+                        relative_weight: Some(relative_weight),
+                        ..
+                    },
+                ..
+            } => {
+                if data_model
+                    .reference_weight(*exercise_id, relative_weight.basis)
+                    .is_none()
+                {
+                    result.insert(*exercise_id);
+                }
+            }
+            data::RoutinePart::RoutineActivity { .. } => {}
+        }
+    }
+}
+
+/// Insert warm-up sets before the first set of each exercise whose routine activities request
+/// warm-up sets. The ramp is derived from the target weight of the first working set. Exercises
+/// whose first set is part of an interval block are skipped.
+fn add_warm_up_sets(
+    routine: &data::Routine,
+    equipment: &data::BarbellEquipment,
+    elements: &mut Vec<data::TrainingSessionElement>,
+    interval_blocks: &mut [data::IntervalBlock],
+) {
+    let mut exercise_ids = BTreeSet::new();
+    collect_warm_up_exercises(&routine.sections, &mut exercise_ids);
+    for exercise_id in exercise_ids {
+        let Some((idx, target_weight)) = elements.iter().enumerate().find_map(|(idx, e)| match e {
+            data::TrainingSessionElement::Set {
+                exercise_id: id,
+                target_weight,
+                set_type,
+                ..
+            } if *id == exercise_id && set_type.is_working() => Some((idx, *target_weight)),
+            _ => None,
+        }) else {
+            continue;
+        };
+        if interval_blocks
+            .iter()
+            .any(|b| b.start <= idx && idx < b.end)
+        {
+            continue;
+        }
+        let warm_up_sets = equipment
+            .warm_up_sets(target_weight.unwrap_or(0.0))
+            .into_iter()
+            .flat_map(|(reps, weight)| {
+                [
+                    data::TrainingSessionElement::Set {
+                        exercise_id,
+                        reps: None,
+                        time: None,
+                        weight: None,
+                        rpe: None,
+                        target_reps: Some(reps),
+                        target_time: None,
+                        target_weight: Some(weight),
+                        target_rpe: None,
+                        automatic: false,
+                        set_type: data::SetType::WarmUp,
+                    },
+                    data::TrainingSessionElement::Rest {
+                        target_time: Some(WARM_UP_REST),
+                        automatic: true,
+                    },
+                ]
+            })
+            .collect::<Vec<_>>();
+        let count = warm_up_sets.len();
+        elements.splice(idx..idx, warm_up_sets);
+        for b in interval_blocks.iter_mut().filter(|b| b.start >= idx) {
+            b.start += count;
+            b.end += count;
+        }
+    }
+}
+
+/// Rest time after a warm-up set in seconds.
+const WARM_UP_REST: u32 = 60;
+
+fn collect_warm_up_exercises(parts: &[data::RoutinePart], result: &mut BTreeSet<u32>) {
+    for part in parts {
+        match part {
+            data::RoutinePart::RoutineSection { parts, .. } => {
+                collect_warm_up_exercises(parts, result);
+            }
+            data::RoutinePart::RoutineActivity {
+                exercise_id: Some(exercise_id),
+                options,
+                ..
+            } if options.auto_warm_up => {
+                result.insert(*exercise_id);
+            }
+            data::RoutinePart::RoutineActivity { .. } => {}
+        }
+    }
+}
+
+/// Step of a new training session.
+#[derive(Clone, Copy)]
+enum Step<'a> {
+    /// Activity together with the index of the activity in the routine.
+    Activity(usize, &'a data::RoutinePart),
+    /// Activity with a fixed duration, which replaces the targets for reps and time.
+    TimedActivity(usize, &'a data::RoutinePart, u32),
+    Rest(u32),
+}
+
+/// Collect all steps in the order of execution. The steps of interval sections are arranged
+/// according to the section kind and recorded as interval block. Interval sections nested in
+/// interval sections are treated as sequential sections.
+fn collect_steps<'a>(
+    part: &'a data::RoutinePart,
+    activity_idx: &mut usize,
+    result: &mut Vec<Step<'a>>,
+    interval_blocks: &mut Vec<data::IntervalBlock>,
+) {
+    match part {
+        data::RoutinePart::RoutineSection {
+            rounds,
+            parts,
+            kind,
+        } => {
+            let mut steps = vec![];
+            let mut nested_interval_blocks = vec![];
+            for p in parts {
+                collect_steps(p, activity_idx, &mut steps, &mut nested_interval_blocks);
+            }
+            let is_exercise = |step: &Step| {
+                matches!(
+                    step,
+                    Step::Activity(
+                        _,
+                        data::RoutinePart::RoutineActivity {
+                            exercise_id: Some(_),
+                            ..
+                        }
+                    )
+                )
+            };
+            let round = match kind {
+                data::SectionKind::Sequential
+                | data::SectionKind::Amrap { .. }
+                | data::SectionKind::ForTime { .. } => steps,
+                data::SectionKind::Emom { interval } => steps
+                    .into_iter()
+                    .filter(is_exercise)
+                    .chain([Step::Rest(*interval)])
+                    .collect(),
+                data::SectionKind::Tabata { work, rest } => steps
+                    .into_iter()
+                    .filter(is_exercise)
+                    .flat_map(|step| match step {
+                        Step::Activity(idx, part) | Step::TimedActivity(idx, part, _) => {
+                            vec![Step::TimedActivity(idx, part, *work), Step::Rest(*rest)]
+                        }
+                        Step::Rest(_) => vec![],
+                    })
+                    .collect(),
+            };
+            let start = result.len();
+            let rounds = kind.planned_rounds(*rounds);
+            for _ in 0..rounds {
+                if *kind == data::SectionKind::Sequential {
+                    let offset = result.len();
+                    interval_blocks.extend(nested_interval_blocks.iter().map(|b| {
+                        data::IntervalBlock {
+                            start: b.start + offset,
+                            end: b.end + offset,
+                            ..*b
+                        }
+                    }));
+                }
+                result.extend(round.iter().copied());
+            }
+            if *kind != data::SectionKind::Sequential && result.len() > start {
+                interval_blocks.push(data::IntervalBlock {
+                    kind: *kind,
+                    start,
+                    end: result.len(),
+                    rounds,
+                    achieved_rounds: None,
+                    time: None,
+                });
+            }
+        }
+        data::RoutinePart::RoutineActivity { .. } => {
+            result.push(Step::Activity(*activity_idx, part));
+            *activity_idx += 1;
         }
     }
-    result
 }
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use chrono::{prelude::*, Duration};
 use seed::{prelude::*, *};
@@ -59,6 +59,7 @@ pub fn init(
     Model {
         training_session_id,
         form: init_form(training_session, data_model),
+        dialog: Dialog::Hidden,
         guide,
         timer_dialog: SMTDialog {
             visible: false,
@@ -87,6 +88,8 @@ pub fn init(
         editing,
         loading: false,
         notification: None,
+        media_session_handlers: vec![],
+        bars: BTreeMap::new(),
     }
 }
 
@@ -96,9 +99,27 @@ fn init_form(training_session: Option<&data::TrainingSession>, data_model: &data
         let mut sections = vec![];
         let mut exercises = vec![];
         let mut position = 0;
-        let mut prev_set_positions: HashMap<u32, usize> = HashMap::new();
-
-        for e in &training_session.elements {
+        let boundaries = training_session
+            .interval_blocks
+            .iter()
+            .filter(|b| b.start <= b.end && b.end <= training_session.elements.len())
+            .flat_map(|b| {
+                (b.start..=b.end)
+                    .step_by(b.round_len().max(1))
+                    .chain([b.end])
+            })
+            .collect::<std::collections::BTreeSet<_>>();
+        let mut boundary_sections: HashMap<usize, usize> = HashMap::new();
+
+        for (element_idx, e) in training_session.elements.iter().enumerate() {
+            if boundaries.contains(&element_idx) {
+                if not(exercises.is_empty()) {
+                    sections.push(FormSection::Set { exercises });
+                    position = 0;
+                }
+                exercises = vec![];
+                boundary_sections.insert(element_idx, sections.len());
+            }
             match e {
                 data::TrainingSessionElement::Set {
                     exercise_id,
@@ -111,6 +132,7 @@ fn init_form(training_session: Option<&data::TrainingSession>, data_model: &data
                     target_weight,
                     target_rpe,
                     automatic,
+                    set_type,
                 } => {
                     if target_time.is_some() && target_reps.is_none() {
                         if not(exercises.is_empty()) {
@@ -119,27 +141,6 @@ fn init_form(training_session: Option<&data::TrainingSession>, data_model: &data
                         }
                         exercises = vec![];
                     }
-                    let prev_set_position = prev_set_positions
-                        .entry(*exercise_id)
-                        .and_modify(|position| *position += 1)
-                        .or_insert(0);
-                    let (prev_reps, prev_time, prev_weight, prev_rpe) =
-                        if let Some(prev_sets) = previous_sets.get(exercise_id) {
-                            if let Some(data::TrainingSessionElement::Set {
-                                reps,
-                                time,
-                                weight,
-                                rpe,
-                                ..
-                            }) = prev_sets.get(*prev_set_position)
-                            {
-                                (*reps, *time, *weight, *rpe)
-                            } else {
-                                (None, None, None, None)
-                            }
-                        } else {
-                            (None, None, None, None)
-                        };
                     exercises.push(ExerciseForm {
                         position,
                         exercise_id: *exercise_id,
@@ -175,11 +176,14 @@ fn init_form(training_session: Option<&data::TrainingSession>, data_model: &data
                         target_time: *target_time,
                         target_weight: *target_weight,
                         target_rpe: *target_rpe,
-                        prev_reps,
-                        prev_time,
-                        prev_weight,
-                        prev_rpe,
+                        prev_reps: None,
+                        prev_time: None,
+                        prev_weight: None,
+                        prev_rpe: None,
                         automatic: *automatic,
+                        set_type: *set_type,
+                        set_type_changed: false,
+                        substituted: false,
                     });
                     position += 1;
                     if target_time.is_some() && target_reps.is_none() {
@@ -210,17 +214,99 @@ fn init_form(training_session: Option<&data::TrainingSession>, data_model: &data
         if not(exercises.is_empty()) {
             sections.push(FormSection::Set { exercises });
         }
+        boundary_sections.insert(training_session.elements.len(), sections.len());
+
+        update_previous_values(&mut sections, &previous_sets);
+
+        let blocks = training_session
+            .interval_blocks
+            .iter()
+            .filter(|b| b.start <= b.end && b.end <= training_session.elements.len())
+            .map(|b| FormBlock {
+                block: *b,
+                section_start: boundary_sections[&b.start],
+                section_end: boundary_sections[&b.end],
+                achieved_rounds: InputField {
+                    input: b.achieved_rounds.map(|v| v.to_string()).unwrap_or_default(),
+                    valid: true,
+                    parsed: b.achieved_rounds,
+                    changed: false,
+                },
+                time: InputField {
+                    input: b.time.map(|v| v.to_string()).unwrap_or_default(),
+                    valid: true,
+                    parsed: b.time,
+                    changed: false,
+                },
+            })
+            .collect();
 
         Form {
             notes: training_session.notes.clone().unwrap_or_default(),
             notes_changed: false,
+            session_rpe: InputField {
+                input: training_session
+                    .session_rpe
+                    .map(|v| v.to_string())
+                    .unwrap_or_default(),
+                valid: true,
+                parsed: training_session.session_rpe,
+                changed: false,
+            },
+            duration: InputField {
+                input: training_session
+                    .duration
+                    .map(|v| v.to_string())
+                    .unwrap_or_default(),
+                valid: true,
+                parsed: training_session.duration,
+                changed: false,
+            },
             sections,
+            blocks,
         }
     } else {
         Form {
             notes: String::new(),
             notes_changed: false,
+            session_rpe: InputField::default(),
+            duration: InputField::default(),
             sections: vec![],
+            blocks: vec![],
+        }
+    }
+}
+
+/// Assign the values of the sets of the previous training session. Warm-up sets and working sets
+/// are matched separately.
+fn update_previous_values(
+    sections: &mut [FormSection],
+    previous_sets: &HashMap<(u32, bool), Vec<data::TrainingSessionElement>>,
+) {
+    let mut prev_set_positions: HashMap<(u32, bool), usize> = HashMap::new();
+    for section in sections {
+        if let FormSection::Set { exercises } = section {
+            for e in exercises {
+                let prev_set_position = prev_set_positions
+                    .entry((e.exercise_id, e.set_type.is_working()))
+                    .and_modify(|position| *position += 1)
+                    .or_insert(0);
+                (e.prev_reps, e.prev_time, e.prev_weight, e.prev_rpe) =
+                    if let Some(data::TrainingSessionElement::Set {
+                        reps,
+                        time,
+                        weight,
+                        rpe,
+                        ..
+                    }) = previous_sets
+                        .get(&(e.exercise_id, e.set_type.is_working()))
+                        .and_then(|prev_sets| prev_sets.get(*prev_set_position))
+                    {
+                        (*reps, *time, *weight, *rpe)
+                    } else {
+                        (None, None, None, None)
+                    };
+            }
         }
     }
 }
@@ -228,8 +314,8 @@ fn init_form(training_session: Option<&data::TrainingSession>, data_model: &data
 fn previous_sets(
     training_session: Option<&data::TrainingSession>,
     data_model: &data::Model,
-) -> HashMap<u32, Vec<data::TrainingSessionElement>> {
-    let mut sets: HashMap<u32, Vec<data::TrainingSessionElement>> = HashMap::new();
+) -> HashMap<(u32, bool), Vec<data::TrainingSessionElement>> {
+    let mut sets: HashMap<(u32, bool), Vec<data::TrainingSessionElement>> = HashMap::new();
     if let Some(training_session) = training_session {
         if let Some(previous_training_session) = &data_model
             .training_sessions
@@ -243,8 +329,15 @@ fn previous_sets(
             .last()
         {
             for e in &previous_training_session.elements {
-                if let data::TrainingSessionElement::Set { exercise_id, .. } = e {
-                    sets.entry(*exercise_id).or_default().push(e.clone());
+                if let data::TrainingSessionElement::Set {
+                    exercise_id,
+                    set_type,
+                    ..
+                } = e
+                {
+                    sets.entry((*exercise_id, set_type.is_working()))
+                        .or_default()
+                        .push(e.clone());
                 }
             }
         }
@@ -259,6 +352,7 @@ fn previous_sets(
 pub struct Model {
     training_session_id: u32,
     form: Form,
+    dialog: Dialog,
     guide: Option<Guide>,
     timer_dialog: SMTDialog,
     timer_stream: Option<StreamHandle>,
@@ -266,6 +360,10 @@ pub struct Model {
     editing: bool,
     loading: bool,
     notification: Option<web_sys::Notification>,
+    /// Handlers of the media session actions, which must be kept alive while they are registered.
+    media_session_handlers: Vec<Closure<dyn FnMut()>>,
+    /// Index of the bar used for each exercise.
+    bars: BTreeMap<u32, usize>,
 }
 
 impl Model {
@@ -277,18 +375,25 @@ impl Model {
 impl Drop for Model {
     fn drop(&mut self) {
         close_notification(self);
+        unregister_media_session(self);
     }
 }
 
 struct Form {
     notes: String,
     notes_changed: bool,
+    session_rpe: InputField<f32>,
+    duration: InputField<u32>,
     sections: Vec<FormSection>,
+    blocks: Vec<FormBlock>,
 }
 
 impl Form {
     fn changed(&self) -> bool {
         self.notes_changed
+            || self.session_rpe.changed
+            || self.duration.changed
+            || self.blocks_changed()
             || self
                 .sections
                 .iter()
@@ -297,26 +402,105 @@ impl Form {
                     _ => None,
                 })
                 .flatten()
-                .any(|e| e.reps.changed || e.time.changed || e.weight.changed || e.rpe.changed)
+                .any(|e| {
+                    e.reps.changed
+                        || e.time.changed
+                        || e.weight.changed
+                        || e.rpe.changed
+                        || e.set_type_changed
+                        || e.substituted
+                })
     }
 
-    fn valid(&self) -> bool {
-        self.sections
+    fn blocks_changed(&self) -> bool {
+        self.blocks
             .iter()
-            .filter_map(|s| match s {
-                FormSection::Set { exercises } => Some(exercises),
-                _ => None,
-            })
-            .flatten()
-            .all(|s| s.reps.valid && s.time.valid && s.weight.valid && s.rpe.valid)
+            .any(|b| b.achieved_rounds.changed || b.time.changed)
+    }
+
+    fn block_of(&self, section_idx: usize) -> Option<usize> {
+        self.blocks
+            .iter()
+            .position(|b| (b.section_start..b.section_end).contains(&section_idx))
+    }
+
+    fn valid(&self) -> bool {
+        self.session_rpe.valid
+            && self.duration.valid
+            && self
+                .blocks
+                .iter()
+                .all(|b| b.achieved_rounds.valid && b.time.valid)
+            && self
+                .sections
+                .iter()
+                .filter_map(|s| match s {
+                    FormSection::Set { exercises } => Some(exercises),
+                    _ => None,
+                })
+                .flatten()
+                .all(|s| s.reps.valid && s.time.valid && s.weight.valid && s.rpe.valid)
     }
 }
 
+enum Dialog {
+    Hidden,
+    SubstituteExercise {
+        section_idx: usize,
+        position: usize,
+        search_term: String,
+    },
+}
+
 enum FormSection {
     Set { exercises: Vec<ExerciseForm> },
     Rest { target_time: u32, automatic: bool },
 }
 
+/// Interval block covering a range of sections.
+struct FormBlock {
+    block: data::IntervalBlock,
+    section_start: usize,
+    section_end: usize,
+    achieved_rounds: InputField<u32>,
+    time: InputField<u32>,
+}
+
+impl FormBlock {
+    /// Index of the round of the given section.
+    fn round_of(&self, section_idx: usize) -> usize {
+        let sections_per_round =
+            (self.section_end - self.section_start) / self.block.rounds.max(1) as usize;
+        (section_idx - self.section_start) / sections_per_round.max(1)
+    }
+
+    /// Point in time at which the current round or the whole block ends.
+    fn deadline(&self, section_idx: usize, start_time: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self.block.kind {
+            data::SectionKind::Emom { interval } => {
+                #[allow(clippy::cast_possible_wrap)]
+                let round = self.round_of(section_idx) as i64;
+                Some(start_time + Duration::seconds(i64::from(interval) * (round + 1)))
+            }
+            data::SectionKind::Amrap { time_cap } => {
+                Some(start_time + Duration::seconds(i64::from(time_cap)))
+            }
+            data::SectionKind::ForTime { time_cap } if time_cap > 0 => {
+                Some(start_time + Duration::seconds(i64::from(time_cap)))
+            }
+            _ => None,
+        }
+    }
+
+    fn to_interval_block(&self) -> data::IntervalBlock {
+        data::IntervalBlock {
+            achieved_rounds: self.achieved_rounds.parsed,
+            time: self.time.parsed,
+            ..self.block
+        }
+    }
+}
+
 #[derive(Clone)]
 struct ExerciseForm {
     position: usize,
@@ -335,6 +519,9 @@ struct ExerciseForm {
     prev_weight: Option<f32>,
     prev_rpe: Option<f32>,
     automatic: bool,
+    set_type: data::SetType,
+    set_type_changed: bool,
+    substituted: bool,
 }
 
 #[derive(Clone)]
@@ -359,6 +546,8 @@ impl<T> Default for InputField<T> {
 struct Guide {
     section_idx: usize,
     section_start_time: DateTime<Utc>,
+    /// Index and start time of the current interval block
+    block: Option<(usize, DateTime<Utc>)>,
     timer: Timer,
     stream: Option<StreamHandle>,
     element: ElRef<web_sys::Element>,
@@ -369,6 +558,7 @@ impl Guide {
         Guide {
             section_idx: 0,
             section_start_time: Utc::now(),
+            block: None,
             timer: Timer::new(beep_volume),
             stream: None,
             element: ElRef::new(),
@@ -378,11 +568,13 @@ impl Guide {
     fn from_ongoing_training_session(
         section_idx: usize,
         section_start_time: DateTime<Utc>,
+        block: Option<(usize, DateTime<Utc>)>,
         beep_volume: u8,
     ) -> Guide {
         Guide {
             section_idx,
             section_start_time,
+            block,
             timer: Timer::new(beep_volume),
             stream: None,
             element: ElRef::new(),
@@ -647,10 +839,22 @@ pub enum Msg {
     TimeChanged(usize, usize, String),
     WeightChanged(usize, usize, String),
     RPEChanged(usize, usize, String),
+    SetTypeChanged(usize, usize, String),
     NotesChanged(String),
+    SessionRPEChanged(String),
+    DurationChanged(String),
+    AchievedRoundsChanged(usize, String),
+    BlockTimeChanged(usize, String),
 
     EnterTargetValues(usize, usize),
     EnterPreviousValues(usize, usize),
+    AcceptSuggestion(usize, usize),
+    ChangeBar(u32),
+
+    ShowSubstituteExerciseDialog(usize, usize),
+    CloseSubstituteExerciseDialog,
+    SubstituteSearchTermChanged(String),
+    SubstituteExercise(u32),
 
     StartGuidedTrainingSession,
     ContinueGuidedTrainingSession(data::OngoingTrainingSession),
@@ -658,6 +862,8 @@ pub enum Msg {
     StartPauseGuideTimer,
     GoToPreviousSection,
     GoToNextSection,
+    MediaAction(MediaAction),
+    EndIntervalBlock,
     ScrollToSection,
 
     EditTrainingSession,
@@ -683,6 +889,13 @@ pub enum Msg {
     TimerTimeChanged(String),
 }
 
+#[derive(Clone, Copy)]
+pub enum MediaAction {
+    PlayPause,
+    Previous,
+    Next,
+}
+
 pub fn update(
     msg: Msg,
     model: &mut Model,
@@ -794,6 +1007,84 @@ pub fn update(
             model.form.notes = notes;
             model.form.notes_changed = true;
         }
+        Msg::SessionRPEChanged(input) => match input.parse::<f32>() {
+            Ok(parsed_rpe) => {
+                let valid = common::valid_rpe(parsed_rpe);
+                let parsed = if valid { Some(parsed_rpe) } else { None };
+                model.form.session_rpe = InputField {
+                    input,
+                    valid,
+                    parsed,
+                    changed: true,
+                }
+            }
+            Err(_) => {
+                model.form.session_rpe = InputField {
+                    input: input.clone(),
+                    valid: input.is_empty(),
+                    parsed: None,
+                    changed: true,
+                }
+            }
+        },
+        Msg::DurationChanged(input) => match input.parse::<u32>() {
+            Ok(parsed_duration) => {
+                let valid = common::valid_time(parsed_duration);
+                let parsed = if valid { Some(parsed_duration) } else { None };
+                model.form.duration = InputField {
+                    input,
+                    valid,
+                    parsed,
+                    changed: true,
+                }
+            }
+            Err(_) => {
+                model.form.duration = InputField {
+                    input: input.clone(),
+                    valid: input.is_empty(),
+                    parsed: None,
+                    changed: true,
+                }
+            }
+        },
+        Msg::AchievedRoundsChanged(block_idx, input) => {
+            model.form.blocks[block_idx].achieved_rounds = match input.parse::<u32>() {
+                Ok(parsed_rounds) => {
+                    let valid = parsed_rounds < 1000;
+                    InputField {
+                        input,
+                        valid,
+                        parsed: if valid { Some(parsed_rounds) } else { None },
+                        changed: true,
+                    }
+                }
+                Err(_) => InputField {
+                    input: input.clone(),
+                    valid: input.is_empty(),
+                    parsed: None,
+                    changed: true,
+                },
+            };
+        }
+        Msg::BlockTimeChanged(block_idx, input) => {
+            model.form.blocks[block_idx].time = match input.parse::<u32>() {
+                Ok(parsed_time) => {
+                    let valid = parsed_time < 86400;
+                    InputField {
+                        input,
+                        valid,
+                        parsed: if valid { Some(parsed_time) } else { None },
+                        changed: true,
+                    }
+                }
+                Err(_) => InputField {
+                    input: input.clone(),
+                    valid: input.is_empty(),
+                    parsed: None,
+                    changed: true,
+                },
+            };
+        }
 
         Msg::EnterTargetValues(section_idx, exercise_idx) => {
             if let FormSection::Set { exercises } = &mut model.form.sections[section_idx] {
@@ -834,6 +1125,45 @@ pub fn update(
                 };
             }
         }
+        Msg::AcceptSuggestion(section_idx, exercise_idx) => {
+            if let Some((suggested_reps, suggested_weight)) =
+                rpe_suggestion(&model.form.sections, section_idx, exercise_idx)
+            {
+                if let FormSection::Set { exercises } = &mut model.form.sections[section_idx] {
+                    let ExerciseForm { reps, weight, .. } = &mut exercises[exercise_idx];
+                    *reps = InputField {
+                        input: suggested_reps.map(|v| v.to_string()).unwrap_or_default(),
+                        valid: true,
+                        parsed: suggested_reps,
+                        changed: true,
+                    };
+                    *weight = InputField {
+                        input: suggested_weight.map(|v| v.to_string()).unwrap_or_default(),
+                        valid: true,
+                        parsed: suggested_weight,
+                        changed: true,
+                    };
+                }
+            }
+        }
+        Msg::SetTypeChanged(section_idx, exercise_idx, input) => {
+            if let FormSection::Set { exercises } = &mut model.form.sections[section_idx] {
+                if let Some(set_type) = input
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|i| data::SetType::ALL.get(i))
+                {
+                    let e = &mut exercises[exercise_idx];
+                    e.set_type_changed = e.set_type_changed || e.set_type != *set_type;
+                    e.set_type = *set_type;
+                }
+            }
+        }
+        Msg::ChangeBar(exercise_id) => {
+            let bars = data_model.barbell_equipment().bars.len().max(1);
+            let bar = model.bars.entry(exercise_id).or_insert(0);
+            *bar = (*bar + 1) % bars;
+        }
         Msg::EnterPreviousValues(section_idx, exercise_idx) => {
             if let FormSection::Set { exercises } = &mut model.form.sections[section_idx] {
                 let ExerciseForm {
@@ -876,8 +1206,10 @@ pub fn update(
 
         Msg::StartGuidedTrainingSession => {
             model.guide = Some(Guide::new(data_model.settings.beep_volume));
+            update_guide_block(model, orders);
             update_guide_timer(model);
             update_streams(model, orders);
+            register_media_session(model, orders);
             orders.notify(data::Msg::StartTrainingSession(model.training_session_id));
             if let Some(guide) = &model.guide {
                 if data_model.settings.automatic_metronome {
@@ -888,7 +1220,7 @@ pub fn update(
                     guide.timer.to_timer_state(),
                 ));
             }
-            show_section_notification(model);
+            announce_section(model, &data_model.settings);
             Url::go_and_push(
                 &crate::Urls::new(&data_model.base_url)
                     .training_session()
@@ -900,6 +1232,14 @@ pub fn update(
             model.guide = Some(Guide::from_ongoing_training_session(
                 ongoing_training_session.section_idx,
                 ongoing_training_session.section_start_time,
+                ongoing_training_session
+                    .block_start_time
+                    .and_then(|block_start_time| {
+                        model
+                            .form
+                            .block_of(ongoing_training_session.section_idx)
+                            .map(|block_idx| (block_idx, block_start_time))
+                    }),
                 data_model.settings.beep_volume,
             ));
             model
@@ -916,7 +1256,8 @@ pub fn update(
                 }
             }
             update_streams(model, orders);
-            show_section_notification(model);
+            register_media_session(model, orders);
+            announce_section(model, &data_model.settings);
             orders.force_render_now().send_msg(Msg::ScrollToSection);
             Url::go_and_push(
                 &crate::Urls::new(&data_model.base_url)
@@ -927,6 +1268,21 @@ pub fn update(
         }
         Msg::UpdateGuidedTrainingSession => {
             if let Some(guide) = &mut model.guide {
+                if let Some((block_idx, block_start_time)) = guide.block {
+                    let block = &model.form.blocks[block_idx];
+                    let resting = matches!(
+                        model.form.sections[guide.section_idx],
+                        FormSection::Rest { .. }
+                    );
+                    if let Some(deadline) = block.deadline(guide.section_idx, block_start_time) {
+                        if Utc::now() >= deadline
+                            && not(matches!(block.block.kind, data::SectionKind::Emom { .. })
+                                && resting)
+                        {
+                            orders.send_msg(Msg::EndIntervalBlock);
+                        }
+                    }
+                }
                 match &model.form.sections[guide.section_idx] {
                     FormSection::Set { exercises } => {
                         let exercise = &exercises[0];
@@ -955,7 +1311,33 @@ pub fn update(
                         }
                     }
                 }
+                let previous_time = guide.timer.time.1;
                 guide.timer.update(&model.audio_context);
+                if guide.timer.is_active() && guide.timer.time.1 != previous_time {
+                    let speech = match guide.timer.time.1 {
+                        Some(COUNTDOWN_ANNOUNCEMENT)
+                            if guide.timer.reset_time > 2 * COUNTDOWN_ANNOUNCEMENT =>
+                        {
+                            Some(format!("{COUNTDOWN_ANNOUNCEMENT} seconds left"))
+                        }
+                        Some(0)
+                            if matches!(
+                                model.form.sections[guide.section_idx],
+                                FormSection::Rest { .. }
+                            ) =>
+                        {
+                            Some(String::from("Rest is over"))
+                        }
+                        _ => None,
+                    };
+                    if let Some(speech) = speech {
+                        common::speak(
+                            &speech,
+                            data_model.settings.speech_volume,
+                            data_model.settings.speech_voice.as_deref(),
+                        );
+                    }
+                }
             }
         }
         Msg::StartPauseGuideTimer => {
@@ -973,15 +1355,16 @@ pub fn update(
                 guide.section_idx -= 1;
                 guide.section_start_time = Utc::now();
             }
+            update_guide_block(model, orders);
             update_guide_timer(model);
             update_streams(model, orders);
             if let Some(guide) = &mut model.guide {
                 if data_model.settings.automatic_metronome {
                     update_metronome(&model.form.sections[guide.section_idx], orders);
                 }
-                if let Some(ongoing_training_session) = &data_model.ongoing_training_session {
+                if data_model.ongoing_training_session.is_some() {
                     orders.notify(data::Msg::UpdateTrainingSession(
-                        ongoing_training_session.section_idx - 1,
+                        guide.section_idx,
                         guide.timer.to_timer_state(),
                     ));
                 }
@@ -989,12 +1372,43 @@ pub fn update(
             close_notification(model);
             orders.force_render_now().send_msg(Msg::ScrollToSection);
         }
+        Msg::MediaAction(action) => {
+            if let Some(guide) = &model.guide {
+                match action {
+                    MediaAction::PlayPause => {
+                        if guide.timer.is_set() {
+                            orders.send_msg(Msg::StartPauseGuideTimer);
+                        }
+                    }
+                    MediaAction::Previous => {
+                        if guide.section_idx > 0 {
+                            orders.send_msg(Msg::GoToPreviousSection);
+                        }
+                    }
+                    MediaAction::Next => {
+                        orders.send_msg(Msg::GoToNextSection);
+                    }
+                }
+            }
+        }
         Msg::GoToNextSection => {
             if let Some(guide) = &mut model.guide {
-                guide.section_idx += 1;
+                guide.section_idx = match guide.block {
+                    Some((block_idx, block_start_time))
+                        if guide.section_idx + 1 == model.form.blocks[block_idx].section_end
+                            && finish_round(
+                                &mut model.form.blocks[block_idx],
+                                block_start_time,
+                            ) =>
+                    {
+                        model.form.blocks[block_idx].section_start
+                    }
+                    _ => guide.section_idx + 1,
+                };
                 if guide.section_idx == model.form.sections.len() {
                     model.guide = None;
                     close_notification(model);
+                    unregister_media_session(model);
                     orders
                         .send_msg(Msg::PauseMetronome)
                         .notify(data::Msg::EndTrainingSession);
@@ -1005,15 +1419,16 @@ pub fn update(
                         update_metronome(&model.form.sections[guide.section_idx], orders);
                     }
 
-                    show_section_notification(model);
+                    update_guide_block(model, orders);
+                    announce_section(model, &data_model.settings);
                 }
             }
             update_guide_timer(model);
             update_streams(model, orders);
             if let Some(guide) = &mut model.guide {
-                if let Some(ongoing_training_session) = &data_model.ongoing_training_session {
+                if data_model.ongoing_training_session.is_some() {
                     orders.notify(data::Msg::UpdateTrainingSession(
-                        ongoing_training_session.section_idx + 1,
+                        guide.section_idx,
                         guide.timer.to_timer_state(),
                     ));
                 }
@@ -1026,6 +1441,33 @@ pub fn update(
                 orders.send_msg(Msg::SaveTrainingSession);
             }
         }
+        Msg::EndIntervalBlock => {
+            if let Some(guide) = &mut model.guide {
+                if let Some((block_idx, _)) = guide.block {
+                    let block = &mut model.form.blocks[block_idx];
+                    match block.block.kind {
+                        data::SectionKind::Emom { .. } => {
+                            // Skip the remaining exercises of the current round
+                            let round = block.round_of(guide.section_idx);
+                            let sections_per_round = (block.section_end - block.section_start)
+                                / block.block.rounds.max(1) as usize;
+                            guide.section_idx =
+                                block.section_start + (round + 1) * sections_per_round - 1;
+                        }
+                        data::SectionKind::ForTime { time_cap } => {
+                            #[allow(clippy::cast_possible_truncation)]
+                            let round = block.round_of(guide.section_idx) as u32;
+                            set_block_result(block, Some(round), Some(time_cap));
+                            guide.section_idx = block.section_end - 1;
+                        }
+                        _ => {
+                            guide.section_idx = block.section_end - 1;
+                        }
+                    }
+                    orders.send_msg(Msg::GoToNextSection);
+                }
+            }
+        }
         Msg::ScrollToSection => {
             if let Some(guide) = &mut model.guide {
                 let mut options = web_sys::ScrollIntoViewOptions::new();
@@ -1046,11 +1488,46 @@ pub fn update(
                     .add_hash_path_part("edit"),
             );
         }
+        Msg::ShowSubstituteExerciseDialog(section_idx, position) => {
+            model.dialog = Dialog::SubstituteExercise {
+                section_idx,
+                position,
+                search_term: String::new(),
+            };
+        }
+        Msg::CloseSubstituteExerciseDialog => {
+            model.dialog = Dialog::Hidden;
+        }
+        Msg::SubstituteSearchTermChanged(value) => {
+            if let Dialog::SubstituteExercise { search_term, .. } = &mut model.dialog {
+                *search_term = value;
+            }
+        }
+        Msg::SubstituteExercise(exercise_id) => {
+            if let Dialog::SubstituteExercise {
+                section_idx,
+                position,
+                ..
+            } = model.dialog
+            {
+                substitute_exercise(model, data_model, section_idx, position, exercise_id);
+            }
+            model.dialog = Dialog::Hidden;
+        }
+
         Msg::SaveTrainingSession => {
             model.loading = true;
             orders.notify(data::Msg::ModifyTrainingSession(
                 model.training_session_id,
                 Some(model.form.notes.clone()),
+                if model.form.session_rpe.changed || model.form.duration.changed {
+                    Some(data::SessionRPE {
+                        rpe: model.form.session_rpe.parsed,
+                        duration: model.form.duration.parsed,
+                    })
+                } else {
+                    None
+                },
                 Some(
                     model
                         .form
@@ -1070,6 +1547,7 @@ pub fn update(
                                     target_weight: e.target_weight,
                                     target_rpe: e.target_rpe,
                                     automatic: e.automatic,
+                                    set_type: e.set_type,
                                 })
                                 .collect(),
                             FormSection::Rest {
@@ -1086,6 +1564,18 @@ pub fn update(
                         })
                         .collect::<Vec<_>>(),
                 ),
+                if model.form.blocks_changed() {
+                    Some(
+                        model
+                            .form
+                            .blocks
+                            .iter()
+                            .map(FormBlock::to_interval_block)
+                            .collect(),
+                    )
+                } else {
+                    None
+                },
             ));
         }
         Msg::DataEvent(event) => {
@@ -1180,16 +1670,58 @@ pub fn update(
     }
 }
 
+/// Replace the exercise of the given set and all following sets of the same exercise.
+fn substitute_exercise(
+    model: &mut Model,
+    data_model: &data::Model,
+    section_idx: usize,
+    position: usize,
+    exercise_id: u32,
+) {
+    let FormSection::Set { exercises } = &model.form.sections[section_idx] else {
+        return;
+    };
+    let replaced_exercise_id = exercises[position].exercise_id;
+    let exercise_name = data_model
+        .exercises
+        .get(&exercise_id)
+        .map_or_else(|| format!("Exercise#{exercise_id}"), |e| e.name.clone());
+    for (idx, section) in model.form.sections.iter_mut().enumerate().skip(section_idx) {
+        if let FormSection::Set { exercises } = section {
+            for e in exercises.iter_mut().filter(|e| {
+                e.exercise_id == replaced_exercise_id
+                    && (idx > section_idx || e.position >= position)
+            }) {
+                e.exercise_id = exercise_id;
+                e.exercise_name.clone_from(&exercise_name);
+                e.substituted = true;
+            }
+        }
+    }
+    update_previous_values(
+        &mut model.form.sections,
+        &previous_sets(
+            data_model.training_sessions.get(&model.training_session_id),
+            data_model,
+        ),
+    );
+}
+
 fn update_streams(model: &mut Model, orders: &mut impl Orders<Msg>) {
     if let Some(guide) = &mut model.guide {
         guide.stream =
-            if guide.timer.is_active() {
+            if guide.timer.is_active() || guide.block.is_some() {
                 Some(orders.stream_with_handle(streams::interval(1000, || {
                     Msg::UpdateGuidedTrainingSession
                 })))
             } else {
                 None
-            }
+            };
+        common::set_media_session_playback_state(if guide.timer.is_active() {
+            "playing"
+        } else {
+            "paused"
+        });
     };
     model.timer_stream = if model.timer_dialog.stopwatch.is_active()
         || model.timer_dialog.metronome.is_active()
@@ -1223,6 +1755,17 @@ fn update_guide_timer(model: &mut Model) {
                 }
             }
             FormSection::Rest { target_time, .. } => {
+                if let Some((block_idx, block_start_time)) = guide.block {
+                    let block = &model.form.blocks[block_idx];
+                    if let (data::SectionKind::Emom { .. }, Some(deadline)) = (
+                        block.block.kind,
+                        block.deadline(guide.section_idx, block_start_time),
+                    ) {
+                        guide.timer.set((deadline - Utc::now()).num_seconds());
+                        guide.timer.start();
+                        return;
+                    }
+                }
                 if *target_time > 0 {
                     guide.timer.set(i64::from(*target_time) - elapsed_time);
                     guide.timer.start();
@@ -1232,6 +1775,92 @@ fn update_guide_timer(model: &mut Model) {
     }
 }
 
+fn update_guide_block(model: &mut Model, orders: &mut impl Orders<Msg>) {
+    if let Some(guide) = &mut model.guide {
+        let block_idx = model.form.block_of(guide.section_idx);
+        if guide.block.map(|(idx, _)| idx) != block_idx {
+            guide.block = block_idx.map(|idx| (idx, Utc::now()));
+            orders.notify(data::Msg::UpdateTrainingSessionBlock(
+                guide.block.map(|(_, start_time)| start_time),
+            ));
+        }
+    }
+}
+
+/// Record the result of a completed round and return whether another round should follow.
+fn finish_round(block: &mut FormBlock, block_start_time: DateTime<Utc>) -> bool {
+    let now = Utc::now();
+    let in_time = block
+        .deadline(block.section_end - 1, block_start_time)
+        .map_or(true, |deadline| now < deadline);
+    match block.block.kind {
+        data::SectionKind::Amrap { .. } => {
+            if in_time {
+                let rounds = block.achieved_rounds.parsed.unwrap_or(0) + 1;
+                set_block_result(block, Some(rounds), None);
+            }
+            in_time
+        }
+        data::SectionKind::ForTime { .. } => {
+            if in_time {
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let time = (now - block_start_time).num_seconds().max(0) as u32;
+                set_block_result(block, Some(block.block.rounds), Some(time));
+            }
+            false
+        }
+        data::SectionKind::Emom { .. } | data::SectionKind::Tabata { .. } => {
+            set_block_result(block, Some(block.block.rounds), None);
+            false
+        }
+        data::SectionKind::Sequential => false,
+    }
+}
+
+fn set_block_result(block: &mut FormBlock, achieved_rounds: Option<u32>, time: Option<u32>) {
+    let field = |value: Option<u32>| InputField {
+        input: value.map(|v| v.to_string()).unwrap_or_default(),
+        valid: true,
+        parsed: value,
+        changed: true,
+    };
+    block.achieved_rounds = field(achieved_rounds);
+    if time.is_some() {
+        block.time = field(time);
+    }
+}
+
+/// Short description of the state of the current interval block.
+fn block_status(model: &Model) -> Option<String> {
+    let guide = model.guide.as_ref()?;
+    let (block_idx, block_start_time) = guide.block?;
+    let block = &model.form.blocks[block_idx];
+    let mut status = vec![block.block.kind.description()];
+    match block.block.kind {
+        data::SectionKind::Amrap { .. } => {
+            status.push(format!(
+                "round {}",
+                block.achieved_rounds.parsed.unwrap_or(0) + 1
+            ));
+        }
+        _ => {
+            status.push(format!(
+                "round {} of {}",
+                block.round_of(guide.section_idx) + 1,
+                block.block.rounds
+            ));
+        }
+    }
+    if let (data::SectionKind::Amrap { .. } | data::SectionKind::ForTime { .. }, Some(deadline)) = (
+        block.block.kind,
+        block.deadline(guide.section_idx, block_start_time),
+    ) {
+        let left = (deadline - Utc::now()).num_seconds().max(0);
+        status.push(format!("{}:{:02} left", left / 60, left % 60));
+    }
+    Some(status.join(" – "))
+}
+
 fn update_metronome(form_section: &FormSection, orders: &mut impl Orders<Msg>) {
     match form_section {
         FormSection::Set { exercises } => {
@@ -1265,11 +1894,48 @@ fn close_notification(model: &mut Model) {
     }
 }
 
-fn show_section_notification(model: &mut Model) {
+/// Map the media keys of the lock screen or headphones to the controls of the guide.
+fn register_media_session(model: &mut Model, orders: &mut impl Orders<Msg>) {
+    unregister_media_session(model);
+    let msg_sender = orders.msg_sender();
+    for (actions, media_action) in [
+        (&["play", "pause"][..], MediaAction::PlayPause),
+        (&["previoustrack"][..], MediaAction::Previous),
+        (&["nexttrack"][..], MediaAction::Next),
+    ] {
+        let msg_sender = msg_sender.clone();
+        let handler = Closure::wrap(Box::new(move || {
+            msg_sender(Some(Msg::MediaAction(media_action)));
+        }) as Box<dyn FnMut()>);
+        for action in actions {
+            common::set_media_session_action_handler(
+                action,
+                Some(handler.as_ref().unchecked_ref()),
+            );
+        }
+        model.media_session_handlers.push(handler);
+    }
+}
+
+fn unregister_media_session(model: &mut Model) {
+    if model.media_session_handlers.is_empty() {
+        return;
+    }
+    for action in ["play", "pause", "previoustrack", "nexttrack"] {
+        common::set_media_session_action_handler(action, None);
+    }
+    model.media_session_handlers.clear();
+    common::set_media_session_title(None);
+    common::set_media_session_playback_state("none");
+}
+
+/// Show a notification and speak an announcement for the current section.
+fn announce_section(model: &mut Model, settings: &data::Settings) {
     if let Some(guide) = &mut model.guide {
         if guide.section_idx < model.form.sections.len() {
             let title;
             let body;
+            let speech;
             match &model.form.sections[guide.section_idx] {
                 FormSection::Set { exercises } => {
                     let exercise = &exercises[0];
@@ -1293,6 +1959,17 @@ fn show_section_notification(model: &mut Model) {
                         target = format!("Target:\n{target}\n");
                     }
                     body = Some(format!("{previously}{target}"));
+                    let spoken_target = format_spoken_set(
+                        exercise.target_reps,
+                        exercise.target_time,
+                        exercise.target_weight,
+                        exercise.target_rpe,
+                    );
+                    speech = if spoken_target.is_empty() {
+                        format!("Next: {title}")
+                    } else {
+                        format!("Next: {title}, {spoken_target}")
+                    };
                 }
                 FormSection::Rest { target_time, .. } => {
                     title = String::from("Rest");
@@ -1301,10 +1978,25 @@ fn show_section_notification(model: &mut Model) {
                     } else {
                         None
                     };
+                    speech = if *target_time > 0 {
+                        format!("Rest, {target_time} seconds")
+                    } else {
+                        title.clone()
+                    };
                 }
             }
 
+            let body = match (body, block_status(model)) {
+                (Some(body), Some(status)) => Some(format!("{status}\n{body}")),
+                (body, status) => body.or(status),
+            };
             show_notification(model, &title, body);
+            common::set_media_session_title(Some(&title));
+            common::speak(
+                &speech,
+                settings.speech_volume,
+                settings.speech_voice.as_deref(),
+            );
         }
     }
 }
@@ -1327,12 +2019,14 @@ pub fn view(model: &Model, data_model: &data::Model) -> Node<Msg> {
             ]
         } else {
             div![
+                view_substitute_exercise_dialog(model, data_model),
                 view_title(training_session, data_model),
                 if model.editing || model.guide.is_some() {
                     nodes![view_training_session_form(model, data_model)]
                 } else {
                     nodes![
                         view_table(training_session, data_model),
+                        view_session_rpe(training_session),
                         view_notes(training_session),
                         common::view_fab("edit", |_| Msg::EditTrainingSession)
                     ]
@@ -1379,49 +2073,217 @@ fn view_table(training_session: &data::TrainingSession, data_model: &data::Model
                 training_session
                     .elements
                     .iter()
-                    .map(|e| match e {
-                        data::TrainingSessionElement::Set {
-                            exercise_id,
-                            reps,
-                            time,
-                            weight,
-                            rpe,
-                            ..
-                        } => {
-                            tr![
-                                td![a![
-                                    attrs! {
-                                        At::Href => {
-                                            crate::Urls::new(&data_model.base_url)
-                                                .exercise()
-                                                .add_hash_path_part(exercise_id.to_string())
-                                        }
-                                    },
-                                    data_model.exercises.get(exercise_id).map_or_else(
-                                        || format!("Exercise#{exercise_id}"),
-                                        |e| e.name.clone()
-                                    )
-                                ]],
-                                td![
-                                    C!["is-vcentered"],
-                                    C!["has-text-centered"],
-                                    span![
-                                        style! {St::WhiteSpace => "nowrap" },
-                                        format_set(*reps, *time, *weight, *rpe)
-                                    ]
-                                ],
-                            ]
-                        }
-                        _ => {
-                            tr![td![C!["p-1"]], td![C!["p-1"]]]
+                    .enumerate()
+                    .flat_map(|(element_idx, e)| nodes![
+                        training_session
+                            .interval_blocks
+                            .iter()
+                            .find(|b| b.start == element_idx)
+                            .map_or(Node::Empty, view_block_row),
+                        match e {
+                            data::TrainingSessionElement::Set {
+                                exercise_id,
+                                reps,
+                                time,
+                                weight,
+                                rpe,
+                                set_type,
+                                ..
+                            } => {
+                                tr![
+                                    IF![not(set_type.is_working()) => C!["has-text-grey"]],
+                                    td![
+                                        a![
+                                            attrs! {
+                                                At::Href => {
+                                                    crate::Urls::new(&data_model.base_url)
+                                                        .exercise()
+                                                        .add_hash_path_part(exercise_id.to_string())
+                                                }
+                                            },
+                                            data_model.exercises.get(exercise_id).map_or_else(
+                                                || format!("Exercise#{exercise_id}"),
+                                                |e| e.name.clone()
+                                            )
+                                        ],
+                                        view_set_type_tag(*set_type)
+                                    ],
+                                    td![
+                                        C!["is-vcentered"],
+                                        C!["has-text-centered"],
+                                        span![
+                                            style! {St::WhiteSpace => "nowrap" },
+                                            format_set(*reps, *time, *weight, *rpe)
+                                        ]
+                                    ],
+                                ]
+                            }
+                            _ => {
+                                tr![td![C!["p-1"]], td![C!["p-1"]]]
+                            }
                         }
-                    })
+                    ])
                     .collect::<Vec<_>>()
             ],
         ]
     ]
 }
 
+fn view_set_type_tag(set_type: data::SetType) -> Node<Msg> {
+    let color = match set_type {
+        data::SetType::Working => return Node::Empty,
+        data::SetType::WarmUp => "is-warning",
+        data::SetType::Drop | data::SetType::BackOff => "is-info",
+        data::SetType::Failure | data::SetType::Amrap => "is-danger",
+    };
+    span![
+        C!["tag"],
+        C![color],
+        C!["is-light"],
+        C!["ml-2"],
+        set_type.name()
+    ]
+}
+
+fn view_set_type_select(exercise: &ExerciseForm, section_idx: usize, position: usize) -> Node<Msg> {
+    div![
+        C!["select"],
+        C!["is-small"],
+        C!["ml-2"],
+        C![IF![exercise.set_type_changed => "is-info"]],
+        input_ev(Ev::Change, move |v| Msg::SetTypeChanged(
+            section_idx,
+            position,
+            v
+        )),
+        select![
+            attrs! {At::from("tabindex") => -1},
+            data::SetType::ALL
+                .iter()
+                .enumerate()
+                .map(|(i, t)| {
+                    option![
+                        t.name(),
+                        attrs! {
+                            At::Value => i,
+                            At::Selected => (*t == exercise.set_type).as_at_value(),
+                        }
+                    ]
+                })
+                .collect::<Vec<_>>()
+        ]
+    ]
+}
+
+fn view_block_row(block: &data::IntervalBlock) -> Node<Msg> {
+    let mut result = vec![];
+    if let Some(rounds) = block.achieved_rounds {
+        result.push(format!("{rounds} rounds"));
+    }
+    if let Some(time) = block.time {
+        result.push(format!("{}:{:02}", time / 60, time % 60));
+    }
+    tr![
+        td![
+            C!["has-text-weight-bold"],
+            span![
+                C!["icon-text"],
+                span![C!["icon"], i![C!["fas fa-stopwatch"]]],
+                span![block.kind.description()]
+            ]
+        ],
+        td![
+            C!["is-vcentered"],
+            C!["has-text-centered"],
+            span![style! {St::WhiteSpace => "nowrap" }, result.join(", ")]
+        ],
+    ]
+}
+
+fn view_block_form(model: &Model, block_idx: usize) -> Node<Msg> {
+    let block = &model.form.blocks[block_idx];
+    let status = model
+        .guide
+        .as_ref()
+        .filter(|guide| guide.block.map_or(false, |(idx, _)| idx == block_idx))
+        .and_then(|_| block_status(model));
+    div![
+        C!["mb-3"],
+        div![
+            C!["has-text-weight-bold"],
+            C!["mb-2"],
+            span![
+                C!["icon-text"],
+                span![C!["icon"], i![C!["fas fa-stopwatch"]]],
+                span![block.block.kind.description()]
+            ]
+        ],
+        IF![status.is_some() => p![C!["mb-2"], status]],
+        div![
+            C!["field"],
+            C!["is-grouped"],
+            div![
+                C!["control"],
+                label![C!["label"], "Rounds"],
+                input_ev(Ev::Input, move |v| Msg::AchievedRoundsChanged(block_idx, v)),
+                input![
+                    C!["input"],
+                    C!["has-text-right"],
+                    C![IF![not(block.achieved_rounds.valid) => "is-danger"]],
+                    C![IF![block.achieved_rounds.changed => "is-info"]],
+                    attrs! {
+                        At::Type => "number",
+                        At::Min => 0,
+                        At::Step => 1,
+                        At::Size => 3,
+                        At::Placeholder => block.block.rounds,
+                        At::Value => block.achieved_rounds.input,
+                    }
+                ],
+            ],
+            IF![
+                matches!(block.block.kind, data::SectionKind::ForTime { .. }) =>
+                div![
+                    C!["control"],
+                    label![C!["label"], "Time (s)"],
+                    input_ev(Ev::Input, move |v| Msg::BlockTimeChanged(block_idx, v)),
+                    input![
+                        C!["input"],
+                        C!["has-text-right"],
+                        C![IF![not(block.time.valid) => "is-danger"]],
+                        C![IF![block.time.changed => "is-info"]],
+                        attrs! {
+                            At::Type => "number",
+                            At::Min => 0,
+                            At::Step => 1,
+                            At::Size => 4,
+                            At::Value => block.time.input,
+                        }
+                    ],
+                ]
+            ],
+        ]
+    ]
+}
+
+fn view_session_rpe(training_session: &data::TrainingSession) -> Node<Msg> {
+    if training_session.session_rpe.is_none() && training_session.duration.is_none() {
+        return empty![];
+    }
+    div![
+        C!["m-3"],
+        h1![C!["title"], C!["is-5"], "Session"],
+        p![
+            "RPE: ",
+            common::value_or_dash(training_session.session_rpe),
+            ", Duration: ",
+            common::value_or_dash(training_session.duration),
+            " min, Load: ",
+            common::value_or_dash(training_session.session_load()),
+        ]
+    ]
+}
+
 fn view_notes(training_session: &data::TrainingSession) -> Node<Msg> {
     if let Some(notes) = &training_session.notes {
         if notes.is_empty() {
@@ -1434,6 +2296,79 @@ fn view_notes(training_session: &data::TrainingSession) -> Node<Msg> {
     }
 }
 
+fn view_plate_breakdown(
+    exercise_form: &ExerciseForm,
+    section_idx: usize,
+    bars: &BTreeMap<u32, usize>,
+    data_model: &data::Model,
+) -> Node<Msg> {
+    let is_barbell_exercise = data_model
+        .exercises
+        .get(&exercise_form.exercise_id)
+        .map_or(false, |e| {
+            e.metadata.equipment == Some(data::Equipment::Barbell)
+        });
+    let Some(weight) = exercise_form
+        .weight
+        .parsed
+        .or(exercise_form.target_weight)
+        .filter(|w| *w > 0.0 && is_barbell_exercise)
+    else {
+        return empty![];
+    };
+    let equipment = data_model.barbell_equipment();
+    let Some(bar) = equipment
+        .bars
+        .get(bars.get(&exercise_form.exercise_id).copied().unwrap_or(0))
+        .or(equipment.bars.first())
+    else {
+        return empty![];
+    };
+    let breakdown = equipment.plate_breakdown(*bar, weight);
+    let closest = breakdown.weight();
+    let exercise_id = exercise_form.exercise_id;
+    let position = exercise_form.position;
+    p![
+        C!["mb-2"],
+        span![
+            C!["icon-text"],
+            span![C!["icon"], i![C!["fas fa-weight-hanging"]]],
+            a![
+                attrs! {At::Title => "Change bar"},
+                ev(Ev::Click, move |_| Msg::ChangeBar(exercise_id)),
+                format!("{bar} kg bar")
+            ],
+            IF![equipment.collars > 0.0 => span![C!["ml-1"], format!("+ {} kg collars", equipment.collars)]],
+            span![
+                C!["ml-1"],
+                if breakdown.plates.is_empty() {
+                    String::new()
+                } else {
+                    format!(
+                        "+ {} per side",
+                        breakdown
+                            .plates
+                            .iter()
+                            .map(ToString::to_string)
+                            .collect::<Vec<_>>()
+                            .join(" + ")
+                    )
+                }
+            ],
+            IF![(closest - weight).abs() > 0.01 => span![
+                C!["ml-2"],
+                C!["has-text-warning-dark"],
+                a![
+                    C!["has-text-warning-dark"],
+                    attrs! {At::Title => "Closest achievable weight"},
+                    ev(Ev::Click, move |_| Msg::WeightChanged(section_idx, position, closest.to_string())),
+                    format!("≈ {closest} kg")
+                ]
+            ]],
+        ]
+    ]
+}
+
 fn view_training_session_form(model: &Model, data_model: &data::Model) -> Node<Msg> {
     let valid = model.form.valid();
     let save_disabled = not(model.form.changed()) || not(valid);
@@ -1455,6 +2390,15 @@ fn view_training_session_form(model: &Model, data_model: &data::Model) -> Node<M
             }
         }
 
+        if let Some(block_idx) = model
+            .form
+            .blocks
+            .iter()
+            .position(|b| b.section_start == section_idx)
+        {
+            form.push(view_block_form(model, block_idx));
+        }
+
         match section {
             FormSection::Set {
                 exercises: exercise_forms,
@@ -1605,6 +2549,18 @@ fn view_training_session_form(model: &Model, data_model: &data::Model) -> Node<M
                                             },
                                             &s.exercise_name
                                         ],
+                                        view_set_type_select(s, section_idx, position),
+                                        a![
+                                            C!["icon"],
+                                            C!["ml-2"],
+                                            C![if s.substituted { "has-text-info" } else { "has-text-grey" }],
+                                            attrs! {
+                                                At::Title => "Substitute",
+                                                At::from("tabindex") => -1
+                                            },
+                                            ev(Ev::Click, move |_| Msg::ShowSubstituteExerciseDialog(section_idx, position)),
+                                            i![C!["fas fa-right-left"]]
+                                        ],
                                     ],
                                     if let Some(guide) = &model.guide {
                                         if guide.timer.is_set() && guide.section_idx == section_idx {
@@ -1615,9 +2571,12 @@ fn view_training_session_form(model: &Model, data_model: &data::Model) -> Node<M
                                     } else {
                                         input_fields
                                     },
+                                    view_plate_breakdown(s, section_idx, &model.bars, data_model),
                                     {
                                         let target = format_set(s.target_reps, s.target_time, s.target_weight, s.target_rpe);
                                         let previous = format_set(s.prev_reps, s.prev_time, s.prev_weight, s.prev_rpe);
+                                        let suggestion = rpe_suggestion(&model.form.sections, section_idx, position)
+                                            .map(|(reps, weight)| format_set(reps, None, weight, s.target_rpe));
                                         p![
                                             IF![not(target.is_empty()) =>
                                                 span![
@@ -1641,6 +2600,20 @@ fn view_training_session_form(model: &Model, data_model: &data::Model) -> Node<M
                                                     ]
                                                 ]
                                             ],
+                                            if let Some(suggestion) = suggestion {
+                                                span![
+                                                    C!["icon-text"],
+                                                    C!["mr-4"],
+                                                    attrs! {At::Title => "Adjusted to the target RPE based on the previous set"},
+                                                    span![C!["icon"], C!["has-text-info"], i![C!["fas fa-gauge"]]],
+                                                    a![
+                                                        ev(Ev::Click, move |_| Msg::AcceptSuggestion(section_idx, position)),
+                                                        suggestion
+                                                    ]
+                                                ]
+                                            } else {
+                                                empty![]
+                                            },
                                             IF![
                                                 s.automatic =>
                                                 span![
@@ -1752,6 +2725,42 @@ fn view_training_session_form(model: &Model, data_model: &data::Model) -> Node<M
                 At::OnKeyPress => "if (event.which == 13) return false;"
             },
             &form,
+            div![
+                C!["field"],
+                C!["is-grouped"],
+                div![
+                    C!["control"],
+                    label![C!["label"], "Session RPE"],
+                    input_ev(Ev::Input, Msg::SessionRPEChanged),
+                    input![
+                        C!["input"],
+                        C!["has-text-right"],
+                        C![IF![not(model.form.session_rpe.valid) => "is-danger"]],
+                        C![IF![model.form.session_rpe.changed => "is-info"]],
+                        attrs! {
+                            At::from("inputmode") => "numeric",
+                            At::Size => 3,
+                            At::Value => model.form.session_rpe.input,
+                        },
+                    ],
+                ],
+                div![
+                    C!["control"],
+                    label![C!["label"], "Duration (min)"],
+                    input_ev(Ev::Input, Msg::DurationChanged),
+                    input![
+                        C!["input"],
+                        C!["has-text-right"],
+                        C![IF![not(model.form.duration.valid) => "is-danger"]],
+                        C![IF![model.form.duration.changed => "is-info"]],
+                        attrs! {
+                            At::from("inputmode") => "numeric",
+                            At::Size => 3,
+                            At::Value => model.form.duration.input,
+                        },
+                    ],
+                ],
+            ],
             div![
                 C!["field"],
                 label![C!["label"], "Notes"],
@@ -1782,6 +2791,78 @@ fn view_training_session_form(model: &Model, data_model: &data::Model) -> Node<M
     ]
 }
 
+fn view_substitute_exercise_dialog(model: &Model, data_model: &data::Model) -> Node<Msg> {
+    let Dialog::SubstituteExercise {
+        section_idx,
+        position,
+        search_term,
+    } = &model.dialog
+    else {
+        return empty![];
+    };
+    let FormSection::Set { exercises } = &model.form.sections[*section_idx] else {
+        return empty![];
+    };
+    let exercise_form = &exercises[*position];
+    let exercises = if search_term.is_empty() {
+        data_model
+            .exercises
+            .get(&exercise_form.exercise_id)
+            .map(|e| e.alternatives(&data_model.exercises))
+            .unwrap_or_default()
+    } else {
+        let mut exercises = data_model
+            .exercises
+            .values()
+            .filter(|e| {
+                e.id != exercise_form.exercise_id
+                    && e.name
+                        .to_lowercase()
+                        .contains(search_term.to_lowercase().trim())
+            })
+            .collect::<Vec<_>>();
+        exercises.sort_by(|a, b| a.name.cmp(&b.name));
+        exercises
+    };
+    common::view_dialog(
+        "primary",
+        &format!("Substitute {}", exercise_form.exercise_name),
+        nodes![
+            div![
+                C!["field"],
+                common::view_search_box(search_term, Msg::SubstituteSearchTermChanged),
+            ],
+            IF![exercises.is_empty() => p![
+                C!["has-text-grey"],
+                if search_term.is_empty() {
+                    "No alternatives defined"
+                } else {
+                    "No matching exercises"
+                }
+            ]],
+            div![
+                C!["table-container"],
+                table![
+                    C!["table"],
+                    C!["is-fullwidth"],
+                    C!["is-hoverable"],
+                    tbody![exercises
+                        .iter()
+                        .map(|e| {
+                            let id = e.id;
+                            tr![td![a![
+                                ev(Ev::Click, move |_| Msg::SubstituteExercise(id)),
+                                &e.name,
+                            ]]]
+                        })
+                        .collect::<Vec<_>>()],
+                ]
+            ],
+        ],
+        &ev(Ev::Click, |_| Msg::CloseSubstituteExerciseDialog),
+    )
+}
+
 fn view_guide_timer(guide: &Guide) -> Node<Msg> {
     div![
         C!["is-size-1"],
@@ -1983,6 +3064,108 @@ fn view_timer_dialog(dialog: &SMTDialog) -> Node<Msg> {
     ]
 }
 
+/// Suggest reps and weight for a set which has not been entered yet. The suggestion is based on
+/// the last performed set of the same exercise and aims at reaching the target RPE. No suggestion
+/// is made if the RPE of the last performed set matched its target RPE.
+fn rpe_suggestion(
+    sections: &[FormSection],
+    section_idx: usize,
+    exercise_idx: usize,
+) -> Option<(Option<u32>, Option<f32>)> {
+    let FormSection::Set { exercises } = &sections[section_idx] else {
+        return None;
+    };
+    let set = &exercises[exercise_idx];
+    if set.reps.changed || set.weight.changed || set.rpe.changed || not(set.set_type.is_working()) {
+        return None;
+    }
+    let target_rpe = set.target_rpe?;
+    let previous_set = sections[..section_idx]
+        .iter()
+        .flat_map(|s| match s {
+            FormSection::Set { exercises } => exercises.as_slice(),
+            _ => &[],
+        })
+        .chain(&exercises[..exercise_idx])
+        .rev()
+        .find(|e| {
+            e.exercise_id == set.exercise_id
+                && e.set_type.is_working()
+                && e.reps.parsed.is_some()
+                && e.rpe.parsed.is_some()
+        })?;
+    let (reps, rpe) = (previous_set.reps.parsed?, previous_set.rpe.parsed?);
+    if previous_set
+        .target_rpe
+        .map_or(true, |r| (r - rpe).abs() < f32::EPSILON)
+    {
+        return None;
+    }
+    match (set.target_weight, previous_set.weight.parsed) {
+        (Some(target_weight), Some(weight)) if weight > 0.0 => {
+            let target_reps = set.target_reps.unwrap_or(reps);
+            let suggested_weight =
+                data::rpe_adjusted_weight(reps, weight, rpe, target_reps, target_rpe)?;
+            if (suggested_weight - target_weight).abs() < f32::EPSILON {
+                return None;
+            }
+            Some((Some(target_reps), Some(suggested_weight)))
+        }
+        (None, None) => {
+            let suggested_reps = data::rpe_adjusted_reps(reps, rpe, target_rpe)?;
+            if set.target_reps == Some(suggested_reps) {
+                return None;
+            }
+            Some((Some(suggested_reps), None))
+        }
+        _ => None,
+    }
+}
+
+/// Time in seconds before the end of a timer at which the remaining time is announced.
+const COUNTDOWN_ANNOUNCEMENT: i64 = 10;
+
+fn format_spoken_set(
+    reps: Option<u32>,
+    time: Option<u32>,
+    weight: Option<f32>,
+    rpe: Option<f32>,
+) -> String {
+    let mut parts = vec![];
+
+    if let Some(reps) = reps {
+        parts.push(if reps == 1 {
+            String::from("1 rep")
+        } else {
+            format!("{reps} reps")
+        });
+    }
+
+    if let Some(time) = time {
+        parts.push(format!("{time} seconds"));
+    }
+
+    let mut result = parts.join(", ");
+
+    if let Some(weight) = weight {
+        if result.is_empty() {
+            result = format!("{weight} kilograms");
+        } else {
+            result = format!("{result} at {weight} kilograms");
+        }
+    }
+
+    if let Some(rpe) = rpe {
+        if result.is_empty() {
+            result = format!("RPE {rpe}");
+        } else {
+            result = format!("{result}, RPE {rpe}");
+        }
+    }
+
+    result
+}
+
 fn format_set(
     reps: Option<u32>,
     time: Option<u32>,
@@ -0,0 +1,271 @@
+use crate::data;
+
+/// Predefined routine. The exercise IDs of the activities refer to the position in `exercises`.
+pub struct Template {
+    pub program: &'static str,
+    pub name: &'static str,
+    pub notes: &'static str,
+    pub exercises: Vec<&'static str>,
+    pub sections: Vec<data::RoutinePart>,
+}
+
+impl Template {
+    /// Replace the exercise positions by the IDs of the corresponding exercises.
+    pub fn instantiate(&self, exercise_ids: &[u32]) -> Vec<data::RoutinePart> {
+        map_exercises(&self.sections, exercise_ids)
+    }
+}
+
+fn map_exercises(parts: &[data::RoutinePart], exercise_ids: &[u32]) -> Vec<data::RoutinePart> {
+    parts
+        .iter()
+        .map(|part| match part {
+            data::RoutinePart::RoutineSection {
+                rounds,
+                parts,
+                kind,
+            } => data::RoutinePart::RoutineSection {
+                rounds: *rounds,
+                parts: map_exercises(parts, exercise_ids),
+                kind: *kind,
+            },
+            data::RoutinePart::RoutineActivity {
+                exercise_id,
+                reps,
+                time,
+                weight,
+                rpe,
+                automatic,
+                options,
+            } => data::RoutinePart::RoutineActivity {
+                exercise_id: exercise_id.map(|idx| exercise_ids[idx as usize]),
+                reps: *reps,
+                time: *time,
+                weight: *weight,
+                rpe: *rpe,
+                automatic: *automatic,
+                options: options.clone(),
+            },
+        })
+        .collect()
+}
+
+pub fn all() -> Vec<Template> {
+    vec![
+        TemplateBuilder::new("5×5", "5×5 A", "Alternate with 5×5 B, three times a week.")
+            .sets("Squat", 5, 5, 180)
+            .sets("Bench Press", 5, 5, 180)
+            .sets("Barbell Row", 5, 5, 180)
+            .build(),
+        TemplateBuilder::new("5×5", "5×5 B", "Alternate with 5×5 A, three times a week.")
+            .sets("Squat", 5, 5, 180)
+            .sets("Overhead Press", 5, 5, 180)
+            .sets("Deadlift", 1, 5, 180)
+            .build(),
+        TemplateBuilder::new("Push/Pull/Legs", "Push", "")
+            .sets("Bench Press", 4, 8, 120)
+            .sets("Overhead Press", 3, 10, 120)
+            .sets("Incline Dumbbell Press", 3, 10, 90)
+            .sets("Lateral Raise", 3, 15, 60)
+            .sets("Triceps Pushdown", 3, 12, 60)
+            .build(),
+        TemplateBuilder::new("Push/Pull/Legs", "Pull", "")
+            .sets("Deadlift", 3, 5, 180)
+            .sets("Pull-up", 3, 8, 120)
+            .sets("Barbell Row", 3, 10, 120)
+            .sets("Face Pull", 3, 15, 60)
+            .sets("Biceps Curl", 3, 12, 60)
+            .build(),
+        TemplateBuilder::new("Push/Pull/Legs", "Legs", "")
+            .sets("Squat", 4, 8, 180)
+            .sets("Romanian Deadlift", 3, 10, 120)
+            .sets("Leg Press", 3, 12, 90)
+            .sets("Leg Curl", 3, 12, 60)
+            .sets("Calf Raise", 4, 15, 60)
+            .build(),
+        TemplateBuilder::new("Upper/Lower", "Upper", "Alternate with Lower.")
+            .sets("Bench Press", 4, 6, 150)
+            .sets("Barbell Row", 4, 8, 120)
+            .sets("Overhead Press", 3, 10, 120)
+            .sets("Pull-up", 3, 8, 120)
+            .sets("Biceps Curl", 2, 12, 60)
+            .sets("Triceps Pushdown", 2, 12, 60)
+            .build(),
+        TemplateBuilder::new("Upper/Lower", "Lower", "Alternate with Upper.")
+            .sets("Squat", 4, 6, 180)
+            .sets("Romanian Deadlift", 3, 8, 150)
+            .sets("Lunge", 3, 10, 90)
+            .sets("Leg Curl", 3, 12, 60)
+            .sets("Calf Raise", 3, 15, 60)
+            .build(),
+        TemplateBuilder::new(
+            "Full-body beginner",
+            "Full-body beginner",
+            "Two to three times a week with at least one rest day in between.",
+        )
+        .sets("Goblet Squat", 3, 10, 90)
+        .sets("Dumbbell Bench Press", 3, 10, 90)
+        .sets("Dumbbell Row", 3, 10, 90)
+        .sets("Romanian Deadlift", 2, 10, 90)
+        .timed_sets("Plank", 3, 30, 60)
+        .build(),
+        TemplateBuilder::new(
+            "Bodyweight basics",
+            "Bodyweight basics",
+            "No equipment needed.",
+        )
+        .circuit(
+            3,
+            120,
+            &[
+                ("Squat", 15, 0),
+                ("Push-up", 10, 0),
+                ("Lunge", 10, 0),
+                ("Glute Bridge", 15, 0),
+                ("Plank", 0, 30),
+            ],
+        )
+        .build(),
+    ]
+}
+
+struct TemplateBuilder {
+    template: Template,
+}
+
+impl TemplateBuilder {
+    fn new(program: &'static str, name: &'static str, notes: &'static str) -> Self {
+        TemplateBuilder {
+            template: Template {
+                program,
+                name,
+                notes,
+                exercises: vec![],
+                sections: vec![],
+            },
+        }
+    }
+
+    fn exercise(&mut self, name: &'static str) -> u32 {
+        let exercises = &mut self.template.exercises;
+        let position = exercises
+            .iter()
+            .position(|e| *e == name)
+            .unwrap_or_else(|| {
+                exercises.push(name);
+                exercises.len() - 1
+            });
+        u32::try_from(position).unwrap()
+    }
+
+    fn activity(&mut self, name: &'static str, reps: u32, time: u32) -> data::RoutinePart {
+        data::RoutinePart::RoutineActivity {
+            exercise_id: Some(self.exercise(name)),
+            reps,
+            time,
+            weight: 0.0,
+            rpe: 0.0,
+            automatic: reps == 0 && time > 0,
+            options: data::ActivityOptions::default(),
+        }
+    }
+
+    fn section(mut self, rounds: u32, parts: Vec<data::RoutinePart>) -> Self {
+        self.template
+            .sections
+            .push(data::RoutinePart::RoutineSection {
+                rounds,
+                parts,
+                kind: data::SectionKind::Sequential,
+            });
+        self
+    }
+
+    /// Add a section of sets with a rest after each set.
+    fn sets(mut self, name: &'static str, sets: u32, reps: u32, rest: u32) -> Self {
+        let parts = vec![self.activity(name, reps, 0), rest_activity(rest)];
+        self.section(sets, parts)
+    }
+
+    /// Add a section of sets with a duration instead of reps.
+    fn timed_sets(mut self, name: &'static str, sets: u32, time: u32, rest: u32) -> Self {
+        let parts = vec![self.activity(name, 0, time), rest_activity(rest)];
+        self.section(sets, parts)
+    }
+
+    /// Add a section of consecutive exercises followed by a rest.
+    fn circuit(mut self, rounds: u32, rest: u32, exercises: &[(&'static str, u32, u32)]) -> Self {
+        let mut parts = exercises
+            .iter()
+            .map(|(name, reps, time)| self.activity(name, *reps, *time))
+            .collect::<Vec<_>>();
+        parts.push(rest_activity(rest));
+        self.section(rounds, parts)
+    }
+
+    fn build(self) -> Template {
+        self.template
+    }
+}
+
+fn rest_activity(time: u32) -> data::RoutinePart {
+    data::RoutinePart::RoutineActivity {
+        exercise_id: None,
+        reps: 0,
+        time,
+        weight: 0.0,
+        rpe: 0.0,
+        automatic: true,
+        options: data::ActivityOptions::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_templates() {
+        let templates = all();
+        assert!(templates.len() >= 9);
+        for template in &templates {
+            let mut exercise_ids = vec![];
+            data::Routine {
+                id: 0,
+                name: String::new(),
+                notes: None,
+                sections: template.sections.clone(),
+            }
+            .exercises()
+            .iter()
+            .for_each(|id| exercise_ids.push(*id as usize));
+            assert!(exercise_ids.iter().all(|id| *id < template.exercises.len()));
+            assert_eq!(exercise_ids.len(), template.exercises.len());
+        }
+
+        let template = &templates[1];
+        assert_eq!(
+            template.exercises,
+            vec!["Squat", "Overhead Press", "Deadlift"]
+        );
+        assert_eq!(
+            template.instantiate(&[7, 8, 9])[2],
+            data::RoutinePart::RoutineSection {
+                rounds: 1,
+                parts: vec![
+                    data::RoutinePart::RoutineActivity {
+                        exercise_id: Some(9),
+                        reps: 5,
+                        time: 0,
+                        weight: 0.0,
+                        rpe: 0.0,
+                        automatic: false,
+                        options: data::ActivityOptions::default(),
+                    },
+                    rest_activity(180)
+                ],
+                kind: data::SectionKind::Sequential,
+            }
+        );
+    }
+}
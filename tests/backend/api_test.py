@@ -3,6 +3,7 @@ from __future__ import annotations
 from collections.abc import Generator
 from http import HTTPStatus
 from pathlib import Path
+from typing import Any, Optional
 
 import pytest
 from werkzeug.test import Client, TestResponse as Response
@@ -46,9 +47,19 @@ def delete_session(client: Client) -> Response:
         ("get", "/api/exercises"),
         ("post", "/api/exercises"),
         ("put", "/api/exercises/1"),
+        ("post", "/api/exercises/1/merge"),
         ("get", "/api/routines"),
         ("post", "/api/routines"),
         ("put", "/api/routines/1"),
+        ("get", "/api/routine_versions"),
+        ("get", "/api/programs"),
+        ("post", "/api/programs"),
+        ("put", "/api/programs/1"),
+        ("get", "/api/schedule"),
+        ("put", "/api/schedule"),
+        ("delete", "/api/schedule"),
+        ("get", "/api/barbell_equipment"),
+        ("put", "/api/barbell_equipment"),
         ("get", "/api/workouts"),
         ("post", "/api/workouts"),
     ],
@@ -74,8 +85,13 @@ def test_session_required(client: Client, method: str, route: str) -> None:
         ("put", "/api/period/2002-02-22"),
         ("post", "/api/exercises"),
         ("put", "/api/exercises/1"),
+        ("post", "/api/exercises/1/merge"),
         ("post", "/api/routines"),
         ("put", "/api/routines/1"),
+        ("post", "/api/programs"),
+        ("put", "/api/programs/1"),
+        ("put", "/api/schedule"),
+        ("put", "/api/barbell_equipment"),
         ("post", "/api/workouts"),
     ],
 )
@@ -104,12 +120,187 @@ def test_json_required(client: Client, method: str, route: str) -> None:
         ("put", "/api/period/2002-02-22", {"invalid": "data"}),
         ("post", "/api/exercises", {"invalid": "data"}),
         ("put", "/api/exercises/1", {"invalid": "data"}),
+        ("put", "/api/exercises/1", {"name": "Exercise 1", "alternatives": [2]}),
+        ("put", "/api/exercises/1", {"name": "Exercise 1", "movement_pattern": "Invalid"}),
+        ("post", "/api/exercises", {"name": "Exercise 4", "primary_muscles": ["Invalid"]}),
+        ("post", "/api/exercises/1/merge", {"invalid": "data"}),
+        ("post", "/api/exercises/1/merge", {"exercise_ids": [2]}),
+        ("post", "/api/exercises/1/merge", {"exercise_ids": [1, 3]}),
         ("post", "/api/routines", {"invalid": "data"}),
         ("put", "/api/routines/1", {"invalid": "data"}),
         ("patch", "/api/routines/1", {"sections": [{"invalid": "data"}]}),
+        (
+            "patch",
+            "/api/routines/1",
+            {"sections": [{"rounds": 1, "kind": {"Invalid": {}}, "parts": []}]},
+        ),
+        (
+            "patch",
+            "/api/routines/1",
+            {
+                "sections": [
+                    {
+                        "rounds": 1,
+                        "kind": "Sequential",
+                        "parts": [
+                            {
+                                "exercise_id": 1,
+                                "reps": 0,
+                                "time": 0,
+                                "weight": 0.0,
+                                "rpe": 0.0,
+                                "automatic": False,
+                                "set_type": "Invalid",
+                                "auto_warm_up": False,
+                                "relative_weight": None,
+                                "progression": None,
+                            }
+                        ],
+                    }
+                ]
+            },
+        ),
+        (
+            "patch",
+            "/api/routines/1",
+            {
+                "sections": [
+                    {
+                        "rounds": 1,
+                        "kind": "Sequential",
+                        "parts": [
+                            {
+                                "exercise_id": 1,
+                                "reps": 0,
+                                "time": 0,
+                                "weight": 0.0,
+                                "rpe": 0.0,
+                                "automatic": False,
+                                "set_type": "Working",
+                                "auto_warm_up": False,
+                                "relative_weight": {"percentage": 80.0, "basis": "Invalid"},
+                                "progression": None,
+                            }
+                        ],
+                    }
+                ]
+            },
+        ),
+        (
+            "patch",
+            "/api/routines/1",
+            {
+                "sections": [
+                    {
+                        "rounds": 1,
+                        "kind": "Sequential",
+                        "parts": [
+                            {
+                                "exercise_id": 1,
+                                "reps": 0,
+                                "time": 0,
+                                "weight": 0.0,
+                                "rpe": 0.0,
+                                "automatic": False,
+                                "set_type": "Working",
+                                "auto_warm_up": False,
+                                "relative_weight": None,
+                                "progression": {"Invalid": {"increment": 2.5}},
+                            }
+                        ],
+                    }
+                ]
+            },
+        ),
+        (
+            "patch",
+            "/api/routines/1",
+            {
+                "sections": [
+                    {
+                        "rounds": 1,
+                        "kind": "Sequential",
+                        "parts": [
+                            {
+                                "exercise_id": 1,
+                                "reps": 0,
+                                "time": 0,
+                                "weight": 0.0,
+                                "rpe": 0.0,
+                                "automatic": False,
+                                "set_type": "Working",
+                                "auto_warm_up": False,
+                                "relative_weight": {"percentage": 80.0, "basis": "TrainingMax"},
+                                "progression": {"Linear": {"increment": 2.5}},
+                            }
+                        ],
+                    }
+                ]
+            },
+        ),
+        ("post", "/api/programs", {"invalid": "data"}),
+        ("put", "/api/programs/1", {"invalid": "data"}),
+        (
+            "post",
+            "/api/programs",
+            {
+                "name": "P2",
+                "weeks": [{"routines": [2], "weight_percentage": 100.0, "rpe_offset": 0.0}],
+                "position": None,
+                "weight_percentages": {},
+            },
+        ),
+        (
+            "put",
+            "/api/programs/1",
+            {
+                "name": "P1",
+                "weeks": [{"routines": [1], "weight_percentage": 100.0, "rpe_offset": 0.0}],
+                "position": None,
+                "weight_percentages": {"2": 100.0},
+            },
+        ),
+        ("put", "/api/schedule", {"invalid": "data"}),
+        (
+            "put",
+            "/api/schedule",
+            {"start": "2002-02-20", "kind": {"Weekdays": [1, None, 3, None, None, None]}},
+        ),
+        (
+            "put",
+            "/api/schedule",
+            {"start": "2002-02-20", "kind": {"Rotation": {"interval": 2, "routines": [1, 2]}}},
+        ),
+        ("put", "/api/barbell_equipment", {"invalid": "data"}),
+        (
+            "put",
+            "/api/barbell_equipment",
+            {"bars": [20.0], "plates": [{"weight": 20.0}], "collars": 0.0},
+        ),
         ("post", "/api/workouts", {"invalid": "data"}),
         ("put", "/api/workouts/1", {"invalid": "data"}),
         ("patch", "/api/workouts/1", {"elements": [{"invalid": "data"}]}),
+        (
+            "patch",
+            "/api/workouts/1",
+            {
+                "elements": [
+                    {
+                        "exercise_id": 1,
+                        "reps": 10,
+                        "time": None,
+                        "weight": None,
+                        "rpe": None,
+                        "target_reps": None,
+                        "target_time": None,
+                        "target_weight": None,
+                        "target_rpe": None,
+                        "set_type": "Invalid",
+                        "automatic": False,
+                    }
+                ]
+            },
+        ),
     ],
 )
 def test_invalid_data(client: Client, method: str, route: str, data: object) -> None:
@@ -280,6 +471,164 @@ def test_delete_user(client: Client) -> None:
     assert not resp.data
 
 
+def test_read_schedule(client: Client) -> None:
+    tests.utils.init_db_users()
+
+    assert create_session(client).status_code == HTTPStatus.OK
+
+    resp = client.get("/api/schedule")
+
+    assert resp.status_code == HTTPStatus.OK
+    assert resp.json is None
+
+    tests.utils.clear_db()
+    tests.utils.init_db_data()
+
+    assert create_session(client).status_code == HTTPStatus.OK
+
+    resp = client.get("/api/schedule")
+
+    assert resp.status_code == HTTPStatus.OK
+    assert resp.json == {
+        "start": "2002-02-20",
+        "kind": {"Weekdays": [1, None, 3, None, None, None, None]},
+    }
+
+    assert create_session(client, 2).status_code == HTTPStatus.OK
+
+    resp = client.get("/api/schedule")
+
+    assert resp.status_code == HTTPStatus.OK
+    assert resp.json == {
+        "start": "2002-02-20",
+        "kind": {"Rotation": {"interval": 2, "routines": [2, 4]}},
+    }
+
+
+def test_replace_schedule(client: Client) -> None:
+    data = {
+        "start": "2002-02-24",
+        "kind": {"Rotation": {"interval": 3, "routines": [3, 1]}},
+    }
+
+    tests.utils.init_db_data()
+
+    assert create_session(client).status_code == HTTPStatus.OK
+
+    resp = client.put("/api/schedule", json=data)
+
+    assert resp.status_code == HTTPStatus.OK
+    assert resp.json == data
+
+    resp = client.get("/api/schedule")
+
+    assert resp.status_code == HTTPStatus.OK
+    assert resp.json == data
+
+    resp = client.put(
+        "/api/schedule",
+        json={"start": "2002-02-24", "kind": {"Rotation": {"interval": 0, "routines": [1]}}},
+    )
+
+    assert resp.status_code == HTTPStatus.CONFLICT
+    assert resp.json
+
+
+def test_replace_schedule_without_schedule(client: Client) -> None:
+    data = {
+        "start": "2002-02-24",
+        "kind": {"Weekdays": [None, None, None, None, None, 1, 3]},
+    }
+
+    tests.utils.init_db_data()
+
+    assert create_session(client).status_code == HTTPStatus.OK
+
+    resp = client.delete("/api/schedule")
+
+    assert resp.status_code == HTTPStatus.NO_CONTENT
+
+    resp = client.put("/api/schedule", json=data)
+
+    assert resp.status_code == HTTPStatus.OK
+    assert resp.json == data
+
+
+def test_delete_schedule(client: Client) -> None:
+    tests.utils.init_db_data()
+
+    assert create_session(client).status_code == HTTPStatus.OK
+
+    resp = client.delete("/api/schedule")
+
+    assert resp.status_code == HTTPStatus.NO_CONTENT
+    assert not resp.data
+
+    resp = client.get("/api/schedule")
+
+    assert resp.status_code == HTTPStatus.OK
+    assert resp.json is None
+
+    resp = client.delete("/api/schedule")
+
+    assert resp.status_code == HTTPStatus.NOT_FOUND
+    assert not resp.data
+
+
+def test_read_barbell_equipment(client: Client) -> None:
+    tests.utils.init_db_data()
+
+    assert create_session(client).status_code == HTTPStatus.OK
+
+    resp = client.get("/api/barbell_equipment")
+
+    assert resp.status_code == HTTPStatus.OK
+    assert resp.json == {
+        "bars": [20.0, 10.0],
+        "plates": [
+            {"weight": 20.0, "count": 2},
+            {"weight": 10.0, "count": 2},
+            {"weight": 1.25, "count": 1},
+        ],
+        "collars": 2.5,
+    }
+
+    assert create_session(client, 2).status_code == HTTPStatus.OK
+
+    resp = client.get("/api/barbell_equipment")
+
+    assert resp.status_code == HTTPStatus.OK
+    assert resp.json is None
+
+
+@pytest.mark.parametrize("user_id", [1, 2])
+def test_replace_barbell_equipment(client: Client, user_id: int) -> None:
+    data = {
+        "bars": [15.0],
+        "plates": [{"weight": 25.0, "count": 4}, {"weight": 2.5, "count": 2}],
+        "collars": 0.0,
+    }
+
+    tests.utils.init_db_data()
+
+    assert create_session(client, user_id).status_code == HTTPStatus.OK
+
+    resp = client.put("/api/barbell_equipment", json=data)
+
+    assert resp.status_code == HTTPStatus.OK
+    assert resp.json == data
+
+    resp = client.get("/api/barbell_equipment")
+
+    assert resp.status_code == HTTPStatus.OK
+    assert resp.json == data
+
+    resp = client.put("/api/barbell_equipment", json={**data, "collars": -1.0})
+
+    assert resp.status_code == HTTPStatus.CONFLICT
+    assert resp.json
+
+
 @pytest.mark.parametrize(
     ("user_id", "route", "data"),
     [
@@ -331,9 +680,39 @@ def test_delete_user(client: Client) -> None:
             1,
             "/api/exercises",
             [
-                {"id": 1, "name": "Exercise 1"},
-                {"id": 3, "name": "Exercise 2"},
-                {"id": 5, "name": "Unused Exercise"},
+                {
+                    "id": 1,
+                    "name": "Exercise 1",
+                    "movement_pattern": "Push",
+                    "equipment": "Barbell",
+                    "laterality": "Bilateral",
+                    "training_max": None,
+                    "primary_muscles": ["Chest"],
+                    "secondary_muscles": ["Triceps"],
+                    "alternatives": [3],
+                },
+                {
+                    "id": 3,
+                    "name": "Exercise 2",
+                    "movement_pattern": None,
+                    "equipment": None,
+                    "laterality": None,
+                    "training_max": None,
+                    "primary_muscles": [],
+                    "secondary_muscles": [],
+                    "alternatives": [],
+                },
+                {
+                    "id": 5,
+                    "name": "Unused Exercise",
+                    "movement_pattern": None,
+                    "equipment": None,
+                    "laterality": None,
+                    "training_max": None,
+                    "primary_muscles": [],
+                    "secondary_muscles": [],
+                    "alternatives": [],
+                },
             ],
         ),
         (
@@ -347,6 +726,7 @@ def test_delete_user(client: Client) -> None:
                     "sections": [
                         {
                             "rounds": 1,
+                            "kind": "Sequential",
                             "parts": [
                                 {
                                     "exercise_id": 3,
@@ -355,6 +735,10 @@ def test_delete_user(client: Client) -> None:
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": False,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                                 {
                                     "exercise_id": None,
@@ -363,11 +747,16 @@ def test_delete_user(client: Client) -> None:
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": False,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                             ],
                         },
                         {
                             "rounds": 2,
+                            "kind": "Sequential",
                             "parts": [
                                 {
                                     "exercise_id": 1,
@@ -376,6 +765,10 @@ def test_delete_user(client: Client) -> None:
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": False,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                                 {
                                     "exercise_id": None,
@@ -384,9 +777,14 @@ def test_delete_user(client: Client) -> None:
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": False,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                                 {
                                     "rounds": 2,
+                                    "kind": "Sequential",
                                     "parts": [
                                         {
                                             "exercise_id": 1,
@@ -395,6 +793,10 @@ def test_delete_user(client: Client) -> None:
                                             "weight": 0.0,
                                             "rpe": 0.0,
                                             "automatic": False,
+                                            "set_type": "Working",
+                                            "auto_warm_up": False,
+                                            "relative_weight": None,
+                                            "progression": None,
                                         },
                                         {
                                             "exercise_id": None,
@@ -403,6 +805,10 @@ def test_delete_user(client: Client) -> None:
                                             "weight": 0.0,
                                             "rpe": 0.0,
                                             "automatic": False,
+                                            "set_type": "Working",
+                                            "auto_warm_up": False,
+                                            "relative_weight": None,
+                                            "progression": None,
                                         },
                                     ],
                                 },
@@ -410,6 +816,7 @@ def test_delete_user(client: Client) -> None:
                         },
                         {
                             "rounds": 3,
+                            "kind": "Sequential",
                             "parts": [
                                 {
                                     "exercise_id": 3,
@@ -418,6 +825,10 @@ def test_delete_user(client: Client) -> None:
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": True,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                                 {
                                     "exercise_id": None,
@@ -426,6 +837,10 @@ def test_delete_user(client: Client) -> None:
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": True,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                             ],
                         },
@@ -438,6 +853,7 @@ def test_delete_user(client: Client) -> None:
                     "sections": [
                         {
                             "rounds": 5,
+                            "kind": "Sequential",
                             "parts": [
                                 {
                                     "exercise_id": 3,
@@ -446,6 +862,10 @@ def test_delete_user(client: Client) -> None:
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": True,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                                 {
                                     "exercise_id": None,
@@ -454,6 +874,10 @@ def test_delete_user(client: Client) -> None:
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": True,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                             ],
                         }
@@ -469,7 +893,11 @@ def test_delete_user(client: Client) -> None:
                     "id": 1,
                     "date": "2002-02-20",
                     "routine_id": 1,
+                    "routine_version_id": 1,
+                    "session_rpe": None,
+                    "duration": None,
                     "notes": "First Workout",
+                    "interval_blocks": [],
                     "elements": [
                         {
                             "exercise_id": 3,
@@ -481,6 +909,7 @@ def test_delete_user(client: Client) -> None:
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                         {
@@ -493,6 +922,7 @@ def test_delete_user(client: Client) -> None:
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                         {
@@ -505,6 +935,7 @@ def test_delete_user(client: Client) -> None:
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                     ],
@@ -513,7 +944,11 @@ def test_delete_user(client: Client) -> None:
                     "id": 3,
                     "date": "2002-02-22",
                     "routine_id": None,
+                    "routine_version_id": None,
+                    "session_rpe": None,
+                    "duration": None,
                     "notes": None,
+                    "interval_blocks": [],
                     "elements": [
                         {
                             "exercise_id": 3,
@@ -525,6 +960,7 @@ def test_delete_user(client: Client) -> None:
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                         {
@@ -537,6 +973,7 @@ def test_delete_user(client: Client) -> None:
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                         {
@@ -549,6 +986,7 @@ def test_delete_user(client: Client) -> None:
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                         {
@@ -561,6 +999,7 @@ def test_delete_user(client: Client) -> None:
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                         {
@@ -573,6 +1012,7 @@ def test_delete_user(client: Client) -> None:
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                     ],
@@ -582,6 +1022,10 @@ def test_delete_user(client: Client) -> None:
                     "date": "2002-02-24",
                     "notes": None,
                     "routine_id": 1,
+                    "routine_version_id": 1,
+                    "session_rpe": None,
+                    "duration": None,
+                    "interval_blocks": [],
                     "elements": [
                         {
                             "exercise_id": 3,
@@ -593,6 +1037,7 @@ def test_delete_user(client: Client) -> None:
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                         {
@@ -605,6 +1050,7 @@ def test_delete_user(client: Client) -> None:
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                         {
@@ -617,12 +1063,78 @@ def test_delete_user(client: Client) -> None:
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                     ],
                 },
             ],
         ),
+        (
+            1,
+            "/api/routine_versions",
+            [
+                {
+                    "id": 1,
+                    "routine_id": 1,
+                    "created": "2002-02-19T12:00:00+00:00",
+                    "sections": [
+                        {
+                            "rounds": 1,
+                            "kind": "Sequential",
+                            "parts": [
+                                {
+                                    "exercise_id": 3,
+                                    "reps": 0,
+                                    "time": 0,
+                                    "weight": 0.0,
+                                    "rpe": 0.0,
+                                    "automatic": False,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
+                                },
+                            ],
+                        },
+                    ],
+                },
+            ],
+        ),
+        (
+            2,
+            "/api/routine_versions",
+            [],
+        ),
+        (
+            1,
+            "/api/programs",
+            [
+                {
+                    "id": 1,
+                    "name": "P1",
+                    "weeks": [
+                        {"routines": [1, 3], "weight_percentage": 100.0, "rpe_offset": 0.0},
+                        {"routines": [1], "weight_percentage": 60.0, "rpe_offset": -2.0},
+                    ],
+                    "position": {"week": 1, "day": 0},
+                    "weight_percentages": {"1": 100.0, "4": 100.0},
+                },
+            ],
+        ),
+        (
+            2,
+            "/api/programs",
+            [
+                {
+                    "id": 2,
+                    "name": "P1",
+                    "weeks": [{"routines": [2], "weight_percentage": 100.0, "rpe_offset": 0.0}],
+                    "position": None,
+                    "weight_percentages": {},
+                },
+            ],
+        ),
     ],
 )
 def test_read_all(client: Client, user_id: int, route: str, data: list[dict[str, object]]) -> None:
@@ -716,12 +1228,62 @@ def test_read_all(client: Client, user_id: int, route: str, data: list[dict[str,
         ),
         (
             "/api/exercises",
-            {"id": 6, "name": "New Exercise"},
+            {
+                "id": 6,
+                "name": "New Exercise",
+                "movement_pattern": "Pull",
+                "equipment": "Cable",
+                "laterality": "Unilateral",
+                "training_max": 120.0,
+                "primary_muscles": ["Back"],
+                "secondary_muscles": ["Biceps", "Forearms"],
+                "alternatives": [1, 5],
+            },
             [
-                {"id": 1, "name": "Exercise 1"},
-                {"id": 3, "name": "Exercise 2"},
-                {"id": 6, "name": "New Exercise"},
-                {"id": 5, "name": "Unused Exercise"},
+                {
+                    "id": 1,
+                    "name": "Exercise 1",
+                    "movement_pattern": "Push",
+                    "equipment": "Barbell",
+                    "laterality": "Bilateral",
+                    "training_max": None,
+                    "primary_muscles": ["Chest"],
+                    "secondary_muscles": ["Triceps"],
+                    "alternatives": [3],
+                },
+                {
+                    "id": 3,
+                    "name": "Exercise 2",
+                    "movement_pattern": None,
+                    "equipment": None,
+                    "laterality": None,
+                    "training_max": None,
+                    "primary_muscles": [],
+                    "secondary_muscles": [],
+                    "alternatives": [],
+                },
+                {
+                    "id": 6,
+                    "name": "New Exercise",
+                    "movement_pattern": "Pull",
+                    "equipment": "Cable",
+                    "laterality": "Unilateral",
+                    "training_max": 120.0,
+                    "primary_muscles": ["Back"],
+                    "secondary_muscles": ["Biceps", "Forearms"],
+                    "alternatives": [1, 5],
+                },
+                {
+                    "id": 5,
+                    "name": "Unused Exercise",
+                    "movement_pattern": None,
+                    "equipment": None,
+                    "laterality": None,
+                    "training_max": None,
+                    "primary_muscles": [],
+                    "secondary_muscles": [],
+                    "alternatives": [],
+                },
             ],
         ),
         (
@@ -733,6 +1295,7 @@ def test_read_all(client: Client, user_id: int, route: str, data: list[dict[str,
                 "sections": [
                     {
                         "rounds": 2,
+                        "kind": {"Amrap": {"time_cap": 600}},
                         "parts": [
                             {
                                 "exercise_id": 1,
@@ -741,9 +1304,14 @@ def test_read_all(client: Client, user_id: int, route: str, data: list[dict[str,
                                 "weight": 0.0,
                                 "rpe": 0.0,
                                 "automatic": False,
+                                "set_type": "WarmUp",
+                                "auto_warm_up": True,
+                                "relative_weight": {"percentage": 75.0, "basis": "TrainingMax"},
+                                "progression": None,
                             },
                             {
                                 "rounds": 2,
+                                "kind": "Sequential",
                                 "parts": [
                                     {
                                         "exercise_id": None,
@@ -752,6 +1320,10 @@ def test_read_all(client: Client, user_id: int, route: str, data: list[dict[str,
                                         "weight": 0.0,
                                         "rpe": 0.0,
                                         "automatic": True,
+                                        "set_type": "Working",
+                                        "auto_warm_up": False,
+                                        "relative_weight": None,
+                                        "progression": None,
                                     },
                                     {
                                         "exercise_id": 1,
@@ -760,6 +1332,12 @@ def test_read_all(client: Client, user_id: int, route: str, data: list[dict[str,
                                         "weight": 0.0,
                                         "rpe": 0.0,
                                         "automatic": True,
+                                        "set_type": "Working",
+                                        "auto_warm_up": False,
+                                        "relative_weight": None,
+                                        "progression": {
+                                            "Double": {"max_reps": 12, "increment": 2.5}
+                                        },
                                     },
                                 ],
                             },
@@ -767,6 +1345,7 @@ def test_read_all(client: Client, user_id: int, route: str, data: list[dict[str,
                     },
                     {
                         "rounds": 3,
+                        "kind": "Sequential",
                         "parts": [
                             {
                                 "exercise_id": 3,
@@ -775,6 +1354,10 @@ def test_read_all(client: Client, user_id: int, route: str, data: list[dict[str,
                                 "weight": 0.0,
                                 "rpe": 0.0,
                                 "automatic": True,
+                                "set_type": "Working",
+                                "auto_warm_up": False,
+                                "relative_weight": None,
+                                "progression": None,
                             },
                             {
                                 "exercise_id": None,
@@ -783,6 +1366,10 @@ def test_read_all(client: Client, user_id: int, route: str, data: list[dict[str,
                                 "weight": 0.0,
                                 "rpe": 0.0,
                                 "automatic": True,
+                                "set_type": "Working",
+                                "auto_warm_up": False,
+                                "relative_weight": None,
+                                "progression": None,
                             },
                         ],
                     },
@@ -796,6 +1383,7 @@ def test_read_all(client: Client, user_id: int, route: str, data: list[dict[str,
                     "sections": [
                         {
                             "rounds": 2,
+                            "kind": {"Amrap": {"time_cap": 600}},
                             "parts": [
                                 {
                                     "exercise_id": 1,
@@ -804,9 +1392,14 @@ def test_read_all(client: Client, user_id: int, route: str, data: list[dict[str,
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": False,
+                                    "set_type": "WarmUp",
+                                    "auto_warm_up": True,
+                                    "relative_weight": {"percentage": 75.0, "basis": "TrainingMax"},
+                                    "progression": None,
                                 },
                                 {
                                     "rounds": 2,
+                                    "kind": "Sequential",
                                     "parts": [
                                         {
                                             "exercise_id": None,
@@ -815,6 +1408,10 @@ def test_read_all(client: Client, user_id: int, route: str, data: list[dict[str,
                                             "weight": 0.0,
                                             "rpe": 0.0,
                                             "automatic": True,
+                                            "set_type": "Working",
+                                            "auto_warm_up": False,
+                                            "relative_weight": None,
+                                            "progression": None,
                                         },
                                         {
                                             "exercise_id": 1,
@@ -823,6 +1420,12 @@ def test_read_all(client: Client, user_id: int, route: str, data: list[dict[str,
                                             "weight": 0.0,
                                             "rpe": 0.0,
                                             "automatic": True,
+                                            "set_type": "Working",
+                                            "auto_warm_up": False,
+                                            "relative_weight": None,
+                                            "progression": {
+                                            "Double": {"max_reps": 12, "increment": 2.5}
+                                        },
                                         },
                                     ],
                                 },
@@ -830,6 +1433,7 @@ def test_read_all(client: Client, user_id: int, route: str, data: list[dict[str,
                         },
                         {
                             "rounds": 3,
+                            "kind": "Sequential",
                             "parts": [
                                 {
                                     "exercise_id": 3,
@@ -838,6 +1442,10 @@ def test_read_all(client: Client, user_id: int, route: str, data: list[dict[str,
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": True,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                                 {
                                     "exercise_id": None,
@@ -846,6 +1454,10 @@ def test_read_all(client: Client, user_id: int, route: str, data: list[dict[str,
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": True,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                             ],
                         },
@@ -858,6 +1470,7 @@ def test_read_all(client: Client, user_id: int, route: str, data: list[dict[str,
                     "sections": [
                         {
                             "rounds": 1,
+                            "kind": "Sequential",
                             "parts": [
                                 {
                                     "exercise_id": 3,
@@ -866,6 +1479,10 @@ def test_read_all(client: Client, user_id: int, route: str, data: list[dict[str,
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": False,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                                 {
                                     "exercise_id": None,
@@ -874,11 +1491,16 @@ def test_read_all(client: Client, user_id: int, route: str, data: list[dict[str,
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": False,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                             ],
                         },
                         {
                             "rounds": 2,
+                            "kind": "Sequential",
                             "parts": [
                                 {
                                     "exercise_id": 1,
@@ -887,6 +1509,10 @@ def test_read_all(client: Client, user_id: int, route: str, data: list[dict[str,
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": False,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                                 {
                                     "exercise_id": None,
@@ -895,9 +1521,14 @@ def test_read_all(client: Client, user_id: int, route: str, data: list[dict[str,
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": False,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                                 {
                                     "rounds": 2,
+                                    "kind": "Sequential",
                                     "parts": [
                                         {
                                             "exercise_id": 1,
@@ -906,6 +1537,10 @@ def test_read_all(client: Client, user_id: int, route: str, data: list[dict[str,
                                             "weight": 0.0,
                                             "rpe": 0.0,
                                             "automatic": False,
+                                            "set_type": "Working",
+                                            "auto_warm_up": False,
+                                            "relative_weight": None,
+                                            "progression": None,
                                         },
                                         {
                                             "exercise_id": None,
@@ -914,6 +1549,10 @@ def test_read_all(client: Client, user_id: int, route: str, data: list[dict[str,
                                             "weight": 0.0,
                                             "rpe": 0.0,
                                             "automatic": False,
+                                            "set_type": "Working",
+                                            "auto_warm_up": False,
+                                            "relative_weight": None,
+                                            "progression": None,
                                         },
                                     ],
                                 },
@@ -921,6 +1560,7 @@ def test_read_all(client: Client, user_id: int, route: str, data: list[dict[str,
                         },
                         {
                             "rounds": 3,
+                            "kind": "Sequential",
                             "parts": [
                                 {
                                     "exercise_id": 3,
@@ -929,6 +1569,10 @@ def test_read_all(client: Client, user_id: int, route: str, data: list[dict[str,
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": True,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                                 {
                                     "exercise_id": None,
@@ -937,6 +1581,10 @@ def test_read_all(client: Client, user_id: int, route: str, data: list[dict[str,
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": True,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                             ],
                         },
@@ -949,6 +1597,7 @@ def test_read_all(client: Client, user_id: int, route: str, data: list[dict[str,
                     "sections": [
                         {
                             "rounds": 5,
+                            "kind": "Sequential",
                             "parts": [
                                 {
                                     "exercise_id": 3,
@@ -957,6 +1606,10 @@ def test_read_all(client: Client, user_id: int, route: str, data: list[dict[str,
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": True,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                                 {
                                     "exercise_id": None,
@@ -965,6 +1618,10 @@ def test_read_all(client: Client, user_id: int, route: str, data: list[dict[str,
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": True,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                             ],
                         }
@@ -972,6 +1629,41 @@ def test_read_all(client: Client, user_id: int, route: str, data: list[dict[str,
                 },
             ],
         ),
+        (
+            "/api/programs",
+            {
+                "id": 3,
+                "name": "P2",
+                "weeks": [
+                    {"routines": [3], "weight_percentage": 100.0, "rpe_offset": 0.0},
+                    {"routines": [], "weight_percentage": 90.0, "rpe_offset": 0.5},
+                ],
+                "position": {"week": 0, "day": 0},
+                "weight_percentages": {"3": 90.0},
+            },
+            [
+                {
+                    "id": 1,
+                    "name": "P1",
+                    "weeks": [
+                        {"routines": [1, 3], "weight_percentage": 100.0, "rpe_offset": 0.0},
+                        {"routines": [1], "weight_percentage": 60.0, "rpe_offset": -2.0},
+                    ],
+                    "position": {"week": 1, "day": 0},
+                    "weight_percentages": {"1": 100.0, "4": 100.0},
+                },
+                {
+                    "id": 3,
+                    "name": "P2",
+                    "weeks": [
+                        {"routines": [3], "weight_percentage": 100.0, "rpe_offset": 0.0},
+                        {"routines": [], "weight_percentage": 90.0, "rpe_offset": 0.5},
+                    ],
+                    "position": {"week": 0, "day": 0},
+                    "weight_percentages": {"3": 90.0},
+                },
+            ],
+        ),
     ],
 )
 def test_create(
@@ -1002,7 +1694,19 @@ def test_create_workout(client: Client) -> None:
     data = {
         "date": "2002-02-24",
         "routine_id": 1,
+        "session_rpe": 8.5,
+        "duration": 60,
         "notes": "",
+        "interval_blocks": [
+            {
+                "kind": {"Emom": {"interval": 60}},
+                "start": 0,
+                "end": 5,
+                "rounds": 1,
+                "achieved_rounds": None,
+                "time": 300,
+            }
+        ],
         "elements": [
             {
                 "exercise_id": 3,
@@ -1014,6 +1718,7 @@ def test_create_workout(client: Client) -> None:
                 "target_time": None,
                 "target_weight": None,
                 "target_rpe": 8,
+                "set_type": "WarmUp",
                 "automatic": False,
             },
             {
@@ -1030,6 +1735,7 @@ def test_create_workout(client: Client) -> None:
                 "target_time": 120,
                 "target_weight": 10,
                 "target_rpe": None,
+                "set_type": "Working",
                 "automatic": False,
             },
             {
@@ -1046,13 +1752,14 @@ def test_create_workout(client: Client) -> None:
                 "target_time": None,
                 "target_weight": None,
                 "target_rpe": None,
+                "set_type": "Working",
                 "automatic": False,
             },
         ],
     }
     created = {
         **data,
-        **{"id": 5},  # noqa: PIE800
+        **{"id": 5, "routine_version_id": 2},  # noqa: PIE800
     }
     result = [
         {
@@ -1060,6 +1767,10 @@ def test_create_workout(client: Client) -> None:
             "id": 1,
             "notes": "First Workout",
             "routine_id": 1,
+            "routine_version_id": 1,
+            "session_rpe": None,
+            "duration": None,
+            "interval_blocks": [],
             "elements": [
                 {
                     "exercise_id": 3,
@@ -1071,6 +1782,7 @@ def test_create_workout(client: Client) -> None:
                     "target_time": None,
                     "target_weight": None,
                     "target_rpe": None,
+                    "set_type": "Working",
                     "automatic": False,
                 },
                 {
@@ -1083,6 +1795,7 @@ def test_create_workout(client: Client) -> None:
                     "target_time": None,
                     "target_weight": None,
                     "target_rpe": None,
+                    "set_type": "Working",
                     "automatic": False,
                 },
                 {
@@ -1095,6 +1808,7 @@ def test_create_workout(client: Client) -> None:
                     "target_time": None,
                     "target_weight": None,
                     "target_rpe": None,
+                    "set_type": "Working",
                     "automatic": False,
                 },
             ],
@@ -1104,6 +1818,10 @@ def test_create_workout(client: Client) -> None:
             "id": 3,
             "notes": None,
             "routine_id": None,
+            "routine_version_id": None,
+            "session_rpe": None,
+            "duration": None,
+            "interval_blocks": [],
             "elements": [
                 {
                     "exercise_id": 3,
@@ -1115,6 +1833,7 @@ def test_create_workout(client: Client) -> None:
                     "target_time": None,
                     "target_weight": None,
                     "target_rpe": None,
+                    "set_type": "Working",
                     "automatic": False,
                 },
                 {
@@ -1127,6 +1846,7 @@ def test_create_workout(client: Client) -> None:
                     "target_time": None,
                     "target_weight": None,
                     "target_rpe": None,
+                    "set_type": "Working",
                     "automatic": False,
                 },
                 {
@@ -1139,6 +1859,7 @@ def test_create_workout(client: Client) -> None:
                     "target_time": None,
                     "target_weight": None,
                     "target_rpe": None,
+                    "set_type": "Working",
                     "automatic": False,
                 },
                 {
@@ -1151,6 +1872,7 @@ def test_create_workout(client: Client) -> None:
                     "target_time": None,
                     "target_weight": None,
                     "target_rpe": None,
+                    "set_type": "Working",
                     "automatic": False,
                 },
                 {
@@ -1163,6 +1885,7 @@ def test_create_workout(client: Client) -> None:
                     "target_time": None,
                     "target_weight": None,
                     "target_rpe": None,
+                    "set_type": "Working",
                     "automatic": False,
                 },
             ],
@@ -1172,6 +1895,10 @@ def test_create_workout(client: Client) -> None:
             "date": "2002-02-24",
             "notes": None,
             "routine_id": 1,
+            "routine_version_id": 1,
+            "session_rpe": None,
+            "duration": None,
+            "interval_blocks": [],
             "elements": [
                 {
                     "exercise_id": 3,
@@ -1183,6 +1910,7 @@ def test_create_workout(client: Client) -> None:
                     "target_time": None,
                     "target_weight": None,
                     "target_rpe": None,
+                    "set_type": "Working",
                     "automatic": False,
                 },
                 {
@@ -1195,6 +1923,7 @@ def test_create_workout(client: Client) -> None:
                     "target_time": None,
                     "target_weight": None,
                     "target_rpe": None,
+                    "set_type": "Working",
                     "automatic": False,
                 },
                 {
@@ -1207,6 +1936,7 @@ def test_create_workout(client: Client) -> None:
                     "target_time": None,
                     "target_weight": None,
                     "target_rpe": None,
+                    "set_type": "Working",
                     "automatic": False,
                 },
             ],
@@ -1229,6 +1959,56 @@ def test_create_workout(client: Client) -> None:
     assert resp.json == result
 
 
+def test_create_routine_version(client: Client) -> None:
+    sections = [
+        {
+            "rounds": 1,
+            "kind": "Sequential",
+            "parts": [
+                {
+                    "exercise_id": 1,
+                    "reps": 5,
+                    "time": 0,
+                    "weight": 0.0,
+                    "rpe": 0.0,
+                    "automatic": False,
+                    "set_type": "Working",
+                    "auto_warm_up": False,
+                    "relative_weight": None,
+                    "progression": None,
+                },
+            ],
+        },
+    ]
+
+    tests.utils.init_db_data()
+
+    assert create_session(client).status_code == HTTPStatus.OK
+
+    resp = client.post("/api/routines", json={"name": "R3", "notes": None, "sections": []})
+
+    assert resp.status_code == HTTPStatus.CREATED
+
+    resp = client.patch("/api/routines/5", json={"sections": sections})
+
+    assert resp.status_code == HTTPStatus.OK
+
+    resp = client.patch("/api/routines/5", json={"name": "R4", "sections": sections})
+
+    assert resp.status_code == HTTPStatus.OK
+
+    resp = client.get("/api/routine_versions")
+
+    assert resp.status_code == HTTPStatus.OK
+    assert [
+        {k: v for k, v in version.items() if k != "created"} for version in resp.json
+    ] == [
+        {"id": 1, "routine_id": 1, "sections": resp.json[0]["sections"]},
+        {"id": 2, "routine_id": 5, "sections": []},
+        {"id": 3, "routine_id": 5, "sections": sections},
+    ]
+
+
 @pytest.mark.parametrize(
     ("route", "data", "response", "result", "conflicting_data"),
     [
@@ -1310,11 +2090,51 @@ def test_create_workout(client: Client) -> None:
         (
             "/api/exercises/1",
             {"name": "Changed Exercise"},
-            {"id": 1, "name": "Changed Exercise"},
+            {
+                "id": 1,
+                "name": "Changed Exercise",
+                "movement_pattern": None,
+                "equipment": None,
+                "laterality": None,
+                "training_max": None,
+                "primary_muscles": [],
+                "secondary_muscles": [],
+                "alternatives": [],
+            },
             [
-                {"id": 1, "name": "Changed Exercise"},
-                {"id": 3, "name": "Exercise 2"},
-                {"id": 5, "name": "Unused Exercise"},
+                {
+                    "id": 1,
+                    "name": "Changed Exercise",
+                    "movement_pattern": None,
+                    "equipment": None,
+                    "laterality": None,
+                    "training_max": None,
+                    "primary_muscles": [],
+                    "secondary_muscles": [],
+                    "alternatives": [],
+                },
+                {
+                    "id": 3,
+                    "name": "Exercise 2",
+                    "movement_pattern": None,
+                    "equipment": None,
+                    "laterality": None,
+                    "training_max": None,
+                    "primary_muscles": [],
+                    "secondary_muscles": [],
+                    "alternatives": [],
+                },
+                {
+                    "id": 5,
+                    "name": "Unused Exercise",
+                    "movement_pattern": None,
+                    "equipment": None,
+                    "laterality": None,
+                    "training_max": None,
+                    "primary_muscles": [],
+                    "secondary_muscles": [],
+                    "alternatives": [],
+                },
             ],
             {"name": "Exercise 2"},
         ),
@@ -1326,6 +2146,7 @@ def test_create_workout(client: Client) -> None:
                 "sections": [
                     {
                         "rounds": 3,
+                        "kind": "Sequential",
                         "parts": [
                             {
                                 "exercise_id": 1,
@@ -1334,9 +2155,14 @@ def test_create_workout(client: Client) -> None:
                                 "weight": 0.0,
                                 "rpe": 0.0,
                                 "automatic": False,
+                                "set_type": "Working",
+                                "auto_warm_up": False,
+                                "relative_weight": None,
+                                "progression": None,
                             },
                             {
                                 "rounds": 2,
+                                "kind": "Sequential",
                                 "parts": [
                                     {
                                         "exercise_id": 1,
@@ -1345,6 +2171,10 @@ def test_create_workout(client: Client) -> None:
                                         "weight": 0.0,
                                         "rpe": 0.0,
                                         "automatic": False,
+                                        "set_type": "Working",
+                                        "auto_warm_up": False,
+                                        "relative_weight": None,
+                                        "progression": None,
                                     },
                                 ],
                             },
@@ -1352,6 +2182,7 @@ def test_create_workout(client: Client) -> None:
                     },
                     {
                         "rounds": 2,
+                        "kind": "Sequential",
                         "parts": [
                             {
                                 "exercise_id": 3,
@@ -1360,6 +2191,10 @@ def test_create_workout(client: Client) -> None:
                                 "weight": 0.0,
                                 "rpe": 0.0,
                                 "automatic": True,
+                                "set_type": "Working",
+                                "auto_warm_up": False,
+                                "relative_weight": None,
+                                "progression": None,
                             },
                             {
                                 "exercise_id": None,
@@ -1368,6 +2203,10 @@ def test_create_workout(client: Client) -> None:
                                 "weight": 0.0,
                                 "rpe": 0.0,
                                 "automatic": True,
+                                "set_type": "Working",
+                                "auto_warm_up": False,
+                                "relative_weight": None,
+                                "progression": None,
                             },
                         ],
                     },
@@ -1380,6 +2219,7 @@ def test_create_workout(client: Client) -> None:
                 "sections": [
                     {
                         "rounds": 3,
+                        "kind": "Sequential",
                         "parts": [
                             {
                                 "exercise_id": 1,
@@ -1388,9 +2228,14 @@ def test_create_workout(client: Client) -> None:
                                 "weight": 0.0,
                                 "rpe": 0.0,
                                 "automatic": False,
+                                "set_type": "Working",
+                                "auto_warm_up": False,
+                                "relative_weight": None,
+                                "progression": None,
                             },
                             {
                                 "rounds": 2,
+                                "kind": "Sequential",
                                 "parts": [
                                     {
                                         "exercise_id": 1,
@@ -1399,6 +2244,10 @@ def test_create_workout(client: Client) -> None:
                                         "weight": 0.0,
                                         "rpe": 0.0,
                                         "automatic": False,
+                                        "set_type": "Working",
+                                        "auto_warm_up": False,
+                                        "relative_weight": None,
+                                        "progression": None,
                                     },
                                 ],
                             },
@@ -1406,6 +2255,7 @@ def test_create_workout(client: Client) -> None:
                     },
                     {
                         "rounds": 2,
+                        "kind": "Sequential",
                         "parts": [
                             {
                                 "exercise_id": 3,
@@ -1414,6 +2264,10 @@ def test_create_workout(client: Client) -> None:
                                 "weight": 0.0,
                                 "rpe": 0.0,
                                 "automatic": True,
+                                "set_type": "Working",
+                                "auto_warm_up": False,
+                                "relative_weight": None,
+                                "progression": None,
                             },
                             {
                                 "exercise_id": None,
@@ -1422,6 +2276,10 @@ def test_create_workout(client: Client) -> None:
                                 "weight": 0.0,
                                 "rpe": 0.0,
                                 "automatic": True,
+                                "set_type": "Working",
+                                "auto_warm_up": False,
+                                "relative_weight": None,
+                                "progression": None,
                             },
                         ],
                     },
@@ -1435,6 +2293,7 @@ def test_create_workout(client: Client) -> None:
                     "sections": [
                         {
                             "rounds": 3,
+                            "kind": "Sequential",
                             "parts": [
                                 {
                                     "exercise_id": 1,
@@ -1443,9 +2302,14 @@ def test_create_workout(client: Client) -> None:
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": False,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                                 {
                                     "rounds": 2,
+                                    "kind": "Sequential",
                                     "parts": [
                                         {
                                             "exercise_id": 1,
@@ -1454,6 +2318,10 @@ def test_create_workout(client: Client) -> None:
                                             "weight": 0.0,
                                             "rpe": 0.0,
                                             "automatic": False,
+                                            "set_type": "Working",
+                                            "auto_warm_up": False,
+                                            "relative_weight": None,
+                                            "progression": None,
                                         },
                                     ],
                                 },
@@ -1461,6 +2329,7 @@ def test_create_workout(client: Client) -> None:
                         },
                         {
                             "rounds": 2,
+                            "kind": "Sequential",
                             "parts": [
                                 {
                                     "exercise_id": 3,
@@ -1469,6 +2338,10 @@ def test_create_workout(client: Client) -> None:
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": True,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                                 {
                                     "exercise_id": None,
@@ -1477,6 +2350,10 @@ def test_create_workout(client: Client) -> None:
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": True,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                             ],
                         },
@@ -1489,6 +2366,7 @@ def test_create_workout(client: Client) -> None:
                     "sections": [
                         {
                             "rounds": 5,
+                            "kind": "Sequential",
                             "parts": [
                                 {
                                     "exercise_id": 3,
@@ -1497,6 +2375,10 @@ def test_create_workout(client: Client) -> None:
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": True,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                                 {
                                     "exercise_id": None,
@@ -1505,6 +2387,10 @@ def test_create_workout(client: Client) -> None:
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": True,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                             ],
                         }
@@ -1523,6 +2409,7 @@ def test_create_workout(client: Client) -> None:
             {
                 "date": "2002-02-23",
                 "notes": "",
+                "interval_blocks": [],
                 "elements": [
                     {
                         "exercise_id": 1,
@@ -1534,6 +2421,7 @@ def test_create_workout(client: Client) -> None:
                         "target_time": None,
                         "target_weight": None,
                         "target_rpe": 8,
+                        "set_type": "Working",
                         "automatic": False,
                     },
                     {
@@ -1546,6 +2434,7 @@ def test_create_workout(client: Client) -> None:
                         "target_time": 120,
                         "target_weight": 10,
                         "target_rpe": None,
+                        "set_type": "Working",
                         "automatic": False,
                     },
                 ],
@@ -1553,8 +2442,12 @@ def test_create_workout(client: Client) -> None:
             {
                 "id": 1,
                 "routine_id": 1,
+                "routine_version_id": 1,
+                "session_rpe": None,
+                "duration": None,
                 "date": "2002-02-23",
                 "notes": "",
+                "interval_blocks": [],
                 "elements": [
                     {
                         "exercise_id": 1,
@@ -1566,6 +2459,7 @@ def test_create_workout(client: Client) -> None:
                         "target_time": None,
                         "target_weight": None,
                         "target_rpe": 8,
+                        "set_type": "Working",
                         "automatic": False,
                     },
                     {
@@ -1578,6 +2472,7 @@ def test_create_workout(client: Client) -> None:
                         "target_time": 120,
                         "target_weight": 10,
                         "target_rpe": None,
+                        "set_type": "Working",
                         "automatic": False,
                     },
                 ],
@@ -1586,8 +2481,12 @@ def test_create_workout(client: Client) -> None:
                 {
                     "id": 1,
                     "routine_id": 1,
+                    "routine_version_id": 1,
+                    "session_rpe": None,
+                    "duration": None,
                     "date": "2002-02-23",
                     "notes": "",
+                    "interval_blocks": [],
                     "elements": [
                         {
                             "exercise_id": 1,
@@ -1599,6 +2498,7 @@ def test_create_workout(client: Client) -> None:
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": 8,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                         {
@@ -1611,6 +2511,7 @@ def test_create_workout(client: Client) -> None:
                             "target_time": 120,
                             "target_weight": 10,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                     ],
@@ -1620,6 +2521,10 @@ def test_create_workout(client: Client) -> None:
                     "date": "2002-02-22",
                     "notes": None,
                     "routine_id": None,
+                    "routine_version_id": None,
+                    "session_rpe": None,
+                    "duration": None,
+                    "interval_blocks": [],
                     "elements": [
                         {
                             "exercise_id": 3,
@@ -1631,6 +2536,7 @@ def test_create_workout(client: Client) -> None:
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                         {
@@ -1643,6 +2549,7 @@ def test_create_workout(client: Client) -> None:
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                         {
@@ -1655,6 +2562,7 @@ def test_create_workout(client: Client) -> None:
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                         {
@@ -1667,6 +2575,7 @@ def test_create_workout(client: Client) -> None:
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                         {
@@ -1679,6 +2588,7 @@ def test_create_workout(client: Client) -> None:
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                     ],
@@ -1688,6 +2598,10 @@ def test_create_workout(client: Client) -> None:
                     "date": "2002-02-24",
                     "notes": None,
                     "routine_id": 1,
+                    "routine_version_id": 1,
+                    "session_rpe": None,
+                    "duration": None,
+                    "interval_blocks": [],
                     "elements": [
                         {
                             "exercise_id": 3,
@@ -1699,6 +2613,7 @@ def test_create_workout(client: Client) -> None:
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                         {
@@ -1711,6 +2626,7 @@ def test_create_workout(client: Client) -> None:
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                         {
@@ -1723,6 +2639,7 @@ def test_create_workout(client: Client) -> None:
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                     ],
@@ -1730,6 +2647,44 @@ def test_create_workout(client: Client) -> None:
             ],
             None,
         ),
+        (
+            "/api/programs/1",
+            {
+                "id": 1,
+                "name": "P1",
+                "weeks": [
+                    {"routines": [3, 1], "weight_percentage": 100.0, "rpe_offset": 0.0},
+                ],
+                "position": None,
+                "weight_percentages": {"1": 100.0},
+            },
+            {
+                "id": 1,
+                "name": "P1",
+                "weeks": [
+                    {"routines": [3, 1], "weight_percentage": 100.0, "rpe_offset": 0.0},
+                ],
+                "position": None,
+                "weight_percentages": {"1": 100.0},
+            },
+            [
+                {
+                    "id": 1,
+                    "name": "P1",
+                    "weeks": [
+                        {"routines": [3, 1], "weight_percentage": 100.0, "rpe_offset": 0.0},
+                    ],
+                    "position": None,
+                    "weight_percentages": {"1": 100.0},
+                },
+            ],
+            {
+                "name": "P1",
+                "weeks": [{"routines": [1], "weight_percentage": 0.0, "rpe_offset": 0.0}],
+                "position": None,
+                "weight_percentages": {},
+            },
+        ),
     ],
 )
 def test_replace(
@@ -1781,6 +2736,7 @@ def test_replace(
                 "sections": [
                     {
                         "rounds": 1,
+                        "kind": "Sequential",
                         "parts": [
                             {
                                 "exercise_id": 3,
@@ -1789,6 +2745,10 @@ def test_replace(
                                 "weight": 0.0,
                                 "rpe": 0.0,
                                 "automatic": False,
+                                "set_type": "Working",
+                                "auto_warm_up": False,
+                                "relative_weight": None,
+                                "progression": None,
                             },
                             {
                                 "exercise_id": None,
@@ -1797,11 +2757,16 @@ def test_replace(
                                 "weight": 0.0,
                                 "rpe": 0.0,
                                 "automatic": False,
+                                "set_type": "Working",
+                                "auto_warm_up": False,
+                                "relative_weight": None,
+                                "progression": None,
                             },
                         ],
                     },
                     {
                         "rounds": 2,
+                        "kind": "Sequential",
                         "parts": [
                             {
                                 "exercise_id": 1,
@@ -1810,6 +2775,10 @@ def test_replace(
                                 "weight": 0.0,
                                 "rpe": 0.0,
                                 "automatic": False,
+                                "set_type": "Working",
+                                "auto_warm_up": False,
+                                "relative_weight": None,
+                                "progression": None,
                             },
                             {
                                 "exercise_id": None,
@@ -1818,9 +2787,14 @@ def test_replace(
                                 "weight": 0.0,
                                 "rpe": 0.0,
                                 "automatic": False,
+                                "set_type": "Working",
+                                "auto_warm_up": False,
+                                "relative_weight": None,
+                                "progression": None,
                             },
                             {
                                 "rounds": 2,
+                                "kind": "Sequential",
                                 "parts": [
                                     {
                                         "exercise_id": 1,
@@ -1829,6 +2803,10 @@ def test_replace(
                                         "weight": 0.0,
                                         "rpe": 0.0,
                                         "automatic": False,
+                                        "set_type": "Working",
+                                        "auto_warm_up": False,
+                                        "relative_weight": None,
+                                        "progression": None,
                                     },
                                     {
                                         "exercise_id": None,
@@ -1837,6 +2815,10 @@ def test_replace(
                                         "weight": 0.0,
                                         "rpe": 0.0,
                                         "automatic": False,
+                                        "set_type": "Working",
+                                        "auto_warm_up": False,
+                                        "relative_weight": None,
+                                        "progression": None,
                                     },
                                 ],
                             },
@@ -1844,6 +2826,7 @@ def test_replace(
                     },
                     {
                         "rounds": 3,
+                        "kind": "Sequential",
                         "parts": [
                             {
                                 "exercise_id": 3,
@@ -1852,6 +2835,10 @@ def test_replace(
                                 "weight": 0.0,
                                 "rpe": 0.0,
                                 "automatic": True,
+                                "set_type": "Working",
+                                "auto_warm_up": False,
+                                "relative_weight": None,
+                                "progression": None,
                             },
                             {
                                 "exercise_id": None,
@@ -1860,6 +2847,10 @@ def test_replace(
                                 "weight": 0.0,
                                 "rpe": 0.0,
                                 "automatic": True,
+                                "set_type": "Working",
+                                "auto_warm_up": False,
+                                "relative_weight": None,
+                                "progression": None,
                             },
                         ],
                     },
@@ -1873,6 +2864,7 @@ def test_replace(
                     "sections": [
                         {
                             "rounds": 1,
+                            "kind": "Sequential",
                             "parts": [
                                 {
                                     "exercise_id": 3,
@@ -1881,6 +2873,10 @@ def test_replace(
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": False,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                                 {
                                     "exercise_id": None,
@@ -1889,11 +2885,16 @@ def test_replace(
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": False,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                             ],
                         },
                         {
                             "rounds": 2,
+                            "kind": "Sequential",
                             "parts": [
                                 {
                                     "exercise_id": 1,
@@ -1902,6 +2903,10 @@ def test_replace(
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": False,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                                 {
                                     "exercise_id": None,
@@ -1910,9 +2915,14 @@ def test_replace(
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": False,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                                 {
                                     "rounds": 2,
+                                    "kind": "Sequential",
                                     "parts": [
                                         {
                                             "exercise_id": 1,
@@ -1921,6 +2931,10 @@ def test_replace(
                                             "weight": 0.0,
                                             "rpe": 0.0,
                                             "automatic": False,
+                                            "set_type": "Working",
+                                            "auto_warm_up": False,
+                                            "relative_weight": None,
+                                            "progression": None,
                                         },
                                         {
                                             "exercise_id": None,
@@ -1929,6 +2943,10 @@ def test_replace(
                                             "weight": 0.0,
                                             "rpe": 0.0,
                                             "automatic": False,
+                                            "set_type": "Working",
+                                            "auto_warm_up": False,
+                                            "relative_weight": None,
+                                            "progression": None,
                                         },
                                     ],
                                 },
@@ -1936,6 +2954,7 @@ def test_replace(
                         },
                         {
                             "rounds": 3,
+                            "kind": "Sequential",
                             "parts": [
                                 {
                                     "exercise_id": 3,
@@ -1944,6 +2963,10 @@ def test_replace(
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": True,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                                 {
                                     "exercise_id": None,
@@ -1952,6 +2975,10 @@ def test_replace(
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": True,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                             ],
                         },
@@ -1964,6 +2991,7 @@ def test_replace(
                     "sections": [
                         {
                             "rounds": 5,
+                            "kind": "Sequential",
                             "parts": [
                                 {
                                     "exercise_id": 3,
@@ -1972,6 +3000,10 @@ def test_replace(
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": True,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                                 {
                                     "exercise_id": None,
@@ -1980,6 +3012,10 @@ def test_replace(
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": True,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                             ],
                         }
@@ -2002,6 +3038,7 @@ def test_replace(
                 "sections": [
                     {
                         "rounds": 1,
+                        "kind": "Sequential",
                         "parts": [
                             {
                                 "exercise_id": 3,
@@ -2010,6 +3047,10 @@ def test_replace(
                                 "weight": 0.0,
                                 "rpe": 0.0,
                                 "automatic": False,
+                                "set_type": "Working",
+                                "auto_warm_up": False,
+                                "relative_weight": None,
+                                "progression": None,
                             },
                             {
                                 "exercise_id": None,
@@ -2018,11 +3059,16 @@ def test_replace(
                                 "weight": 0.0,
                                 "rpe": 0.0,
                                 "automatic": False,
+                                "set_type": "Working",
+                                "auto_warm_up": False,
+                                "relative_weight": None,
+                                "progression": None,
                             },
                         ],
                     },
                     {
                         "rounds": 2,
+                        "kind": "Sequential",
                         "parts": [
                             {
                                 "exercise_id": 1,
@@ -2031,6 +3077,10 @@ def test_replace(
                                 "weight": 0.0,
                                 "rpe": 0.0,
                                 "automatic": False,
+                                "set_type": "Working",
+                                "auto_warm_up": False,
+                                "relative_weight": None,
+                                "progression": None,
                             },
                             {
                                 "exercise_id": None,
@@ -2039,9 +3089,14 @@ def test_replace(
                                 "weight": 0.0,
                                 "rpe": 0.0,
                                 "automatic": False,
+                                "set_type": "Working",
+                                "auto_warm_up": False,
+                                "relative_weight": None,
+                                "progression": None,
                             },
                             {
                                 "rounds": 2,
+                                "kind": "Sequential",
                                 "parts": [
                                     {
                                         "exercise_id": 1,
@@ -2050,6 +3105,10 @@ def test_replace(
                                         "weight": 0.0,
                                         "rpe": 0.0,
                                         "automatic": False,
+                                        "set_type": "Working",
+                                        "auto_warm_up": False,
+                                        "relative_weight": None,
+                                        "progression": None,
                                     },
                                     {
                                         "exercise_id": None,
@@ -2058,6 +3117,10 @@ def test_replace(
                                         "weight": 0.0,
                                         "rpe": 0.0,
                                         "automatic": False,
+                                        "set_type": "Working",
+                                        "auto_warm_up": False,
+                                        "relative_weight": None,
+                                        "progression": None,
                                     },
                                 ],
                             },
@@ -2065,6 +3128,7 @@ def test_replace(
                     },
                     {
                         "rounds": 3,
+                        "kind": "Sequential",
                         "parts": [
                             {
                                 "exercise_id": 3,
@@ -2073,6 +3137,10 @@ def test_replace(
                                 "weight": 0.0,
                                 "rpe": 0.0,
                                 "automatic": True,
+                                "set_type": "Working",
+                                "auto_warm_up": False,
+                                "relative_weight": None,
+                                "progression": None,
                             },
                             {
                                 "exercise_id": None,
@@ -2081,6 +3149,10 @@ def test_replace(
                                 "weight": 0.0,
                                 "rpe": 0.0,
                                 "automatic": True,
+                                "set_type": "Working",
+                                "auto_warm_up": False,
+                                "relative_weight": None,
+                                "progression": None,
                             },
                         ],
                     },
@@ -2094,6 +3166,7 @@ def test_replace(
                     "sections": [
                         {
                             "rounds": 1,
+                            "kind": "Sequential",
                             "parts": [
                                 {
                                     "exercise_id": 3,
@@ -2102,6 +3175,10 @@ def test_replace(
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": False,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                                 {
                                     "exercise_id": None,
@@ -2110,11 +3187,16 @@ def test_replace(
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": False,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                             ],
                         },
                         {
                             "rounds": 2,
+                            "kind": "Sequential",
                             "parts": [
                                 {
                                     "exercise_id": 1,
@@ -2123,6 +3205,10 @@ def test_replace(
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": False,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                                 {
                                     "exercise_id": None,
@@ -2131,9 +3217,14 @@ def test_replace(
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": False,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                                 {
                                     "rounds": 2,
+                                    "kind": "Sequential",
                                     "parts": [
                                         {
                                             "exercise_id": 1,
@@ -2142,6 +3233,10 @@ def test_replace(
                                             "weight": 0.0,
                                             "rpe": 0.0,
                                             "automatic": False,
+                                            "set_type": "Working",
+                                            "auto_warm_up": False,
+                                            "relative_weight": None,
+                                            "progression": None,
                                         },
                                         {
                                             "exercise_id": None,
@@ -2150,6 +3245,10 @@ def test_replace(
                                             "weight": 0.0,
                                             "rpe": 0.0,
                                             "automatic": False,
+                                            "set_type": "Working",
+                                            "auto_warm_up": False,
+                                            "relative_weight": None,
+                                            "progression": None,
                                         },
                                     ],
                                 },
@@ -2157,6 +3256,7 @@ def test_replace(
                         },
                         {
                             "rounds": 3,
+                            "kind": "Sequential",
                             "parts": [
                                 {
                                     "exercise_id": 3,
@@ -2165,6 +3265,10 @@ def test_replace(
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": True,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                                 {
                                     "exercise_id": None,
@@ -2173,6 +3277,10 @@ def test_replace(
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": True,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                             ],
                         },
@@ -2185,6 +3293,7 @@ def test_replace(
                     "sections": [
                         {
                             "rounds": 5,
+                            "kind": "Sequential",
                             "parts": [
                                 {
                                     "exercise_id": 3,
@@ -2193,6 +3302,10 @@ def test_replace(
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": True,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                                 {
                                     "exercise_id": None,
@@ -2201,6 +3314,10 @@ def test_replace(
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": True,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                             ],
                         }
@@ -2218,6 +3335,7 @@ def test_replace(
                 "sections": [
                     {
                         "rounds": 3,
+                        "kind": "Sequential",
                         "parts": [
                             {
                                 "exercise_id": 1,
@@ -2226,9 +3344,14 @@ def test_replace(
                                 "weight": 0.0,
                                 "rpe": 0.0,
                                 "automatic": False,
+                                "set_type": "Working",
+                                "auto_warm_up": False,
+                                "relative_weight": None,
+                                "progression": None,
                             },
                             {
                                 "rounds": 2,
+                                "kind": "Sequential",
                                 "parts": [
                                     {
                                         "exercise_id": 1,
@@ -2237,6 +3360,10 @@ def test_replace(
                                         "weight": 0.0,
                                         "rpe": 0.0,
                                         "automatic": False,
+                                        "set_type": "Working",
+                                        "auto_warm_up": False,
+                                        "relative_weight": None,
+                                        "progression": None,
                                     },
                                 ],
                             },
@@ -2244,6 +3371,7 @@ def test_replace(
                     },
                     {
                         "rounds": 2,
+                        "kind": "Sequential",
                         "parts": [
                             {
                                 "exercise_id": 3,
@@ -2252,6 +3380,10 @@ def test_replace(
                                 "weight": 0.0,
                                 "rpe": 0.0,
                                 "automatic": True,
+                                "set_type": "Working",
+                                "auto_warm_up": False,
+                                "relative_weight": None,
+                                "progression": None,
                             },
                             {
                                 "exercise_id": None,
@@ -2260,6 +3392,10 @@ def test_replace(
                                 "weight": 0.0,
                                 "rpe": 0.0,
                                 "automatic": True,
+                                "set_type": "Working",
+                                "auto_warm_up": False,
+                                "relative_weight": None,
+                                "progression": None,
                             },
                         ],
                     },
@@ -2272,6 +3408,7 @@ def test_replace(
                 "sections": [
                     {
                         "rounds": 3,
+                        "kind": "Sequential",
                         "parts": [
                             {
                                 "exercise_id": 1,
@@ -2280,9 +3417,14 @@ def test_replace(
                                 "weight": 0.0,
                                 "rpe": 0.0,
                                 "automatic": False,
+                                "set_type": "Working",
+                                "auto_warm_up": False,
+                                "relative_weight": None,
+                                "progression": None,
                             },
                             {
                                 "rounds": 2,
+                                "kind": "Sequential",
                                 "parts": [
                                     {
                                         "exercise_id": 1,
@@ -2291,6 +3433,10 @@ def test_replace(
                                         "weight": 0.0,
                                         "rpe": 0.0,
                                         "automatic": False,
+                                        "set_type": "Working",
+                                        "auto_warm_up": False,
+                                        "relative_weight": None,
+                                        "progression": None,
                                     },
                                 ],
                             },
@@ -2298,6 +3444,7 @@ def test_replace(
                     },
                     {
                         "rounds": 2,
+                        "kind": "Sequential",
                         "parts": [
                             {
                                 "exercise_id": 3,
@@ -2306,6 +3453,10 @@ def test_replace(
                                 "weight": 0.0,
                                 "rpe": 0.0,
                                 "automatic": True,
+                                "set_type": "Working",
+                                "auto_warm_up": False,
+                                "relative_weight": None,
+                                "progression": None,
                             },
                             {
                                 "exercise_id": None,
@@ -2314,6 +3465,10 @@ def test_replace(
                                 "weight": 0.0,
                                 "rpe": 0.0,
                                 "automatic": True,
+                                "set_type": "Working",
+                                "auto_warm_up": False,
+                                "relative_weight": None,
+                                "progression": None,
                             },
                         ],
                     },
@@ -2327,6 +3482,7 @@ def test_replace(
                     "sections": [
                         {
                             "rounds": 3,
+                            "kind": "Sequential",
                             "parts": [
                                 {
                                     "exercise_id": 1,
@@ -2335,9 +3491,14 @@ def test_replace(
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": False,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                                 {
                                     "rounds": 2,
+                                    "kind": "Sequential",
                                     "parts": [
                                         {
                                             "exercise_id": 1,
@@ -2346,6 +3507,10 @@ def test_replace(
                                             "weight": 0.0,
                                             "rpe": 0.0,
                                             "automatic": False,
+                                            "set_type": "Working",
+                                            "auto_warm_up": False,
+                                            "relative_weight": None,
+                                            "progression": None,
                                         },
                                     ],
                                 },
@@ -2353,6 +3518,7 @@ def test_replace(
                         },
                         {
                             "rounds": 2,
+                            "kind": "Sequential",
                             "parts": [
                                 {
                                     "exercise_id": 3,
@@ -2361,6 +3527,10 @@ def test_replace(
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": True,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                                 {
                                     "exercise_id": None,
@@ -2369,6 +3539,10 @@ def test_replace(
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": True,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                             ],
                         },
@@ -2381,6 +3555,7 @@ def test_replace(
                     "sections": [
                         {
                             "rounds": 5,
+                            "kind": "Sequential",
                             "parts": [
                                 {
                                     "exercise_id": 3,
@@ -2389,6 +3564,10 @@ def test_replace(
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": True,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                                 {
                                     "exercise_id": None,
@@ -2397,6 +3576,10 @@ def test_replace(
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": True,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                             ],
                         }
@@ -2414,7 +3597,11 @@ def test_replace(
                 "id": 1,
                 "date": "2002-02-23",
                 "routine_id": 1,
+                "routine_version_id": 1,
+                "session_rpe": None,
+                "duration": None,
                 "notes": "First Workout",
+                "interval_blocks": [],
                 "elements": [
                     {
                         "exercise_id": 3,
@@ -2426,6 +3613,7 @@ def test_replace(
                         "target_time": None,
                         "target_weight": None,
                         "target_rpe": None,
+                        "set_type": "Working",
                         "automatic": False,
                     },
                     {
@@ -2438,6 +3626,7 @@ def test_replace(
                         "target_time": None,
                         "target_weight": None,
                         "target_rpe": None,
+                        "set_type": "Working",
                         "automatic": False,
                     },
                     {
@@ -2450,6 +3639,7 @@ def test_replace(
                         "target_time": None,
                         "target_weight": None,
                         "target_rpe": None,
+                        "set_type": "Working",
                         "automatic": False,
                     },
                 ],
@@ -2459,7 +3649,11 @@ def test_replace(
                     "id": 1,
                     "date": "2002-02-23",
                     "routine_id": 1,
+                    "routine_version_id": 1,
+                    "session_rpe": None,
+                    "duration": None,
                     "notes": "First Workout",
+                    "interval_blocks": [],
                     "elements": [
                         {
                             "exercise_id": 3,
@@ -2471,6 +3665,7 @@ def test_replace(
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                         {
@@ -2483,6 +3678,7 @@ def test_replace(
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                         {
@@ -2495,6 +3691,7 @@ def test_replace(
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                     ],
@@ -2503,7 +3700,11 @@ def test_replace(
                     "id": 3,
                     "date": "2002-02-22",
                     "routine_id": None,
+                    "routine_version_id": None,
+                    "session_rpe": None,
+                    "duration": None,
                     "notes": None,
+                    "interval_blocks": [],
                     "elements": [
                         {
                             "exercise_id": 3,
@@ -2515,6 +3716,7 @@ def test_replace(
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                         {
@@ -2527,6 +3729,7 @@ def test_replace(
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                         {
@@ -2539,6 +3742,7 @@ def test_replace(
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                         {
@@ -2551,6 +3755,7 @@ def test_replace(
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                         {
@@ -2563,6 +3768,7 @@ def test_replace(
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                     ],
@@ -2572,6 +3778,10 @@ def test_replace(
                     "date": "2002-02-24",
                     "notes": None,
                     "routine_id": 1,
+                    "routine_version_id": 1,
+                    "session_rpe": None,
+                    "duration": None,
+                    "interval_blocks": [],
                     "elements": [
                         {
                             "exercise_id": 3,
@@ -2583,6 +3793,7 @@ def test_replace(
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                         {
@@ -2595,6 +3806,7 @@ def test_replace(
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                         {
@@ -2607,6 +3819,7 @@ def test_replace(
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                     ],
@@ -2623,7 +3836,11 @@ def test_replace(
                 "id": 1,
                 "date": "2002-02-20",
                 "routine_id": 1,
+                "routine_version_id": 1,
+                "session_rpe": None,
+                "duration": None,
                 "notes": "",
+                "interval_blocks": [],
                 "elements": [
                     {
                         "exercise_id": 3,
@@ -2635,6 +3852,7 @@ def test_replace(
                         "target_time": None,
                         "target_weight": None,
                         "target_rpe": None,
+                        "set_type": "Working",
                         "automatic": False,
                     },
                     {
@@ -2647,6 +3865,7 @@ def test_replace(
                         "target_time": None,
                         "target_weight": None,
                         "target_rpe": None,
+                        "set_type": "Working",
                         "automatic": False,
                     },
                     {
@@ -2659,6 +3878,7 @@ def test_replace(
                         "target_time": None,
                         "target_weight": None,
                         "target_rpe": None,
+                        "set_type": "Working",
                         "automatic": False,
                     },
                 ],
@@ -2668,7 +3888,11 @@ def test_replace(
                     "id": 1,
                     "date": "2002-02-20",
                     "routine_id": 1,
+                    "routine_version_id": 1,
+                    "session_rpe": None,
+                    "duration": None,
                     "notes": "",
+                    "interval_blocks": [],
                     "elements": [
                         {
                             "exercise_id": 3,
@@ -2680,6 +3904,7 @@ def test_replace(
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                         {
@@ -2692,6 +3917,7 @@ def test_replace(
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                         {
@@ -2704,6 +3930,7 @@ def test_replace(
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                     ],
@@ -2712,7 +3939,11 @@ def test_replace(
                     "id": 3,
                     "date": "2002-02-22",
                     "routine_id": None,
+                    "routine_version_id": None,
+                    "session_rpe": None,
+                    "duration": None,
                     "notes": None,
+                    "interval_blocks": [],
                     "elements": [
                         {
                             "exercise_id": 3,
@@ -2724,6 +3955,7 @@ def test_replace(
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                         {
@@ -2736,6 +3968,7 @@ def test_replace(
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                         {
@@ -2748,6 +3981,7 @@ def test_replace(
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                         {
@@ -2760,6 +3994,7 @@ def test_replace(
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                         {
@@ -2772,6 +4007,7 @@ def test_replace(
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                     ],
@@ -2781,6 +4017,10 @@ def test_replace(
                     "date": "2002-02-24",
                     "notes": None,
                     "routine_id": 1,
+                    "routine_version_id": 1,
+                    "session_rpe": None,
+                    "duration": None,
+                    "interval_blocks": [],
                     "elements": [
                         {
                             "exercise_id": 3,
@@ -2792,6 +4032,7 @@ def test_replace(
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                         {
@@ -2804,6 +4045,7 @@ def test_replace(
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                         {
@@ -2816,6 +4058,7 @@ def test_replace(
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                     ],
@@ -2826,6 +4069,7 @@ def test_replace(
         (
             "/api/workouts/1",
             {
+                "interval_blocks": [],
                 "elements": [
                     {
                         "exercise_id": 1,
@@ -2837,6 +4081,7 @@ def test_replace(
                         "target_time": None,
                         "target_weight": None,
                         "target_rpe": 8,
+                        "set_type": "Working",
                         "automatic": False,
                     },
                     {
@@ -2853,6 +4098,7 @@ def test_replace(
                         "target_time": 120,
                         "target_weight": 10,
                         "target_rpe": None,
+                        "set_type": "Working",
                         "automatic": False,
                     },
                 ],
@@ -2860,8 +4106,12 @@ def test_replace(
             {
                 "id": 1,
                 "routine_id": 1,
+                "routine_version_id": 1,
+                "session_rpe": None,
+                "duration": None,
                 "date": "2002-02-20",
                 "notes": "First Workout",
+                "interval_blocks": [],
                 "elements": [
                     {
                         "exercise_id": 1,
@@ -2873,6 +4123,7 @@ def test_replace(
                         "target_time": None,
                         "target_weight": None,
                         "target_rpe": 8,
+                        "set_type": "Working",
                         "automatic": False,
                     },
                     {
@@ -2889,6 +4140,7 @@ def test_replace(
                         "target_time": 120,
                         "target_weight": 10,
                         "target_rpe": None,
+                        "set_type": "Working",
                         "automatic": False,
                     },
                 ],
@@ -2898,7 +4150,11 @@ def test_replace(
                     "id": 1,
                     "date": "2002-02-20",
                     "routine_id": 1,
+                    "routine_version_id": 1,
+                    "session_rpe": None,
+                    "duration": None,
                     "notes": "First Workout",
+                    "interval_blocks": [],
                     "elements": [
                         {
                             "exercise_id": 1,
@@ -2910,6 +4166,7 @@ def test_replace(
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": 8,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                         {
@@ -2926,6 +4183,7 @@ def test_replace(
                             "target_time": 120,
                             "target_weight": 10,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                     ],
@@ -2934,7 +4192,11 @@ def test_replace(
                     "id": 3,
                     "date": "2002-02-22",
                     "routine_id": None,
+                    "routine_version_id": None,
+                    "session_rpe": None,
+                    "duration": None,
                     "notes": None,
+                    "interval_blocks": [],
                     "elements": [
                         {
                             "exercise_id": 3,
@@ -2946,6 +4208,7 @@ def test_replace(
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                         {
@@ -2958,6 +4221,7 @@ def test_replace(
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                         {
@@ -2970,6 +4234,7 @@ def test_replace(
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                         {
@@ -2982,6 +4247,7 @@ def test_replace(
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                         {
@@ -2994,6 +4260,7 @@ def test_replace(
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                     ],
@@ -3003,6 +4270,10 @@ def test_replace(
                     "date": "2002-02-24",
                     "notes": None,
                     "routine_id": 1,
+                    "routine_version_id": 1,
+                    "session_rpe": None,
+                    "duration": None,
+                    "interval_blocks": [],
                     "elements": [
                         {
                             "exercise_id": 3,
@@ -3014,6 +4285,7 @@ def test_replace(
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                         {
@@ -3026,6 +4298,7 @@ def test_replace(
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                         {
@@ -3038,6 +4311,7 @@ def test_replace(
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                     ],
@@ -3116,8 +4390,28 @@ def test_modify(
         (
             "/api/exercises/3",
             [
-                {"id": 1, "name": "Exercise 1"},
-                {"id": 5, "name": "Unused Exercise"},
+                {
+                    "id": 1,
+                    "name": "Exercise 1",
+                    "movement_pattern": "Push",
+                    "equipment": "Barbell",
+                    "laterality": "Bilateral",
+                    "training_max": None,
+                    "primary_muscles": ["Chest"],
+                    "secondary_muscles": ["Triceps"],
+                    "alternatives": [],
+                },
+                {
+                    "id": 5,
+                    "name": "Unused Exercise",
+                    "movement_pattern": None,
+                    "equipment": None,
+                    "laterality": None,
+                    "training_max": None,
+                    "primary_muscles": [],
+                    "secondary_muscles": [],
+                    "alternatives": [],
+                },
             ],
         ),
         (
@@ -3130,6 +4424,7 @@ def test_modify(
                     "sections": [
                         {
                             "rounds": 1,
+                            "kind": "Sequential",
                             "parts": [
                                 {
                                     "exercise_id": 3,
@@ -3138,6 +4433,10 @@ def test_modify(
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": False,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                                 {
                                     "exercise_id": None,
@@ -3146,11 +4445,16 @@ def test_modify(
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": False,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                             ],
                         },
                         {
                             "rounds": 2,
+                            "kind": "Sequential",
                             "parts": [
                                 {
                                     "exercise_id": 1,
@@ -3159,6 +4463,10 @@ def test_modify(
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": False,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                                 {
                                     "exercise_id": None,
@@ -3167,9 +4475,14 @@ def test_modify(
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": False,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                                 {
                                     "rounds": 2,
+                                    "kind": "Sequential",
                                     "parts": [
                                         {
                                             "exercise_id": 1,
@@ -3178,6 +4491,10 @@ def test_modify(
                                             "weight": 0.0,
                                             "rpe": 0.0,
                                             "automatic": False,
+                                            "set_type": "Working",
+                                            "auto_warm_up": False,
+                                            "relative_weight": None,
+                                            "progression": None,
                                         },
                                         {
                                             "exercise_id": None,
@@ -3186,6 +4503,10 @@ def test_modify(
                                             "weight": 0.0,
                                             "rpe": 0.0,
                                             "automatic": False,
+                                            "set_type": "Working",
+                                            "auto_warm_up": False,
+                                            "relative_weight": None,
+                                            "progression": None,
                                         },
                                     ],
                                 },
@@ -3193,6 +4514,7 @@ def test_modify(
                         },
                         {
                             "rounds": 3,
+                            "kind": "Sequential",
                             "parts": [
                                 {
                                     "exercise_id": 3,
@@ -3201,6 +4523,10 @@ def test_modify(
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": True,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                                 {
                                     "exercise_id": None,
@@ -3209,6 +4535,10 @@ def test_modify(
                                     "weight": 0.0,
                                     "rpe": 0.0,
                                     "automatic": True,
+                                    "set_type": "Working",
+                                    "auto_warm_up": False,
+                                    "relative_weight": None,
+                                    "progression": None,
                                 },
                             ],
                         },
@@ -3223,7 +4553,11 @@ def test_modify(
                     "id": 1,
                     "date": "2002-02-20",
                     "routine_id": 1,
+                    "routine_version_id": 1,
+                    "session_rpe": None,
+                    "duration": None,
                     "notes": "First Workout",
+                    "interval_blocks": [],
                     "elements": [
                         {
                             "exercise_id": 3,
@@ -3235,6 +4569,7 @@ def test_modify(
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                         {
@@ -3247,6 +4582,7 @@ def test_modify(
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                         {
@@ -3259,6 +4595,7 @@ def test_modify(
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                     ],
@@ -3268,6 +4605,10 @@ def test_modify(
                     "date": "2002-02-24",
                     "notes": None,
                     "routine_id": 1,
+                    "routine_version_id": 1,
+                    "session_rpe": None,
+                    "duration": None,
+                    "interval_blocks": [],
                     "elements": [
                         {
                             "exercise_id": 3,
@@ -3279,6 +4620,7 @@ def test_modify(
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                         {
@@ -3291,6 +4633,7 @@ def test_modify(
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                         {
@@ -3303,12 +4646,17 @@ def test_modify(
                             "target_time": None,
                             "target_weight": None,
                             "target_rpe": None,
+                            "set_type": "Working",
                             "automatic": False,
                         },
                     ],
                 },
             ],
         ),
+        (
+            "/api/programs/1",
+            [],
+        ),
     ],
 )
 def test_delete(
@@ -3334,3 +4682,57 @@ def test_delete(
 
     assert resp.status_code == HTTPStatus.NOT_FOUND
     assert not resp.data
+
+
+def test_merge_exercises(client: Client) -> None:
+    def exercise_ids(parts: list[dict[str, Any]]) -> set[Optional[int]]:
+        result: set[Optional[int]] = set()
+        for p in parts:
+            if "parts" in p:
+                result |= exercise_ids(p["parts"])
+            else:
+                result.add(p["exercise_id"])
+        return result
+
+    tests.utils.init_db_data()
+
+    assert create_session(client).status_code == HTTPStatus.OK
+
+    resp = client.post("/api/exercises/1/merge", json={"exercise_ids": [3]})
+
+    assert resp.status_code == HTTPStatus.OK
+    assert resp.json["id"] == 1
+    assert resp.json["alternatives"] == []
+
+    resp = client.get("/api/exercises")
+
+    assert resp.status_code == HTTPStatus.OK
+    assert [e["id"] for e in resp.json] == [1, 5]
+
+    resp = client.get("/api/routines")
+
+    assert resp.status_code == HTTPStatus.OK
+    assert [3 in exercise_ids(r["sections"]) for r in resp.json] == [False, False]
+    assert [1 in exercise_ids(r["sections"]) for r in resp.json] == [True, True]
+
+    resp = client.get("/api/workouts")
+
+    assert resp.status_code == HTTPStatus.OK
+    assert [e["exercise_id"] for w in resp.json for e in w["elements"]] == [1] * 11
+
+    resp = client.get("/api/routine_versions")
+
+    assert resp.status_code == HTTPStatus.OK
+    assert [(v["id"], v["routine_id"]) for v in resp.json] == [(1, 1), (2, 1), (3, 3)]
+    assert [3 in exercise_ids(v["sections"]) for v in resp.json] == [True, False, False]
+
+
+def test_merge_exercises_not_found(client: Client) -> None:
+    tests.utils.init_db_data()
+
+    assert create_session(client).status_code == HTTPStatus.OK
+
+    resp = client.post("/api/exercises/2/merge", json={"exercise_ids": [3]})
+
+    assert resp.status_code == HTTPStatus.NOT_FOUND
+    assert not resp.data
@@ -117,6 +117,18 @@ def test_completeness_constraints(tmp_path: Path) -> None:
         ("b9f4e42c7135", "8a0dc258bf2a"),
         ("8a0dc258bf2a", "22f3ddb25741"),
         ("22f3ddb25741", "06f82ead211b"),
+        ("06f82ead211b", "5c7e2a91d3f4"),
+        ("5c7e2a91d3f4", "e1b5f07a9c62"),
+        ("e1b5f07a9c62", "7d4a8e3b2f10"),
+        ("7d4a8e3b2f10", "a3f9c6d1e8b4"),
+        ("a3f9c6d1e8b4", "b6d2f8a4c1e7"),
+        ("b6d2f8a4c1e7", "c4e9a7b3d5f1"),
+        ("c4e9a7b3d5f1", "d8f1b5c3e9a2"),
+        ("d8f1b5c3e9a2", "e5a3c7f9b1d4"),
+        ("e5a3c7f9b1d4", "f6c2a8d4e0b7"),
+        ("f6c2a8d4e0b7", "a8e4d0b6f2c3"),
+        ("a8e4d0b6f2c3", "b2f6c8e0a4d7"),
+        ("b2f6c8e0a4d7", "c3a7e1f5b9d2"),
     ],
 )
 def test_up(tmp_path: Path, source: str, target: str) -> None:
@@ -132,6 +144,18 @@ def test_up(tmp_path: Path, source: str, target: str) -> None:
         ("8a0dc258bf2a", "b9f4e42c7135"),
         ("22f3ddb25741", "8a0dc258bf2a"),
         ("06f82ead211b", "22f3ddb25741"),
+        ("5c7e2a91d3f4", "06f82ead211b"),
+        ("e1b5f07a9c62", "5c7e2a91d3f4"),
+        ("7d4a8e3b2f10", "e1b5f07a9c62"),
+        ("a3f9c6d1e8b4", "7d4a8e3b2f10"),
+        ("b6d2f8a4c1e7", "a3f9c6d1e8b4"),
+        ("c4e9a7b3d5f1", "b6d2f8a4c1e7"),
+        ("d8f1b5c3e9a2", "c4e9a7b3d5f1"),
+        ("e5a3c7f9b1d4", "d8f1b5c3e9a2"),
+        ("f6c2a8d4e0b7", "e5a3c7f9b1d4"),
+        ("a8e4d0b6f2c3", "f6c2a8d4e0b7"),
+        ("b2f6c8e0a4d7", "a8e4d0b6f2c3"),
+        ("c3a7e1f5b9d2", "b2f6c8e0a4d7"),
     ],
 )
 def test_down(tmp_path: Path, source: str, target: str) -> None:
@@ -3,13 +3,25 @@ from __future__ import annotations
 import datetime
 
 from valens.models import (
+    BarbellBar,
+    BarbellEquipment,
+    BarbellPlate,
     BodyFat,
     BodyWeight,
     Exercise,
+    ExerciseAlternative,
+    ExerciseMuscle,
     Period,
+    Program,
+    ProgramRoutine,
+    ProgramWeek,
+    ProgramWorkout,
     Routine,
     RoutineActivity,
     RoutineSection,
+    RoutineVersion,
+    Schedule,
+    ScheduleRoutine,
     Sex,
     User,
     Workout,
@@ -25,7 +37,19 @@ def users_only() -> list[User]:
 
 
 def users() -> list[User]:
-    exercise_1 = Exercise(id=1, user_id=1, name="Exercise 1")
+    exercise_1 = Exercise(
+        id=1,
+        user_id=1,
+        name="Exercise 1",
+        movement_pattern="Push",
+        equipment="Barbell",
+        laterality="Bilateral",
+        muscles=[
+            ExerciseMuscle(muscle_group="Chest", secondary=False),
+            ExerciseMuscle(muscle_group="Triceps", secondary=True),
+        ],
+        alternatives=[ExerciseAlternative(alternative_id=3)],
+    )
     exercise_2 = Exercise(id=2, user_id=2, name="Exercise 2")
     exercise_3 = Exercise(id=3, user_id=1, name="Exercise 2")
     exercise_4 = Exercise(id=4, user_id=2, name="Exercise 3")
@@ -175,6 +199,32 @@ def users() -> list[User]:
                             ],
                         ),
                     ],
+                    versions=[
+                        RoutineVersion(
+                            id=1,
+                            created=datetime.datetime(2002, 2, 19, 12, 0),
+                            sections=[
+                                {
+                                    "rounds": 1,
+                                    "kind": "Sequential",
+                                    "parts": [
+                                        {
+                                            "exercise_id": 3,
+                                            "reps": 0,
+                                            "time": 0,
+                                            "weight": 0.0,
+                                            "rpe": 0.0,
+                                            "automatic": False,
+                                            "set_type": "Working",
+                                            "auto_warm_up": False,
+                                            "relative_weight": None,
+                                            "progression": None,
+                                        },
+                                    ],
+                                },
+                            ],
+                        ),
+                    ],
                 ),
                 Routine(
                     id=3,
@@ -213,6 +263,7 @@ def users() -> list[User]:
                     id=1,
                     user_id=1,
                     routine_id=1,
+                    routine_version_id=1,
                     date=datetime.date(2002, 2, 20),
                     notes="First Workout",
                     elements=[
@@ -238,6 +289,7 @@ def users() -> list[User]:
                     id=4,
                     user_id=1,
                     routine_id=1,
+                    routine_version_id=1,
                     date=datetime.date(2002, 2, 24),
                     notes=None,
                     elements=[
@@ -247,6 +299,52 @@ def users() -> list[User]:
                     ],
                 ),
             ],
+            programs=[
+                Program(
+                    id=1,
+                    user_id=1,
+                    name="P1",
+                    position_week=1,
+                    position_day=0,
+                    weeks=[
+                        ProgramWeek(
+                            position=1,
+                            weight_percentage=100.0,
+                            rpe_offset=0.0,
+                            routines=[
+                                ProgramRoutine(position=1, routine_id=1),
+                                ProgramRoutine(position=2, routine_id=3),
+                            ],
+                        ),
+                        ProgramWeek(
+                            position=2,
+                            weight_percentage=60.0,
+                            rpe_offset=-2.0,
+                            routines=[ProgramRoutine(position=1, routine_id=1)],
+                        ),
+                    ],
+                    workouts=[
+                        ProgramWorkout(workout_id=1, weight_percentage=100.0),
+                        ProgramWorkout(workout_id=4, weight_percentage=100.0),
+                    ],
+                ),
+            ],
+            schedule=Schedule(
+                start=datetime.date(2002, 2, 20),
+                routines=[
+                    ScheduleRoutine(position=1, routine_id=1),
+                    ScheduleRoutine(position=3, routine_id=3),
+                ],
+            ),
+            barbell_equipment=BarbellEquipment(
+                collars=2.5,
+                bars=[BarbellBar(position=1, weight=20.0), BarbellBar(position=2, weight=10.0)],
+                plates=[
+                    BarbellPlate(position=1, weight=20.0, count=2),
+                    BarbellPlate(position=2, weight=10.0, count=2),
+                    BarbellPlate(position=3, weight=1.25, count=1),
+                ],
+            ),
         ),
         User(
             id=2,
@@ -363,6 +461,29 @@ def users() -> list[User]:
                     ],
                 ),
             ],
+            programs=[
+                Program(
+                    id=2,
+                    user_id=2,
+                    name="P1",
+                    weeks=[
+                        ProgramWeek(
+                            position=1,
+                            weight_percentage=100.0,
+                            rpe_offset=0.0,
+                            routines=[ProgramRoutine(position=1, routine_id=2)],
+                        ),
+                    ],
+                ),
+            ],
+            schedule=Schedule(
+                start=datetime.date(2002, 2, 20),
+                interval=2,
+                routines=[
+                    ScheduleRoutine(position=1, routine_id=2),
+                    ScheduleRoutine(position=2, routine_id=4),
+                ],
+            ),
         ),
     ]
 
@@ -0,0 +1,500 @@
+BEGIN TRANSACTION;
+CREATE TABLE alembic_version (
+	version_num VARCHAR(32) NOT NULL,
+	CONSTRAINT alembic_version_pkc PRIMARY KEY (version_num)
+);
+INSERT INTO "alembic_version" VALUES('a8e4d0b6f2c3');
+CREATE TABLE body_fat (
+	user_id INTEGER NOT NULL,
+	date DATE NOT NULL,
+	chest INTEGER,
+	abdominal INTEGER,
+	tigh INTEGER,
+	tricep INTEGER,
+	subscapular INTEGER,
+	suprailiac INTEGER,
+	midaxillary INTEGER,
+	CONSTRAINT ck_body_fat_abdominal_gt_0 CHECK (abdominal > 0),
+	CONSTRAINT ck_body_fat_abdominal_type_integer_or_null CHECK (typeof(abdominal) = 'integer' or typeof(abdominal) = 'null'),
+	CONSTRAINT ck_body_fat_chest_gt_0 CHECK (chest > 0),
+	CONSTRAINT ck_body_fat_chest_type_integer_or_null CHECK (typeof(chest) = 'integer' or typeof(chest) = 'null'),
+	CONSTRAINT ck_body_fat_midaxillary_gt_0 CHECK (midaxillary > 0),
+	CONSTRAINT ck_body_fat_midaxillary_type_integer_or_null CHECK (typeof(midaxillary) = 'integer' or typeof(midaxillary) = 'null'),
+	CONSTRAINT ck_body_fat_subscapular_gt_0 CHECK (subscapular > 0),
+	CONSTRAINT ck_body_fat_subscapular_type_integer_or_null CHECK (typeof(subscapular) = 'integer' or typeof(subscapular) = 'null'),
+	CONSTRAINT ck_body_fat_suprailiac_gt_0 CHECK (suprailiac > 0),
+	CONSTRAINT ck_body_fat_suprailiac_type_integer_or_null CHECK (typeof(suprailiac) = 'integer' or typeof(suprailiac) = 'null'),
+	CONSTRAINT ck_body_fat_tigh_gt_0 CHECK (tigh > 0),
+	CONSTRAINT ck_body_fat_tigh_type_integer_or_null CHECK (typeof(tigh) = 'integer' or typeof(tigh) = 'null'),
+	CONSTRAINT ck_body_fat_tricep_gt_0 CHECK (tricep > 0),
+	CONSTRAINT ck_body_fat_tricep_type_integer_or_null CHECK (typeof(tricep) = 'integer' or typeof(tricep) = 'null'),
+	CONSTRAINT fk_body_fat_user_id_user FOREIGN KEY(user_id) REFERENCES user (id) ON DELETE CASCADE,
+	CONSTRAINT pk_body_fat PRIMARY KEY (user_id, date)
+);
+INSERT INTO "body_fat" VALUES(1,'2002-02-20',1,2,3,4,5,6,7);
+INSERT INTO "body_fat" VALUES(1,'2002-02-21',NULL,NULL,10,11,NULL,13,NULL);
+INSERT INTO "body_fat" VALUES(2,'2002-02-20',15,16,17,18,19,20,21);
+INSERT INTO "body_fat" VALUES(2,'2002-02-22',22,23,24,25,26,27,28);
+CREATE TABLE body_weight (
+	user_id INTEGER NOT NULL,
+	date DATE NOT NULL,
+	weight FLOAT NOT NULL,
+	CONSTRAINT ck_body_weight_weight_gt_0 CHECK (weight > 0),
+	CONSTRAINT ck_body_weight_weight_type_real CHECK (typeof(weight) = 'real'),
+	CONSTRAINT fk_body_weight_user_id_user FOREIGN KEY(user_id) REFERENCES user (id) ON DELETE CASCADE,
+	CONSTRAINT pk_body_weight PRIMARY KEY (user_id, date)
+);
+INSERT INTO "body_weight" VALUES(1,'2002-02-20',67.5);
+INSERT INTO "body_weight" VALUES(1,'2002-02-21',67.7);
+INSERT INTO "body_weight" VALUES(1,'2002-02-22',67.3);
+INSERT INTO "body_weight" VALUES(2,'2002-02-20',100.0);
+INSERT INTO "body_weight" VALUES(2,'2002-02-21',101.0);
+INSERT INTO "body_weight" VALUES(2,'2002-02-22',102.0);
+INSERT INTO "body_weight" VALUES(2,'2002-02-24',104.0);
+INSERT INTO "body_weight" VALUES(2,'2002-02-25',105.0);
+INSERT INTO "body_weight" VALUES(2,'2002-02-26',106.0);
+INSERT INTO "body_weight" VALUES(2,'2002-02-28',108.0);
+INSERT INTO "body_weight" VALUES(2,'2002-03-01',109.0);
+INSERT INTO "body_weight" VALUES(2,'2002-03-02',110.0);
+INSERT INTO "body_weight" VALUES(2,'2002-03-03',111.0);
+INSERT INTO "body_weight" VALUES(2,'2002-03-05',113.0);
+INSERT INTO "body_weight" VALUES(2,'2002-03-06',114.0);
+INSERT INTO "body_weight" VALUES(2,'2002-03-07',115.0);
+INSERT INTO "body_weight" VALUES(2,'2002-03-08',116.0);
+INSERT INTO "body_weight" VALUES(2,'2002-03-10',118.0);
+INSERT INTO "body_weight" VALUES(2,'2002-03-11',119.0);
+INSERT INTO "body_weight" VALUES(2,'2002-03-12',120.0);
+CREATE TABLE exercise (
+	id INTEGER NOT NULL,
+	user_id INTEGER NOT NULL,
+	name VARCHAR NOT NULL,
+	movement_pattern VARCHAR,
+	equipment VARCHAR,
+	laterality VARCHAR,
+	training_max FLOAT,
+	CONSTRAINT ck_exercise_equipment_type_text_or_null CHECK (typeof(equipment) = 'text' or typeof(equipment) = 'null'),
+	CONSTRAINT ck_exercise_laterality_type_text_or_null CHECK (typeof(laterality) = 'text' or typeof(laterality) = 'null'),
+	CONSTRAINT ck_exercise_movement_pattern_type_text_or_null CHECK (typeof(movement_pattern) = 'text' or typeof(movement_pattern) = 'null'),
+	CONSTRAINT ck_exercise_training_max_gt_0 CHECK (training_max > 0),
+	CONSTRAINT ck_exercise_training_max_type_real_or_null CHECK (typeof(training_max) = 'real' or typeof(training_max) = 'null'),
+	CONSTRAINT fk_exercise_user_id_user FOREIGN KEY(user_id) REFERENCES user (id) ON DELETE CASCADE,
+	CONSTRAINT pk_exercise PRIMARY KEY (id),
+	CONSTRAINT uq_exercise_user_id UNIQUE (user_id, name)
+);
+INSERT INTO "exercise" VALUES(1,1,'Exercise 1','Squat','Barbell','Bilateral',100.0);
+INSERT INTO "exercise" VALUES(2,2,'Exercise 2',NULL,NULL,NULL,NULL);
+INSERT INTO "exercise" VALUES(3,1,'Exercise 2','Push','Dumbbell',NULL,NULL);
+INSERT INTO "exercise" VALUES(4,2,'Exercise 3',NULL,NULL,'Unilateral',62.5);
+INSERT INTO "exercise" VALUES(5,1,'Unused Exercise',NULL,NULL,NULL,NULL);
+CREATE TABLE exercise_alternative (
+	exercise_id INTEGER NOT NULL,
+	alternative_id INTEGER NOT NULL,
+	CONSTRAINT fk_exercise_alternative_alternative_id_exercise FOREIGN KEY(alternative_id) REFERENCES exercise (id) ON DELETE CASCADE,
+	CONSTRAINT fk_exercise_alternative_exercise_id_exercise FOREIGN KEY(exercise_id) REFERENCES exercise (id) ON DELETE CASCADE,
+	CONSTRAINT pk_exercise_alternative PRIMARY KEY (exercise_id, alternative_id)
+);
+INSERT INTO "exercise_alternative" VALUES(1,5);
+INSERT INTO "exercise_alternative" VALUES(3,5);
+INSERT INTO "exercise_alternative" VALUES(5,1);
+CREATE TABLE exercise_muscle (
+	exercise_id INTEGER NOT NULL,
+	muscle_group VARCHAR NOT NULL,
+	secondary BOOLEAN NOT NULL,
+	CONSTRAINT ck_exercise_muscle_muscle_group_type_text CHECK (typeof(muscle_group) = 'text'),
+	CONSTRAINT ck_exercise_muscle_secondary_ge_0 CHECK (secondary >= 0),
+	CONSTRAINT ck_exercise_muscle_secondary_le_1 CHECK (secondary <= 1),
+	CONSTRAINT ck_exercise_muscle_secondary_type_integer CHECK (typeof(secondary) = 'integer'),
+	CONSTRAINT fk_exercise_muscle_exercise_id_exercise FOREIGN KEY(exercise_id) REFERENCES exercise (id) ON DELETE CASCADE,
+	CONSTRAINT pk_exercise_muscle PRIMARY KEY (exercise_id, muscle_group)
+);
+INSERT INTO "exercise_muscle" VALUES(1,'Quadriceps',0);
+INSERT INTO "exercise_muscle" VALUES(1,'Glutes',1);
+INSERT INTO "exercise_muscle" VALUES(3,'Chest',0);
+INSERT INTO "exercise_muscle" VALUES(3,'Triceps',1);
+INSERT INTO "exercise_muscle" VALUES(4,'Hamstrings',0);
+CREATE TABLE period (
+	user_id INTEGER NOT NULL,
+	date DATE NOT NULL,
+	intensity INTEGER NOT NULL,
+	CONSTRAINT ck_period_intensity_ge_1 CHECK (intensity >= 1),
+	CONSTRAINT ck_period_intensity_le_4 CHECK (intensity <= 4),
+	CONSTRAINT ck_period_intensity_type_integer CHECK (typeof(intensity) = 'integer'),
+	CONSTRAINT fk_period_user_id_user FOREIGN KEY(user_id) REFERENCES user (id) ON DELETE CASCADE,
+	CONSTRAINT pk_period PRIMARY KEY (user_id, date)
+);
+INSERT INTO "period" VALUES(1,'2002-02-20',2);
+INSERT INTO "period" VALUES(1,'2002-02-21',4);
+INSERT INTO "period" VALUES(1,'2002-02-22',1);
+CREATE TABLE program (
+	id INTEGER NOT NULL,
+	user_id INTEGER NOT NULL,
+	name VARCHAR NOT NULL,
+	position_week INTEGER,
+	position_day INTEGER,
+	CONSTRAINT ck_program_position_day_ge_0 CHECK (position_day >= 0),
+	CONSTRAINT ck_program_position_day_type_integer_or_null CHECK (typeof(position_day) = 'integer' or typeof(position_day) = 'null'),
+	CONSTRAINT ck_program_position_week_ge_0 CHECK (position_week >= 0),
+	CONSTRAINT ck_program_position_week_type_integer_or_null CHECK (typeof(position_week) = 'integer' or typeof(position_week) = 'null'),
+	CONSTRAINT fk_program_user_id_user FOREIGN KEY(user_id) REFERENCES user (id) ON DELETE CASCADE,
+	CONSTRAINT pk_program PRIMARY KEY (id),
+	CONSTRAINT uq_program_user_id UNIQUE (user_id, name)
+);
+INSERT INTO "program" VALUES(1,1,'P1',1,0);
+INSERT INTO "program" VALUES(2,2,'P1',NULL,NULL);
+CREATE TABLE program_routine (
+	program_id INTEGER NOT NULL,
+	week_position INTEGER NOT NULL,
+	position INTEGER NOT NULL,
+	routine_id INTEGER NOT NULL,
+	CONSTRAINT ck_program_routine_position_gt_0 CHECK (position > 0),
+	CONSTRAINT ck_program_routine_position_type_integer CHECK (typeof(position) = 'integer'),
+	CONSTRAINT fk_program_routine_program_id_program_week FOREIGN KEY(program_id, week_position) REFERENCES program_week (program_id, position) ON DELETE CASCADE,
+	CONSTRAINT fk_program_routine_routine_id_routine FOREIGN KEY(routine_id) REFERENCES routine (id) ON DELETE CASCADE,
+	CONSTRAINT pk_program_routine PRIMARY KEY (program_id, week_position, position)
+);
+INSERT INTO "program_routine" VALUES(1,1,1,1);
+INSERT INTO "program_routine" VALUES(1,1,2,3);
+INSERT INTO "program_routine" VALUES(1,2,1,1);
+INSERT INTO "program_routine" VALUES(2,1,1,2);
+CREATE TABLE program_week (
+	program_id INTEGER NOT NULL,
+	position INTEGER NOT NULL,
+	weight_percentage FLOAT NOT NULL,
+	rpe_offset FLOAT NOT NULL,
+	CONSTRAINT ck_program_week_position_gt_0 CHECK (position > 0),
+	CONSTRAINT ck_program_week_position_type_integer CHECK (typeof(position) = 'integer'),
+	CONSTRAINT ck_program_week_rpe_offset_type_real CHECK (typeof(rpe_offset) = 'real'),
+	CONSTRAINT ck_program_week_weight_percentage_gt_0 CHECK (weight_percentage > 0),
+	CONSTRAINT ck_program_week_weight_percentage_type_real CHECK (typeof(weight_percentage) = 'real'),
+	CONSTRAINT fk_program_week_program_id_program FOREIGN KEY(program_id) REFERENCES program (id) ON DELETE CASCADE,
+	CONSTRAINT pk_program_week PRIMARY KEY (program_id, position)
+);
+INSERT INTO "program_week" VALUES(1,1,100.0,0.0);
+INSERT INTO "program_week" VALUES(1,2,60.0,-2.0);
+INSERT INTO "program_week" VALUES(2,1,100.0,0.0);
+CREATE TABLE program_workout (
+	workout_id INTEGER NOT NULL,
+	program_id INTEGER NOT NULL,
+	weight_percentage FLOAT NOT NULL,
+	CONSTRAINT ck_program_workout_weight_percentage_gt_0 CHECK (weight_percentage > 0),
+	CONSTRAINT ck_program_workout_weight_percentage_type_real CHECK (typeof(weight_percentage) = 'real'),
+	CONSTRAINT fk_program_workout_program_id_program FOREIGN KEY(program_id) REFERENCES program (id) ON DELETE CASCADE,
+	CONSTRAINT fk_program_workout_workout_id_workout FOREIGN KEY(workout_id) REFERENCES workout (id) ON DELETE CASCADE,
+	CONSTRAINT pk_program_workout PRIMARY KEY (workout_id)
+);
+INSERT INTO "program_workout" VALUES(1,1,100.0);
+INSERT INTO "program_workout" VALUES(4,1,100.0);
+CREATE TABLE routine (
+	id INTEGER NOT NULL,
+	user_id INTEGER NOT NULL,
+	name VARCHAR NOT NULL,
+	notes VARCHAR,
+	CONSTRAINT fk_routine_user_id_user FOREIGN KEY(user_id) REFERENCES user (id) ON DELETE CASCADE,
+	CONSTRAINT pk_routine PRIMARY KEY (id),
+	CONSTRAINT uq_routine_user_id UNIQUE (user_id, name)
+);
+INSERT INTO "routine" VALUES(1,1,'R1','First Routine');
+INSERT INTO "routine" VALUES(2,2,'R1','');
+INSERT INTO "routine" VALUES(3,1,'R2',NULL);
+INSERT INTO "routine" VALUES(4,2,'Empty','TBD');
+CREATE TABLE routine_activity (
+	id INTEGER NOT NULL,
+	exercise_id INTEGER,
+	reps INTEGER NOT NULL,
+	time INTEGER NOT NULL,
+	weight FLOAT NOT NULL,
+	rpe FLOAT NOT NULL,
+	automatic BOOLEAN NOT NULL,
+	set_type VARCHAR DEFAULT 'Working' NOT NULL,
+	auto_warm_up BOOLEAN DEFAULT 0 NOT NULL,
+	relative_weight_percentage FLOAT,
+	relative_weight_basis VARCHAR,
+	progression VARCHAR,
+	progression_increment FLOAT,
+	progression_max_reps INTEGER,
+	CONSTRAINT ck_routine_activity_auto_warm_up_ge_0 CHECK (auto_warm_up >= 0),
+	CONSTRAINT ck_routine_activity_auto_warm_up_le_1 CHECK (auto_warm_up <= 1),
+	CONSTRAINT ck_routine_activity_auto_warm_up_type_integer CHECK (typeof(auto_warm_up) = 'integer'),
+	CONSTRAINT ck_routine_activity_progression_increment_ge_0 CHECK (progression_increment >= 0),
+	CONSTRAINT ck_routine_activity_progression_increment_type_real_or_null CHECK (typeof(progression_increment) = 'real' or typeof(progression_increment) = 'null'),
+	CONSTRAINT ck_routine_activity_progression_max_reps_gt_0 CHECK (progression_max_reps > 0),
+	CONSTRAINT ck_routine_activity_progression_max_reps_type_integer_or_null CHECK (typeof(progression_max_reps) = 'integer' or typeof(progression_max_reps) = 'null'),
+	CONSTRAINT ck_routine_activity_progression_type_text_or_null CHECK (typeof(progression) = 'text' or typeof(progression) = 'null'),
+	CONSTRAINT ck_routine_activity_relative_weight_basis_type_text_or_null CHECK (typeof(relative_weight_basis) = 'text' or typeof(relative_weight_basis) = 'null'),
+	CONSTRAINT ck_routine_activity_relative_weight_percentage_gt_0 CHECK (relative_weight_percentage > 0),
+	CONSTRAINT ck_routine_activity_relative_weight_percentage_type_real_or_null CHECK (typeof(relative_weight_percentage) = 'real' or typeof(relative_weight_percentage) = 'null'),
+	CONSTRAINT ck_routine_activity_reps_ge_0 CHECK (reps >= 0),
+	CONSTRAINT ck_routine_activity_reps_type_integer CHECK (typeof(reps) = 'integer'),
+	CONSTRAINT ck_routine_activity_rpe_ge_0 CHECK (rpe >= 0),
+	CONSTRAINT ck_routine_activity_rpe_le_10 CHECK (rpe <= 10),
+	CONSTRAINT ck_routine_activity_rpe_type_real CHECK (typeof(rpe) = 'real'),
+	CONSTRAINT ck_routine_activity_set_type_type_text CHECK (typeof(set_type) = 'text'),
+	CONSTRAINT ck_routine_activity_time_ge_0 CHECK (time >= 0),
+	CONSTRAINT ck_routine_activity_time_type_integer CHECK (typeof(time) = 'integer'),
+	CONSTRAINT ck_routine_activity_weight_ge_0 CHECK (weight >= 0),
+	CONSTRAINT ck_routine_activity_weight_type_real CHECK (typeof(weight) = 'real'),
+	CONSTRAINT fk_routine_activity_exercise_id_exercise FOREIGN KEY(exercise_id) REFERENCES exercise (id) ON DELETE CASCADE,
+	CONSTRAINT fk_routine_activity_id_routine_part FOREIGN KEY(id) REFERENCES routine_part (id),
+	CONSTRAINT pk_routine_activity PRIMARY KEY (id)
+);
+INSERT INTO "routine_activity" VALUES(5,1,0,0,0.0,0.0,0,'Working',1,75.0,'TrainingMax',NULL,NULL,NULL);
+INSERT INTO "routine_activity" VALUES(6,NULL,0,60,0.0,0.0,0,'Working',0,NULL,NULL,NULL,NULL,NULL);
+INSERT INTO "routine_activity" VALUES(8,3,0,0,0.0,0.0,0,'WarmUp',0,80.0,'OneRepMax',NULL,NULL,NULL);
+INSERT INTO "routine_activity" VALUES(9,NULL,0,30,0.0,0.0,0,'Working',0,NULL,NULL,NULL,NULL,NULL);
+INSERT INTO "routine_activity" VALUES(10,3,0,20,0.0,0.0,1,'Working',0,NULL,NULL,NULL,NULL,NULL);
+INSERT INTO "routine_activity" VALUES(11,NULL,0,10,0.0,0.0,1,'Working',0,NULL,NULL,NULL,NULL,NULL);
+INSERT INTO "routine_activity" VALUES(12,3,0,20,0.0,0.0,1,'Working',0,NULL,NULL,NULL,NULL,NULL);
+INSERT INTO "routine_activity" VALUES(13,NULL,0,10,0.0,0.0,1,'Working',0,NULL,NULL,NULL,NULL,NULL);
+INSERT INTO "routine_activity" VALUES(14,NULL,0,30,0.0,0.0,0,'Working',0,NULL,NULL,NULL,NULL,NULL);
+INSERT INTO "routine_activity" VALUES(15,1,0,0,0.0,0.0,0,'Amrap',0,NULL,NULL,'Double',2.5,12);
+INSERT INTO "routine_activity" VALUES(18,2,0,0,0.0,0.0,0,'Working',1,NULL,NULL,'Linear',5.0,NULL);
+INSERT INTO "routine_activity" VALUES(19,4,0,0,0.0,0.0,0,'Working',0,NULL,NULL,NULL,NULL,NULL);
+CREATE TABLE routine_part (
+	id INTEGER NOT NULL,
+	type VARCHAR NOT NULL,
+	routine_section_id INTEGER,
+	position INTEGER NOT NULL,
+	CONSTRAINT ck_routine_part_position_gt_0 CHECK (position > 0),
+	CONSTRAINT ck_routine_part_position_type_integer CHECK (typeof(position) = 'integer'),
+	CONSTRAINT ck_routine_part_type_type_text CHECK (typeof(type) = 'text'),
+	CONSTRAINT fk_routine_part_routine_section_id_routine_section FOREIGN KEY(routine_section_id) REFERENCES routine_section (id) ON DELETE CASCADE,
+	CONSTRAINT pk_routine_part PRIMARY KEY (id)
+);
+INSERT INTO "routine_part" VALUES(1,'routine_section',NULL,2);
+INSERT INTO "routine_part" VALUES(2,'routine_section',NULL,1);
+INSERT INTO "routine_part" VALUES(3,'routine_section',NULL,3);
+INSERT INTO "routine_part" VALUES(4,'routine_section',NULL,1);
+INSERT INTO "routine_part" VALUES(5,'routine_activity',1,1);
+INSERT INTO "routine_part" VALUES(6,'routine_activity',1,2);
+INSERT INTO "routine_part" VALUES(7,'routine_section',1,3);
+INSERT INTO "routine_part" VALUES(8,'routine_activity',2,1);
+INSERT INTO "routine_part" VALUES(9,'routine_activity',2,2);
+INSERT INTO "routine_part" VALUES(10,'routine_activity',3,1);
+INSERT INTO "routine_part" VALUES(11,'routine_activity',3,2);
+INSERT INTO "routine_part" VALUES(12,'routine_activity',4,1);
+INSERT INTO "routine_part" VALUES(13,'routine_activity',4,2);
+INSERT INTO "routine_part" VALUES(14,'routine_activity',7,2);
+INSERT INTO "routine_part" VALUES(15,'routine_activity',7,1);
+INSERT INTO "routine_part" VALUES(16,'routine_section',NULL,1);
+INSERT INTO "routine_part" VALUES(17,'routine_section',NULL,2);
+INSERT INTO "routine_part" VALUES(18,'routine_activity',16,1);
+INSERT INTO "routine_part" VALUES(19,'routine_activity',17,1);
+CREATE TABLE routine_section (
+	id INTEGER NOT NULL,
+	routine_id INTEGER,
+	rounds INTEGER NOT NULL,
+	kind VARCHAR DEFAULT 'Sequential' NOT NULL,
+	interval INTEGER,
+	time_cap INTEGER,
+	work INTEGER,
+	rest INTEGER,
+	CONSTRAINT ck_routine_section_interval_gt_0 CHECK (interval > 0),
+	CONSTRAINT ck_routine_section_interval_type_integer_or_null CHECK (typeof(interval) = 'integer' or typeof(interval) = 'null'),
+	CONSTRAINT ck_routine_section_kind_type_text CHECK (typeof(kind) = 'text'),
+	CONSTRAINT ck_routine_section_rest_ge_0 CHECK (rest >= 0),
+	CONSTRAINT ck_routine_section_rest_type_integer_or_null CHECK (typeof(rest) = 'integer' or typeof(rest) = 'null'),
+	CONSTRAINT ck_routine_section_rounds_gt_0 CHECK (rounds > 0),
+	CONSTRAINT ck_routine_section_rounds_type_integer CHECK (typeof(rounds) = 'integer'),
+	CONSTRAINT ck_routine_section_time_cap_ge_0 CHECK (time_cap >= 0),
+	CONSTRAINT ck_routine_section_time_cap_type_integer_or_null CHECK (typeof(time_cap) = 'integer' or typeof(time_cap) = 'null'),
+	CONSTRAINT ck_routine_section_work_gt_0 CHECK (work > 0),
+	CONSTRAINT ck_routine_section_work_type_integer_or_null CHECK (typeof(work) = 'integer' or typeof(work) = 'null'),
+	CONSTRAINT fk_routine_section_id_routine_part FOREIGN KEY(id) REFERENCES routine_part (id),
+	CONSTRAINT fk_routine_section_routine_id_routine FOREIGN KEY(routine_id) REFERENCES routine (id) ON DELETE CASCADE,
+	CONSTRAINT pk_routine_section PRIMARY KEY (id)
+);
+INSERT INTO "routine_section" VALUES(1,1,2,'Sequential',NULL,NULL,NULL,NULL);
+INSERT INTO "routine_section" VALUES(2,1,1,'Sequential',NULL,NULL,NULL,NULL);
+INSERT INTO "routine_section" VALUES(3,1,3,'Sequential',NULL,NULL,NULL,NULL);
+INSERT INTO "routine_section" VALUES(4,3,5,'Amrap',NULL,600,NULL,NULL);
+INSERT INTO "routine_section" VALUES(7,NULL,2,'Sequential',NULL,NULL,NULL,NULL);
+INSERT INTO "routine_section" VALUES(16,2,3,'Emom',60,NULL,NULL,NULL);
+INSERT INTO "routine_section" VALUES(17,2,4,'Tabata',NULL,NULL,20,10);
+CREATE TABLE schedule (
+	user_id INTEGER NOT NULL,
+	start DATE NOT NULL,
+	interval INTEGER,
+	CONSTRAINT ck_schedule_interval_gt_0 CHECK (interval > 0),
+	CONSTRAINT ck_schedule_interval_type_integer_or_null CHECK (typeof(interval) = 'integer' or typeof(interval) = 'null'),
+	CONSTRAINT fk_schedule_user_id_user FOREIGN KEY(user_id) REFERENCES user (id) ON DELETE CASCADE,
+	CONSTRAINT pk_schedule PRIMARY KEY (user_id)
+);
+CREATE TABLE schedule_routine (
+	user_id INTEGER NOT NULL,
+	position INTEGER NOT NULL,
+	routine_id INTEGER NOT NULL,
+	CONSTRAINT ck_schedule_routine_position_gt_0 CHECK (position > 0),
+	CONSTRAINT ck_schedule_routine_position_type_integer CHECK (typeof(position) = 'integer'),
+	CONSTRAINT fk_schedule_routine_routine_id_routine FOREIGN KEY(routine_id) REFERENCES routine (id) ON DELETE CASCADE,
+	CONSTRAINT fk_schedule_routine_user_id_schedule FOREIGN KEY(user_id) REFERENCES schedule (user_id) ON DELETE CASCADE,
+	CONSTRAINT pk_schedule_routine PRIMARY KEY (user_id, position)
+);
+CREATE TABLE user (
+	id INTEGER NOT NULL,
+	name VARCHAR NOT NULL,
+	sex VARCHAR(6) NOT NULL,
+	CONSTRAINT pk_user PRIMARY KEY (id),
+	CONSTRAINT uq_user_name UNIQUE (name)
+);
+INSERT INTO "user" VALUES(1,'Alice','FEMALE');
+INSERT INTO "user" VALUES(2,'Bob','MALE');
+CREATE TABLE workout (
+	id INTEGER NOT NULL,
+	user_id INTEGER NOT NULL,
+	routine_id INTEGER,
+	date DATE NOT NULL,
+	notes VARCHAR,
+	session_rpe FLOAT,
+	duration INTEGER,
+	CONSTRAINT ck_workout_duration_gt_0 CHECK (duration > 0),
+	CONSTRAINT ck_workout_duration_type_integer_or_null CHECK (typeof(duration) = 'integer' or typeof(duration) = 'null'),
+	CONSTRAINT ck_workout_session_rpe_ge_0 CHECK (session_rpe >= 0),
+	CONSTRAINT ck_workout_session_rpe_le_10 CHECK (session_rpe <= 10),
+	CONSTRAINT ck_workout_session_rpe_type_real_or_null CHECK (typeof(session_rpe) = 'real' or typeof(session_rpe) = 'null'),
+	CONSTRAINT fk_workout_routine_id_routine FOREIGN KEY(routine_id) REFERENCES routine (id) ON DELETE CASCADE,
+	CONSTRAINT fk_workout_user_id_user FOREIGN KEY(user_id) REFERENCES user (id) ON DELETE CASCADE,
+	CONSTRAINT pk_workout PRIMARY KEY (id)
+);
+INSERT INTO "workout" VALUES(1,1,1,'2002-02-20','First Workout',8.5,3600);
+INSERT INTO "workout" VALUES(2,2,NULL,'2002-02-20','',7.0,NULL);
+INSERT INTO "workout" VALUES(3,1,NULL,'2002-02-22',NULL,NULL,NULL);
+INSERT INTO "workout" VALUES(4,1,1,'2002-02-24',NULL,NULL,2700);
+CREATE TABLE workout_element (
+	workout_id INTEGER NOT NULL,
+	position INTEGER NOT NULL,
+	type VARCHAR NOT NULL,
+	automatic BOOLEAN NOT NULL,
+	CONSTRAINT ck_workout_element_automatic_type_integer CHECK (typeof(automatic) = 'integer'),
+	CONSTRAINT ck_workout_element_position_gt_0 CHECK (position > 0),
+	CONSTRAINT ck_workout_element_position_type_integer CHECK (typeof(position) = 'integer'),
+	CONSTRAINT ck_workout_element_rpe_ge_0 CHECK (automatic >= 0),
+	CONSTRAINT ck_workout_element_rpe_le_1 CHECK (automatic <= 1),
+	CONSTRAINT ck_workout_element_type_type_text CHECK (typeof(type) = 'text'),
+	CONSTRAINT fk_workout_element_workout_id_workout FOREIGN KEY(workout_id) REFERENCES workout (id) ON DELETE CASCADE,
+	CONSTRAINT pk_workout_element PRIMARY KEY (workout_id, position)
+);
+INSERT INTO "workout_element" VALUES(1,2,'set',0);
+INSERT INTO "workout_element" VALUES(1,1,'set',0);
+INSERT INTO "workout_element" VALUES(1,3,'set',0);
+INSERT INTO "workout_element" VALUES(4,2,'set',0);
+INSERT INTO "workout_element" VALUES(4,1,'set',0);
+INSERT INTO "workout_element" VALUES(4,3,'set',0);
+INSERT INTO "workout_element" VALUES(3,1,'set',0);
+INSERT INTO "workout_element" VALUES(3,2,'set',0);
+INSERT INTO "workout_element" VALUES(3,3,'set',0);
+INSERT INTO "workout_element" VALUES(3,4,'set',0);
+INSERT INTO "workout_element" VALUES(3,5,'set',0);
+INSERT INTO "workout_element" VALUES(2,1,'set',0);
+INSERT INTO "workout_element" VALUES(2,4,'set',0);
+INSERT INTO "workout_element" VALUES(2,5,'set',0);
+INSERT INTO "workout_element" VALUES(2,6,'set',0);
+INSERT INTO "workout_element" VALUES(2,7,'set',0);
+INSERT INTO "workout_element" VALUES(2,2,'set',0);
+INSERT INTO "workout_element" VALUES(2,3,'set',0);
+CREATE TABLE workout_interval_block (
+	workout_id INTEGER NOT NULL,
+	position INTEGER NOT NULL,
+	kind VARCHAR NOT NULL,
+	interval INTEGER,
+	time_cap INTEGER,
+	work INTEGER,
+	rest INTEGER,
+	start_index INTEGER NOT NULL,
+	end_index INTEGER NOT NULL,
+	rounds INTEGER NOT NULL,
+	achieved_rounds INTEGER,
+	time INTEGER,
+	CONSTRAINT ck_workout_interval_block_achieved_rounds_ge_0 CHECK (achieved_rounds >= 0),
+	CONSTRAINT ck_workout_interval_block_achieved_rounds_type_integer_or_null CHECK (typeof(achieved_rounds) = 'integer' or typeof(achieved_rounds) = 'null'),
+	CONSTRAINT ck_workout_interval_block_end_index_ge_start_index CHECK (end_index >= start_index),
+	CONSTRAINT ck_workout_interval_block_end_index_type_integer CHECK (typeof(end_index) = 'integer'),
+	CONSTRAINT ck_workout_interval_block_interval_gt_0 CHECK (interval > 0),
+	CONSTRAINT ck_workout_interval_block_interval_type_integer_or_null CHECK (typeof(interval) = 'integer' or typeof(interval) = 'null'),
+	CONSTRAINT ck_workout_interval_block_kind_type_text CHECK (typeof(kind) = 'text'),
+	CONSTRAINT ck_workout_interval_block_position_gt_0 CHECK (position > 0),
+	CONSTRAINT ck_workout_interval_block_position_type_integer CHECK (typeof(position) = 'integer'),
+	CONSTRAINT ck_workout_interval_block_rest_ge_0 CHECK (rest >= 0),
+	CONSTRAINT ck_workout_interval_block_rest_type_integer_or_null CHECK (typeof(rest) = 'integer' or typeof(rest) = 'null'),
+	CONSTRAINT ck_workout_interval_block_rounds_gt_0 CHECK (rounds > 0),
+	CONSTRAINT ck_workout_interval_block_rounds_type_integer CHECK (typeof(rounds) = 'integer'),
+	CONSTRAINT ck_workout_interval_block_start_index_ge_0 CHECK (start_index >= 0),
+	CONSTRAINT ck_workout_interval_block_start_index_type_integer CHECK (typeof(start_index) = 'integer'),
+	CONSTRAINT ck_workout_interval_block_time_cap_ge_0 CHECK (time_cap >= 0),
+	CONSTRAINT ck_workout_interval_block_time_cap_type_integer_or_null CHECK (typeof(time_cap) = 'integer' or typeof(time_cap) = 'null'),
+	CONSTRAINT ck_workout_interval_block_time_ge_0 CHECK (time >= 0),
+	CONSTRAINT ck_workout_interval_block_time_type_integer_or_null CHECK (typeof(time) = 'integer' or typeof(time) = 'null'),
+	CONSTRAINT ck_workout_interval_block_work_gt_0 CHECK (work > 0),
+	CONSTRAINT ck_workout_interval_block_work_type_integer_or_null CHECK (typeof(work) = 'integer' or typeof(work) = 'null'),
+	CONSTRAINT fk_workout_interval_block_workout_id_workout FOREIGN KEY(workout_id) REFERENCES workout (id) ON DELETE CASCADE,
+	CONSTRAINT pk_workout_interval_block PRIMARY KEY (workout_id, position)
+);
+INSERT INTO "workout_interval_block" VALUES(3,1,'Amrap',NULL,600,NULL,NULL,0,4,1,1,540);
+INSERT INTO "workout_interval_block" VALUES(2,1,'Emom',60,NULL,NULL,NULL,0,2,3,3,NULL);
+CREATE TABLE workout_rest (
+	workout_id INTEGER NOT NULL,
+	position INTEGER NOT NULL,
+	target_time INTEGER,
+	CONSTRAINT ck_workout_rest_position_gt_0 CHECK (position > 0),
+	CONSTRAINT ck_workout_rest_position_type_integer CHECK (typeof(position) = 'integer'),
+	CONSTRAINT ck_workout_rest_target_time_gt_0 CHECK (target_time > 0),
+	CONSTRAINT ck_workout_rest_target_time_type_integer_or_null CHECK (typeof(target_time) = 'integer' or typeof(target_time) = 'null'),
+	CONSTRAINT fk_workout_rest_workout_id_workout_element FOREIGN KEY(workout_id, position) REFERENCES workout_element (workout_id, position) ON DELETE CASCADE,
+	CONSTRAINT pk_workout_rest PRIMARY KEY (workout_id, position)
+);
+CREATE TABLE workout_set (
+	workout_id INTEGER NOT NULL,
+	position INTEGER NOT NULL,
+	exercise_id INTEGER NOT NULL,
+	reps INTEGER,
+	time INTEGER,
+	weight FLOAT,
+	rpe FLOAT,
+	target_reps INTEGER,
+	target_time INTEGER,
+	target_weight FLOAT,
+	target_rpe FLOAT,
+	set_type VARCHAR DEFAULT 'Working' NOT NULL,
+	CONSTRAINT ck_workout_set_position_gt_0 CHECK (position > 0),
+	CONSTRAINT ck_workout_set_position_type_integer CHECK (typeof(position) = 'integer'),
+	CONSTRAINT ck_workout_set_reps_gt_0 CHECK (reps > 0),
+	CONSTRAINT ck_workout_set_reps_type_integer_or_null CHECK (typeof(reps) = 'integer' or typeof(reps) = 'null'),
+	CONSTRAINT ck_workout_set_rpe_ge_0 CHECK (rpe >= 0),
+	CONSTRAINT ck_workout_set_rpe_le_10 CHECK (rpe <= 10),
+	CONSTRAINT ck_workout_set_rpe_type_real_or_null CHECK (typeof(rpe) = 'real' or typeof(rpe) = 'null'),
+	CONSTRAINT ck_workout_set_set_type_type_text CHECK (typeof(set_type) = 'text'),
+	CONSTRAINT ck_workout_set_target_reps_gt_0 CHECK (target_reps > 0),
+	CONSTRAINT ck_workout_set_target_reps_type_integer_or_null CHECK (typeof(target_reps) = 'integer' or typeof(target_reps) = 'null'),
+	CONSTRAINT ck_workout_set_target_rpe_ge_0 CHECK (target_rpe >= 0),
+	CONSTRAINT ck_workout_set_target_rpe_le_10 CHECK (target_rpe <= 10),
+	CONSTRAINT ck_workout_set_target_rpe_type_real_or_null CHECK (typeof(target_rpe) = 'real' or typeof(target_rpe) = 'null'),
+	CONSTRAINT ck_workout_set_target_time_gt_0 CHECK (target_time > 0),
+	CONSTRAINT ck_workout_set_target_time_type_integer_or_null CHECK (typeof(target_time) = 'integer' or typeof(target_time) = 'null'),
+	CONSTRAINT ck_workout_set_target_weight_gt_0 CHECK (target_weight > 0),
+	CONSTRAINT ck_workout_set_target_weight_type_real_or_null CHECK (typeof(target_weight) = 'real' or typeof(target_weight) = 'null'),
+	CONSTRAINT ck_workout_set_time_gt_0 CHECK (time > 0),
+	CONSTRAINT ck_workout_set_time_type_integer_or_null CHECK (typeof(time) = 'integer' or typeof(time) = 'null'),
+	CONSTRAINT ck_workout_set_weight_gt_0 CHECK (weight > 0),
+	CONSTRAINT ck_workout_set_weight_type_real_or_null CHECK (typeof(weight) = 'real' or typeof(weight) = 'null'),
+	CONSTRAINT fk_workout_set_exercise_id_exercise FOREIGN KEY(exercise_id) REFERENCES exercise (id) ON DELETE CASCADE,
+	CONSTRAINT fk_workout_set_workout_id_workout_element FOREIGN KEY(workout_id, position) REFERENCES workout_element (workout_id, position) ON DELETE CASCADE,
+	CONSTRAINT pk_workout_set PRIMARY KEY (workout_id, position)
+);
+INSERT INTO "workout_set" VALUES(1,2,1,9,4,NULL,8.5,NULL,NULL,NULL,NULL,'Working');
+INSERT INTO "workout_set" VALUES(1,1,3,10,4,NULL,8.0,NULL,NULL,NULL,NULL,'Working');
+INSERT INTO "workout_set" VALUES(1,3,1,NULL,60,NULL,9.0,NULL,NULL,NULL,NULL,'Working');
+INSERT INTO "workout_set" VALUES(4,2,1,9,4,NULL,8.0,NULL,NULL,NULL,NULL,'Working');
+INSERT INTO "workout_set" VALUES(4,1,3,11,4,NULL,8.5,NULL,NULL,NULL,NULL,'Working');
+INSERT INTO "workout_set" VALUES(4,3,1,NULL,60,NULL,8.5,NULL,NULL,NULL,NULL,'Working');
+INSERT INTO "workout_set" VALUES(3,1,3,9,NULL,NULL,NULL,NULL,NULL,NULL,NULL,'WarmUp');
+INSERT INTO "workout_set" VALUES(3,2,3,8,NULL,NULL,NULL,NULL,NULL,NULL,NULL,'Working');
+INSERT INTO "workout_set" VALUES(3,3,3,7,NULL,NULL,NULL,NULL,NULL,NULL,NULL,'Working');
+INSERT INTO "workout_set" VALUES(3,4,3,6,NULL,NULL,NULL,NULL,NULL,NULL,NULL,'Working');
+INSERT INTO "workout_set" VALUES(3,5,3,5,NULL,NULL,NULL,NULL,NULL,NULL,NULL,'Amrap');
+INSERT INTO "workout_set" VALUES(2,1,2,10,4,10.0,8.5,NULL,NULL,NULL,NULL,'Working');
+INSERT INTO "workout_set" VALUES(2,4,4,7,NULL,7.5,NULL,NULL,NULL,NULL,NULL,'Working');
+INSERT INTO "workout_set" VALUES(2,5,4,6,NULL,7.5,NULL,NULL,NULL,NULL,NULL,'Working');
+INSERT INTO "workout_set" VALUES(2,6,4,5,NULL,7.5,NULL,NULL,NULL,NULL,NULL,'Drop');
+INSERT INTO "workout_set" VALUES(2,7,4,4,NULL,7.5,NULL,NULL,NULL,NULL,NULL,'Drop');
+INSERT INTO "workout_set" VALUES(2,2,2,9,4,8.0,9.0,NULL,NULL,NULL,NULL,'Working');
+INSERT INTO "workout_set" VALUES(2,3,2,8,4,6.0,9.5,NULL,NULL,NULL,NULL,'Working');
+COMMIT;
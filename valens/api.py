@@ -1,10 +1,10 @@
 from __future__ import annotations
 
-from datetime import date
+from datetime import date, datetime, timezone
 from functools import singledispatch, wraps
 from http import HTTPStatus
 from itertools import chain
-from typing import Any, Callable, Optional
+from typing import Any, Callable, Optional, Union
 
 from flask import Blueprint, jsonify, request, session
 from flask.typing import ResponseReturnValue
@@ -14,18 +14,31 @@ from sqlalchemy.orm import selectinload
 
 from valens import database as db, version
 from valens.models import (
+    BarbellBar,
+    BarbellEquipment,
+    BarbellPlate,
     BodyFat,
     BodyWeight,
     Exercise,
+    ExerciseAlternative,
+    ExerciseMuscle,
     Period,
+    Program,
+    ProgramRoutine,
+    ProgramWeek,
+    ProgramWorkout,
     Routine,
     RoutineActivity,
     RoutinePart,
     RoutineSection,
+    RoutineVersion,
+    Schedule,
+    ScheduleRoutine,
     Sex,
     User,
     Workout,
     WorkoutElement,
+    WorkoutIntervalBlock,
     WorkoutRest,
     WorkoutSet,
 )
@@ -37,6 +50,48 @@ class DeserializationError(Exception):
     pass
 
 
+SECTION_KIND_PARAMETERS = {
+    "Sequential": [],
+    "Emom": ["interval"],
+    "Amrap": ["time_cap"],
+    "Tabata": ["work", "rest"],
+    "ForTime": ["time_cap"],
+}
+
+MUSCLE_GROUPS = {
+    "Chest",
+    "Back",
+    "LowerBack",
+    "Shoulders",
+    "Biceps",
+    "Triceps",
+    "Forearms",
+    "Abs",
+    "Glutes",
+    "Quadriceps",
+    "Hamstrings",
+    "Adductors",
+    "Calves",
+    "Neck",
+}
+
+MOVEMENT_PATTERNS = {"Push", "Pull", "Squat", "Hinge", "Lunge", "Carry", "Core", "Isolation"}
+
+EQUIPMENT = {"Barbell", "Dumbbell", "Kettlebell", "Machine", "Cable", "Band", "Bodyweight", "Other"}
+
+LATERALITIES = {"Bilateral", "Unilateral"}
+
+SET_TYPES = {"Working", "WarmUp", "Drop", "Failure", "Amrap", "BackOff"}
+
+WEIGHT_BASES = {"TrainingMax", "OneRepMax"}
+
+PROGRESSION_PARAMETERS = {
+    "Linear": ["increment"],
+    "Double": ["max_reps", "increment"],
+    "RPECapped": ["increment"],
+}
+
+
 @singledispatch
 def to_dict(
     model: object, exclude: Optional[list[str]] = None, include: Optional[list[str]] = None
@@ -44,6 +99,16 @@ def to_dict(
     return model_to_dict(model, exclude, include)
 
 
+@to_dict.register
+def _(model: Exercise) -> dict[str, object]:
+    return {
+        **model_to_dict(model),
+        "primary_muscles": sorted(m.muscle_group for m in model.muscles if not m.secondary),
+        "secondary_muscles": sorted(m.muscle_group for m in model.muscles if m.secondary),
+        "alternatives": sorted(a.alternative_id for a in model.alternatives),
+    }
+
+
 @to_dict.register
 def _(model: Routine) -> dict[str, object]:
     return {
@@ -55,7 +120,10 @@ def _(model: Routine) -> dict[str, object]:
 @to_dict.register
 def _(model: RoutineSection) -> dict[str, object]:
     return {
-        **model_to_dict(model, exclude=["id", "routine_id"]),
+        **model_to_dict(
+            model, exclude=["id", "routine_id", "kind", "interval", "time_cap", "work", "rest"]
+        ),
+        "kind": section_kind_to_dict(model),
         "parts": [to_dict(p) for p in sorted(model.parts, key=lambda x: x.position)],
     }
 
@@ -63,7 +131,62 @@ def _(model: RoutineSection) -> dict[str, object]:
 @to_dict.register
 def _(model: RoutineActivity) -> dict[str, object]:
     return {
-        **model_to_dict(model, exclude=["id"]),
+        **model_to_dict(
+            model,
+            exclude=[
+                "id",
+                "relative_weight_percentage",
+                "relative_weight_basis",
+                "progression",
+                "progression_increment",
+                "progression_max_reps",
+            ],
+        ),
+        "relative_weight": relative_weight_to_dict(model),
+        "progression": progression_to_dict(model),
+    }
+
+
+@to_dict.register
+def _(model: RoutineVersion) -> dict[str, object]:
+    return {
+        **model_to_dict(model, exclude=["created"]),
+        "created": model.created.replace(tzinfo=timezone.utc).isoformat(),
+    }
+
+
+@to_dict.register
+def _(model: Program) -> dict[str, object]:
+    return {
+        **model_to_dict(model, exclude=["user_id", "position_week", "position_day"]),
+        "weeks": [to_dict(w) for w in model.weeks],
+        "position": program_position_to_dict(model),
+        "weight_percentages": {str(w.workout_id): w.weight_percentage for w in model.workouts},
+    }
+
+
+@to_dict.register
+def _(model: ProgramWeek) -> dict[str, object]:
+    return {
+        **model_to_dict(model, exclude=["program_id", "position"]),
+        "routines": [r.routine_id for r in model.routines],
+    }
+
+
+@to_dict.register
+def _(model: Schedule) -> dict[str, object]:
+    return {
+        **model_to_dict(model, exclude=["user_id", "interval"]),
+        "kind": schedule_kind_to_dict(model),
+    }
+
+
+@to_dict.register
+def _(model: BarbellEquipment) -> dict[str, object]:
+    return {
+        "bars": [b.weight for b in model.bars],
+        "plates": [{"weight": p.weight, "count": p.count} for p in model.plates],
+        "collars": model.collars,
     }
 
 
@@ -72,6 +195,19 @@ def _(model: Workout) -> dict[str, object]:
     return {
         **model_to_dict(model),
         "elements": [to_dict(e) for e in model.elements],
+        "interval_blocks": [to_dict(b) for b in model.interval_blocks],
+    }
+
+
+@to_dict.register
+def _(model: WorkoutIntervalBlock) -> dict[str, object]:
+    return {
+        "kind": section_kind_to_dict(model),
+        "start": model.start_index,
+        "end": model.end_index,
+        "rounds": model.rounds,
+        "achieved_rounds": model.achieved_rounds,
+        "time": model.time,
     }
 
 
@@ -82,6 +218,45 @@ def _(model: WorkoutElement) -> dict[str, object]:
     }
 
 
+def section_kind_to_dict(model: Union[RoutineSection, WorkoutIntervalBlock]) -> object:
+    parameters = {p: getattr(model, p) for p in SECTION_KIND_PARAMETERS[model.kind]}
+    return {model.kind: parameters} if parameters else model.kind
+
+
+def relative_weight_to_dict(model: RoutineActivity) -> object:
+    if model.relative_weight_basis is None:
+        return None
+    return {"percentage": model.relative_weight_percentage, "basis": model.relative_weight_basis}
+
+
+def progression_to_dict(model: RoutineActivity) -> object:
+    if model.progression is None:
+        return None
+    return {
+        model.progression: {
+            p: getattr(model, f"progression_{p}") for p in PROGRESSION_PARAMETERS[model.progression]
+        }
+    }
+
+
+def program_position_to_dict(model: Program) -> object:
+    if model.position_week is None or model.position_day is None:
+        return None
+    return {"week": model.position_week, "day": model.position_day}
+
+
+def schedule_kind_to_dict(model: Schedule) -> object:
+    if model.interval is None:
+        weekdays = {r.position: r.routine_id for r in model.routines}
+        return {"Weekdays": [weekdays.get(position) for position in range(1, 8)]}
+    return {
+        "Rotation": {
+            "interval": model.interval,
+            "routines": [r.routine_id for r in model.routines],
+        }
+    }
+
+
 def model_to_dict(
     model: object, exclude: Optional[list[str]] = None, include: Optional[list[str]] = None
 ) -> dict[str, object]:
@@ -96,6 +271,65 @@ def model_to_dict(
     }
 
 
+def to_choice(json: object, choices: set[str], name: str) -> str:
+    if not isinstance(json, str) or json not in choices:
+        raise DeserializationError(f"invalid {name}: {json}")
+    return json
+
+
+def to_optional_choice(json: object, choices: set[str], name: str) -> Optional[str]:
+    return None if json is None else to_choice(json, choices, name)
+
+
+def to_exercise_muscles(json: dict[str, Any]) -> list[ExerciseMuscle]:  # type: ignore[misc]
+    return [
+        ExerciseMuscle(
+            muscle_group=to_choice(muscle_group, MUSCLE_GROUPS, "muscle group"),
+            secondary=secondary,
+        )
+        for key, secondary in [("primary_muscles", False), ("secondary_muscles", True)]
+        for muscle_group in json.get(key, [])
+    ]
+
+
+def to_exercise_alternatives(  # type: ignore[misc]
+    json: dict[str, Any]
+) -> list[ExerciseAlternative]:
+    alternative_ids = set(json.get("alternatives", []))
+    exercise_ids = (
+        db.session.execute(
+            select(Exercise.id)
+            .where(Exercise.user_id == session["user_id"])
+            .where(Exercise.id.in_(alternative_ids))
+        )
+        .scalars()
+        .all()
+    )
+    if len(exercise_ids) != len(alternative_ids):
+        raise DeserializationError("unknown alternative exercise")
+    return [ExerciseAlternative(alternative_id=i) for i in sorted(alternative_ids)]
+
+
+def to_merged_exercises(  # type: ignore[misc]
+    json: dict[str, Any], survivor: Exercise
+) -> list[Exercise]:
+    exercise_ids = set(json["exercise_ids"])
+    if survivor.id in exercise_ids:
+        raise DeserializationError("survivor must not be merged")
+    exercises = (
+        db.session.execute(
+            select(Exercise)
+            .where(Exercise.user_id == session["user_id"])
+            .where(Exercise.id.in_(exercise_ids))
+        )
+        .scalars()
+        .all()
+    )
+    if len(exercises) != len(exercise_ids):
+        raise DeserializationError("unknown exercise")
+    return list(exercises)
+
+
 def to_routine_parts(json: list[dict[str, Any]]) -> list[RoutinePart]:  # type: ignore[misc]
     return [
         to_routine_section(part, position)
@@ -113,13 +347,28 @@ def to_routine_section(json: dict[str, Any], position: int) -> RoutineSection:
     return RoutineSection(
         position=position,
         rounds=json["rounds"],
+        **to_section_kind(json.get("kind", "Sequential")),
         parts=to_routine_parts(json["parts"]),
     )
 
 
+def to_section_kind(json: object) -> dict[str, object]:
+    if isinstance(json, str):
+        kind, parameters = json, {}
+    elif isinstance(json, dict) and len(json) == 1:
+        [(kind, parameters)] = json.items()
+    else:
+        raise DeserializationError(f"invalid section kind: {json}")
+    if kind not in SECTION_KIND_PARAMETERS or not isinstance(parameters, dict):
+        raise DeserializationError(f"invalid section kind: {json}")
+    return {"kind": kind, **{p: parameters[p] for p in SECTION_KIND_PARAMETERS[kind]}}
+
+
 def to_routine_activity(  # type: ignore[misc]
     json: dict[str, Any], position: int
 ) -> RoutineActivity:
+    if json.get("relative_weight") is not None and json.get("progression") is not None:
+        raise DeserializationError("progression cannot be combined with relative weight")
     return RoutineActivity(
         position=position,
         exercise_id=json["exercise_id"],
@@ -128,7 +377,145 @@ def to_routine_activity(  # type: ignore[misc]
         weight=json["weight"],
         rpe=json["rpe"],
         automatic=json["automatic"],
+        set_type=to_choice(json.get("set_type", "Working"), SET_TYPES, "set type"),
+        auto_warm_up=json.get("auto_warm_up", False),
+        **to_relative_weight(json.get("relative_weight")),
+        **to_progression(json.get("progression")),
+    )
+
+
+def to_relative_weight(json: object) -> dict[str, object]:
+    if json is None:
+        return {"relative_weight_percentage": None, "relative_weight_basis": None}
+    if not isinstance(json, dict):
+        raise DeserializationError(f"invalid relative weight: {json}")
+    return {
+        "relative_weight_percentage": json["percentage"],
+        "relative_weight_basis": to_choice(json["basis"], WEIGHT_BASES, "weight basis"),
+    }
+
+
+def to_progression(json: object) -> dict[str, object]:
+    if json is None:
+        return {}
+    if not isinstance(json, dict) or len(json) != 1:
+        raise DeserializationError(f"invalid progression: {json}")
+    [(kind, parameters)] = json.items()
+    if kind not in PROGRESSION_PARAMETERS or not isinstance(parameters, dict):
+        raise DeserializationError(f"invalid progression: {json}")
+    return {
+        "progression": kind,
+        **{f"progression_{p}": parameters[p] for p in PROGRESSION_PARAMETERS[kind]},
+    }
+
+
+def routine_of(part: RoutinePart) -> Routine:
+    if isinstance(part, RoutineSection) and part.routine_id is not None:
+        return part.routine
+    return routine_of(part.section)
+
+
+def add_routine_version(routine: Routine) -> RoutineVersion:
+    sections = [to_dict(s) for s in sorted(routine.sections, key=lambda x: x.position)]
+    if not routine.versions or routine.versions[-1].sections != sections:
+        routine.versions.append(
+            RoutineVersion(created=datetime.now(timezone.utc), sections=sections)
+        )
+    return routine.versions[-1]
+
+
+def to_program_weeks(json: list[dict[str, Any]]) -> list[ProgramWeek]:  # type: ignore[misc]
+    check_routine_ids({r for week in json for r in week["routines"]})
+    return [
+        ProgramWeek(
+            position=position,
+            weight_percentage=float(week["weight_percentage"]),
+            rpe_offset=float(week["rpe_offset"]),
+            routines=[
+                ProgramRoutine(position=routine_position, routine_id=routine_id)
+                for routine_position, routine_id in enumerate(week["routines"], start=1)
+            ],
+        )
+        for position, week in enumerate(json, start=1)
+    ]
+
+
+def check_routine_ids(routine_ids: set[int]) -> None:
+    known_routine_ids = (
+        db.session.execute(
+            select(Routine.id)
+            .where(Routine.user_id == session["user_id"])
+            .where(Routine.id.in_(routine_ids))
+        )
+        .scalars()
+        .all()
     )
+    if len(known_routine_ids) != len(routine_ids):
+        raise DeserializationError("unknown routine")
+
+
+def to_program_position(json: object) -> dict[str, object]:
+    if json is None:
+        return {"position_week": None, "position_day": None}
+    if not isinstance(json, dict):
+        raise DeserializationError(f"invalid program position: {json}")
+    return {"position_week": json["week"], "position_day": json["day"]}
+
+
+def to_program_workouts(json: object) -> list[ProgramWorkout]:
+    if not isinstance(json, dict):
+        raise DeserializationError(f"invalid weight percentages: {json}")
+    weight_percentages = {int(workout_id): float(p) for workout_id, p in json.items()}
+    workout_ids = (
+        db.session.execute(
+            select(Workout.id)
+            .where(Workout.user_id == session["user_id"])
+            .where(Workout.id.in_(weight_percentages))
+        )
+        .scalars()
+        .all()
+    )
+    if len(workout_ids) != len(weight_percentages):
+        raise DeserializationError("unknown workout")
+    return [
+        ProgramWorkout(workout_id=workout_id, weight_percentage=weight_percentage)
+        for workout_id, weight_percentage in sorted(weight_percentages.items())
+    ]
+
+
+def to_schedule_kind(json: object) -> dict[str, object]:
+    if not isinstance(json, dict) or len(json) != 1:
+        raise DeserializationError(f"invalid schedule kind: {json}")
+    [(kind, parameters)] = json.items()
+    if kind == "Weekdays" and isinstance(parameters, list) and len(parameters) == 7:
+        interval, routine_ids = None, parameters
+    elif kind == "Rotation" and isinstance(parameters, dict):
+        interval, routine_ids = parameters["interval"], parameters["routines"]
+    else:
+        raise DeserializationError(f"invalid schedule kind: {json}")
+    check_routine_ids({r for r in routine_ids if r is not None})
+    return {
+        "interval": interval,
+        "routines": [
+            ScheduleRoutine(position=position, routine_id=routine_id)
+            for position, routine_id in enumerate(routine_ids, start=1)
+            if routine_id is not None
+        ],
+    }
+
+
+def to_barbell_bars(json: list[float]) -> list[BarbellBar]:
+    return [
+        BarbellBar(position=position, weight=float(weight))
+        for position, weight in enumerate(json, start=1)
+    ]
+
+
+def to_barbell_plates(json: list[dict[str, Any]]) -> list[BarbellPlate]:  # type: ignore[misc]
+    return [
+        BarbellPlate(position=position, weight=float(plate["weight"]), count=plate["count"])
+        for position, plate in enumerate(json, start=1)
+    ]
 
 
 def to_workout_elements(json: list[dict[str, Any]]) -> list[WorkoutElement]:  # type: ignore[misc]
@@ -144,6 +531,7 @@ def to_workout_elements(json: list[dict[str, Any]]) -> list[WorkoutElement]:  #
             target_time=element["target_time"],
             target_weight=element["target_weight"],
             target_rpe=element["target_rpe"],
+            set_type=to_choice(element.get("set_type", "Working"), SET_TYPES, "set type"),
             automatic=element["automatic"],
         )
         if "exercise_id" in element
@@ -156,6 +544,23 @@ def to_workout_elements(json: list[dict[str, Any]]) -> list[WorkoutElement]:  #
     ]
 
 
+def to_workout_interval_blocks(  # type: ignore[misc]
+    json: list[dict[str, Any]]
+) -> list[WorkoutIntervalBlock]:
+    return [
+        WorkoutIntervalBlock(
+            position=position,
+            **to_section_kind(block["kind"]),
+            start_index=block["start"],
+            end_index=block["end"],
+            rounds=block["rounds"],
+            achieved_rounds=block["achieved_rounds"],
+            time=block["time"],
+        )
+        for position, block in enumerate(json, start=1)
+    ]
+
+
 def json_expected(function: Callable) -> Callable:  # type: ignore[type-arg]
     @wraps(function)
     def decorated_function(*args: object, **kwargs: object) -> ResponseReturnValue:
@@ -645,8 +1050,16 @@ def create_exercise() -> ResponseReturnValue:
         exercise = Exercise(
             user_id=session["user_id"],
             name=data["name"],
+            movement_pattern=to_optional_choice(
+                data.get("movement_pattern"), MOVEMENT_PATTERNS, "movement pattern"
+            ),
+            equipment=to_optional_choice(data.get("equipment"), EQUIPMENT, "equipment"),
+            laterality=to_optional_choice(data.get("laterality"), LATERALITIES, "laterality"),
+            training_max=data.get("training_max"),
+            muscles=to_exercise_muscles(data),
+            alternatives=to_exercise_alternatives(data),
         )
-    except (KeyError, ValueError) as e:
+    except (DeserializationError, KeyError, ValueError) as e:
         return jsonify({"details": str(e)}), HTTPStatus.BAD_REQUEST
 
     db.session.add(exercise)
@@ -684,9 +1097,22 @@ def replace_exercise(id_: int) -> ResponseReturnValue:
 
     assert isinstance(data, dict)
 
+    for m in chain(exercise.muscles, exercise.alternatives):
+        db.session.delete(m)
+
+    db.session.flush()
+
     try:
         exercise.name = data["name"]
-    except (KeyError, ValueError) as e:
+        exercise.movement_pattern = to_optional_choice(
+            data.get("movement_pattern"), MOVEMENT_PATTERNS, "movement pattern"
+        )
+        exercise.equipment = to_optional_choice(data.get("equipment"), EQUIPMENT, "equipment")
+        exercise.laterality = to_optional_choice(data.get("laterality"), LATERALITIES, "laterality")
+        exercise.training_max = data.get("training_max")
+        exercise.muscles = to_exercise_muscles(data)
+        exercise.alternatives = to_exercise_alternatives(data)
+    except (DeserializationError, KeyError, ValueError) as e:
         return jsonify({"details": str(e)}), HTTPStatus.BAD_REQUEST
 
     try:
@@ -722,6 +1148,73 @@ def delete_exercise(id_: int) -> ResponseReturnValue:
     return "", HTTPStatus.NO_CONTENT
 
 
+@bp.route("/exercises/<int:id_>/merge", methods=["POST"])
+@session_required
+@json_expected
+def merge_exercises(id_: int) -> ResponseReturnValue:
+    try:
+        survivor = (
+            db.session.execute(
+                select(Exercise)
+                .where(Exercise.id == id_)
+                .where(Exercise.user_id == session["user_id"])
+            )
+            .scalars()
+            .one()
+        )
+    except (NoResultFound, ValueError):
+        return "", HTTPStatus.NOT_FOUND
+
+    data = request.json
+
+    assert isinstance(data, dict)
+
+    try:
+        exercises = to_merged_exercises(data, survivor)
+    except (DeserializationError, KeyError, ValueError) as e:
+        return jsonify({"details": str(e)}), HTTPStatus.BAD_REQUEST
+
+    exercise_ids = {e.id for e in exercises}
+
+    for exercise in (
+        db.session.execute(
+            select(Exercise)
+            .where(Exercise.user_id == session["user_id"])
+            .where(Exercise.id.not_in(exercise_ids))
+        )
+        .scalars()
+        .all()
+    ):
+        alternative_ids = {a.alternative_id for a in exercise.alternatives}
+        if alternative_ids.isdisjoint(exercise_ids):
+            continue
+        exercise.alternatives = [
+            a for a in exercise.alternatives if a.alternative_id not in exercise_ids
+        ]
+        if exercise.id != survivor.id and survivor.id not in alternative_ids:
+            exercise.alternatives.append(ExerciseAlternative(alternative_id=survivor.id))
+
+    routines: set[Routine] = set()
+
+    for exercise in exercises:
+        for activity in list(exercise.routine_activities):
+            activity.exercise = survivor
+            routines.add(routine_of(activity))
+        for workout_set in list(exercise.sets):
+            workout_set.exercise = survivor
+        db.session.delete(exercise)
+
+    try:
+        db.session.flush()
+        for routine in sorted(routines, key=lambda r: r.id):
+            add_routine_version(routine)
+        db.session.commit()
+    except IntegrityError as e:
+        return jsonify({"details": str(e)}), HTTPStatus.CONFLICT
+
+    return jsonify(to_dict(survivor)), HTTPStatus.OK
+
+
 @bp.route("/routines")
 @session_required
 def read_routines() -> ResponseReturnValue:
@@ -758,6 +1251,8 @@ def create_routine() -> ResponseReturnValue:
     db.session.add(routine)
 
     try:
+        db.session.flush()
+        add_routine_version(routine)
         db.session.commit()
     except IntegrityError as e:
         return jsonify({"details": str(e)}), HTTPStatus.CONFLICT
@@ -801,6 +1296,9 @@ def update_routine(id_: int) -> ResponseReturnValue:
         return jsonify({"details": str(e)}), HTTPStatus.BAD_REQUEST
 
     try:
+        if "sections" in data or request.method == "PUT":
+            db.session.flush()
+            add_routine_version(routine)
         db.session.commit()
     except IntegrityError as e:
         return jsonify({"details": str(e)}), HTTPStatus.CONFLICT
@@ -833,6 +1331,263 @@ def delete_routine(id_: int) -> ResponseReturnValue:
     return "", HTTPStatus.NO_CONTENT
 
 
+@bp.route("/routine_versions")
+@session_required
+def read_routine_versions() -> ResponseReturnValue:
+    versions = (
+        db.session.execute(
+            select(RoutineVersion)
+            .join(Routine)
+            .where(Routine.user_id == session["user_id"])
+            .order_by(RoutineVersion.id)
+        )
+        .scalars()
+        .all()
+    )
+    return jsonify([to_dict(v) for v in versions])
+
+
+@bp.route("/programs")
+@session_required
+def read_programs() -> ResponseReturnValue:
+    programs = (
+        db.session.execute(
+            select(Program)
+            .where(Program.user_id == session["user_id"])
+            .options(selectinload(Program.weeks))
+        )
+        .scalars()
+        .all()
+    )
+    return jsonify([to_dict(p) for p in programs])
+
+
+@bp.route("/programs", methods=["POST"])
+@session_required
+@json_expected
+def create_program() -> ResponseReturnValue:
+    data = request.json
+
+    assert isinstance(data, dict)
+
+    try:
+        program = Program(
+            user_id=session["user_id"],
+            name=data["name"],
+            **to_program_position(data.get("position")),
+            weeks=to_program_weeks(data["weeks"]),
+            workouts=to_program_workouts(data.get("weight_percentages", {})),
+        )
+    except (DeserializationError, KeyError, ValueError) as e:
+        return jsonify({"details": str(e)}), HTTPStatus.BAD_REQUEST
+
+    db.session.add(program)
+
+    try:
+        db.session.commit()
+    except IntegrityError as e:
+        return jsonify({"details": str(e)}), HTTPStatus.CONFLICT
+
+    return (
+        jsonify(to_dict(program)),
+        HTTPStatus.CREATED,
+        {"Location": f"/programs/{program.id}"},
+    )
+
+
+@bp.route("/programs/<int:id_>", methods=["PUT"])
+@session_required
+@json_expected
+def replace_program(id_: int) -> ResponseReturnValue:
+    try:
+        program = (
+            db.session.execute(
+                select(Program)
+                .where(Program.id == id_)
+                .where(Program.user_id == session["user_id"])
+            )
+            .scalars()
+            .one()
+        )
+    except (NoResultFound, ValueError):
+        return "", HTTPStatus.NOT_FOUND
+
+    data = request.json
+
+    assert isinstance(data, dict)
+
+    for m in chain(program.weeks, program.workouts):
+        db.session.delete(m)
+
+    db.session.flush()
+
+    try:
+        program.name = data["name"]
+        for key, value in to_program_position(data.get("position")).items():
+            setattr(program, key, value)
+        program.weeks = to_program_weeks(data["weeks"])
+        program.workouts = to_program_workouts(data.get("weight_percentages", {}))
+    except (DeserializationError, KeyError, ValueError) as e:
+        return jsonify({"details": str(e)}), HTTPStatus.BAD_REQUEST
+
+    try:
+        db.session.commit()
+    except IntegrityError as e:
+        return jsonify({"details": str(e)}), HTTPStatus.CONFLICT
+
+    return (
+        jsonify(to_dict(program)),
+        HTTPStatus.OK,
+    )
+
+
+@bp.route("/programs/<int:id_>", methods=["DELETE"])
+@session_required
+def delete_program(id_: int) -> ResponseReturnValue:
+    try:
+        program = (
+            db.session.execute(
+                select(Program)
+                .where(Program.id == id_)
+                .where(Program.user_id == session["user_id"])
+            )
+            .scalars()
+            .one()
+        )
+    except (NoResultFound, ValueError):
+        return "", HTTPStatus.NOT_FOUND
+
+    db.session.delete(program)
+    db.session.commit()
+
+    return "", HTTPStatus.NO_CONTENT
+
+
+@bp.route("/schedule")
+@session_required
+def read_schedule() -> ResponseReturnValue:
+    schedule = (
+        db.session.execute(select(Schedule).where(Schedule.user_id == session["user_id"]))
+        .scalars()
+        .one_or_none()
+    )
+    return jsonify(None if schedule is None else to_dict(schedule))
+
+
+@bp.route("/schedule", methods=["PUT"])
+@session_required
+@json_expected
+def replace_schedule() -> ResponseReturnValue:
+    schedule = (
+        db.session.execute(select(Schedule).where(Schedule.user_id == session["user_id"]))
+        .scalars()
+        .one_or_none()
+    )
+
+    data = request.json
+
+    assert isinstance(data, dict)
+
+    if schedule is not None:
+        db.session.delete(schedule)
+        db.session.flush()
+
+    try:
+        schedule = Schedule(
+            user_id=session["user_id"],
+            start=date.fromisoformat(data["start"]),
+            **to_schedule_kind(data["kind"]),
+        )
+    except (DeserializationError, KeyError, ValueError) as e:
+        return jsonify({"details": str(e)}), HTTPStatus.BAD_REQUEST
+
+    db.session.add(schedule)
+
+    try:
+        db.session.commit()
+    except IntegrityError as e:
+        return jsonify({"details": str(e)}), HTTPStatus.CONFLICT
+
+    return (
+        jsonify(to_dict(schedule)),
+        HTTPStatus.OK,
+    )
+
+
+@bp.route("/schedule", methods=["DELETE"])
+@session_required
+def delete_schedule() -> ResponseReturnValue:
+    try:
+        schedule = (
+            db.session.execute(select(Schedule).where(Schedule.user_id == session["user_id"]))
+            .scalars()
+            .one()
+        )
+    except NoResultFound:
+        return "", HTTPStatus.NOT_FOUND
+
+    db.session.delete(schedule)
+    db.session.commit()
+
+    return "", HTTPStatus.NO_CONTENT
+
+
+@bp.route("/barbell_equipment")
+@session_required
+def read_barbell_equipment() -> ResponseReturnValue:
+    equipment = (
+        db.session.execute(
+            select(BarbellEquipment).where(BarbellEquipment.user_id == session["user_id"])
+        )
+        .scalars()
+        .one_or_none()
+    )
+    return jsonify(None if equipment is None else to_dict(equipment))
+
+
+@bp.route("/barbell_equipment", methods=["PUT"])
+@session_required
+@json_expected
+def replace_barbell_equipment() -> ResponseReturnValue:
+    equipment = (
+        db.session.execute(
+            select(BarbellEquipment).where(BarbellEquipment.user_id == session["user_id"])
+        )
+        .scalars()
+        .one_or_none()
+    )
+
+    data = request.json
+
+    assert isinstance(data, dict)
+
+    if equipment is not None:
+        db.session.delete(equipment)
+        db.session.flush()
+
+    try:
+        equipment = BarbellEquipment(
+            user_id=session["user_id"],
+            collars=float(data["collars"]),
+            bars=to_barbell_bars(data["bars"]),
+            plates=to_barbell_plates(data["plates"]),
+        )
+    except (DeserializationError, KeyError, ValueError) as e:
+        return jsonify({"details": str(e)}), HTTPStatus.BAD_REQUEST
+
+    db.session.add(equipment)
+
+    try:
+        db.session.commit()
+    except IntegrityError as e:
+        return jsonify({"details": str(e)}), HTTPStatus.CONFLICT
+
+    return (
+        jsonify(to_dict(equipment)),
+        HTTPStatus.OK,
+    )
+
+
 @bp.route("/workouts")
 @session_required
 def read_workouts() -> ResponseReturnValue:
@@ -870,9 +1625,13 @@ def create_workout() -> ResponseReturnValue:
         workout = Workout(
             user_id=session["user_id"],
             routine=routine,
+            routine_version=add_routine_version(routine),
             date=date.fromisoformat(data["date"]),
             notes=data["notes"],
+            session_rpe=data.get("session_rpe"),
+            duration=data.get("duration"),
             elements=to_workout_elements(data["elements"]),
+            interval_blocks=to_workout_interval_blocks(data.get("interval_blocks", [])),
         )
     except (DeserializationError, KeyError, ValueError) as e:
         return jsonify({"details": str(e)}), HTTPStatus.BAD_REQUEST
@@ -915,13 +1674,25 @@ def update_workout(id_: int) -> ResponseReturnValue:
 
         db.session.flush()
 
+    if "interval_blocks" in data or request.method == "PUT":
+        for b in workout.interval_blocks:
+            db.session.delete(b)
+
+        db.session.flush()
+
     try:
         if "date" in data or request.method == "PUT":
             workout.date = date.fromisoformat(data["date"])
         if "notes" in data or request.method == "PUT":
             workout.notes = data["notes"]
+        if "session_rpe" in data or request.method == "PUT":
+            workout.session_rpe = data.get("session_rpe")
+        if "duration" in data or request.method == "PUT":
+            workout.duration = data.get("duration")
         if "elements" in data or request.method == "PUT":
             workout.elements = to_workout_elements(data["elements"])
+        if "interval_blocks" in data or request.method == "PUT":
+            workout.interval_blocks = to_workout_interval_blocks(data.get("interval_blocks", []))
     except (DeserializationError, KeyError, ValueError) as e:
         return jsonify({"details": str(e)}), HTTPStatus.BAD_REQUEST
 
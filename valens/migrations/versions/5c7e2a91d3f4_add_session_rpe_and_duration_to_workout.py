@@ -0,0 +1,48 @@
+"""
+Add session_rpe and duration to workout.
+
+Revision ID: 5c7e2a91d3f4
+Revises: 06f82ead211b
+Create Date: 2023-04-09
+
+"""
+from typing import Union
+
+import sqlalchemy as sa
+from alembic import op
+
+revision = "5c7e2a91d3f4"
+down_revision = "06f82ead211b"
+branch_labels = None
+depends_on = None
+
+
+check_constraints: list[tuple[str, Union[str, sa.ColumnElement[bool]]]] = [
+    (
+        "session_rpe_type_real_or_null",
+        "typeof(session_rpe) = 'real' or typeof(session_rpe) = 'null'",
+    ),
+    (
+        "duration_type_integer_or_null",
+        "typeof(duration) = 'integer' or typeof(duration) = 'null'",
+    ),
+    ("session_rpe_ge_0", sa.column("session_rpe") >= 0),
+    ("session_rpe_le_10", sa.column("session_rpe") <= 10),
+    ("duration_gt_0", sa.column("duration") > 0),
+]
+
+
+def upgrade() -> None:
+    with op.batch_alter_table("workout", schema=None) as batch_op:
+        batch_op.add_column(sa.Column("session_rpe", sa.Float(), nullable=True))
+        batch_op.add_column(sa.Column("duration", sa.Integer(), nullable=True))
+        for constraint_name, condition in check_constraints:
+            batch_op.create_check_constraint(constraint_name, condition)
+
+
+def downgrade() -> None:
+    with op.batch_alter_table("workout", schema=None) as batch_op:
+        for constraint_name, _ in check_constraints:
+            batch_op.drop_constraint(constraint_name, type_="check")
+        batch_op.drop_column("duration")
+        batch_op.drop_column("session_rpe")
@@ -0,0 +1,36 @@
+"""
+Add set_type to workout_set.
+
+Revision ID: 7d4a8e3b2f10
+Revises: e1b5f07a9c62
+Create Date: 2023-04-11
+
+"""
+import sqlalchemy as sa
+from alembic import op
+
+revision = "7d4a8e3b2f10"
+down_revision = "e1b5f07a9c62"
+branch_labels = None
+depends_on = None
+
+
+check_constraints = [
+    ("set_type_type_text", "typeof(set_type) = 'text'"),
+]
+
+
+def upgrade() -> None:
+    with op.batch_alter_table("workout_set", schema=None) as batch_op:
+        batch_op.add_column(
+            sa.Column("set_type", sa.String(), nullable=False, server_default="Working")
+        )
+        for constraint_name, condition in check_constraints:
+            batch_op.create_check_constraint(constraint_name, condition)
+
+
+def downgrade() -> None:
+    with op.batch_alter_table("workout_set", schema=None) as batch_op:
+        for constraint_name, _ in check_constraints:
+            batch_op.drop_constraint(constraint_name, type_="check")
+        batch_op.drop_column("set_type")
@@ -0,0 +1,122 @@
+"""
+Add section kinds and interval blocks.
+
+Revision ID: a3f9c6d1e8b4
+Revises: 7d4a8e3b2f10
+Create Date: 2023-04-12
+
+"""
+from typing import Union
+
+import sqlalchemy as sa
+from alembic import op
+
+revision = "a3f9c6d1e8b4"
+down_revision = "7d4a8e3b2f10"
+branch_labels = None
+depends_on = None
+
+
+check_constraints: list[tuple[str, Union[str, sa.ColumnElement[bool]]]] = [
+    ("kind_type_text", "typeof(kind) = 'text'"),
+    ("interval_type_integer_or_null", "typeof(interval) = 'integer' or typeof(interval) = 'null'"),
+    ("time_cap_type_integer_or_null", "typeof(time_cap) = 'integer' or typeof(time_cap) = 'null'"),
+    ("work_type_integer_or_null", "typeof(work) = 'integer' or typeof(work) = 'null'"),
+    ("rest_type_integer_or_null", "typeof(rest) = 'integer' or typeof(rest) = 'null'"),
+    ("interval_gt_0", sa.column("interval") > 0),
+    ("time_cap_ge_0", sa.column("time_cap") >= 0),
+    ("work_gt_0", sa.column("work") > 0),
+    ("rest_ge_0", sa.column("rest") >= 0),
+]
+
+
+def upgrade() -> None:
+    with op.batch_alter_table("routine_section", schema=None) as batch_op:
+        batch_op.add_column(
+            sa.Column("kind", sa.String(), nullable=False, server_default="Sequential")
+        )
+        batch_op.add_column(sa.Column("interval", sa.Integer(), nullable=True))
+        batch_op.add_column(sa.Column("time_cap", sa.Integer(), nullable=True))
+        batch_op.add_column(sa.Column("work", sa.Integer(), nullable=True))
+        batch_op.add_column(sa.Column("rest", sa.Integer(), nullable=True))
+        for constraint_name, condition in check_constraints:
+            batch_op.create_check_constraint(constraint_name, condition)
+
+    op.create_table(
+        "workout_interval_block",
+        sa.Column("workout_id", sa.Integer(), nullable=False),
+        sa.Column("position", sa.Integer(), nullable=False),
+        sa.Column("kind", sa.String(), nullable=False),
+        sa.Column("interval", sa.Integer(), nullable=True),
+        sa.Column("time_cap", sa.Integer(), nullable=True),
+        sa.Column("work", sa.Integer(), nullable=True),
+        sa.Column("rest", sa.Integer(), nullable=True),
+        sa.Column("start_index", sa.Integer(), nullable=False),
+        sa.Column("end_index", sa.Integer(), nullable=False),
+        sa.Column("rounds", sa.Integer(), nullable=False),
+        sa.Column("achieved_rounds", sa.Integer(), nullable=True),
+        sa.Column("time", sa.Integer(), nullable=True),
+        sa.CheckConstraint(
+            "typeof(position) = 'integer'",
+            name=op.f("ck_workout_interval_block_position_type_integer"),
+        ),
+        *(
+            sa.CheckConstraint(condition, name=op.f(f"ck_workout_interval_block_{constraint_name}"))
+            for constraint_name, condition in check_constraints
+        ),
+        sa.CheckConstraint(
+            "typeof(start_index) = 'integer'",
+            name=op.f("ck_workout_interval_block_start_index_type_integer"),
+        ),
+        sa.CheckConstraint(
+            "typeof(end_index) = 'integer'",
+            name=op.f("ck_workout_interval_block_end_index_type_integer"),
+        ),
+        sa.CheckConstraint(
+            "typeof(rounds) = 'integer'",
+            name=op.f("ck_workout_interval_block_rounds_type_integer"),
+        ),
+        sa.CheckConstraint(
+            "typeof(achieved_rounds) = 'integer' or typeof(achieved_rounds) = 'null'",
+            name=op.f("ck_workout_interval_block_achieved_rounds_type_integer_or_null"),
+        ),
+        sa.CheckConstraint(
+            "typeof(time) = 'integer' or typeof(time) = 'null'",
+            name=op.f("ck_workout_interval_block_time_type_integer_or_null"),
+        ),
+        sa.CheckConstraint("position > 0", name=op.f("ck_workout_interval_block_position_gt_0")),
+        sa.CheckConstraint(
+            "start_index >= 0", name=op.f("ck_workout_interval_block_start_index_ge_0")
+        ),
+        sa.CheckConstraint(
+            "end_index >= start_index",
+            name=op.f("ck_workout_interval_block_end_index_ge_start_index"),
+        ),
+        sa.CheckConstraint("rounds > 0", name=op.f("ck_workout_interval_block_rounds_gt_0")),
+        sa.CheckConstraint(
+            "achieved_rounds >= 0", name=op.f("ck_workout_interval_block_achieved_rounds_ge_0")
+        ),
+        sa.CheckConstraint("time >= 0", name=op.f("ck_workout_interval_block_time_ge_0")),
+        sa.ForeignKeyConstraint(
+            ["workout_id"],
+            ["workout.id"],
+            name=op.f("fk_workout_interval_block_workout_id_workout"),
+            ondelete="CASCADE",
+        ),
+        sa.PrimaryKeyConstraint(
+            "workout_id", "position", name=op.f("pk_workout_interval_block")
+        ),
+    )
+
+
+def downgrade() -> None:
+    op.drop_table("workout_interval_block")
+
+    with op.batch_alter_table("routine_section", schema=None) as batch_op:
+        for constraint_name, _ in check_constraints:
+            batch_op.drop_constraint(constraint_name, type_="check")
+        batch_op.drop_column("rest")
+        batch_op.drop_column("work")
+        batch_op.drop_column("time_cap")
+        batch_op.drop_column("interval")
+        batch_op.drop_column("kind")
@@ -0,0 +1,64 @@
+"""
+Add schedule.
+
+Revision ID: a8e4d0b6f2c3
+Revises: f6c2a8d4e0b7
+Create Date: 2023-04-18
+
+"""
+import sqlalchemy as sa
+from alembic import op
+
+revision = "a8e4d0b6f2c3"
+down_revision = "f6c2a8d4e0b7"
+branch_labels = None
+depends_on = None
+
+
+def upgrade() -> None:
+    op.create_table(
+        "schedule",
+        sa.Column("user_id", sa.Integer(), nullable=False),
+        sa.Column("start", sa.Date(), nullable=False),
+        sa.Column("interval", sa.Integer(), nullable=True),
+        sa.CheckConstraint(
+            "typeof(interval) = 'integer' or typeof(interval) = 'null'",
+            name=op.f("ck_schedule_interval_type_integer_or_null"),
+        ),
+        sa.CheckConstraint("interval > 0", name=op.f("ck_schedule_interval_gt_0")),
+        sa.ForeignKeyConstraint(
+            ["user_id"],
+            ["user.id"],
+            name=op.f("fk_schedule_user_id_user"),
+            ondelete="CASCADE",
+        ),
+        sa.PrimaryKeyConstraint("user_id", name=op.f("pk_schedule")),
+    )
+    op.create_table(
+        "schedule_routine",
+        sa.Column("user_id", sa.Integer(), nullable=False),
+        sa.Column("position", sa.Integer(), nullable=False),
+        sa.Column("routine_id", sa.Integer(), nullable=False),
+        sa.CheckConstraint(
+            "typeof(position) = 'integer'", name=op.f("ck_schedule_routine_position_type_integer")
+        ),
+        sa.CheckConstraint("position > 0", name=op.f("ck_schedule_routine_position_gt_0")),
+        sa.ForeignKeyConstraint(
+            ["routine_id"],
+            ["routine.id"],
+            name=op.f("fk_schedule_routine_routine_id_routine"),
+            ondelete="CASCADE",
+        ),
+        sa.ForeignKeyConstraint(
+            ["user_id"],
+            ["schedule.user_id"],
+            name=op.f("fk_schedule_routine_user_id_schedule"),
+            ondelete="CASCADE",
+        ),
+        sa.PrimaryKeyConstraint("user_id", "position", name=op.f("pk_schedule_routine")),
+    )
+
+
+def downgrade() -> None:
+    op.drop_table("schedule_routine")
+    op.drop_table("schedule")
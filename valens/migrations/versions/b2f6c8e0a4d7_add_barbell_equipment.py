@@ -0,0 +1,85 @@
+"""
+Add barbell equipment.
+
+Revision ID: b2f6c8e0a4d7
+Revises: a8e4d0b6f2c3
+Create Date: 2023-04-19
+
+"""
+import sqlalchemy as sa
+from alembic import op
+
+revision = "b2f6c8e0a4d7"
+down_revision = "a8e4d0b6f2c3"
+branch_labels = None
+depends_on = None
+
+
+def upgrade() -> None:
+    op.create_table(
+        "barbell_equipment",
+        sa.Column("user_id", sa.Integer(), nullable=False),
+        sa.Column("collars", sa.Float(), nullable=False),
+        sa.CheckConstraint(
+            "typeof(collars) = 'real'", name=op.f("ck_barbell_equipment_collars_type_real")
+        ),
+        sa.CheckConstraint("collars >= 0", name=op.f("ck_barbell_equipment_collars_ge_0")),
+        sa.ForeignKeyConstraint(
+            ["user_id"],
+            ["user.id"],
+            name=op.f("fk_barbell_equipment_user_id_user"),
+            ondelete="CASCADE",
+        ),
+        sa.PrimaryKeyConstraint("user_id", name=op.f("pk_barbell_equipment")),
+    )
+    op.create_table(
+        "barbell_bar",
+        sa.Column("user_id", sa.Integer(), nullable=False),
+        sa.Column("position", sa.Integer(), nullable=False),
+        sa.Column("weight", sa.Float(), nullable=False),
+        sa.CheckConstraint(
+            "typeof(position) = 'integer'", name=op.f("ck_barbell_bar_position_type_integer")
+        ),
+        sa.CheckConstraint("typeof(weight) = 'real'", name=op.f("ck_barbell_bar_weight_type_real")),
+        sa.CheckConstraint("position > 0", name=op.f("ck_barbell_bar_position_gt_0")),
+        sa.CheckConstraint("weight >= 0", name=op.f("ck_barbell_bar_weight_ge_0")),
+        sa.ForeignKeyConstraint(
+            ["user_id"],
+            ["barbell_equipment.user_id"],
+            name=op.f("fk_barbell_bar_user_id_barbell_equipment"),
+            ondelete="CASCADE",
+        ),
+        sa.PrimaryKeyConstraint("user_id", "position", name=op.f("pk_barbell_bar")),
+    )
+    op.create_table(
+        "barbell_plate",
+        sa.Column("user_id", sa.Integer(), nullable=False),
+        sa.Column("position", sa.Integer(), nullable=False),
+        sa.Column("weight", sa.Float(), nullable=False),
+        sa.Column("count", sa.Integer(), nullable=False),
+        sa.CheckConstraint(
+            "typeof(position) = 'integer'", name=op.f("ck_barbell_plate_position_type_integer")
+        ),
+        sa.CheckConstraint(
+            "typeof(weight) = 'real'", name=op.f("ck_barbell_plate_weight_type_real")
+        ),
+        sa.CheckConstraint(
+            "typeof(count) = 'integer'", name=op.f("ck_barbell_plate_count_type_integer")
+        ),
+        sa.CheckConstraint("position > 0", name=op.f("ck_barbell_plate_position_gt_0")),
+        sa.CheckConstraint("weight > 0", name=op.f("ck_barbell_plate_weight_gt_0")),
+        sa.CheckConstraint("count >= 0", name=op.f("ck_barbell_plate_count_ge_0")),
+        sa.ForeignKeyConstraint(
+            ["user_id"],
+            ["barbell_equipment.user_id"],
+            name=op.f("fk_barbell_plate_user_id_barbell_equipment"),
+            ondelete="CASCADE",
+        ),
+        sa.PrimaryKeyConstraint("user_id", "position", name=op.f("pk_barbell_plate")),
+    )
+
+
+def downgrade() -> None:
+    op.drop_table("barbell_plate")
+    op.drop_table("barbell_bar")
+    op.drop_table("barbell_equipment")
@@ -0,0 +1,36 @@
+"""
+Add set_type to routine_activity.
+
+Revision ID: b6d2f8a4c1e7
+Revises: a3f9c6d1e8b4
+Create Date: 2023-04-13
+
+"""
+import sqlalchemy as sa
+from alembic import op
+
+revision = "b6d2f8a4c1e7"
+down_revision = "a3f9c6d1e8b4"
+branch_labels = None
+depends_on = None
+
+
+check_constraints = [
+    ("set_type_type_text", "typeof(set_type) = 'text'"),
+]
+
+
+def upgrade() -> None:
+    with op.batch_alter_table("routine_activity", schema=None) as batch_op:
+        batch_op.add_column(
+            sa.Column("set_type", sa.String(), nullable=False, server_default="Working")
+        )
+        for constraint_name, condition in check_constraints:
+            batch_op.create_check_constraint(constraint_name, condition)
+
+
+def downgrade() -> None:
+    with op.batch_alter_table("routine_activity", schema=None) as batch_op:
+        for constraint_name, _ in check_constraints:
+            batch_op.drop_constraint(constraint_name, type_="check")
+        batch_op.drop_column("set_type")
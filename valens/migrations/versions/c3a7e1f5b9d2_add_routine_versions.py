@@ -0,0 +1,50 @@
+"""
+Add routine versions.
+
+Revision ID: c3a7e1f5b9d2
+Revises: b2f6c8e0a4d7
+Create Date: 2023-04-20
+
+"""
+import sqlalchemy as sa
+from alembic import op
+
+revision = "c3a7e1f5b9d2"
+down_revision = "b2f6c8e0a4d7"
+branch_labels = None
+depends_on = None
+
+
+def upgrade() -> None:
+    op.create_table(
+        "routine_version",
+        sa.Column("id", sa.Integer(), nullable=False),
+        sa.Column("routine_id", sa.Integer(), nullable=False),
+        sa.Column("created", sa.DateTime(), nullable=False),
+        sa.Column("sections", sa.JSON(), nullable=False),
+        sa.ForeignKeyConstraint(
+            ["routine_id"],
+            ["routine.id"],
+            name=op.f("fk_routine_version_routine_id_routine"),
+            ondelete="CASCADE",
+        ),
+        sa.PrimaryKeyConstraint("id", name=op.f("pk_routine_version")),
+    )
+    with op.batch_alter_table("workout", schema=None) as batch_op:
+        batch_op.add_column(sa.Column("routine_version_id", sa.Integer(), nullable=True))
+        batch_op.create_foreign_key(
+            batch_op.f("fk_workout_routine_version_id_routine_version"),
+            "routine_version",
+            ["routine_version_id"],
+            ["id"],
+            ondelete="CASCADE",
+        )
+
+
+def downgrade() -> None:
+    with op.batch_alter_table("workout", schema=None) as batch_op:
+        batch_op.drop_constraint(
+            batch_op.f("fk_workout_routine_version_id_routine_version"), type_="foreignkey"
+        )
+        batch_op.drop_column("routine_version_id")
+    op.drop_table("routine_version")
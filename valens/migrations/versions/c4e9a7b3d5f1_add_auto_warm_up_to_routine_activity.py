@@ -0,0 +1,40 @@
+"""
+Add auto_warm_up to routine_activity.
+
+Revision ID: c4e9a7b3d5f1
+Revises: b6d2f8a4c1e7
+Create Date: 2023-04-14
+
+"""
+from typing import Union
+
+import sqlalchemy as sa
+from alembic import op
+
+revision = "c4e9a7b3d5f1"
+down_revision = "b6d2f8a4c1e7"
+branch_labels = None
+depends_on = None
+
+
+check_constraints: list[tuple[str, Union[str, sa.ColumnElement[bool]]]] = [
+    ("auto_warm_up_type_integer", "typeof(auto_warm_up) = 'integer'"),
+    ("auto_warm_up_ge_0", sa.column("auto_warm_up") >= 0),
+    ("auto_warm_up_le_1", sa.column("auto_warm_up") <= 1),
+]
+
+
+def upgrade() -> None:
+    with op.batch_alter_table("routine_activity", schema=None) as batch_op:
+        batch_op.add_column(
+            sa.Column("auto_warm_up", sa.Boolean(), nullable=False, server_default=sa.false())
+        )
+        for constraint_name, condition in check_constraints:
+            batch_op.create_check_constraint(constraint_name, condition)
+
+
+def downgrade() -> None:
+    with op.batch_alter_table("routine_activity", schema=None) as batch_op:
+        for constraint_name, _ in check_constraints:
+            batch_op.drop_constraint(constraint_name, type_="check")
+        batch_op.drop_column("auto_warm_up")
@@ -0,0 +1,65 @@
+"""
+Add relative weights and training maxes.
+
+Revision ID: d8f1b5c3e9a2
+Revises: c4e9a7b3d5f1
+Create Date: 2023-04-15
+
+"""
+from typing import Union
+
+import sqlalchemy as sa
+from alembic import op
+
+revision = "d8f1b5c3e9a2"
+down_revision = "c4e9a7b3d5f1"
+branch_labels = None
+depends_on = None
+
+
+exercise_check_constraints: list[tuple[str, Union[str, sa.ColumnElement[bool]]]] = [
+    (
+        "training_max_type_real_or_null",
+        "typeof(training_max) = 'real' or typeof(training_max) = 'null'",
+    ),
+    ("training_max_gt_0", sa.column("training_max") > 0),
+]
+
+routine_activity_check_constraints: list[tuple[str, Union[str, sa.ColumnElement[bool]]]] = [
+    (
+        "relative_weight_percentage_type_real_or_null",
+        "typeof(relative_weight_percentage) = 'real' "
+        "or typeof(relative_weight_percentage) = 'null'",
+    ),
+    (
+        "relative_weight_basis_type_text_or_null",
+        "typeof(relative_weight_basis) = 'text' or typeof(relative_weight_basis) = 'null'",
+    ),
+    ("relative_weight_percentage_gt_0", sa.column("relative_weight_percentage") > 0),
+]
+
+
+def upgrade() -> None:
+    with op.batch_alter_table("exercise", schema=None) as batch_op:
+        batch_op.add_column(sa.Column("training_max", sa.Float(), nullable=True))
+        for constraint_name, condition in exercise_check_constraints:
+            batch_op.create_check_constraint(constraint_name, condition)
+
+    with op.batch_alter_table("routine_activity", schema=None) as batch_op:
+        batch_op.add_column(sa.Column("relative_weight_percentage", sa.Float(), nullable=True))
+        batch_op.add_column(sa.Column("relative_weight_basis", sa.String(), nullable=True))
+        for constraint_name, condition in routine_activity_check_constraints:
+            batch_op.create_check_constraint(constraint_name, condition)
+
+
+def downgrade() -> None:
+    with op.batch_alter_table("routine_activity", schema=None) as batch_op:
+        for constraint_name, _ in routine_activity_check_constraints:
+            batch_op.drop_constraint(constraint_name, type_="check")
+        batch_op.drop_column("relative_weight_basis")
+        batch_op.drop_column("relative_weight_percentage")
+
+    with op.batch_alter_table("exercise", schema=None) as batch_op:
+        for constraint_name, _ in exercise_check_constraints:
+            batch_op.drop_constraint(constraint_name, type_="check")
+        batch_op.drop_column("training_max")
@@ -0,0 +1,89 @@
+"""
+Add exercise metadata.
+
+Revision ID: e1b5f07a9c62
+Revises: 5c7e2a91d3f4
+Create Date: 2023-04-10
+
+"""
+import sqlalchemy as sa
+from alembic import op
+
+revision = "e1b5f07a9c62"
+down_revision = "5c7e2a91d3f4"
+branch_labels = None
+depends_on = None
+
+
+check_constraints = [
+    (
+        "movement_pattern_type_text_or_null",
+        "typeof(movement_pattern) = 'text' or typeof(movement_pattern) = 'null'",
+    ),
+    ("equipment_type_text_or_null", "typeof(equipment) = 'text' or typeof(equipment) = 'null'"),
+    ("laterality_type_text_or_null", "typeof(laterality) = 'text' or typeof(laterality) = 'null'"),
+]
+
+
+def upgrade() -> None:
+    with op.batch_alter_table("exercise", schema=None) as batch_op:
+        batch_op.add_column(sa.Column("movement_pattern", sa.String(), nullable=True))
+        batch_op.add_column(sa.Column("equipment", sa.String(), nullable=True))
+        batch_op.add_column(sa.Column("laterality", sa.String(), nullable=True))
+        for constraint_name, condition in check_constraints:
+            batch_op.create_check_constraint(constraint_name, condition)
+
+    op.create_table(
+        "exercise_muscle",
+        sa.Column("exercise_id", sa.Integer(), nullable=False),
+        sa.Column("muscle_group", sa.String(), nullable=False),
+        sa.Column("secondary", sa.Boolean(), nullable=False),
+        sa.CheckConstraint(
+            "typeof(muscle_group) = 'text'",
+            name=op.f("ck_exercise_muscle_muscle_group_type_text"),
+        ),
+        sa.CheckConstraint(
+            "typeof(secondary) = 'integer'", name=op.f("ck_exercise_muscle_secondary_type_integer")
+        ),
+        sa.CheckConstraint("secondary >= 0", name=op.f("ck_exercise_muscle_secondary_ge_0")),
+        sa.CheckConstraint("secondary <= 1", name=op.f("ck_exercise_muscle_secondary_le_1")),
+        sa.ForeignKeyConstraint(
+            ["exercise_id"],
+            ["exercise.id"],
+            name=op.f("fk_exercise_muscle_exercise_id_exercise"),
+            ondelete="CASCADE",
+        ),
+        sa.PrimaryKeyConstraint("exercise_id", "muscle_group", name=op.f("pk_exercise_muscle")),
+    )
+    op.create_table(
+        "exercise_alternative",
+        sa.Column("exercise_id", sa.Integer(), nullable=False),
+        sa.Column("alternative_id", sa.Integer(), nullable=False),
+        sa.ForeignKeyConstraint(
+            ["exercise_id"],
+            ["exercise.id"],
+            name=op.f("fk_exercise_alternative_exercise_id_exercise"),
+            ondelete="CASCADE",
+        ),
+        sa.ForeignKeyConstraint(
+            ["alternative_id"],
+            ["exercise.id"],
+            name=op.f("fk_exercise_alternative_alternative_id_exercise"),
+            ondelete="CASCADE",
+        ),
+        sa.PrimaryKeyConstraint(
+            "exercise_id", "alternative_id", name=op.f("pk_exercise_alternative")
+        ),
+    )
+
+
+def downgrade() -> None:
+    op.drop_table("exercise_alternative")
+    op.drop_table("exercise_muscle")
+
+    with op.batch_alter_table("exercise", schema=None) as batch_op:
+        for constraint_name, _ in check_constraints:
+            batch_op.drop_constraint(constraint_name, type_="check")
+        batch_op.drop_column("laterality")
+        batch_op.drop_column("equipment")
+        batch_op.drop_column("movement_pattern")
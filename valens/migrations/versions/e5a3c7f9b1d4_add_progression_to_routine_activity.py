@@ -0,0 +1,53 @@
+"""
+Add progression to routine_activity.
+
+Revision ID: e5a3c7f9b1d4
+Revises: d8f1b5c3e9a2
+Create Date: 2023-04-16
+
+"""
+from typing import Union
+
+import sqlalchemy as sa
+from alembic import op
+
+revision = "e5a3c7f9b1d4"
+down_revision = "d8f1b5c3e9a2"
+branch_labels = None
+depends_on = None
+
+
+check_constraints: list[tuple[str, Union[str, sa.ColumnElement[bool]]]] = [
+    (
+        "progression_type_text_or_null",
+        "typeof(progression) = 'text' or typeof(progression) = 'null'",
+    ),
+    (
+        "progression_increment_type_real_or_null",
+        "typeof(progression_increment) = 'real' or typeof(progression_increment) = 'null'",
+    ),
+    (
+        "progression_max_reps_type_integer_or_null",
+        "typeof(progression_max_reps) = 'integer' or typeof(progression_max_reps) = 'null'",
+    ),
+    ("progression_increment_ge_0", sa.column("progression_increment") >= 0),
+    ("progression_max_reps_gt_0", sa.column("progression_max_reps") > 0),
+]
+
+
+def upgrade() -> None:
+    with op.batch_alter_table("routine_activity", schema=None) as batch_op:
+        batch_op.add_column(sa.Column("progression", sa.String(), nullable=True))
+        batch_op.add_column(sa.Column("progression_increment", sa.Float(), nullable=True))
+        batch_op.add_column(sa.Column("progression_max_reps", sa.Integer(), nullable=True))
+        for constraint_name, condition in check_constraints:
+            batch_op.create_check_constraint(constraint_name, condition)
+
+
+def downgrade() -> None:
+    with op.batch_alter_table("routine_activity", schema=None) as batch_op:
+        for constraint_name, _ in check_constraints:
+            batch_op.drop_constraint(constraint_name, type_="check")
+        batch_op.drop_column("progression_max_reps")
+        batch_op.drop_column("progression_increment")
+        batch_op.drop_column("progression")
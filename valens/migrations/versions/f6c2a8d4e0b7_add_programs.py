@@ -0,0 +1,131 @@
+"""
+Add programs.
+
+Revision ID: f6c2a8d4e0b7
+Revises: e5a3c7f9b1d4
+Create Date: 2023-04-17
+
+"""
+import sqlalchemy as sa
+from alembic import op
+
+revision = "f6c2a8d4e0b7"
+down_revision = "e5a3c7f9b1d4"
+branch_labels = None
+depends_on = None
+
+
+def upgrade() -> None:
+    op.create_table(
+        "program",
+        sa.Column("id", sa.Integer(), nullable=False),
+        sa.Column("user_id", sa.Integer(), nullable=False),
+        sa.Column("name", sa.String(), nullable=False),
+        sa.Column("position_week", sa.Integer(), nullable=True),
+        sa.Column("position_day", sa.Integer(), nullable=True),
+        sa.CheckConstraint(
+            "typeof(position_week) = 'integer' or typeof(position_week) = 'null'",
+            name=op.f("ck_program_position_week_type_integer_or_null"),
+        ),
+        sa.CheckConstraint(
+            "typeof(position_day) = 'integer' or typeof(position_day) = 'null'",
+            name=op.f("ck_program_position_day_type_integer_or_null"),
+        ),
+        sa.CheckConstraint("position_week >= 0", name=op.f("ck_program_position_week_ge_0")),
+        sa.CheckConstraint("position_day >= 0", name=op.f("ck_program_position_day_ge_0")),
+        sa.ForeignKeyConstraint(
+            ["user_id"],
+            ["user.id"],
+            name=op.f("fk_program_user_id_user"),
+            ondelete="CASCADE",
+        ),
+        sa.PrimaryKeyConstraint("id", name=op.f("pk_program")),
+        sa.UniqueConstraint("user_id", "name", name=op.f("uq_program_user_id")),
+    )
+    op.create_table(
+        "program_week",
+        sa.Column("program_id", sa.Integer(), nullable=False),
+        sa.Column("position", sa.Integer(), nullable=False),
+        sa.Column("weight_percentage", sa.Float(), nullable=False),
+        sa.Column("rpe_offset", sa.Float(), nullable=False),
+        sa.CheckConstraint(
+            "typeof(position) = 'integer'", name=op.f("ck_program_week_position_type_integer")
+        ),
+        sa.CheckConstraint(
+            "typeof(weight_percentage) = 'real'",
+            name=op.f("ck_program_week_weight_percentage_type_real"),
+        ),
+        sa.CheckConstraint(
+            "typeof(rpe_offset) = 'real'", name=op.f("ck_program_week_rpe_offset_type_real")
+        ),
+        sa.CheckConstraint("position > 0", name=op.f("ck_program_week_position_gt_0")),
+        sa.CheckConstraint(
+            "weight_percentage > 0", name=op.f("ck_program_week_weight_percentage_gt_0")
+        ),
+        sa.ForeignKeyConstraint(
+            ["program_id"],
+            ["program.id"],
+            name=op.f("fk_program_week_program_id_program"),
+            ondelete="CASCADE",
+        ),
+        sa.PrimaryKeyConstraint("program_id", "position", name=op.f("pk_program_week")),
+    )
+    op.create_table(
+        "program_routine",
+        sa.Column("program_id", sa.Integer(), nullable=False),
+        sa.Column("week_position", sa.Integer(), nullable=False),
+        sa.Column("position", sa.Integer(), nullable=False),
+        sa.Column("routine_id", sa.Integer(), nullable=False),
+        sa.CheckConstraint(
+            "typeof(position) = 'integer'", name=op.f("ck_program_routine_position_type_integer")
+        ),
+        sa.CheckConstraint("position > 0", name=op.f("ck_program_routine_position_gt_0")),
+        sa.ForeignKeyConstraint(
+            ["program_id", "week_position"],
+            ["program_week.program_id", "program_week.position"],
+            name=op.f("fk_program_routine_program_id_program_week"),
+            ondelete="CASCADE",
+        ),
+        sa.ForeignKeyConstraint(
+            ["routine_id"],
+            ["routine.id"],
+            name=op.f("fk_program_routine_routine_id_routine"),
+            ondelete="CASCADE",
+        ),
+        sa.PrimaryKeyConstraint(
+            "program_id", "week_position", "position", name=op.f("pk_program_routine")
+        ),
+    )
+    op.create_table(
+        "program_workout",
+        sa.Column("workout_id", sa.Integer(), nullable=False),
+        sa.Column("program_id", sa.Integer(), nullable=False),
+        sa.Column("weight_percentage", sa.Float(), nullable=False),
+        sa.CheckConstraint(
+            "typeof(weight_percentage) = 'real'",
+            name=op.f("ck_program_workout_weight_percentage_type_real"),
+        ),
+        sa.CheckConstraint(
+            "weight_percentage > 0", name=op.f("ck_program_workout_weight_percentage_gt_0")
+        ),
+        sa.ForeignKeyConstraint(
+            ["program_id"],
+            ["program.id"],
+            name=op.f("fk_program_workout_program_id_program"),
+            ondelete="CASCADE",
+        ),
+        sa.ForeignKeyConstraint(
+            ["workout_id"],
+            ["workout.id"],
+            name=op.f("fk_program_workout_workout_id_workout"),
+            ondelete="CASCADE",
+        ),
+        sa.PrimaryKeyConstraint("workout_id", name=op.f("pk_program_workout")),
+    )
+
+
+def downgrade() -> None:
+    op.drop_table("program_workout")
+    op.drop_table("program_routine")
+    op.drop_table("program_week")
+    op.drop_table("program")
@@ -2,21 +2,24 @@ from __future__ import annotations
 
 import datetime
 import enum
-from typing import Optional
+from typing import Any, Optional
 
 from sqlalchemy import (
     CheckConstraint,
     Constraint,
     Date,
+    DateTime,
     Enum,
     Float,
     ForeignKey,
     ForeignKeyConstraint,
     Integer,
+    JSON,
     MetaData,
     String,
     UniqueConstraint,
     column,
+    false,
 )
 from sqlalchemy.orm import DeclarativeBase, Mapped, mapped_column, relationship
 
@@ -70,6 +73,23 @@ class User(Base):
     workouts: Mapped[list[Workout]] = relationship(
         "Workout", backref="user", cascade="all, delete-orphan", passive_deletes=True
     )
+    programs: Mapped[list[Program]] = relationship(
+        "Program", backref="user", cascade="all, delete-orphan", passive_deletes=True
+    )
+    schedule: Mapped[Optional[Schedule]] = relationship(
+        "Schedule",
+        backref="user",
+        uselist=False,
+        cascade="all, delete-orphan",
+        passive_deletes=True,
+    )
+    barbell_equipment: Mapped[Optional[BarbellEquipment]] = relationship(
+        "BarbellEquipment",
+        backref="user",
+        uselist=False,
+        cascade="all, delete-orphan",
+        passive_deletes=True,
+    )
 
 
 class BodyWeight(Base):
@@ -156,12 +176,44 @@ class Period(Base):
 
 class Exercise(Base):
     __tablename__ = "exercise"
-    __table_args__ = (UniqueConstraint("user_id", "name"),)
+    __table_args__: tuple[Constraint, ...] = (
+        UniqueConstraint("user_id", "name"),
+        CheckConstraint(
+            "typeof(movement_pattern) = 'text' or typeof(movement_pattern) = 'null'",
+            name="movement_pattern_type_text_or_null",
+        ),
+        CheckConstraint(
+            "typeof(equipment) = 'text' or typeof(equipment) = 'null'",
+            name="equipment_type_text_or_null",
+        ),
+        CheckConstraint(
+            "typeof(laterality) = 'text' or typeof(laterality) = 'null'",
+            name="laterality_type_text_or_null",
+        ),
+        CheckConstraint(
+            "typeof(training_max) = 'real' or typeof(training_max) = 'null'",
+            name="training_max_type_real_or_null",
+        ),
+        CheckConstraint(column("training_max") > 0, name="training_max_gt_0"),
+    )
 
     id: Mapped[int] = mapped_column(Integer, primary_key=True)
     user_id: Mapped[int] = mapped_column(ForeignKey("user.id", ondelete="CASCADE"), nullable=False)
     name: Mapped[str] = mapped_column(String, nullable=False)
+    movement_pattern: Mapped[Optional[str]] = mapped_column(String)
+    equipment: Mapped[Optional[str]] = mapped_column(String)
+    laterality: Mapped[Optional[str]] = mapped_column(String)
+    training_max: Mapped[Optional[float]]
 
+    muscles: Mapped[list[ExerciseMuscle]] = relationship(
+        "ExerciseMuscle", back_populates="exercise", cascade="all, delete-orphan"
+    )
+    alternatives: Mapped[list[ExerciseAlternative]] = relationship(
+        "ExerciseAlternative",
+        back_populates="exercise",
+        foreign_keys="ExerciseAlternative.exercise_id",
+        cascade="all, delete-orphan",
+    )
     sets: Mapped[list[WorkoutSet]] = relationship(
         "WorkoutSet", back_populates="exercise", cascade="all, delete-orphan"
     )
@@ -170,6 +222,39 @@ class Exercise(Base):
     )
 
 
+class ExerciseMuscle(Base):
+    __tablename__ = "exercise_muscle"
+    __table_args__ = (
+        CheckConstraint("typeof(muscle_group) = 'text'", name="muscle_group_type_text"),
+        CheckConstraint("typeof(secondary) = 'integer'", name="secondary_type_integer"),
+        CheckConstraint(column("secondary") >= 0, name="secondary_ge_0"),
+        CheckConstraint(column("secondary") <= 1, name="secondary_le_1"),
+    )
+
+    exercise_id: Mapped[int] = mapped_column(
+        ForeignKey("exercise.id", ondelete="CASCADE"), primary_key=True
+    )
+    muscle_group: Mapped[str] = mapped_column(String, primary_key=True)
+    secondary: Mapped[bool] = mapped_column(nullable=False)
+
+    exercise: Mapped[Exercise] = relationship("Exercise", back_populates="muscles")
+
+
+class ExerciseAlternative(Base):
+    __tablename__ = "exercise_alternative"
+
+    exercise_id: Mapped[int] = mapped_column(
+        ForeignKey("exercise.id", ondelete="CASCADE"), primary_key=True
+    )
+    alternative_id: Mapped[int] = mapped_column(
+        ForeignKey("exercise.id", ondelete="CASCADE"), primary_key=True
+    )
+
+    exercise: Mapped[Exercise] = relationship(
+        "Exercise", back_populates="alternatives", foreign_keys=exercise_id
+    )
+
+
 class Routine(Base):
     __tablename__ = "routine"
     __table_args__ = (UniqueConstraint("user_id", "name"),)
@@ -182,9 +267,28 @@ class Routine(Base):
     sections: Mapped[list[RoutineSection]] = relationship(
         "RoutineSection", back_populates="routine", cascade="all, delete-orphan"
     )
+    versions: Mapped[list[RoutineVersion]] = relationship(
+        "RoutineVersion",
+        back_populates="routine",
+        cascade="all, delete-orphan",
+        order_by="RoutineVersion.id",
+    )
     workouts: Mapped[list[Workout]] = relationship("Workout", back_populates="routine")
 
 
+class RoutineVersion(Base):
+    __tablename__ = "routine_version"
+
+    id: Mapped[int] = mapped_column(Integer, primary_key=True)
+    routine_id: Mapped[int] = mapped_column(
+        ForeignKey("routine.id", ondelete="CASCADE"), nullable=False
+    )
+    created: Mapped[datetime.datetime] = mapped_column(DateTime, nullable=False)
+    sections: Mapped[list[Any]] = mapped_column(JSON, nullable=False)
+
+    routine: Mapped[Routine] = relationship("Routine", back_populates="versions")
+
+
 class RoutinePart(Base):
     __tablename__ = "routine_part"
     __table_args__: tuple[CheckConstraint, ...] = (
@@ -216,12 +320,38 @@ class RoutineSection(RoutinePart):
     __tablename__ = "routine_section"
     __table_args__ = (
         CheckConstraint("typeof(rounds) = 'integer'", name="rounds_type_integer"),
+        CheckConstraint("typeof(kind) = 'text'", name="kind_type_text"),
+        CheckConstraint(
+            "typeof(interval) = 'integer' or typeof(interval) = 'null'",
+            name="interval_type_integer_or_null",
+        ),
+        CheckConstraint(
+            "typeof(time_cap) = 'integer' or typeof(time_cap) = 'null'",
+            name="time_cap_type_integer_or_null",
+        ),
+        CheckConstraint(
+            "typeof(work) = 'integer' or typeof(work) = 'null'",
+            name="work_type_integer_or_null",
+        ),
+        CheckConstraint(
+            "typeof(rest) = 'integer' or typeof(rest) = 'null'",
+            name="rest_type_integer_or_null",
+        ),
         CheckConstraint(column("rounds") > 0, name="rounds_gt_0"),
+        CheckConstraint(column("interval") > 0, name="interval_gt_0"),
+        CheckConstraint(column("time_cap") >= 0, name="time_cap_ge_0"),
+        CheckConstraint(column("work") > 0, name="work_gt_0"),
+        CheckConstraint(column("rest") >= 0, name="rest_ge_0"),
     )
 
     id: Mapped[int] = mapped_column(Integer, ForeignKey("routine_part.id"), primary_key=True)
     routine_id: Mapped[Optional[int]] = mapped_column(ForeignKey("routine.id", ondelete="CASCADE"))
     rounds: Mapped[int] = mapped_column(Integer, nullable=False)
+    kind: Mapped[str] = mapped_column(String, nullable=False, server_default="Sequential")
+    interval: Mapped[Optional[int]]
+    time_cap: Mapped[Optional[int]]
+    work: Mapped[Optional[int]]
+    rest: Mapped[Optional[int]]
 
     parts: Mapped[list[RoutinePart]] = relationship(
         "RoutinePart",
@@ -249,6 +379,36 @@ class RoutineActivity(RoutinePart):
         CheckConstraint("typeof(rpe) = 'real'", name="rpe_type_real"),
         CheckConstraint(column("rpe") >= 0, name="rpe_ge_0"),
         CheckConstraint(column("rpe") <= 10, name="rpe_le_10"),
+        CheckConstraint("typeof(set_type) = 'text'", name="set_type_type_text"),
+        CheckConstraint("typeof(auto_warm_up) = 'integer'", name="auto_warm_up_type_integer"),
+        CheckConstraint(column("auto_warm_up") >= 0, name="auto_warm_up_ge_0"),
+        CheckConstraint(column("auto_warm_up") <= 1, name="auto_warm_up_le_1"),
+        CheckConstraint(
+            "typeof(relative_weight_percentage) = 'real' "
+            "or typeof(relative_weight_percentage) = 'null'",
+            name="relative_weight_percentage_type_real_or_null",
+        ),
+        CheckConstraint(
+            "typeof(relative_weight_basis) = 'text' or typeof(relative_weight_basis) = 'null'",
+            name="relative_weight_basis_type_text_or_null",
+        ),
+        CheckConstraint(
+            column("relative_weight_percentage") > 0, name="relative_weight_percentage_gt_0"
+        ),
+        CheckConstraint(
+            "typeof(progression) = 'text' or typeof(progression) = 'null'",
+            name="progression_type_text_or_null",
+        ),
+        CheckConstraint(
+            "typeof(progression_increment) = 'real' or typeof(progression_increment) = 'null'",
+            name="progression_increment_type_real_or_null",
+        ),
+        CheckConstraint(
+            "typeof(progression_max_reps) = 'integer' or typeof(progression_max_reps) = 'null'",
+            name="progression_max_reps_type_integer_or_null",
+        ),
+        CheckConstraint(column("progression_increment") >= 0, name="progression_increment_ge_0"),
+        CheckConstraint(column("progression_max_reps") > 0, name="progression_max_reps_gt_0"),
     )
 
     id: Mapped[int] = mapped_column(Integer, ForeignKey("routine_part.id"), primary_key=True)
@@ -260,6 +420,13 @@ class RoutineActivity(RoutinePart):
     weight: Mapped[float]
     rpe: Mapped[float]
     automatic: Mapped[bool]
+    set_type: Mapped[str] = mapped_column(String, nullable=False, server_default="Working")
+    auto_warm_up: Mapped[bool] = mapped_column(nullable=False, server_default=false())
+    relative_weight_percentage: Mapped[Optional[float]]
+    relative_weight_basis: Mapped[Optional[str]] = mapped_column(String)
+    progression: Mapped[Optional[str]] = mapped_column(String)
+    progression_increment: Mapped[Optional[float]]
+    progression_max_reps: Mapped[Optional[int]]
 
     exercise: Mapped[Exercise] = relationship("Exercise", back_populates="routine_activities")
 
@@ -272,17 +439,42 @@ class RoutineActivity(RoutinePart):
 
 class Workout(Base):
     __tablename__ = "workout"
+    __table_args__ = (
+        CheckConstraint(
+            "typeof(session_rpe) = 'real' or typeof(session_rpe) = 'null'",
+            name="session_rpe_type_real_or_null",
+        ),
+        CheckConstraint(
+            "typeof(duration) = 'integer' or typeof(duration) = 'null'",
+            name="duration_type_integer_or_null",
+        ),
+        CheckConstraint(column("session_rpe") >= 0, name="session_rpe_ge_0"),
+        CheckConstraint(column("session_rpe") <= 10, name="session_rpe_le_10"),
+        CheckConstraint(column("duration") > 0, name="duration_gt_0"),
+    )
 
     id: Mapped[int] = mapped_column(Integer, primary_key=True)
     user_id: Mapped[int] = mapped_column(ForeignKey("user.id", ondelete="CASCADE"), nullable=False)
     routine_id: Mapped[Optional[int]] = mapped_column(ForeignKey("routine.id", ondelete="CASCADE"))
+    routine_version_id: Mapped[Optional[int]] = mapped_column(
+        ForeignKey("routine_version.id", ondelete="CASCADE")
+    )
     date: Mapped[datetime.date] = mapped_column(Date, nullable=False)
     notes: Mapped[Optional[str]] = mapped_column(String)
+    session_rpe: Mapped[Optional[float]]
+    duration: Mapped[Optional[int]]
 
     routine: Mapped[Routine] = relationship("Routine", back_populates="workouts")
+    routine_version: Mapped[Optional[RoutineVersion]] = relationship("RoutineVersion")
     elements: Mapped[list[WorkoutElement]] = relationship(
         "WorkoutElement", back_populates="workout", cascade="all, delete-orphan"
     )
+    interval_blocks: Mapped[list[WorkoutIntervalBlock]] = relationship(
+        "WorkoutIntervalBlock",
+        back_populates="workout",
+        cascade="all, delete-orphan",
+        order_by="WorkoutIntervalBlock.position",
+    )
 
 
 class WorkoutElement(Base):
@@ -356,6 +548,7 @@ class WorkoutSet(WorkoutElement):
             "typeof(target_rpe) = 'real' or typeof(target_rpe) = 'null'",
             name="target_rpe_type_real_or_null",
         ),
+        CheckConstraint("typeof(set_type) = 'text'", name="set_type_type_text"),
         CheckConstraint(column("position") > 0, name="position_gt_0"),
         CheckConstraint(column("reps") > 0, name="reps_gt_0"),
         CheckConstraint(column("time") > 0, name="time_gt_0"),
@@ -387,6 +580,7 @@ class WorkoutSet(WorkoutElement):
     target_time: Mapped[Optional[int]]
     target_weight: Mapped[Optional[float]]
     target_rpe: Mapped[Optional[float]]
+    set_type: Mapped[str] = mapped_column(String, nullable=False, server_default="Working")
 
     exercise: Mapped[Exercise] = relationship("Exercise", back_populates="sets")
 
@@ -424,3 +618,276 @@ class WorkoutRest(WorkoutElement):
         "polymorphic_identity": "rest",
         "polymorphic_load": "selectin",
     }
+
+
+class WorkoutIntervalBlock(Base):
+    __tablename__ = "workout_interval_block"
+    __table_args__ = (
+        CheckConstraint("typeof(position) = 'integer'", name="position_type_integer"),
+        CheckConstraint("typeof(kind) = 'text'", name="kind_type_text"),
+        CheckConstraint(
+            "typeof(interval) = 'integer' or typeof(interval) = 'null'",
+            name="interval_type_integer_or_null",
+        ),
+        CheckConstraint(
+            "typeof(time_cap) = 'integer' or typeof(time_cap) = 'null'",
+            name="time_cap_type_integer_or_null",
+        ),
+        CheckConstraint(
+            "typeof(work) = 'integer' or typeof(work) = 'null'",
+            name="work_type_integer_or_null",
+        ),
+        CheckConstraint(
+            "typeof(rest) = 'integer' or typeof(rest) = 'null'",
+            name="rest_type_integer_or_null",
+        ),
+        CheckConstraint("typeof(start_index) = 'integer'", name="start_index_type_integer"),
+        CheckConstraint("typeof(end_index) = 'integer'", name="end_index_type_integer"),
+        CheckConstraint("typeof(rounds) = 'integer'", name="rounds_type_integer"),
+        CheckConstraint(
+            "typeof(achieved_rounds) = 'integer' or typeof(achieved_rounds) = 'null'",
+            name="achieved_rounds_type_integer_or_null",
+        ),
+        CheckConstraint(
+            "typeof(time) = 'integer' or typeof(time) = 'null'",
+            name="time_type_integer_or_null",
+        ),
+        CheckConstraint(column("position") > 0, name="position_gt_0"),
+        CheckConstraint(column("interval") > 0, name="interval_gt_0"),
+        CheckConstraint(column("time_cap") >= 0, name="time_cap_ge_0"),
+        CheckConstraint(column("work") > 0, name="work_gt_0"),
+        CheckConstraint(column("rest") >= 0, name="rest_ge_0"),
+        CheckConstraint(column("start_index") >= 0, name="start_index_ge_0"),
+        CheckConstraint(
+            column("end_index") >= column("start_index"), name="end_index_ge_start_index"
+        ),
+        CheckConstraint(column("rounds") > 0, name="rounds_gt_0"),
+        CheckConstraint(column("achieved_rounds") >= 0, name="achieved_rounds_ge_0"),
+        CheckConstraint(column("time") >= 0, name="time_ge_0"),
+    )
+
+    workout_id: Mapped[int] = mapped_column(
+        ForeignKey("workout.id", ondelete="CASCADE"), primary_key=True
+    )
+    position: Mapped[int] = mapped_column(primary_key=True)
+    kind: Mapped[str] = mapped_column(String, nullable=False)
+    interval: Mapped[Optional[int]]
+    time_cap: Mapped[Optional[int]]
+    work: Mapped[Optional[int]]
+    rest: Mapped[Optional[int]]
+    start_index: Mapped[int] = mapped_column(Integer, nullable=False)
+    end_index: Mapped[int] = mapped_column(Integer, nullable=False)
+    rounds: Mapped[int] = mapped_column(Integer, nullable=False)
+    achieved_rounds: Mapped[Optional[int]]
+    time: Mapped[Optional[int]]
+
+    workout: Mapped[Workout] = relationship("Workout", back_populates="interval_blocks")
+
+
+class Program(Base):
+    __tablename__ = "program"
+    __table_args__ = (
+        UniqueConstraint("user_id", "name"),
+        CheckConstraint(
+            "typeof(position_week) = 'integer' or typeof(position_week) = 'null'",
+            name="position_week_type_integer_or_null",
+        ),
+        CheckConstraint(
+            "typeof(position_day) = 'integer' or typeof(position_day) = 'null'",
+            name="position_day_type_integer_or_null",
+        ),
+        CheckConstraint(column("position_week") >= 0, name="position_week_ge_0"),
+        CheckConstraint(column("position_day") >= 0, name="position_day_ge_0"),
+    )
+
+    id: Mapped[int] = mapped_column(Integer, primary_key=True)
+    user_id: Mapped[int] = mapped_column(ForeignKey("user.id", ondelete="CASCADE"), nullable=False)
+    name: Mapped[str] = mapped_column(String, nullable=False)
+    position_week: Mapped[Optional[int]]
+    position_day: Mapped[Optional[int]]
+
+    weeks: Mapped[list[ProgramWeek]] = relationship(
+        "ProgramWeek",
+        back_populates="program",
+        cascade="all, delete-orphan",
+        order_by="ProgramWeek.position",
+    )
+    workouts: Mapped[list[ProgramWorkout]] = relationship(
+        "ProgramWorkout", back_populates="program", cascade="all, delete-orphan"
+    )
+
+
+class ProgramWeek(Base):
+    __tablename__ = "program_week"
+    __table_args__ = (
+        CheckConstraint("typeof(position) = 'integer'", name="position_type_integer"),
+        CheckConstraint("typeof(weight_percentage) = 'real'", name="weight_percentage_type_real"),
+        CheckConstraint("typeof(rpe_offset) = 'real'", name="rpe_offset_type_real"),
+        CheckConstraint(column("position") > 0, name="position_gt_0"),
+        CheckConstraint(column("weight_percentage") > 0, name="weight_percentage_gt_0"),
+    )
+
+    program_id: Mapped[int] = mapped_column(
+        ForeignKey("program.id", ondelete="CASCADE"), primary_key=True
+    )
+    position: Mapped[int] = mapped_column(primary_key=True)
+    weight_percentage: Mapped[float] = mapped_column(Float, nullable=False)
+    rpe_offset: Mapped[float] = mapped_column(Float, nullable=False)
+
+    program: Mapped[Program] = relationship("Program", back_populates="weeks")
+    routines: Mapped[list[ProgramRoutine]] = relationship(
+        "ProgramRoutine",
+        back_populates="week",
+        cascade="all, delete-orphan",
+        order_by="ProgramRoutine.position",
+    )
+
+
+class ProgramRoutine(Base):
+    __tablename__ = "program_routine"
+    __table_args__ = (
+        CheckConstraint("typeof(position) = 'integer'", name="position_type_integer"),
+        CheckConstraint(column("position") > 0, name="position_gt_0"),
+        ForeignKeyConstraint(
+            ["program_id", "week_position"],
+            [ProgramWeek.program_id, ProgramWeek.position],
+            ondelete="CASCADE",
+        ),
+    )
+
+    program_id: Mapped[int] = mapped_column(primary_key=True)
+    week_position: Mapped[int] = mapped_column(primary_key=True)
+    position: Mapped[int] = mapped_column(primary_key=True)
+    routine_id: Mapped[int] = mapped_column(
+        ForeignKey("routine.id", ondelete="CASCADE"), nullable=False
+    )
+
+    week: Mapped[ProgramWeek] = relationship("ProgramWeek", back_populates="routines")
+    routine: Mapped[Routine] = relationship("Routine")
+
+
+class ProgramWorkout(Base):
+    __tablename__ = "program_workout"
+    __table_args__ = (
+        CheckConstraint("typeof(weight_percentage) = 'real'", name="weight_percentage_type_real"),
+        CheckConstraint(column("weight_percentage") > 0, name="weight_percentage_gt_0"),
+    )
+
+    workout_id: Mapped[int] = mapped_column(
+        ForeignKey("workout.id", ondelete="CASCADE"), primary_key=True
+    )
+    program_id: Mapped[int] = mapped_column(
+        ForeignKey("program.id", ondelete="CASCADE"), nullable=False
+    )
+    weight_percentage: Mapped[float] = mapped_column(Float, nullable=False)
+
+    program: Mapped[Program] = relationship("Program", back_populates="workouts")
+    workout: Mapped[Workout] = relationship("Workout")
+
+
+class Schedule(Base):
+    __tablename__ = "schedule"
+    __table_args__ = (
+        CheckConstraint(
+            "typeof(interval) = 'integer' or typeof(interval) = 'null'",
+            name="interval_type_integer_or_null",
+        ),
+        CheckConstraint(column("interval") > 0, name="interval_gt_0"),
+    )
+
+    user_id: Mapped[int] = mapped_column(
+        ForeignKey("user.id", ondelete="CASCADE"), primary_key=True
+    )
+    start: Mapped[datetime.date] = mapped_column(Date, nullable=False)
+    interval: Mapped[Optional[int]]
+
+    routines: Mapped[list[ScheduleRoutine]] = relationship(
+        "ScheduleRoutine",
+        back_populates="schedule",
+        cascade="all, delete-orphan",
+        order_by="ScheduleRoutine.position",
+    )
+
+
+class ScheduleRoutine(Base):
+    __tablename__ = "schedule_routine"
+    __table_args__ = (
+        CheckConstraint("typeof(position) = 'integer'", name="position_type_integer"),
+        CheckConstraint(column("position") > 0, name="position_gt_0"),
+    )
+
+    user_id: Mapped[int] = mapped_column(
+        ForeignKey("schedule.user_id", ondelete="CASCADE"), primary_key=True
+    )
+    position: Mapped[int] = mapped_column(primary_key=True)
+    routine_id: Mapped[int] = mapped_column(
+        ForeignKey("routine.id", ondelete="CASCADE"), nullable=False
+    )
+
+    schedule: Mapped[Schedule] = relationship("Schedule", back_populates="routines")
+    routine: Mapped[Routine] = relationship("Routine")
+
+
+class BarbellEquipment(Base):
+    __tablename__ = "barbell_equipment"
+    __table_args__ = (
+        CheckConstraint("typeof(collars) = 'real'", name="collars_type_real"),
+        CheckConstraint(column("collars") >= 0, name="collars_ge_0"),
+    )
+
+    user_id: Mapped[int] = mapped_column(
+        ForeignKey("user.id", ondelete="CASCADE"), primary_key=True
+    )
+    collars: Mapped[float] = mapped_column(Float, nullable=False)
+
+    bars: Mapped[list[BarbellBar]] = relationship(
+        "BarbellBar",
+        back_populates="equipment",
+        cascade="all, delete-orphan",
+        order_by="BarbellBar.position",
+    )
+    plates: Mapped[list[BarbellPlate]] = relationship(
+        "BarbellPlate",
+        back_populates="equipment",
+        cascade="all, delete-orphan",
+        order_by="BarbellPlate.position",
+    )
+
+
+class BarbellBar(Base):
+    __tablename__ = "barbell_bar"
+    __table_args__ = (
+        CheckConstraint("typeof(position) = 'integer'", name="position_type_integer"),
+        CheckConstraint("typeof(weight) = 'real'", name="weight_type_real"),
+        CheckConstraint(column("position") > 0, name="position_gt_0"),
+        CheckConstraint(column("weight") >= 0, name="weight_ge_0"),
+    )
+
+    user_id: Mapped[int] = mapped_column(
+        ForeignKey("barbell_equipment.user_id", ondelete="CASCADE"), primary_key=True
+    )
+    position: Mapped[int] = mapped_column(primary_key=True)
+    weight: Mapped[float] = mapped_column(Float, nullable=False)
+
+    equipment: Mapped[BarbellEquipment] = relationship("BarbellEquipment", back_populates="bars")
+
+
+class BarbellPlate(Base):
+    __tablename__ = "barbell_plate"
+    __table_args__ = (
+        CheckConstraint("typeof(position) = 'integer'", name="position_type_integer"),
+        CheckConstraint("typeof(weight) = 'real'", name="weight_type_real"),
+        CheckConstraint("typeof(count) = 'integer'", name="count_type_integer"),
+        CheckConstraint(column("position") > 0, name="position_gt_0"),
+        CheckConstraint(column("weight") > 0, name="weight_gt_0"),
+        CheckConstraint(column("count") >= 0, name="count_ge_0"),
+    )
+
+    user_id: Mapped[int] = mapped_column(
+        ForeignKey("barbell_equipment.user_id", ondelete="CASCADE"), primary_key=True
+    )
+    position: Mapped[int] = mapped_column(primary_key=True)
+    weight: Mapped[float] = mapped_column(Float, nullable=False)
+    count: Mapped[int] = mapped_column(Integer, nullable=False)
+
+    equipment: Mapped[BarbellEquipment] = relationship("BarbellEquipment", back_populates="plates")